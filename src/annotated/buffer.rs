@@ -0,0 +1,104 @@
+use super::{hlsl_register, VULKAN_BUFFER_SET};
+use crate::{ast::SemanticAnalysisError, options::CompileOptions, types::Type};
+
+#[derive(Clone)]
+pub struct Buffer {
+    name: String,
+    slot: usize,
+    space: usize,
+    element_type: Type,
+    writable: bool,
+}
+
+impl Buffer {
+    pub fn new(
+        name: String,
+        slot: usize,
+        space: usize,
+        element_type: Type,
+        writable: bool,
+        max_buffers: usize,
+    ) -> Result<Self, SemanticAnalysisError> {
+        if slot >= max_buffers {
+            Err(SemanticAnalysisError::SlotOutOfRange(
+                "buffers",
+                slot,
+                max_buffers,
+            ))
+        } else {
+            Ok(Buffer {
+                name,
+                slot,
+                space,
+                element_type,
+                writable,
+            })
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    pub fn element_type(&self) -> &Type {
+        &self.element_type
+    }
+
+    pub fn writable(&self) -> bool {
+        self.writable
+    }
+
+    pub fn buffer_type(&self) -> Type {
+        Type::buffer(self.element_type.clone(), self.writable)
+    }
+
+    // The number of the "u" register for writable buffers, otherwise of the
+    // "t" register
+    pub fn hlsl_register_index(&self, options: &CompileOptions) -> usize {
+        match self.writable {
+            true => self.slot,
+            false => self.slot + options.buffers_index(),
+        }
+    }
+
+    pub fn generate_hlsl(self, options: &CompileOptions) -> String {
+        let class = if self.writable { 'u' } else { 't' };
+
+        format!(
+            "{} {} : register({});\n",
+            self.buffer_type().hlsl(),
+            self.name,
+            hlsl_register(class, self.hlsl_register_index(options), self.space)
+        )
+    }
+
+    // Shader storage blocks have their own bindings, so the slot is used as is
+    pub fn generate_glsl(self, options: &CompileOptions) -> String {
+        let layout = if options.get_glsl_version().is_vulkan() {
+            format!(
+                "std430, set = {}, binding = {}",
+                VULKAN_BUFFER_SET, self.slot
+            )
+        } else {
+            format!("std430, binding = {}", self.slot)
+        };
+
+        format!(
+            "layout({}) {}buffer acsl_buffer_{} {{\n{}{} {}[];\n}};\n",
+            layout,
+            if self.writable { "" } else { "readonly " },
+            self.slot,
+            options.indent(1),
+            self.element_type.glsl(),
+            self.name
+        )
+    }
+}
@@ -1,4 +1,6 @@
 use super::statement::Statement;
+use crate::{options::CompileOptions, spirv::Context};
+use std::collections::HashMap;
 
 pub struct CodeBlock {
     indent: usize,
@@ -10,15 +12,13 @@ impl CodeBlock {
         CodeBlock { indent, statements }
     }
 
-    pub fn hlsl(self) -> String {
+    pub fn hlsl(self, options: &CompileOptions) -> String {
         let mut hlsl = format!("{{\n");
+        let indentation = options.indent(self.indent);
 
         for statement in self.statements {
-            for _ in 0..self.indent * 4 {
-                hlsl.push(' ');
-            }
-
-            hlsl.push_str(&statement.hlsl());
+            hlsl.push_str(&indentation);
+            hlsl.push_str(&statement.hlsl(&indentation, options));
         }
 
         hlsl.push_str("}\n");
@@ -31,22 +31,75 @@ impl CodeBlock {
         in_vertex_main: bool,
         in_fragment_main: bool,
         position_output_name: &str,
+        options: &CompileOptions,
     ) -> String {
         let mut glsl = String::new();
+        let indentation = options.indent(self.indent);
 
         for statement in self.statements {
-            for _ in 0..self.indent * 4 {
-                glsl.push(' ');
-            }
-
+            glsl.push_str(&indentation);
             glsl.push_str(&statement.glsl(
-                self.indent,
+                &indentation,
                 in_vertex_main,
                 in_fragment_main,
                 position_output_name,
+                options,
             ));
         }
 
         glsl
     }
+
+    pub fn wgsl(self, options: &CompileOptions) -> String {
+        let mut wgsl = "{\n".to_owned();
+        let indentation = options.indent(self.indent);
+
+        for statement in self.statements {
+            wgsl.push_str(&indentation);
+            wgsl.push_str(&statement.wgsl());
+        }
+
+        wgsl.push_str("}\n");
+
+        wgsl
+    }
+
+    pub fn msl(
+        self,
+        resource_arguments: &HashMap<String, String>,
+        options: &CompileOptions,
+    ) -> String {
+        let mut msl = "{\n".to_owned();
+        let indentation = options.indent(self.indent);
+
+        for statement in self.statements {
+            msl.push_str(&indentation);
+            msl.push_str(&statement.msl(resource_arguments));
+        }
+
+        msl.push_str("}\n");
+
+        msl
+    }
+
+    pub fn discards(&self) -> bool {
+        self.statements.iter().any(Statement::is_discard)
+    }
+
+    // Returns true when the block ended with a return
+    pub fn spirv(&self, context: &mut Context) -> bool {
+        for statement in &self.statements {
+            if statement.spirv(context) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn references(&self, variables: &mut Vec<String>, functions: &mut Vec<String>) {
+        for statement in &self.statements {
+            statement.references(variables, functions);
+        }
+    }
 }
@@ -1,5 +1,6 @@
-use super::expression::Expression;
-use crate::types::Type;
+use super::{expression::Expression, msl_identifier};
+use crate::{options::CompileOptions, types::Type};
+use std::collections::HashMap;
 
 pub struct Constant {
     name: String,
@@ -24,6 +25,10 @@ impl Constant {
         &self.constant_type
     }
 
+    pub fn expression(&self) -> &Expression {
+        &self.expression
+    }
+
     pub fn generate_hlsl(self) -> String {
         format!(
             "static const {} {} = {};\n",
@@ -33,12 +38,30 @@ impl Constant {
         )
     }
 
-    pub fn generate_glsl(self) -> String {
+    pub fn generate_glsl(self, options: &CompileOptions) -> String {
         format!(
             "const {} {} = {};\n",
             self.constant_type.glsl(),
             self.name,
-            self.expression.glsl()
+            self.expression.glsl(options)
+        )
+    }
+
+    pub fn generate_wgsl(self) -> String {
+        format!(
+            "const {}: {} = {};\n",
+            self.name,
+            self.constant_type.wgsl(),
+            self.expression.wgsl()
+        )
+    }
+
+    pub fn generate_msl(self) -> String {
+        format!(
+            "constant {} {} = {};\n",
+            self.constant_type.msl(),
+            msl_identifier(&self.name),
+            self.expression.msl(&HashMap::new())
         )
     }
 }
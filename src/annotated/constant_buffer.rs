@@ -1,25 +1,41 @@
-use super::{CONSTANT_BUFFER_INDEX, MAX_CONSTANT_BUFFERS};
-use crate::{ast::SemanticAnalysisError, types::Type};
+use super::{hlsl_register, msl_identifier, CONSTANT_BUFFER_INDEX, VULKAN_CONSTANT_BUFFER_SET};
+use crate::{
+    ast::SemanticAnalysisError,
+    options::CompileOptions,
+    spirv::{
+        Context, Global, DECORATION_BINDING, DECORATION_BLOCK, DECORATION_DESCRIPTOR_SET,
+        STORAGE_CLASS_UNIFORM,
+    },
+    types::Type,
+};
 
 #[derive(Clone)]
 pub struct ConstantBuffer {
     name: String,
     slot: usize,
+    space: usize,
     cb_type: Type,
 }
 
 impl ConstantBuffer {
-    pub fn new(name: String, slot: usize, cb_type: Type) -> Result<Self, SemanticAnalysisError> {
-        if slot >= MAX_CONSTANT_BUFFERS {
+    pub fn new(
+        name: String,
+        slot: usize,
+        space: usize,
+        cb_type: Type,
+        max_constant_buffers: usize,
+    ) -> Result<Self, SemanticAnalysisError> {
+        if slot >= max_constant_buffers {
             Err(SemanticAnalysisError::SlotOutOfRange(
                 "constant buffers",
                 slot,
-                MAX_CONSTANT_BUFFERS,
+                max_constant_buffers,
             ))
         } else {
             Ok(ConstantBuffer {
                 name,
                 slot,
+                space,
                 cb_type,
             })
         }
@@ -33,26 +49,103 @@ impl ConstantBuffer {
         self.slot
     }
 
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
     pub fn cb_type(&self) -> &Type {
         &self.cb_type
     }
 
-    pub fn generate_hlsl(self) -> String {
+    pub fn generate_hlsl(self, options: &CompileOptions) -> String {
+        // Constant buffers in other spaces can share the slot
+        let block_name = match self.space {
+            0 => format!("acsl_constant_buffer_{}", self.slot),
+            space => format!("acsl_constant_buffer_{}_space{}", self.slot, space),
+        };
+
         format!(
-            "cbuffer acsl_constant_buffer_{} : register(b{}) {{\n    {} {};\n}}\n",
-            self.slot,
-            self.slot,
+            "cbuffer {} : register({}) {{\n{}{} {};\n}}\n",
+            block_name,
+            hlsl_register('b', self.slot, self.space),
+            options.indent(1),
             self.cb_type.hlsl(),
             self.name
         )
     }
 
-    pub fn generate_glsl(self) -> String {
+    pub fn generate_glsl(self, options: &CompileOptions) -> String {
+        if options.get_glsl_version().is_vulkan() {
+            format!(
+                "layout(std140, set = {}, binding = {}) uniform acsl_constant_buffer_{} {{\n{}{} {};\n}};\n",
+                VULKAN_CONSTANT_BUFFER_SET,
+                self.slot,
+                self.slot,
+                options.indent(1),
+                self.cb_type.glsl(),
+                self.name
+            )
+        } else if options.get_glsl_version().explicit_uniform_locations() {
+            format!(
+                "layout(location = {}) uniform {} {};\n",
+                self.slot + CONSTANT_BUFFER_INDEX,
+                self.cb_type.glsl(),
+                self.name
+            )
+        } else {
+            format!("uniform {} {};\n", self.cb_type.glsl(), self.name)
+        }
+    }
+
+    pub fn generate_wgsl(self) -> String {
         format!(
-            "layout(location = {}) uniform {} {};\n",
+            "@group(0) @binding({}) var<uniform> {}: {};\n",
             self.slot + CONSTANT_BUFFER_INDEX,
-            self.cb_type.glsl(),
-            self.name
+            self.name,
+            self.cb_type.wgsl()
         )
     }
+
+    // Metal has no global resources, constant buffers are passed to every
+    // function using them, with a binding on entry points
+    pub fn msl_parameter(&self, entry_point: bool) -> String {
+        let mut msl = format!(
+            "constant {}& {}",
+            self.cb_type.msl(),
+            msl_identifier(&self.name)
+        );
+        if entry_point {
+            msl.push_str(&format!(" [[buffer({})]]", self.slot));
+        }
+        msl
+    }
+
+    pub fn msl_argument(&self) -> String {
+        msl_identifier(&self.name)
+    }
+
+    // Declared as a uniform block with the Vulkan set and binding
+    pub fn declare_spirv(&self, context: &mut Context) {
+        let module = &mut context.module;
+
+        let member_type = self.cb_type.spirv(module);
+        let block_name = format!("acsl_constant_buffer_{}", self.slot);
+        let (block_type, _) = module.type_struct(&block_name, &[member_type]);
+        module.name(block_type, &block_name);
+        module.member_name(block_type, 0, &self.name);
+        module.decorate(block_type, DECORATION_BLOCK, &[]);
+        module.member_layout(block_type, 0, 0, self.cb_type.is_float_matrix());
+
+        let pointer_type = module.type_pointer(STORAGE_CLASS_UNIFORM, block_type);
+        let variable = module.global_variable(pointer_type, STORAGE_CLASS_UNIFORM);
+        module.name(variable, &self.name);
+        module.decorate(
+            variable,
+            DECORATION_DESCRIPTOR_SET,
+            &[VULKAN_CONSTANT_BUFFER_SET as u32],
+        );
+        module.decorate(variable, DECORATION_BINDING, &[self.slot as u32]);
+
+        context.define_global(&self.name, Global::ConstantBuffer { variable });
+    }
 }
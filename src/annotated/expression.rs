@@ -1,11 +1,19 @@
-use crate::types::Type;
+use super::{msl_identifier, storage_texture, texture};
+use crate::{
+    options::CompileOptions,
+    spirv::{self, Context, Global, Id},
+    types::Type,
+};
+use std::collections::HashMap;
 
 pub enum Expression {
     Variable(String, Type),
     FunctionCall(String, Vec<Expression>, Type),
     FloatLiteral(f64),
+    UintLiteral(u32),
     StructCreation(String, Vec<Expression>, Type),
     MemberAccess(Box<Expression>, String, Type),
+    Index(Box<Expression>, Box<Expression>, Type),
     Empty,
     Multiply(Box<Expression>, Box<Expression>, Type),
     Add(Box<Expression>, Box<Expression>, Type),
@@ -17,18 +25,44 @@ impl Expression {
         match self {
             Expression::Empty => String::new(),
             Expression::Variable(variable, _) => variable,
-            Expression::FunctionCall(name, mut parameters, _) => {
-                let mut string = if name == "sample_texture" {
-                    format!(
-                        "{0}.Sample(acsl_{0}_sampler_state, ",
-                        parameters.remove(0).hlsl()
-                    )
-                } else if name == "load" {
-                    format!("{}.Load(", parameters.remove(0).hlsl())
-                } else if name == "frac2" || name == "frac3" || name == "frac4" {
+            Expression::FunctionCall(name, mut parameters, _)
+                if is_storage_texture_access(&name, &parameters) =>
+            {
+                let texture = parameters.remove(0).hlsl();
+                let coordinates = parameters.remove(0).hlsl();
+                match parameters.pop() {
+                    Some(texel) => format!("{}[{}] = {}", texture, coordinates, texel.hlsl()),
+                    None => format!("{}[{}]", texture, coordinates),
+                }
+            }
+            Expression::FunctionCall(name, parameters, _) if is_texture_call(&parameters) => {
+                texture_call(name, parameters, Expression::hlsl).hlsl()
+            }
+            Expression::FunctionCall(name, mut parameters, _) if name == "emit" => {
+                let stream = parameters.remove(0).hlsl();
+                format!("{}.Append({})", stream, parameters.remove(0).hlsl())
+            }
+            Expression::FunctionCall(name, mut parameters, _) if name == "end_primitive" => {
+                format!("{}.RestartStrip()", parameters.remove(0).hlsl())
+            }
+            Expression::FunctionCall(name, parameters, _) => {
+                let mut string = if name == "frac2" || name == "frac3" || name == "frac4" {
                     format!("frac(")
-                } else if name == "uint_to_float" {
-                    format!("float(")
+                } else if name == "uint_to_float"
+                    || name == "int_to_float"
+                    || name == "half_to_float"
+                {
+                    "float(".to_owned()
+                } else if name == "float_to_uint" || name == "int_to_uint" {
+                    "uint(".to_owned()
+                } else if name == "float_to_int" || name == "uint_to_int" {
+                    "int(".to_owned()
+                } else if name == "half" {
+                    "float16_t(".to_owned()
+                } else if name.starts_with("wave_") {
+                    format!("{}(", hlsl_wave_intrinsic(&name))
+                } else if name == "group_barrier" {
+                    "GroupMemoryBarrierWithGroupSync(".to_owned()
                 } else {
                     format!("{}(", name)
                 };
@@ -75,8 +109,29 @@ impl Expression {
                     format!("{}", value)
                 }
             }
-            Expression::MemberAccess(expression, member_name, _) => {
-                format!("{}.{}", expression.hlsl(), member_name)
+            Expression::UintLiteral(value) => format!("{}u", value),
+            // Tessellation factors are arrays, read back into a vector
+            Expression::MemberAccess(expression, member_name, member_type) => {
+                let elements = match expression.get_type() {
+                    Type::Struct(structure) => structure.hlsl_factor_elements(&member_name),
+                    _ => None,
+                };
+                let hlsl = format!("{}.{}", expression.hlsl(), member_name);
+
+                match elements {
+                    Some(elements) => format!(
+                        "{}({})",
+                        member_type.hlsl(),
+                        (0..elements)
+                            .map(|element| format!("{}[{}]", hlsl, element))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                    None => hlsl,
+                }
+            }
+            Expression::Index(expression, index, _) => {
+                format!("{}[{}]", expression.hlsl(), index.hlsl())
             }
             Expression::Multiply(left_expression, right_expression, _) => {
                 let left_type = left_expression.get_type();
@@ -105,6 +160,284 @@ impl Expression {
         }
     }
 
+    pub fn wgsl(self) -> String {
+        match self {
+            Expression::Empty => String::new(),
+            Expression::Variable(variable, _) => variable,
+            Expression::FunctionCall(name, parameters, _) if is_texture_call(&parameters) => {
+                texture_call(name, parameters, Expression::wgsl).wgsl()
+            }
+            Expression::FunctionCall(name, parameters, _) => {
+                let name = match name.as_str() {
+                    "float" => "f32",
+                    "float2" => "vec2<f32>",
+                    "float3" => "vec3<f32>",
+                    "float4" => "vec4<f32>",
+                    "uint2" => "vec2<u32>",
+                    "uint3" => "vec3<u32>",
+                    "frac" | "frac2" | "frac3" | "frac4" => "fract",
+                    "uint_to_float" | "int_to_float" | "half_to_float" => "f32",
+                    "float_to_uint" | "int_to_uint" => "u32",
+                    "float_to_int" | "uint_to_int" => "i32",
+                    "half" => "f16",
+                    _ => &name,
+                };
+
+                let mut string = format!("{}(", name);
+
+                let parameters_len = parameters.len();
+                for (i, parameter) in parameters.into_iter().enumerate() {
+                    string.push_str(&parameter.wgsl());
+
+                    if i != parameters_len - 1 {
+                        string.push_str(", ");
+                    }
+                }
+
+                string.push(')');
+
+                string
+            }
+            Expression::StructCreation(name, members, _) => {
+                let mut wgsl = format!("{}(", name);
+
+                let last = members.len();
+                for (i, member) in members.into_iter().enumerate() {
+                    wgsl.push_str(&member.wgsl());
+
+                    if i != last - 1 {
+                        wgsl.push_str(", ");
+                    }
+                }
+
+                wgsl.push(')');
+
+                wgsl
+            }
+            Expression::FloatLiteral(value) => {
+                if value.fract() == 0.0 {
+                    format!("{}.0", value)
+                } else {
+                    format!("{}", value)
+                }
+            }
+            Expression::UintLiteral(value) => format!("{}u", value),
+            Expression::MemberAccess(expression, member_name, _) => {
+                format!("{}.{}", expression.wgsl(), member_name)
+            }
+            Expression::Index(expression, index, _) => {
+                format!("{}[{}]", expression.wgsl(), index.wgsl())
+            }
+            Expression::Multiply(left_expression, right_expression, _) => {
+                format!("({} * {})", left_expression.wgsl(), right_expression.wgsl())
+            }
+            Expression::Add(left_expression, right_expression, _) => {
+                format!("({} + {})", left_expression.wgsl(), right_expression.wgsl())
+            }
+            Expression::Subtract(left_expression, right_expression, _) => {
+                format!("({} - {})", left_expression.wgsl(), right_expression.wgsl())
+            }
+        }
+    }
+
+    // "resource_arguments" holds the resources to pass on to each user function
+    pub fn msl(self, resource_arguments: &HashMap<String, String>) -> String {
+        match self {
+            Expression::Empty => String::new(),
+            Expression::Variable(variable, _) => msl_identifier(&variable),
+            Expression::FunctionCall(name, parameters, _) if is_texture_call(&parameters) => {
+                texture_call(name, parameters, |parameter| {
+                    parameter.msl(resource_arguments)
+                })
+                .msl()
+            }
+            Expression::FunctionCall(name, parameters, _) => {
+                let mut arguments: Vec<String> = parameters
+                    .into_iter()
+                    .map(|parameter| parameter.msl(resource_arguments))
+                    .collect();
+                if let Some(resources) = resource_arguments.get(&name) {
+                    if !resources.is_empty() {
+                        arguments.push(resources.clone());
+                    }
+                }
+
+                let name = match name.as_str() {
+                    "frac" | "frac2" | "frac3" | "frac4" => "fract".to_owned(),
+                    "uint_to_float" | "int_to_float" | "half_to_float" => "float".to_owned(),
+                    "float_to_uint" | "int_to_uint" => "uint".to_owned(),
+                    "float_to_int" | "uint_to_int" => "int".to_owned(),
+                    _ => msl_identifier(&name),
+                };
+
+                format!("{}({})", name, arguments.join(", "))
+            }
+            Expression::StructCreation(name, members, _) => {
+                let members: Vec<String> = members
+                    .into_iter()
+                    .map(|member| member.msl(resource_arguments))
+                    .collect();
+
+                format!("{}{{{}}}", name, members.join(", "))
+            }
+            Expression::FloatLiteral(value) => {
+                if value.fract() == 0.0 {
+                    format!("{}.0", value)
+                } else {
+                    format!("{}", value)
+                }
+            }
+            Expression::UintLiteral(value) => format!("{}u", value),
+            Expression::MemberAccess(expression, member_name, _) => {
+                format!("{}.{}", expression.msl(resource_arguments), member_name)
+            }
+            Expression::Index(expression, index, _) => format!(
+                "{}[{}]",
+                expression.msl(resource_arguments),
+                index.msl(resource_arguments)
+            ),
+            Expression::Multiply(left_expression, right_expression, _) => format!(
+                "({} * {})",
+                left_expression.msl(resource_arguments),
+                right_expression.msl(resource_arguments)
+            ),
+            Expression::Add(left_expression, right_expression, _) => format!(
+                "({} + {})",
+                left_expression.msl(resource_arguments),
+                right_expression.msl(resource_arguments)
+            ),
+            Expression::Subtract(left_expression, right_expression, _) => format!(
+                "({} - {})",
+                left_expression.msl(resource_arguments),
+                right_expression.msl(resource_arguments)
+            ),
+        }
+    }
+
+    // Emits the instructions computing the expression and returns the id of the result
+    pub fn spirv(&self, context: &mut Context) -> Id {
+        match self {
+            Expression::Empty => panic!("Empty expressions have no value"),
+            Expression::Variable(variable, variable_type) => {
+                context.variable(variable, variable_type)
+            }
+            Expression::FunctionCall(name, parameters, return_type) => {
+                spirv_function_call(name, parameters, return_type, context)
+            }
+            Expression::StructCreation(_, members, struct_type) => {
+                let members: Vec<Id> = members.iter().map(|member| member.spirv(context)).collect();
+                spirv_composite(struct_type, &members, context)
+            }
+            Expression::FloatLiteral(value) => context.module.constant_float(*value as f32),
+            Expression::UintLiteral(value) => context.module.constant_uint(*value),
+            Expression::MemberAccess(expression, member_name, member_type) => {
+                let composite_type = expression.get_type();
+                let composite = expression.spirv(context);
+
+                // "x" of a scalar is the scalar itself
+                if composite_type.is_float() {
+                    return composite;
+                }
+
+                let index = composite_type
+                    .members()
+                    .iter()
+                    .position(|(name, _)| name == member_name)
+                    .unwrap();
+                let result_type = member_type.spirv(&mut context.module);
+                context.module.emit_result(
+                    spirv::OP_COMPOSITE_EXTRACT,
+                    result_type,
+                    &[composite, index as u32],
+                )
+            }
+            Expression::Index(_, _, _) => panic!("SPIR-V output has no storage buffers"),
+            Expression::Multiply(left_expression, right_expression, product_type) => {
+                let left_type = left_expression.get_type();
+                let right_type = right_expression.get_type();
+                let mut left = left_expression.spirv(context);
+                let mut right = right_expression.spirv(context);
+
+                let scalar = |value_type: &Type| value_type.is_float();
+                let opcode = if left_type.is_integer() {
+                    spirv::OP_I_MUL
+                } else if left_type.is_float_matrix() && right_type.is_float_matrix() {
+                    spirv::OP_MATRIX_TIMES_MATRIX
+                } else if left_type.is_float_matrix() && right_type.is_float_vector() {
+                    spirv::OP_MATRIX_TIMES_VECTOR
+                } else if left_type.is_float_vector() && right_type.is_float_matrix() {
+                    spirv::OP_VECTOR_TIMES_MATRIX
+                } else if scalar(&left_type) && scalar(&right_type) {
+                    spirv::OP_F_MUL
+                } else {
+                    // The scalar always comes second
+                    if scalar(&left_type) {
+                        std::mem::swap(&mut left, &mut right);
+                    }
+
+                    if product_type.is_float_matrix() {
+                        spirv::OP_MATRIX_TIMES_SCALAR
+                    } else if scalar(&left_type) || scalar(&right_type) {
+                        spirv::OP_VECTOR_TIMES_SCALAR
+                    } else {
+                        spirv::OP_F_MUL
+                    }
+                };
+
+                let result_type = product_type.spirv(&mut context.module);
+                context
+                    .module
+                    .emit_result(opcode, result_type, &[left, right])
+            }
+            Expression::Add(left_expression, right_expression, sum_type)
+            | Expression::Subtract(left_expression, right_expression, sum_type) => {
+                let add = matches!(self, Expression::Add(..));
+                let opcode = match (add, sum_type.is_integer()) {
+                    (true, true) => spirv::OP_I_ADD,
+                    (true, false) => spirv::OP_F_ADD,
+                    (false, true) => spirv::OP_I_SUB,
+                    (false, false) => spirv::OP_F_SUB,
+                };
+
+                let left = left_expression.spirv(context);
+                let right = right_expression.spirv(context);
+                let result_type = sum_type.spirv(&mut context.module);
+                context
+                    .module
+                    .emit_result(opcode, result_type, &[left, right])
+            }
+        }
+    }
+
+    // Collects the names of the variables and functions used by the expression
+    pub fn references(&self, variables: &mut Vec<String>, functions: &mut Vec<String>) {
+        match self {
+            Expression::Empty | Expression::FloatLiteral(_) | Expression::UintLiteral(_) => {}
+            Expression::Variable(variable, _) => push_unique(variables, variable),
+            Expression::FunctionCall(name, parameters, _) => {
+                push_unique(functions, name);
+                for parameter in parameters {
+                    parameter.references(variables, functions);
+                }
+            }
+            Expression::StructCreation(_, members, _) => {
+                for member in members {
+                    member.references(variables, functions);
+                }
+            }
+            Expression::MemberAccess(expression, _, _) => {
+                expression.references(variables, functions)
+            }
+            Expression::Multiply(left_expression, right_expression, _)
+            | Expression::Index(left_expression, right_expression, _)
+            | Expression::Add(left_expression, right_expression, _)
+            | Expression::Subtract(left_expression, right_expression, _) => {
+                left_expression.references(variables, functions);
+                right_expression.references(variables, functions);
+            }
+        }
+    }
+
     pub fn get_type(&self) -> Type {
         match self {
             Expression::Empty => Type::void(),
@@ -112,27 +445,74 @@ impl Expression {
             Expression::FunctionCall(_, _, return_type) => return_type.clone(),
             Expression::StructCreation(_, _, struct_type) => struct_type.clone(),
             Expression::FloatLiteral(_) => Type::float(),
+            Expression::UintLiteral(_) => Type::uint(),
             Expression::MemberAccess(_, _, member_type) => member_type.clone(),
+            Expression::Index(_, _, element_type) => element_type.clone(),
             Expression::Multiply(_, _, product_type) => product_type.clone(),
             Expression::Add(_, _, sum_type) => sum_type.clone(),
             Expression::Subtract(_, _, sum_type) => sum_type.clone(),
         }
     }
 
-    pub fn glsl(self) -> String {
+    pub fn glsl(self, options: &CompileOptions) -> String {
         match self {
             Expression::Empty => String::new(),
             Expression::Variable(variable, _) => variable,
+            // Images always hold four components
+            Expression::FunctionCall(name, mut parameters, texel_type)
+                if is_storage_texture_access(&name, &parameters) =>
+            {
+                let texture = parameters.remove(0).glsl(options);
+                let coordinates = parameters.remove(0).glsl(options);
+                match parameters.pop() {
+                    Some(texel) => {
+                        let texel_type = texel.get_type();
+                        format!(
+                            "imageStore({}, ivec2({}), {})",
+                            texture,
+                            coordinates,
+                            storage_texture::glsl_texel_vec4(&texel_type, &texel.glsl(options))
+                        )
+                    }
+                    None => format!(
+                        "imageLoad({}, ivec2({})){}",
+                        texture,
+                        coordinates,
+                        storage_texture::glsl_texel_swizzle(&texel_type)
+                    ),
+                }
+            }
+            Expression::FunctionCall(name, parameters, _) if is_texture_call(&parameters) => {
+                texture_call(name, parameters, |parameter| parameter.glsl(options)).glsl(options)
+            }
+            // The stream is implicit, "acsl_emit" writes the outputs of the
+            // geometry shader before emitting the vertex
+            Expression::FunctionCall(name, mut parameters, _) if name == "emit" => {
+                format!("acsl_emit({})", parameters.remove(1).glsl(options))
+            }
+            Expression::FunctionCall(name, _, _) if name == "end_primitive" => {
+                "EndPrimitive()".to_owned()
+            }
             Expression::FunctionCall(name, parameters, _) => {
                 let name = match name.as_str() {
                     "float1" => "vec1",
                     "float2" => "vec2",
                     "float3" => "vec3",
                     "float4" => "vec4",
-                    "sample_texture" => "texture",
+                    "uint2" => "uvec2",
+                    "uint3" => "uvec3",
                     "frac" | "frac2" | "frac3" | "frac4" => "fract",
-                    "uint_to_float" => "float",
-                    "load" => panic!("Not supported on GLSL"),
+                    "uint_to_float" | "int_to_float" | "half_to_float" => "float",
+                    "float_to_uint" | "int_to_uint" => "uint",
+                    "float_to_int" | "uint_to_int" => "int",
+                    "half" => "float16_t",
+                    "atomic_add" => "atomicAdd",
+                    "atomic_min" => "atomicMin",
+                    "atomic_max" => "atomicMax",
+                    "atomic_and" => "atomicAnd",
+                    "atomic_or" => "atomicOr",
+                    "atomic_exchange" => "atomicExchange",
+                    "atomic_compare_exchange" => "atomicCompSwap",
                     _ => &name,
                 };
 
@@ -141,7 +521,7 @@ impl Expression {
                 let mut i = 0;
                 let parameters_len = parameters.len();
                 for parameter in parameters {
-                    string.push_str(&parameter.glsl());
+                    string.push_str(&parameter.glsl(options));
 
                     if i != parameters_len - 1 {
                         string.push_str(", ");
@@ -164,7 +544,7 @@ impl Expression {
                 let mut i = 0;
                 let last = members.len();
                 for member in members {
-                    glsl.push_str(&member.glsl());
+                    glsl.push_str(&member.glsl(options));
 
                     if i != last - 1 {
                         glsl.push_str(", ");
@@ -184,18 +564,441 @@ impl Expression {
                     format!("{}", value)
                 }
             }
+            Expression::UintLiteral(value) => format!("{}u", value),
             Expression::MemberAccess(expression, member_name, _) => {
-                format!("{}.{}", expression.glsl(), member_name)
+                format!("{}.{}", expression.glsl(options), member_name)
+            }
+            Expression::Index(expression, index, _) => {
+                format!("{}[{}]", expression.glsl(options), index.glsl(options))
             }
             Expression::Multiply(left_expression, right_expression, _) => {
-                format!("({} * {})", left_expression.glsl(), right_expression.glsl())
+                format!(
+                    "({} * {})",
+                    left_expression.glsl(options),
+                    right_expression.glsl(options)
+                )
             }
             Expression::Add(left_expression, right_expression, _) => {
-                format!("({} + {})", left_expression.glsl(), right_expression.glsl())
+                format!(
+                    "({} + {})",
+                    left_expression.glsl(options),
+                    right_expression.glsl(options)
+                )
             }
             Expression::Subtract(left_expression, right_expression, _) => {
-                format!("({} - {})", left_expression.glsl(), right_expression.glsl())
+                format!(
+                    "({} - {})",
+                    left_expression.glsl(options),
+                    right_expression.glsl(options)
+                )
             }
         }
     }
 }
+
+// Textures can't be passed to user functions, so any call taking one first is
+// one of the texture functions
+fn is_texture_call(parameters: &[Expression]) -> bool {
+    parameters
+        .first()
+        .is_some_and(|texture| texture.get_type().texture_dimension().is_some())
+}
+
+// Renders the arguments of a texture function, the offset of "sample_offset"
+// is folded into whole numbers
+fn texture_call(
+    name: String,
+    mut parameters: Vec<Expression>,
+    mut render: impl FnMut(Expression) -> String,
+) -> texture::TextureCall {
+    let texture_type = parameters[0].get_type();
+    let offset = match name.as_str() {
+        "sample_offset" => constant_offset(&parameters.pop().unwrap()),
+        _ => Vec::new(),
+    };
+
+    let texture = render(parameters.remove(0));
+    let sampler = match parameters.first() {
+        Some(sampler) if sampler.get_type().is_sampler() => Some(render(parameters.remove(0))),
+        _ => None,
+    };
+    let arguments = parameters.into_iter().map(render).collect();
+
+    texture::TextureCall {
+        name,
+        texture_type,
+        texture,
+        sampler,
+        arguments,
+        offset,
+    }
+}
+
+// Offsets are built from float literals by the semantic analysis
+fn constant_offset(offset: &Expression) -> Vec<i64> {
+    match offset {
+        Expression::FunctionCall(_, components, _) => components
+            .iter()
+            .map(|component| match component {
+                Expression::FloatLiteral(value) => *value as i64,
+                _ => panic!("Texture offsets are folded into literals"),
+            })
+            .collect(),
+        _ => panic!("Texture offsets are folded into literals"),
+    }
+}
+
+// "load" and "store" on storage textures, "load" is also used for textures
+fn is_storage_texture_access(name: &str, parameters: &[Expression]) -> bool {
+    (name == "load" || name == "store")
+        && parameters
+            .first()
+            .is_some_and(|texture| texture.get_type().storage_texel().is_some())
+}
+
+pub fn is_atomic(name: &str) -> bool {
+    matches!(
+        name,
+        "atomic_add"
+            | "atomic_min"
+            | "atomic_max"
+            | "atomic_and"
+            | "atomic_or"
+            | "atomic_exchange"
+            | "atomic_compare_exchange"
+    )
+}
+
+// HLSL atomic operations are statements which write the previous value to
+// "original", "InterlockedExchange" always needs one
+pub fn hlsl_atomic(name: &str, parameters: Vec<Expression>, original: Option<&str>) -> String {
+    let function = match (name, original) {
+        ("atomic_add", _) => "InterlockedAdd",
+        ("atomic_min", _) => "InterlockedMin",
+        ("atomic_max", _) => "InterlockedMax",
+        ("atomic_and", _) => "InterlockedAnd",
+        ("atomic_or", _) => "InterlockedOr",
+        ("atomic_exchange", _) => "InterlockedExchange",
+        ("atomic_compare_exchange", Some(_)) => "InterlockedCompareExchange",
+        ("atomic_compare_exchange", None) => "InterlockedCompareStore",
+        _ => panic!("Unknown atomic operation \"{}\"", name),
+    };
+
+    let mut arguments: Vec<String> = parameters
+        .into_iter()
+        .map(|parameter| parameter.hlsl())
+        .collect();
+    if let Some(original) = original {
+        arguments.push(original.to_owned());
+    }
+
+    format!("{}({});\n", function, arguments.join(", "))
+}
+
+fn hlsl_wave_intrinsic(name: &str) -> &'static str {
+    match name {
+        "wave_lane_index" => "WaveGetLaneIndex",
+        "wave_lane_count" => "WaveGetLaneCount",
+        "wave_active_sum" => "WaveActiveSum",
+        "wave_read_first" => "WaveReadLaneFirst",
+        name => panic!("Unknown wave intrinsic \"{}\"", name),
+    }
+}
+
+fn spirv_function_call(
+    name: &str,
+    parameters: &[Expression],
+    return_type: &Type,
+    context: &mut Context,
+) -> Id {
+    if is_texture_call(parameters) {
+        return spirv_texture_call(name, parameters, return_type, context);
+    }
+
+    match name {
+        "float" => parameters[0].spirv(context),
+        "float1" | "float2" | "float3" | "float4" | "uint2" | "uint3" => {
+            let components: Vec<Id> = parameters
+                .iter()
+                .map(|parameter| parameter.spirv(context))
+                .collect();
+            spirv_composite(return_type, &components, context)
+        }
+        "frac" | "frac2" | "frac3" | "frac4" | "floor" => {
+            let instruction = if name == "floor" {
+                spirv::GLSL_STD_450_FLOOR
+            } else {
+                spirv::GLSL_STD_450_FRACT
+            };
+
+            let value = parameters[0].spirv(context);
+            let result_type = return_type.spirv(&mut context.module);
+            let glsl_std_450 = context.module.glsl_std_450();
+            context.module.emit_result(
+                spirv::OP_EXT_INST,
+                result_type,
+                &[glsl_std_450, instruction, value],
+            )
+        }
+        "uint_to_float" => {
+            let value = parameters[0].spirv(context);
+            let result_type = return_type.spirv(&mut context.module);
+            context
+                .module
+                .emit_result(spirv::OP_CONVERT_U_TO_F, result_type, &[value])
+        }
+        "float_to_uint" => {
+            let value = parameters[0].spirv(context);
+            let result_type = return_type.spirv(&mut context.module);
+            context
+                .module
+                .emit_result(spirv::OP_CONVERT_F_TO_U, result_type, &[value])
+        }
+        "int_to_float" => {
+            let value = parameters[0].spirv(context);
+            let result_type = return_type.spirv(&mut context.module);
+            context
+                .module
+                .emit_result(spirv::OP_CONVERT_S_TO_F, result_type, &[value])
+        }
+        "float_to_int" => {
+            let value = parameters[0].spirv(context);
+            let result_type = return_type.spirv(&mut context.module);
+            context
+                .module
+                .emit_result(spirv::OP_CONVERT_F_TO_S, result_type, &[value])
+        }
+        // Only the signedness differs, the bits are kept
+        "uint_to_int" | "int_to_uint" => {
+            let value = parameters[0].spirv(context);
+            let result_type = return_type.spirv(&mut context.module);
+            context
+                .module
+                .emit_result(spirv::OP_BITCAST, result_type, &[value])
+        }
+        "half" | "half_to_float" => {
+            let value = parameters[0].spirv(context);
+            let result_type = return_type.spirv(&mut context.module);
+            context
+                .module
+                .emit_result(spirv::OP_F_CONVERT, result_type, &[value])
+        }
+        name => {
+            let arguments: Vec<Id> = parameters
+                .iter()
+                .map(|parameter| parameter.spirv(context))
+                .collect();
+            let (function, result_type) = context.function(name).unwrap();
+
+            let mut operands = vec![function];
+            operands.extend(arguments);
+            context
+                .module
+                .emit_result(spirv::OP_FUNCTION_CALL, result_type, &operands)
+        }
+    }
+}
+
+// Implicit level of detail, used by "sample", "sample_bias", "sample_offset" and
+// "sample_compare", is only valid in fragment shaders
+fn spirv_texture_call(
+    name: &str,
+    parameters: &[Expression],
+    return_type: &Type,
+    context: &mut Context,
+) -> Id {
+    let (image, image_type, sampler, sampler_type, _) = spirv_texture(&parameters[0], context);
+    let result_type = return_type.spirv(&mut context.module);
+
+    if name == "texture_size" {
+        context.module.capability(spirv::CAPABILITY_IMAGE_QUERY);
+
+        let level = context.module.constant_int(0);
+        let image = context
+            .module
+            .emit_result(spirv::OP_LOAD, image_type, &[image]);
+        return context.module.emit_result(
+            spirv::OP_IMAGE_QUERY_SIZE_LOD,
+            result_type,
+            &[image, level],
+        );
+    }
+
+    if name == "load" {
+        let coordinates = parameters[1].spirv(context);
+        let level = match parameters.get(2) {
+            Some(level) => level.spirv(context),
+            None => context.module.constant_int(0),
+        };
+
+        let image = context
+            .module
+            .emit_result(spirv::OP_LOAD, image_type, &[image]);
+        let component_type = match return_type.is_integer() {
+            true => context.module.type_uint(),
+            false => context.module.type_float(),
+        };
+        let texel_type = context.module.type_vector(component_type, 4);
+        let texel = context.module.emit_result(
+            spirv::OP_IMAGE_FETCH,
+            texel_type,
+            &[image, coordinates, spirv::IMAGE_OPERANDS_LOD, level],
+        );
+
+        return spirv_narrow(texel, return_type, context);
+    }
+
+    let (sampler, sampler_type, arguments) = match parameters.get(1) {
+        Some(parameter) if parameter.get_type().is_sampler() => {
+            let (sampler, sampler_type) = spirv_sampler(parameter, context);
+            (sampler, sampler_type, &parameters[2..])
+        }
+        _ => (sampler, sampler_type, &parameters[1..]),
+    };
+
+    let mut operands: Vec<Id> = Vec::new();
+    for (i, argument) in arguments.iter().enumerate() {
+        if name == "sample_offset" && i == arguments.len() - 1 {
+            let int_type = context.module.type_int();
+            let offset = constant_offset(argument);
+            let offset_type = context.module.type_vector(int_type, offset.len() as u32);
+            let components: Vec<Id> = offset
+                .into_iter()
+                .map(|component| context.module.constant_int(component as i32))
+                .collect();
+            operands.push(spirv::IMAGE_OPERANDS_CONST_OFFSET);
+            operands.push(context.module.constant_composite(offset_type, &components));
+        } else {
+            operands.push(argument.spirv(context));
+        }
+    }
+
+    let sampled_image_type = context.module.type_sampled_image(image_type);
+    let image = context
+        .module
+        .emit_result(spirv::OP_LOAD, image_type, &[image]);
+    let sampler = context
+        .module
+        .emit_result(spirv::OP_LOAD, sampler_type, &[sampler]);
+    let sampled_image = context.module.emit_result(
+        spirv::OP_SAMPLED_IMAGE,
+        sampled_image_type,
+        &[image, sampler],
+    );
+    operands.insert(0, sampled_image);
+
+    // The image operands follow the coordinates
+    let opcode = match name {
+        "sample_level" => {
+            operands.insert(2, spirv::IMAGE_OPERANDS_LOD);
+            spirv::OP_IMAGE_SAMPLE_EXPLICIT_LOD
+        }
+        "sample_bias" => {
+            operands.insert(2, spirv::IMAGE_OPERANDS_BIAS);
+            spirv::OP_IMAGE_SAMPLE_IMPLICIT_LOD
+        }
+        "sample_grad" => {
+            operands.insert(2, spirv::IMAGE_OPERANDS_GRAD);
+            spirv::OP_IMAGE_SAMPLE_EXPLICIT_LOD
+        }
+        "sample_compare" => spirv::OP_IMAGE_SAMPLE_DREF_IMPLICIT_LOD,
+        "sample_compare_level_zero" => {
+            operands.push(spirv::IMAGE_OPERANDS_LOD);
+            operands.push(context.module.constant_float(0.0));
+            spirv::OP_IMAGE_SAMPLE_DREF_EXPLICIT_LOD
+        }
+        // Gathers of color textures take the component to gather
+        "gather" => {
+            operands.push(context.module.constant_int(0));
+            spirv::OP_IMAGE_GATHER
+        }
+        _ => spirv::OP_IMAGE_SAMPLE_IMPLICIT_LOD,
+    };
+
+    // Color textures are sampled as four components, narrowed like fetches
+    if matches!(
+        opcode,
+        spirv::OP_IMAGE_SAMPLE_IMPLICIT_LOD | spirv::OP_IMAGE_SAMPLE_EXPLICIT_LOD
+    ) {
+        let float_type = context.module.type_float();
+        let texel_type = context.module.type_vector(float_type, 4);
+        let texel = context.module.emit_result(opcode, texel_type, &operands);
+        return spirv_narrow(texel, return_type, context);
+    }
+
+    context.module.emit_result(opcode, result_type, &operands)
+}
+
+// Texels are read as four components and narrowed to the element type
+fn spirv_narrow(texel: Id, element_type: &Type, context: &mut Context) -> Id {
+    let result_type = element_type.spirv(&mut context.module);
+
+    match element_type.members().len() {
+        0 | 1 => context
+            .module
+            .emit_result(spirv::OP_COMPOSITE_EXTRACT, result_type, &[texel, 0]),
+        4 => texel,
+        n => {
+            let mut operands = vec![texel, texel];
+            operands.extend(0..n as u32);
+            context
+                .module
+                .emit_result(spirv::OP_VECTOR_SHUFFLE, result_type, &operands)
+        }
+    }
+}
+
+// Constant operands are folded into a constant composite
+fn spirv_composite(composite_type: &Type, constituents: &[Id], context: &mut Context) -> Id {
+    let result_type = composite_type.spirv(&mut context.module);
+
+    if constituents
+        .iter()
+        .all(|constituent| context.module.is_constant(*constituent))
+    {
+        context.module.constant_composite(result_type, constituents)
+    } else {
+        context
+            .module
+            .emit_result(spirv::OP_COMPOSITE_CONSTRUCT, result_type, constituents)
+    }
+}
+
+fn spirv_texture(expression: &Expression, context: &Context) -> (Id, Id, Id, Id, bool) {
+    let name = match expression {
+        Expression::Variable(name, _) => name,
+        _ => panic!("Textures must be referenced by name"),
+    };
+
+    match context.texture(name) {
+        Some(Global::Texture {
+            image,
+            image_type,
+            sampler,
+            sampler_type,
+            uint,
+        }) => (*image, *image_type, *sampler, *sampler_type, *uint),
+        _ => panic!("\"{}\" is not a texture", name),
+    }
+}
+
+fn spirv_sampler(expression: &Expression, context: &Context) -> (Id, Id) {
+    let name = match expression {
+        Expression::Variable(name, _) => name,
+        _ => panic!("Samplers must be referenced by name"),
+    };
+
+    match context.sampler(name) {
+        Some(Global::Sampler {
+            sampler,
+            sampler_type,
+        }) => (*sampler, *sampler_type),
+        _ => panic!("\"{}\" is not a sampler", name),
+    }
+}
+
+pub(super) fn push_unique(names: &mut Vec<String>, name: &str) {
+    if !names.iter().any(|existing| existing == name) {
+        names.push(name.to_owned());
+    }
+}
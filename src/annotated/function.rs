@@ -1,11 +1,33 @@
-use super::code_block::CodeBlock;
-use crate::types::Type;
+use super::{
+    code_block::CodeBlock,
+    msl_identifier,
+    structure::{glsl_fragment_input, glsl_vertex_input},
+};
+use crate::{
+    feature::Feature,
+    options::CompileOptions,
+    spirv::{
+        Context, Id, FUNCTION_CONTROL_NONE, OP_FUNCTION, OP_FUNCTION_END, OP_FUNCTION_PARAMETER,
+        OP_LABEL, OP_RETURN, OP_UNREACHABLE,
+    },
+    types::Type,
+};
+use std::collections::HashMap;
 
 pub struct Function {
     name: String,
     return_type: Type,
     parameters: Vec<FunctionParameter>,
     code_block: Option<CodeBlock>,
+    attribute: Option<FunctionAttribute>,
+}
+
+// "#[compute(x, y, z)]" gives the thread group size of a compute kernel,
+// "#[geometry(n)]" the maximum number of vertices emitted by "geometry_main()"
+#[derive(Clone, Copy)]
+pub enum FunctionAttribute {
+    Compute(usize, usize, usize),
+    Geometry(usize),
 }
 
 pub struct FunctionParameter {
@@ -19,12 +41,14 @@ impl Function {
         parameters: Vec<FunctionParameter>,
         return_type: Type,
         code_block: CodeBlock,
+        attribute: Option<FunctionAttribute>,
     ) -> Self {
         Function {
             name,
             parameters,
             return_type,
             code_block: Some(code_block),
+            attribute,
         }
     }
 
@@ -68,12 +92,21 @@ impl Function {
                 Type::float4(),
             ),
             Function::new_builtin(
-                "sample_texture".to_owned(),
+                "uint2".to_owned(),
                 vec![
-                    FunctionParameter::new("texture".to_owned(), Type::texture()),
-                    FunctionParameter::new("coordinates".to_owned(), Type::float2()),
+                    FunctionParameter::new("x".to_owned(), Type::uint()),
+                    FunctionParameter::new("y".to_owned(), Type::uint()),
                 ],
-                Type::float4(),
+                Type::uintn(2),
+            ),
+            Function::new_builtin(
+                "uint3".to_owned(),
+                vec![
+                    FunctionParameter::new("x".to_owned(), Type::uint()),
+                    FunctionParameter::new("y".to_owned(), Type::uint()),
+                    FunctionParameter::new("z".to_owned(), Type::uint()),
+                ],
+                Type::uint3(),
             ),
             Function::new_builtin(
                 "frac".to_owned(),
@@ -101,18 +134,58 @@ impl Function {
                 Type::float(),
             ),
             Function::new_builtin(
-                "load".to_owned(),
-                vec![
-                    FunctionParameter::new("texture".to_owned(), Type::texture()),
-                    FunctionParameter::new("uv".to_owned(), Type::float3()),
-                ],
+                "uint_to_float".to_owned(),
+                vec![FunctionParameter::new("value".to_owned(), Type::uint())],
+                Type::float(),
+            ),
+            Function::new_builtin(
+                "float_to_uint".to_owned(),
+                vec![FunctionParameter::new("value".to_owned(), Type::float())],
                 Type::uint(),
             ),
             Function::new_builtin(
-                "uint_to_float".to_owned(),
+                "int_to_float".to_owned(),
+                vec![FunctionParameter::new("value".to_owned(), Type::int())],
+                Type::float(),
+            ),
+            Function::new_builtin(
+                "float_to_int".to_owned(),
+                vec![FunctionParameter::new("value".to_owned(), Type::float())],
+                Type::int(),
+            ),
+            Function::new_builtin(
+                "uint_to_int".to_owned(),
                 vec![FunctionParameter::new("value".to_owned(), Type::uint())],
+                Type::int(),
+            ),
+            Function::new_builtin(
+                "int_to_uint".to_owned(),
+                vec![FunctionParameter::new("value".to_owned(), Type::int())],
+                Type::uint(),
+            ),
+            Function::new_builtin(
+                "half".to_owned(),
+                vec![FunctionParameter::new("x".to_owned(), Type::float())],
+                Type::half(),
+            ),
+            Function::new_builtin(
+                "half_to_float".to_owned(),
+                vec![FunctionParameter::new("value".to_owned(), Type::half())],
                 Type::float(),
             ),
+            Function::new_builtin("wave_lane_index".to_owned(), vec![], Type::uint()),
+            Function::new_builtin("wave_lane_count".to_owned(), vec![], Type::uint()),
+            Function::new_builtin(
+                "wave_active_sum".to_owned(),
+                vec![FunctionParameter::new("value".to_owned(), Type::float())],
+                Type::float(),
+            ),
+            Function::new_builtin(
+                "wave_read_first".to_owned(),
+                vec![FunctionParameter::new("value".to_owned(), Type::float())],
+                Type::float(),
+            ),
+            Function::new_builtin("group_barrier".to_owned(), vec![], Type::void()),
         ]
         .into_boxed_slice()
     }
@@ -129,33 +202,124 @@ impl Function {
         &self.return_type
     }
 
-    pub fn generate_hlsl(self) -> String {
-        let mut string = format!("{} {}(", self.return_type.hlsl(), self.name);
+    pub fn thread_group_size(&self) -> Option<(usize, usize, usize)> {
+        match self.attribute {
+            Some(FunctionAttribute::Compute(x, y, z)) => Some((x, y, z)),
+            _ => None,
+        }
+    }
 
-        for i in 0..self.parameters.len() {
-            string.push_str(&format!(
-                "{} {}",
-                self.parameters[i].parameter_type.hlsl(),
-                self.parameters[i].name
-            ));
+    pub fn max_vertex_count(&self) -> Option<usize> {
+        match self.attribute {
+            Some(FunctionAttribute::Geometry(max_vertices)) => Some(max_vertices),
+            _ => None,
+        }
+    }
 
-            if i != self.parameters.len() - 1 {
-                string.push_str(", ");
+    pub fn is_compute_kernel(&self) -> bool {
+        self.thread_group_size().is_some()
+    }
+
+    pub fn generate_hlsl(self, options: &CompileOptions) -> String {
+        let name = match self.name.as_str() {
+            "vertex_main" => options.get_vertex_entry_point(),
+            "fragment_main" => options.get_fragment_entry_point(),
+            name => name,
+        };
+
+        let mut string = match self.attribute {
+            Some(FunctionAttribute::Compute(x, y, z)) => {
+                format!("[numthreads({}, {}, {})]\n", x, y, z)
             }
+            Some(FunctionAttribute::Geometry(max_vertices)) => {
+                format!("[maxvertexcount({})]\n", max_vertices)
+            }
+            None => String::new(),
+        };
+
+        string.push_str(&format!("{} {}(", self.return_type.hlsl(), name));
+
+        let front_face = self.front_face_member().map(str::to_owned);
+        let mut parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|parameter| self.hlsl_parameter(parameter))
+            .collect();
+        if front_face.is_some() {
+            parameters[0] = format!(
+                "{} acsl_{}",
+                self.parameters[0].parameter_type.hlsl(),
+                self.parameters[0].name
+            );
+            parameters.push("bool acsl_front_facing: SV_IsFrontFace".to_owned());
         }
+        string.push_str(&parameters.join(", "));
 
         string.push_str(") ");
 
-        if &self.name == "fragment_main" {
+        // Structures carry the semantics of each render target
+        if &self.name == "fragment_main" && self.return_type == Type::float4() {
             string.push_str(": SV_TARGET ");
         }
 
-        string.push_str(&self.code_block.unwrap().hlsl());
+        let mut hlsl = self.code_block.unwrap().hlsl(options);
+        if let Some(member) = front_face {
+            let parameter = &self.parameters[0];
+            hlsl.insert_str(
+                "{\n".len(),
+                &format!(
+                    "{0}{1} {2} = acsl_{2};\n{0}{2}.{3} = uint(acsl_front_facing);\n",
+                    options.indent(1),
+                    parameter.parameter_type.hlsl(),
+                    parameter.name,
+                    member
+                ),
+            );
+        }
+        string.push_str(&hlsl);
 
         string
     }
 
-    pub fn generate_glsl(self, position_output_name: &str) -> (String, String) {
+    // The inputs of the tessellation and geometry stages are read through
+    // their system values and modifiers
+    fn hlsl_parameter(&self, parameter: &FunctionParameter) -> String {
+        let parameter_type = &parameter.parameter_type;
+        match (self.name.as_str(), parameter_type) {
+            ("domain_main", Type::Patch(control_point_type, count)) => format!(
+                "const OutputPatch<{}, {}> {}",
+                control_point_type.hlsl(),
+                count,
+                parameter.name
+            ),
+            (_, Type::GeometryInput(_, topology)) => format!(
+                "{} {}[{}]",
+                parameter_type.hlsl(),
+                parameter.name,
+                topology.vertices()
+            ),
+            (_, Type::Stream(_, _)) => {
+                format!("inout {} {}", parameter_type.hlsl(), parameter.name)
+            }
+            ("hull_main", _) if *parameter_type == Type::uint() => {
+                format!("uint {} : SV_OutputControlPointID", parameter.name)
+            }
+            ("domain_main", _) if parameter_type.is_float_vector() => {
+                format!(
+                    "{} {} : SV_DomainLocation",
+                    parameter_type.hlsl(),
+                    parameter.name
+                )
+            }
+            _ => format!("{} {}", parameter_type.hlsl(), parameter.name),
+        }
+    }
+
+    pub fn generate_glsl(
+        self,
+        position_output_name: &str,
+        options: &CompileOptions,
+    ) -> (String, String) {
         let (mut glsl, vertex_main, fragment_main) = match self.name() {
             "vertex_main" | "fragment_main" => {
                 let vertex = self.name() == "vertex_main";
@@ -165,19 +329,35 @@ impl Function {
                 let parameter = &self.parameters[0];
                 let parameter_type = parameter.parameter_type();
                 glsl.push_str(&format!(
-                    "    {} {} = {}(",
+                    "{}{} {} = {}(",
+                    options.indent(1),
                     parameter_type.glsl(),
                     parameter.name,
                     parameter_type.glsl()
                 ));
 
+                // System values are read from their builtins
+                let vulkan = options.get_glsl_version().is_vulkan();
                 let members = parameter_type.members();
                 for i in 0..members.len() {
-                    glsl.push_str(&format!(
-                        "acsl_{}_input_{}",
-                        if vertex { "vertex" } else { "pixel" },
-                        members[i].0
-                    ));
+                    let builtin = match parameter_type {
+                        Type::Struct(structure) if vertex => structure
+                            .semantic(i)
+                            .and_then(|semantic| glsl_vertex_input(semantic, vulkan)),
+                        Type::Struct(structure) => {
+                            structure.semantic(i).and_then(glsl_fragment_input)
+                        }
+                        _ => None,
+                    };
+
+                    match builtin {
+                        Some(builtin) => glsl.push_str(builtin),
+                        None => glsl.push_str(&format!(
+                            "acsl_{}_input_{}",
+                            if vertex { "vertex" } else { "pixel" },
+                            members[i].0
+                        )),
+                    }
 
                     if i != members.len() - 1 {
                         glsl.push_str(", ");
@@ -188,36 +368,352 @@ impl Function {
 
                 (glsl, vertex, !vertex)
             }
-            _ => (
-                format!("{} {}() {{\n", self.return_type.glsl(), self.name),
-                false,
-                false,
-            ),
+            _ => (self.glsl_signature(), false, false),
         };
 
+        let discards = self.discards();
         glsl.push_str(&self.code_block.unwrap().glsl(
             vertex_main,
             fragment_main,
             position_output_name,
+            options,
         ));
 
         glsl.push_str("}\n");
 
+        // Functions which discard are only called by the fragment shader, and
+        // GLSL rejects "discard" in the others
         if vertex_main {
             (glsl, String::new())
-        } else if fragment_main {
+        } else if fragment_main || discards {
             (String::new(), glsl)
         } else {
             (glsl.clone(), glsl)
         }
     }
 
+    // With tessellation or geometry stages the entry points are called by the
+    // "main" of their shader, which moves their inputs and outputs through the
+    // interface variables
+    pub fn generate_glsl_stage_function(self, options: &CompileOptions) -> String {
+        let mut glsl = self.glsl_signature();
+        glsl.push_str(&self.code_block.unwrap().glsl(false, false, "", options));
+        glsl.push_str("}\n");
+
+        glsl
+    }
+
+    // Streams are implicit in GLSL, the vertices are emitted by "acsl_emit"
+    fn glsl_signature(&self) -> String {
+        let parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .filter(|parameter| !matches!(parameter.parameter_type, Type::Stream(_, _)))
+            .map(|parameter| format!("{} {}", parameter.parameter_type.glsl(), parameter.name))
+            .collect();
+
+        format!(
+            "{} {}({}) {{\n",
+            self.return_type.glsl(),
+            self.name,
+            parameters.join(", ")
+        )
+    }
+
+    // Compute kernels become the "main" of their own shader, with the thread
+    // IDs gathered from the builtin variables
+    pub fn generate_glsl_compute(self, options: &CompileOptions) -> String {
+        let mut glsl = "void main() {\n".to_owned();
+
+        if let Some(parameter) = self.parameters.first() {
+            glsl.push_str(&format!(
+                "{}{} {} = {}(gl_GlobalInvocationID, gl_WorkGroupID, gl_LocalInvocationID, gl_LocalInvocationIndex);\n\n",
+                options.indent(1),
+                parameter.parameter_type.glsl(),
+                parameter.name,
+                parameter.parameter_type.glsl()
+            ));
+        }
+
+        glsl.push_str(&self.code_block.unwrap().glsl(false, false, "", options));
+        glsl.push_str("}\n");
+
+        glsl
+    }
+
+    pub fn generate_wgsl(self, options: &CompileOptions) -> String {
+        let mut string = match self.name.as_str() {
+            "vertex_main" => format!("@vertex\nfn {}(", options.get_vertex_entry_point()),
+            "fragment_main" => format!("@fragment\nfn {}(", options.get_fragment_entry_point()),
+            name => format!("fn {}(", name),
+        };
+
+        let front_face = self.front_face_member().map(str::to_owned);
+        for i in 0..self.parameters.len() {
+            string.push_str(&format!(
+                "{}{}: {}",
+                if front_face.is_some() { "acsl_" } else { "" },
+                self.parameters[i].name,
+                self.parameters[i].parameter_type.wgsl()
+            ));
+
+            if i != self.parameters.len() - 1 {
+                string.push_str(", ");
+            }
+        }
+
+        if front_face.is_some() {
+            string.push_str(", @builtin(front_facing) acsl_front_facing: bool");
+        }
+
+        string.push_str(") ");
+
+        if &self.name == "fragment_main" && self.return_type == Type::float4() {
+            string.push_str(&format!("-> @location(0) {} ", self.return_type.wgsl()));
+        } else if self.return_type != Type::void() {
+            string.push_str(&format!("-> {} ", self.return_type.wgsl()));
+        }
+
+        let mut wgsl = self.code_block.unwrap().wgsl(options);
+        if let Some(member) = front_face {
+            let parameter = &self.parameters[0];
+            wgsl.insert_str(
+                "{\n".len(),
+                &format!(
+                    "{0}var {1}: {2} = acsl_{1};\n{0}{1}.{3} = select(0u, 1u, acsl_front_facing);\n",
+                    options.indent(1),
+                    parameter.name,
+                    parameter.parameter_type.wgsl(),
+                    member
+                ),
+            );
+        }
+        string.push_str(&wgsl);
+
+        string
+    }
+
+    pub fn generate_msl(
+        self,
+        resource_parameters: &[String],
+        resource_arguments: &HashMap<String, String>,
+        options: &CompileOptions,
+    ) -> String {
+        let (mut string, entry_point) = match self.name.as_str() {
+            "vertex_main" => (
+                format!(
+                    "vertex {} {}(",
+                    self.return_type.msl(),
+                    options.get_vertex_entry_point()
+                ),
+                true,
+            ),
+            "fragment_main" => (
+                format!(
+                    "fragment {} {}(",
+                    self.return_type.msl(),
+                    options.get_fragment_entry_point()
+                ),
+                true,
+            ),
+            name => (
+                format!("{} {}(", self.return_type.msl(), msl_identifier(name)),
+                false,
+            ),
+        };
+
+        let (mut parameters, prologue) = self.msl_parameters(entry_point, options);
+        parameters.extend_from_slice(resource_parameters);
+
+        string.push_str(&parameters.join(", "));
+        string.push_str(") ");
+
+        let mut msl = self.code_block.unwrap().msl(resource_arguments, options);
+        msl.insert_str("{\n".len(), &prologue);
+        string.push_str(&msl);
+
+        string
+    }
+
+    // MSL passes system values as parameters of the entry point, the prologue
+    // moves them into the input structure
+    fn msl_parameters(&self, entry_point: bool, options: &CompileOptions) -> (Vec<String>, String) {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|parameter| {
+                format!(
+                    "{} {}{}",
+                    parameter.parameter_type.msl(),
+                    msl_identifier(&parameter.name),
+                    if entry_point { " [[stage_in]]" } else { "" }
+                )
+            })
+            .collect();
+
+        let (input, structure) = match self.parameters.first() {
+            Some(input) if entry_point => match &input.parameter_type {
+                Type::Struct(structure) => (input, structure),
+                _ => return (parameters, String::new()),
+            },
+            _ => return (parameters, String::new()),
+        };
+        let input_type = input.parameter_type.msl();
+
+        if let Some(member) = self.front_face_member() {
+            return (
+                vec![
+                    format!("{} acsl_{} [[stage_in]]", input_type, input.name),
+                    "bool acsl_front_facing [[front_facing]]".to_owned(),
+                ],
+                format!(
+                    "{0}{1} {2} = acsl_{3};\n{0}{2}.{4} = uint(acsl_front_facing);\n",
+                    options.indent(1),
+                    input_type,
+                    msl_identifier(&input.name),
+                    input.name,
+                    member
+                ),
+            );
+        }
+
+        if self.name != "vertex_main" || !structure.has_vertex_system_values() {
+            return (parameters, String::new());
+        }
+
+        // The attributes are gathered in a structure of their own
+        let mut parameters = Vec::new();
+        if structure.has_vertex_attributes() {
+            parameters.push(format!(
+                "acsl_{}_attributes acsl_{} [[stage_in]]",
+                structure.name(),
+                input.name
+            ));
+        }
+        for (semantic, parameter) in [
+            ("SV_VERTEX_ID", "uint acsl_vertex_id [[vertex_id]]"),
+            ("SV_INSTANCE_ID", "uint acsl_instance_id [[instance_id]]"),
+        ] {
+            if structure.find_semantic(semantic).is_some() {
+                parameters.push(parameter.to_owned());
+            }
+        }
+
+        let members: Vec<String> = structure
+            .members()
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| match structure.semantic(i) {
+                Some("SV_VERTEX_ID") => "acsl_vertex_id".to_owned(),
+                Some("SV_INSTANCE_ID") => "acsl_instance_id".to_owned(),
+                _ => format!("acsl_{}.{}", input.name, name),
+            })
+            .collect();
+
+        (
+            parameters,
+            format!(
+                "{0}{1} {2} = {1}{{{3}}};\n",
+                options.indent(1),
+                input_type,
+                msl_identifier(&input.name),
+                members.join(", ")
+            ),
+        )
+    }
+
+    // Reserves the id of the function so it can be called before its definition
+    pub fn declare_spirv(&self, context: &mut Context) {
+        let function = context.module.id();
+        let return_type = self.return_type.spirv(&mut context.module);
+        context.define_function(&self.name, function, return_type);
+    }
+
+    pub fn generate_spirv(&self, context: &mut Context) {
+        let (function, return_type) = context.function(&self.name).unwrap();
+        let parameter_types: Vec<Id> = self
+            .parameters
+            .iter()
+            .map(|parameter| parameter.parameter_type.spirv(&mut context.module))
+            .collect();
+        let function_type = context.module.type_function(return_type, &parameter_types);
+
+        context.module.name(function, &self.name);
+        context.module.emit(
+            OP_FUNCTION,
+            &[return_type, function, FUNCTION_CONTROL_NONE, function_type],
+        );
+
+        context.clear_locals();
+        for (parameter, parameter_type) in self.parameters.iter().zip(parameter_types) {
+            let value = context
+                .module
+                .emit_result(OP_FUNCTION_PARAMETER, parameter_type, &[]);
+            context.module.name(value, &parameter.name);
+            context.set_local(&parameter.name, value);
+        }
+
+        let label = context.module.id();
+        context.module.emit(OP_LABEL, &[label]);
+
+        if !self.code_block.as_ref().unwrap().spirv(context) {
+            if self.return_type == Type::void() {
+                context.module.emit(OP_RETURN, &[]);
+            } else {
+                context.module.emit(OP_UNREACHABLE, &[]);
+            }
+        }
+
+        context.module.emit(OP_FUNCTION_END, &[]);
+    }
+
+    // Builtin functions which not every target can express
+    pub fn required_feature(&self) -> Option<Feature> {
+        match self.code_block {
+            None if self.name.starts_with("wave_") => Some(Feature::WaveIntrinsics),
+            None if self.name == "group_barrier" => Some(Feature::ComputeShaders),
+            _ => self.return_type.required_feature(),
+        }
+    }
+
+    // HLSL, WGSL and MSL only read the facing of a fragment from a parameter
+    // of the entry point, it's copied into the member of the input structure
+    fn front_face_member(&self) -> Option<&str> {
+        if self.name != "fragment_main" {
+            return None;
+        }
+
+        match &self.parameters.first()?.parameter_type {
+            Type::Struct(structure) => structure
+                .find_semantic("SV_IS_FRONT_FACE")
+                .map(|i| structure.members()[i].0.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn discards(&self) -> bool {
+        self.code_block
+            .as_ref()
+            .is_some_and(|code_block| code_block.discards())
+    }
+
+    pub fn is_entry_point(&self) -> bool {
+        self.name == "vertex_main" || self.name == "fragment_main"
+    }
+
+    pub fn references(&self, variables: &mut Vec<String>, functions: &mut Vec<String>) {
+        if let Some(code_block) = &self.code_block {
+            code_block.references(variables, functions);
+        }
+    }
+
     fn new_builtin(name: String, parameters: Vec<FunctionParameter>, return_type: Type) -> Self {
         Function {
             name,
             parameters,
             return_type,
             code_block: None,
+            attribute: None,
         }
     }
 }
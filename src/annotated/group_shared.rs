@@ -0,0 +1,31 @@
+use crate::types::Type;
+
+pub struct GroupShared {
+    name: String,
+    variable_type: Type,
+}
+
+impl GroupShared {
+    pub fn new(name: String, variable_type: Type) -> Self {
+        GroupShared {
+            name,
+            variable_type,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_type(&self) -> &Type {
+        &self.variable_type
+    }
+
+    pub fn generate_hlsl(self) -> String {
+        format!("groupshared {} {};\n", self.variable_type.hlsl(), self.name)
+    }
+
+    pub fn generate_glsl(self) -> String {
+        format!("shared {} {};\n", self.variable_type.glsl(), self.name)
+    }
+}
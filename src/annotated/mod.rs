@@ -1,79 +1,486 @@
 use crate::{
     ast::{scope::Scope, SemanticAnalysisError},
+    feature::Feature,
+    options::CompileOptions,
+    reflection::Reflection,
+    semantic::{Domain, Interpolation, Semantic, Stage},
+    spirv::{self, Context, Id},
+    target::Target,
     types::Type,
 };
+use buffer::Buffer;
 use constant::Constant;
 use constant_buffer::ConstantBuffer;
 use function::Function;
-use std::{collections::VecDeque, rc::Rc};
-use structure::Struct;
+use group_shared::GroupShared;
+use pipeline::StageCode;
+use sampler::Sampler;
+use slots::{Binding, Slots};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
+use storage_texture::StorageTexture;
+use structure::{is_vertex_system_value, render_target, Struct};
 use texture::Texture;
 
+pub mod buffer;
 pub mod code_block;
 pub mod constant;
 pub mod constant_buffer;
 pub mod expression;
 pub mod function;
+pub mod group_shared;
+pub mod pipeline;
+pub mod sampler;
+pub mod slots;
 pub mod statement;
+pub mod storage_texture;
 pub mod structure;
 pub mod texture;
 
+// The declarations an entry point depends on, directly or through the
+// functions and constants it uses
+struct Reachable {
+    functions: Vec<String>,
+    variables: Vec<String>,
+    structs: Vec<String>,
+}
+
 enum DeclarationType {
     Function,
     Struct,
-    ConstantBuffer(usize),
-    Texture(usize),
+    ConstantBuffer(Binding),
+    Texture(Binding),
+    Buffer(Binding),
+    StorageTexture(Binding),
+    Sampler(Binding),
     Constant,
+    GroupShared,
 }
 
 pub struct AnnotatedSyntaxTree {
     functions: VecDeque<Function>,
     structs: VecDeque<Rc<Struct>>,
     constants: VecDeque<Constant>,
+    group_shared: VecDeque<GroupShared>,
 
-    constant_buffers: Box<[Option<ConstantBuffer>]>,
-    textures: Box<[Option<Texture>]>,
+    constant_buffers: Slots<ConstantBuffer>,
+    textures: Slots<Texture>,
+    buffers: Slots<Buffer>,
+    storage_textures: Slots<StorageTexture>,
+    samplers: Slots<Sampler>,
 
     declaration_order: Vec<DeclarationType>,
 
     builtin_functions: Box<[Function]>,
+    thread_id: Rc<Struct>,
 
     type_aliases: Vec<(String, Type)>,
     global_scope: Scope,
 
     vertex_input_type: Option<Type>,
+    vertex_output_type: Option<Type>,
     fragment_input_type: Option<Type>,
+    // "float4", or a structure with a member for each render target
+    fragment_output_type: Option<Type>,
+    semantic_locations: Vec<(String, usize)>,
+
+    features: RefCell<Vec<Feature>>,
+    // The textures sampled with each sampler declaration
+    sampled_textures: RefCell<Vec<(String, String)>>,
+    // The textures queried by "texture_size", HLSL declares a function for each
+    sized_textures: RefCell<Vec<String>>,
 }
 
 pub const MAX_CONSTANT_BUFFERS: usize = 32;
 pub const MAX_TEXTURES: usize = 8;
+pub const MAX_BUFFERS: usize = 8;
+pub const MAX_STORAGE_TEXTURES: usize = 8;
+pub const MAX_SAMPLERS: usize = 8;
 
 pub const CONSTANT_BUFFER_INDEX: usize = 0;
 pub const TEXTURES_INDEX: usize = MAX_CONSTANT_BUFFERS;
+// Read-only buffers take the HLSL "t" registers after the textures, writable
+// buffers use "u" registers from 0
+pub const BUFFERS_INDEX: usize = MAX_TEXTURES;
+// Storage textures take the HLSL "u" registers after the buffers
+pub const STORAGE_TEXTURES_INDEX: usize = MAX_BUFFERS;
+// Sampler declarations take the HLSL "s" registers after the samplers of the
+// textures
+pub const SAMPLER_STATES_INDEX: usize = MAX_TEXTURES;
+
+// Vulkan descriptor sets, the binding within a set is the slot
+pub const VULKAN_CONSTANT_BUFFER_SET: usize = 0;
+pub const VULKAN_TEXTURE_SET: usize = 1;
+pub const VULKAN_SAMPLER_SET: usize = 2;
+pub const VULKAN_BUFFER_SET: usize = 3;
+pub const VULKAN_STORAGE_TEXTURE_SET: usize = 4;
 
 impl AnnotatedSyntaxTree {
-    pub fn new() -> Self {
+    pub fn new(options: &CompileOptions) -> Self {
         AnnotatedSyntaxTree {
             functions: VecDeque::new(),
             structs: VecDeque::new(),
             constants: VecDeque::new(),
-            constant_buffers: vec![None; MAX_CONSTANT_BUFFERS].into_boxed_slice(),
-            textures: vec![None; MAX_TEXTURES].into_boxed_slice(),
+            group_shared: VecDeque::new(),
+            constant_buffers: Slots::new("constant buffer", options.get_max_constant_buffers()),
+            textures: Slots::new("texture", options.get_max_textures()),
+            buffers: Slots::new("buffer", options.get_max_buffers()),
+            storage_textures: Slots::new("storage texture", options.get_max_storage_textures()),
+            samplers: Slots::new("sampler", options.get_max_samplers()),
             declaration_order: Vec::new(),
             builtin_functions: Function::builtin_functions(),
+            thread_id: Rc::new(Struct::thread_id()),
             type_aliases: Vec::new(),
             global_scope: Scope::new(),
             vertex_input_type: None,
+            vertex_output_type: None,
             fragment_input_type: None,
+            fragment_output_type: None,
+            semantic_locations: options.get_semantic_locations().to_vec(),
+            features: RefCell::new(Vec::new()),
+            sampled_textures: RefCell::new(Vec::new()),
+            sized_textures: RefCell::new(Vec::new()),
         }
     }
 
     pub fn verify_graphics_functions(&self) -> Result<(), SemanticAnalysisError> {
+        // Files with only compute kernels have no graphics pipeline
+        if self.vertex_input_type.is_none()
+            && self.fragment_input_type.is_none()
+            && self
+                .functions
+                .iter()
+                .any(|function| function.is_compute_kernel())
+        {
+            return Ok(());
+        }
+
         // Verify the functions have been added
-        if self.vertex_input_type.is_none() {
-            return Err(SemanticAnalysisError::NoVertexMain);
-        } else if self.fragment_input_type.is_none() {
-            return Err(SemanticAnalysisError::NoFragmentMain);
+        let (vertex_output_type, fragment_input_type) =
+            match (&self.vertex_output_type, &self.fragment_input_type) {
+                (None, _) => return Err(SemanticAnalysisError::NoVertexMain),
+                (_, None) => return Err(SemanticAnalysisError::NoFragmentMain),
+                (Some(vertex_output_type), Some(fragment_input_type)) => {
+                    (vertex_output_type, fragment_input_type)
+                }
+            };
+
+        // Each stage takes the output of the one before it
+        let mut output = (vertex_output_type, "vertex_main");
+
+        if let Some(present) = pipeline::TESSELLATION_ENTRY_POINTS
+            .iter()
+            .find(|name| self.user_function(name).is_some())
+        {
+            let mut functions = Vec::new();
+            for name in pipeline::TESSELLATION_ENTRY_POINTS {
+                match self.user_function(name) {
+                    Some(function) => functions.push(function),
+                    None => {
+                        return Err(SemanticAnalysisError::MissingEntryPoint(
+                            name.to_owned(),
+                            present.to_string(),
+                        ))
+                    }
+                }
+            }
+            let (hull_main, hull_constants, domain_main) =
+                (functions[0], functions[1], functions[2]);
+
+            // The control points of the input patch are the vertices
+            let patch_type = hull_main.parameters()[0].parameter_type();
+            self.link_stage(
+                hull_main.name(),
+                patch_type.stage_element().unwrap(),
+                output,
+            )?;
+
+            // Both functions of the hull shader read the same patch
+            let constants_patch_type = hull_constants.parameters()[0].parameter_type();
+            if constants_patch_type != patch_type {
+                return Err(SemanticAnalysisError::StageTypeMismatch(
+                    hull_constants.name().to_owned(),
+                    constants_patch_type.to_string(),
+                    hull_main.name().to_owned(),
+                    patch_type.to_string(),
+                ));
+            }
+
+            // The domain shader reads the patch constants, its location in
+            // the domain they decide, and the control points of the patch
+            let constants_type = hull_constants.return_type();
+            let domain = match pipeline::stage_struct(constants_type) {
+                Some(constants) => pipeline::patch_domain(constants).unwrap(),
+                None => unreachable!(),
+            };
+            let parameters = domain_main.parameters();
+            for (parameter, expected) in [
+                (parameters[0].parameter_type(), constants_type.clone()),
+                (parameters[1].parameter_type(), domain.location_type()),
+            ] {
+                if *parameter != expected {
+                    return Err(SemanticAnalysisError::StageTypeMismatch(
+                        domain_main.name().to_owned(),
+                        parameter.to_string(),
+                        hull_constants.name().to_owned(),
+                        expected.to_string(),
+                    ));
+                }
+            }
+
+            let control_point_type = parameters[2].parameter_type().stage_element().unwrap();
+            if control_point_type != hull_main.return_type() {
+                return Err(SemanticAnalysisError::StageTypeMismatch(
+                    domain_main.name().to_owned(),
+                    control_point_type.to_string(),
+                    hull_main.name().to_owned(),
+                    hull_main.return_type().to_string(),
+                ));
+            }
+
+            output = (domain_main.return_type(), domain_main.name());
+        }
+
+        if let Some(geometry_main) = self.user_function(pipeline::GEOMETRY_MAIN) {
+            let parameters = geometry_main.parameters();
+            self.link_stage(
+                geometry_main.name(),
+                parameters[0].parameter_type().stage_element().unwrap(),
+                output,
+            )?;
+
+            match parameters[1].parameter_type() {
+                Type::Stream(vertex_type, _) => output = (vertex_type, geometry_main.name()),
+                _ => unreachable!(),
+            }
+        }
+
+        // The vertices reaching the rasterizer are interpolated for the
+        // fragments, a direct link keeps the errors of both functions
+        if output.0 != fragment_input_type {
+            if output.1 != "vertex_main" {
+                return Err(SemanticAnalysisError::StageTypeMismatch(
+                    "fragment_main".to_owned(),
+                    fragment_input_type.to_string(),
+                    output.1.to_owned(),
+                    output.0.to_string(),
+                ));
+            }
+
+            let vertex_first = self
+                .functions
+                .iter()
+                .position(|function| function.name() == "vertex_main")
+                < self
+                    .functions
+                    .iter()
+                    .position(|function| function.name() == "fragment_main");
+            return Err(match vertex_first {
+                true => SemanticAnalysisError::FragmentMainParameterTypeMismatch(
+                    fragment_input_type.to_string(),
+                    vertex_output_type.to_string(),
+                ),
+                false => SemanticAnalysisError::VertexMainReturnTypeMismatch(
+                    vertex_output_type.to_string(),
+                    fragment_input_type.to_string(),
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Verifies "name()" takes the vertices or control points returned by the
+    // previous stage, they are passed on before reaching the rasterizer
+    fn link_stage(
+        &self,
+        name: &str,
+        input_type: &Type,
+        (output_type, producer): (&Type, &str),
+    ) -> Result<(), SemanticAnalysisError> {
+        if input_type != output_type {
+            return Err(SemanticAnalysisError::StageTypeMismatch(
+                name.to_owned(),
+                input_type.to_string(),
+                producer.to_owned(),
+                output_type.to_string(),
+            ));
+        }
+
+        match pipeline::stage_struct(output_type) {
+            Some(structure) => {
+                verify_semantics(structure, &self.semantic_locations, Stage::Intermediate)
+            }
+            None => unreachable!(),
+        }
+    }
+
+    // The functions declared in the file, without the builtins
+    fn user_function(&self, name: &str) -> Option<&Function> {
+        self.functions
+            .iter()
+            .find(|function| function.name() == name)
+    }
+
+    // The domain of the tessellated patches and the number of control points
+    // "hull_main()" outputs for each of them
+    fn tessellation(&self) -> Option<(Domain, usize)> {
+        let constants = self.user_function(pipeline::HULL_CONSTANTS)?.return_type();
+        let domain = pipeline::patch_domain(pipeline::stage_struct(constants)?)?;
+
+        match self.user_function(pipeline::DOMAIN_MAIN)?.parameters()[2].parameter_type() {
+            Type::Patch(_, control_points) => Some((domain, *control_points)),
+            _ => None,
+        }
+    }
+
+    // The declarations and the "main()" of each shader when tessellation or
+    // geometry stages sit between "vertex_main()" and "fragment_main()", along
+    // with the "acsl_emit" function of the geometry shader
+    fn glsl_stages(&self, options: &CompileOptions) -> Option<(StageCode, StageCode, String)> {
+        let tessellation = self.tessellation();
+        let geometry_main = self.user_function(pipeline::GEOMETRY_MAIN);
+        if tessellation.is_none() && geometry_main.is_none() {
+            return None;
+        }
+
+        let mut declarations = StageCode::default();
+        let mut mains = StageCode::default();
+        let mut emit = String::new();
+
+        let vertex_input = pipeline::stage_struct(self.vertex_input_type.as_ref()?)?;
+        let mut output = pipeline::stage_struct(self.vertex_output_type.as_ref()?)?;
+        let consumer = match tessellation {
+            Some(_) => "hull",
+            None => "geometry",
+        };
+        declarations.vertex = format!(
+            "{}\n",
+            pipeline::glsl_interface(output, "out", consumer, false, options)
+        );
+        mains.vertex = pipeline::glsl_vertex_main(vertex_input, output, consumer, options);
+
+        if let Some((domain, control_points)) = tessellation {
+            let hull_main = self.user_function(pipeline::HULL_MAIN)?;
+            let hull_constants = self.user_function(pipeline::HULL_CONSTANTS)?;
+            let domain_main = self.user_function(pipeline::DOMAIN_MAIN)?;
+
+            let input_control_points = match hull_main.parameters()[0].parameter_type() {
+                Type::Patch(_, input_control_points) => *input_control_points,
+                _ => return None,
+            };
+            let control_point = pipeline::stage_struct(hull_main.return_type())?;
+            let constants = pipeline::stage_struct(hull_constants.return_type())?;
+
+            declarations.hull = format!(
+                "layout(vertices = {}) out;\n\n{}\n{}\n",
+                control_points,
+                pipeline::glsl_interface(output, "in", "hull", true, options),
+                pipeline::glsl_interface(control_point, "out", "domain", true, options)
+            );
+            mains.hull = pipeline::glsl_hull_main(
+                output,
+                input_control_points,
+                control_point,
+                constants,
+                domain,
+                options,
+            );
+
+            output = pipeline::stage_struct(domain_main.return_type())?;
+            let consumer = match geometry_main {
+                Some(_) => "geometry",
+                None => "pixel",
+            };
+            declarations.domain = format!(
+                "layout({}, equal_spacing, cw) in;\n\n{}\n{}\n",
+                domain.glsl(),
+                pipeline::glsl_interface(control_point, "in", "domain", true, options),
+                pipeline::glsl_interface(output, "out", consumer, false, options)
+            );
+            mains.domain = pipeline::glsl_domain_main(
+                constants,
+                domain,
+                control_point,
+                control_points,
+                output,
+                consumer,
+                options,
+            );
+        }
+
+        if let Some(geometry_main) = geometry_main {
+            let parameters = geometry_main.parameters();
+            let (input_topology, (vertex, output_topology)) = match (
+                parameters[0].parameter_type(),
+                parameters[1].parameter_type(),
+            ) {
+                (Type::GeometryInput(_, input_topology), Type::Stream(vertex, output_topology)) => {
+                    (
+                        *input_topology,
+                        (pipeline::stage_struct(vertex)?, *output_topology),
+                    )
+                }
+                _ => return None,
+            };
+
+            declarations.geometry = format!(
+                "layout({}) in;\nlayout({}, max_vertices = {}) out;\n\n{}\n{}\n",
+                input_topology.glsl_input(),
+                output_topology.glsl_output(),
+                geometry_main.max_vertex_count()?,
+                pipeline::glsl_interface(output, "in", "geometry", true, options),
+                pipeline::glsl_interface(vertex, "out", "pixel", false, options)
+            );
+            mains.geometry = pipeline::glsl_geometry_main(output, input_topology, options);
+            emit = pipeline::glsl_emit(vertex, options);
+        }
+
+        Some((declarations, mains, emit))
+    }
+
+    // The attributes of "hull_main()" and "domain_main()" describing the
+    // patches passed between them
+    fn hlsl_tessellation_attributes(&self) -> (String, String) {
+        match self.tessellation() {
+            Some((domain, control_points)) => (
+                format!(
+                    "[domain(\"{}\")]\n[partitioning(\"integer\")]\n[outputtopology(\"triangle_cw\")]\n[outputcontrolpoints({})]\n[patchconstantfunc(\"{}\")]\n",
+                    domain.hlsl(),
+                    control_points,
+                    pipeline::HULL_CONSTANTS
+                ),
+                format!("[domain(\"{}\")]\n", domain.hlsl()),
+            ),
+            None => (String::new(), String::new()),
+        }
+    }
+
+    pub fn require_feature(&self, feature: Feature) {
+        let mut features = self.features.borrow_mut();
+        if !features.contains(&feature) {
+            features.push(feature);
+        }
+    }
+
+    pub fn verify_features(&self, target: Target) -> Result<(), SemanticAnalysisError> {
+        for feature in self.features.borrow().iter() {
+            if target.is_unimplemented(*feature) {
+                return Err(SemanticAnalysisError::UnimplementedFeature(
+                    feature.to_string(),
+                    target.to_string(),
+                ));
+            }
+
+            if !target.supports(*feature) {
+                return Err(SemanticAnalysisError::UnsupportedFeature(
+                    feature.to_string(),
+                    target.to_string(),
+                ));
+            }
         }
 
         Ok(())
@@ -121,10 +528,35 @@ impl AnnotatedSyntaxTree {
         return Err(SemanticAnalysisError::UnknownType(name.to_string()));
     }
 
+    // The builtin parameter of compute kernels
+    pub fn thread_id_type(&self) -> Type {
+        Type::Struct(self.thread_id.clone())
+    }
+
     pub fn global_scope(&self) -> &Scope {
         &self.global_scope
     }
 
+    pub fn max_constant_buffers(&self) -> usize {
+        self.constant_buffers.max()
+    }
+
+    pub fn max_textures(&self) -> usize {
+        self.textures.max()
+    }
+
+    pub fn max_buffers(&self) -> usize {
+        self.buffers.max()
+    }
+
+    pub fn max_storage_textures(&self) -> usize {
+        self.storage_textures.max()
+    }
+
+    pub fn max_samplers(&self) -> usize {
+        self.samplers.max()
+    }
+
     pub fn push_function(&mut self, function: Function) -> Result<(), SemanticAnalysisError> {
         if !self.verify_type_name(function.name()) {
             return Err(SemanticAnalysisError::MultipleDefinition(
@@ -132,7 +564,46 @@ impl AnnotatedSyntaxTree {
             ));
         }
 
-        if self.vertex_input_type.is_none() && function.name() == "vertex_main" {
+        if function.max_vertex_count().is_some() && function.name() != pipeline::GEOMETRY_MAIN {
+            return Err(SemanticAnalysisError::MisplacedAttribute(
+                "geometry".to_owned(),
+                function.name().to_owned(),
+            ));
+        }
+
+        // Patches, primitives and streams are only passed to the entry points
+        // of their stages
+        if !pipeline::is_stage_entry_point(function.name()) {
+            for parameter in function.parameters() {
+                if parameter.parameter_type().is_stage_type() {
+                    return Err(SemanticAnalysisError::StageTypeOutsideEntryPoint(
+                        function.name().to_owned(),
+                        parameter.parameter_type().to_string(),
+                    ));
+                }
+            }
+        }
+
+        if function.is_compute_kernel() {
+            if *function.return_type() != Type::void() {
+                return Err(SemanticAnalysisError::InvalidComputeReturnType(
+                    function.name().to_owned(),
+                    function.return_type().to_string(),
+                ));
+            }
+
+            match function.parameters() {
+                [] => {}
+                [parameter] if *parameter.parameter_type() == self.thread_id_type() => {}
+                _ => {
+                    return Err(SemanticAnalysisError::InvalidComputeParameters(
+                        function.name().to_owned(),
+                    ))
+                }
+            }
+
+            self.require_feature(Feature::ComputeShaders);
+        } else if self.vertex_input_type.is_none() && function.name() == "vertex_main" {
             // Verify parameter count
             if function.parameters().len() != 1 {
                 return Err(SemanticAnalysisError::VertexMainParameterCount);
@@ -141,7 +612,9 @@ impl AnnotatedSyntaxTree {
             // Verify input type
             let vertex_input_type = function.parameters()[0].parameter_type();
             match vertex_input_type {
-                Type::Struct(_) => {} // TODO: Check for semantics
+                Type::Struct(structure) if structure.has_semantics() => {
+                    verify_semantics(structure, &self.semantic_locations, Stage::VertexInput)?
+                }
                 _ => {
                     return Err(SemanticAnalysisError::InvalidVertexMainParameterType(
                         vertex_input_type.to_string(),
@@ -150,28 +623,14 @@ impl AnnotatedSyntaxTree {
             }
             self.vertex_input_type = Some(vertex_input_type.clone());
 
-            // Verify return type
-            match &self.fragment_input_type {
-                Some(fragement_input_type) => {
-                    if function.return_type() != fragement_input_type {
-                        return Err(SemanticAnalysisError::VertexMainReturnTypeMismatch(
-                            function.return_type().to_string(),
-                            fragement_input_type.to_string(),
-                        ));
-                    }
-                }
-                None => match function.return_type() {
-                    Type::Struct(_) => {
-                        // TODO: Check for semantics
-                        self.fragment_input_type = Some(function.return_type().clone())
-                    }
-                    _ => {
-                        return Err(SemanticAnalysisError::InvalidVertexMainReturnType(
-                            function.return_type().to_string(),
-                        ))
-                    }
-                },
+            // Verify return type, the stage it feeds is only known once every
+            // function has been added
+            if pipeline::stage_struct(function.return_type()).is_none() {
+                return Err(SemanticAnalysisError::InvalidVertexMainReturnType(
+                    function.return_type().to_string(),
+                ));
             }
+            self.vertex_output_type = Some(function.return_type().clone());
         } else if function.name() == "fragment_main" {
             // Verify parameter count
             if function.parameters().len() != 1 {
@@ -179,34 +638,45 @@ impl AnnotatedSyntaxTree {
             }
 
             // Verify parameter type
-            match &self.fragment_input_type {
-                Some(fragement_input_type) => {
-                    if function.parameters()[0].parameter_type() != fragement_input_type {
-                        return Err(SemanticAnalysisError::FragmentMainParameterTypeMismatch(
-                            function.return_type().to_string(),
-                            fragement_input_type.to_string(),
-                        ));
-                    }
+            let fragment_input_type = function.parameters()[0].parameter_type();
+            match fragment_input_type {
+                Type::Struct(structure) if structure.has_semantics() => {
+                    verify_semantics(structure, &self.semantic_locations, Stage::Interstage)?
+                }
+                _ => {
+                    return Err(SemanticAnalysisError::InvalidFragmentMainParameterType(
+                        fragment_input_type.to_string(),
+                    ))
                 }
-                None => match function.return_type() {
-                    Type::Struct(_) => {
-                        // TODO: Check for semantics
-                        self.fragment_input_type = Some(function.return_type().clone())
-                    }
-                    _ => {
-                        return Err(SemanticAnalysisError::InvalidFragmentMainParameterType(
-                            function.return_type().to_string(),
-                        ))
-                    }
-                },
             }
+            self.fragment_input_type = Some(fragment_input_type.clone());
 
             // Verify return type
-            if *function.return_type() != Type::float4() {
-                return Err(SemanticAnalysisError::InvalidFragmentMainReturnType(
-                    function.return_type().to_string(),
-                ));
+            match function.return_type() {
+                Type::Struct(structure) => {
+                    verify_render_targets(structure)?;
+                    verify_semantics(structure, &self.semantic_locations, Stage::FragmentOutput)?
+                }
+                return_type if *return_type == Type::float4() => {}
+                return_type => {
+                    return Err(SemanticAnalysisError::InvalidFragmentMainReturnType(
+                        return_type.to_string(),
+                    ))
+                }
             }
+            self.fragment_output_type = Some(function.return_type().clone());
+        } else if function.name() == pipeline::HULL_MAIN {
+            pipeline::verify_hull_main(&function, &self.semantic_locations)?;
+            self.require_feature(Feature::TessellationShaders);
+        } else if function.name() == pipeline::HULL_CONSTANTS {
+            pipeline::verify_hull_constants(&function, &self.semantic_locations)?;
+            self.require_feature(Feature::TessellationShaders);
+        } else if function.name() == pipeline::DOMAIN_MAIN {
+            pipeline::verify_domain_main(&function)?;
+            self.require_feature(Feature::TessellationShaders);
+        } else if function.name() == pipeline::GEOMETRY_MAIN {
+            pipeline::verify_geometry_main(&function)?;
+            self.require_feature(Feature::GeometryShaders);
         }
 
         self.declaration_order.push(DeclarationType::Function);
@@ -246,31 +716,126 @@ impl AnnotatedSyntaxTree {
         &mut self,
         constant_buffer: ConstantBuffer,
     ) -> Result<(), SemanticAnalysisError> {
+        let binding = (constant_buffer.slot(), constant_buffer.space());
+        self.constant_buffers.verify_unused(binding)?;
+
         self.global_scope.define_variable(
             constant_buffer.name().to_owned(),
             constant_buffer.cb_type().clone(),
             false,
         )?;
 
-        let slot = constant_buffer.slot();
-        self.constant_buffers[slot] = Some(constant_buffer);
+        self.constant_buffers.insert(binding, constant_buffer);
+        if self.constant_buffers.is_shared(binding.0) {
+            self.require_feature(Feature::SharedSlots);
+        }
         self.declaration_order
-            .push(DeclarationType::ConstantBuffer(slot));
+            .push(DeclarationType::ConstantBuffer(binding));
 
         Ok(())
     }
 
     pub fn push_texture(&mut self, texture: Texture) -> Result<(), SemanticAnalysisError> {
+        let binding = (texture.slot(), texture.space());
+        self.textures.verify_unused(binding)?;
+
+        self.global_scope.define_variable(
+            texture.name().to_owned(),
+            texture.resource_type(),
+            false,
+        )?;
+
+        self.textures.insert(binding, texture);
+        if self.textures.is_shared(binding.0) {
+            self.require_feature(Feature::SharedSlots);
+        }
+        self.declaration_order
+            .push(DeclarationType::Texture(binding));
+
+        Ok(())
+    }
+
+    // Buffers are immutable variables, writing to an element is checked
+    // against the buffer type
+    pub fn push_buffer(&mut self, buffer: Buffer) -> Result<(), SemanticAnalysisError> {
+        let binding = (buffer.slot(), buffer.space());
+        self.buffers.verify_unused(binding)?;
+
         self.global_scope
-            .define_variable(texture.name().to_owned(), Type::texture(), false)?;
+            .define_variable(buffer.name().to_owned(), buffer.buffer_type(), false)?;
+
+        self.buffers.insert(binding, buffer);
+        if self.buffers.is_shared(binding.0) {
+            self.require_feature(Feature::SharedSlots);
+        }
+        self.declaration_order
+            .push(DeclarationType::Buffer(binding));
+
+        Ok(())
+    }
+
+    pub fn push_storage_texture(
+        &mut self,
+        storage_texture: StorageTexture,
+    ) -> Result<(), SemanticAnalysisError> {
+        let binding = (storage_texture.slot(), storage_texture.space());
+        self.storage_textures.verify_unused(binding)?;
+
+        self.global_scope.define_variable(
+            storage_texture.name().to_owned(),
+            storage_texture.storage_texture_type(),
+            false,
+        )?;
+
+        self.storage_textures.insert(binding, storage_texture);
+        if self.storage_textures.is_shared(binding.0) {
+            self.require_feature(Feature::SharedSlots);
+        }
+        self.declaration_order
+            .push(DeclarationType::StorageTexture(binding));
+
+        Ok(())
+    }
+
+    pub fn push_sampler(&mut self, sampler: Sampler) -> Result<(), SemanticAnalysisError> {
+        let binding = (sampler.slot(), sampler.space());
+        self.samplers.verify_unused(binding)?;
+
+        self.global_scope.define_variable(
+            sampler.name().to_owned(),
+            sampler.sampler_type(),
+            false,
+        )?;
 
-        let slot = texture.slot();
-        self.textures[slot] = Some(texture);
-        self.declaration_order.push(DeclarationType::Texture(slot));
+        self.samplers.insert(binding, sampler);
+        if self.samplers.is_shared(binding.0) {
+            self.require_feature(Feature::SharedSlots);
+        }
+        self.declaration_order
+            .push(DeclarationType::Sampler(binding));
 
         Ok(())
     }
 
+    // Records a texture sampled with a sampler, for the reflection and to find
+    // the textures OpenGL can't sample with several samplers
+    pub fn use_sampler(&self, texture: &str, sampler: &str) {
+        let mut sampled_textures = self.sampled_textures.borrow_mut();
+        if !sampled_textures
+            .iter()
+            .any(|(t, s)| t == texture && s == sampler)
+        {
+            if sampled_textures.iter().any(|(t, _)| t == texture) {
+                self.require_feature(Feature::MultipleSamplersPerTexture);
+            }
+            sampled_textures.push((texture.to_owned(), sampler.to_owned()));
+        }
+    }
+
+    pub fn use_texture_size(&self, texture: &str) {
+        expression::push_unique(&mut self.sized_textures.borrow_mut(), texture);
+    }
+
     pub fn push_constant(&mut self, constant: Constant) -> Result<(), SemanticAnalysisError> {
         self.global_scope.define_variable(
             constant.name().to_owned(),
@@ -284,26 +849,309 @@ impl AnnotatedSyntaxTree {
         Ok(())
     }
 
-    pub fn generate_hlsl(mut self) -> String {
-        let mut hlsl = format!("// Generated from Alexandria Common Shader Language\n\n");
+    pub fn push_group_shared(
+        &mut self,
+        group_shared: GroupShared,
+    ) -> Result<(), SemanticAnalysisError> {
+        self.global_scope.define_variable(
+            group_shared.name().to_owned(),
+            group_shared.get_type().clone(),
+            true,
+        )?;
+
+        self.group_shared.push_back(group_shared);
+        self.declaration_order.push(DeclarationType::GroupShared);
+
+        Ok(())
+    }
+
+    pub fn is_group_shared(&self, name: &str) -> bool {
+        self.group_shared
+            .iter()
+            .any(|group_shared| group_shared.name() == name)
+    }
+
+    // Functions which discard the fragment can only run in the fragment stage,
+    // so they must be called from "fragment_main()" and no other entry point
+    pub fn verify_discards(&self) -> Result<(), SemanticAnalysisError> {
+        let fragment = self.reachable(&["fragment_main"]);
+
+        let mut entry_points = vec![
+            "vertex_main",
+            pipeline::HULL_MAIN,
+            pipeline::HULL_CONSTANTS,
+            pipeline::DOMAIN_MAIN,
+            pipeline::GEOMETRY_MAIN,
+        ];
+        entry_points.extend(
+            self.functions
+                .iter()
+                .filter(|function| function.is_compute_kernel())
+                .map(|function| function.name()),
+        );
+        let other_stages = self.reachable(&entry_points);
+
+        for function in self.functions.iter().filter(|function| function.discards()) {
+            let name = function.name().to_owned();
+            if !fragment.functions.contains(&name) || other_stages.functions.contains(&name) {
+                return Err(SemanticAnalysisError::DiscardOutsideFragmentMain(name));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reachable(&self, entry_points: &[&str]) -> Reachable {
+        let mut functions: Vec<String> = entry_points
+            .iter()
+            .map(|entry_point| entry_point.to_string())
+            .collect();
+        let mut variables = Vec::new();
+
+        // Both lists grow while they are walked
+        let mut next_function = 0;
+        let mut next_variable = 0;
+        while next_function < functions.len() || next_variable < variables.len() {
+            while next_function < functions.len() {
+                let name = &functions[next_function];
+                if let Some(function) = self.functions.iter().find(|f| f.name() == name) {
+                    function.references(&mut variables, &mut functions);
+                }
+                next_function += 1;
+            }
+
+            while next_variable < variables.len() {
+                let name = &variables[next_variable];
+                if let Some(constant) = self.constants.iter().find(|c| c.name() == name) {
+                    constant
+                        .expression()
+                        .references(&mut variables, &mut functions);
+                }
+                next_variable += 1;
+            }
+        }
+
+        let mut structs = Vec::new();
+        for function in &self.functions {
+            if functions.iter().any(|name| name == function.name()) {
+                function.return_type().struct_names(&mut structs);
+                for parameter in function.parameters() {
+                    parameter.parameter_type().struct_names(&mut structs);
+                }
+            }
+        }
+
+        for constant_buffer in self.constant_buffers.iter() {
+            if variables.iter().any(|name| name == constant_buffer.name()) {
+                constant_buffer.cb_type().struct_names(&mut structs);
+            }
+        }
+
+        for buffer in self.buffers.iter() {
+            if variables.iter().any(|name| name == buffer.name()) {
+                buffer.element_type().struct_names(&mut structs);
+            }
+        }
+
+        for constant in &self.constants {
+            if variables.iter().any(|name| name == constant.name()) {
+                constant.get_type().struct_names(&mut structs);
+            }
+        }
+
+        for group_shared in &self.group_shared {
+            if variables.iter().any(|name| name == group_shared.name()) {
+                group_shared.get_type().struct_names(&mut structs);
+            }
+        }
+
+        Reachable {
+            functions,
+            variables,
+            structs,
+        }
+    }
+
+    // Filters "variables" down to the resources, ordered by constant buffer
+    // slot then texture slot
+    fn msl_resources(&self, variables: &[String]) -> Vec<String> {
+        let constant_buffers = self
+            .constant_buffers
+            .iter()
+            .map(|constant_buffer| constant_buffer.name());
+        let textures = self.textures.iter().map(|texture| texture.name());
+
+        constant_buffers
+            .chain(textures)
+            .filter(|name| variables.iter().any(|variable| variable == name))
+            .map(|name| name.to_owned())
+            .collect()
+    }
+
+    fn msl_resource_argument(&self, resource: &str) -> String {
+        for constant_buffer in self.constant_buffers.iter() {
+            if constant_buffer.name() == resource {
+                return constant_buffer.msl_argument();
+            }
+        }
+
+        for texture in self.textures.iter() {
+            if texture.name() == resource {
+                return texture.msl_argument();
+            }
+        }
+
+        panic!("\"{}\" is not a resource", resource)
+    }
+
+    pub fn reflect(&self, options: &CompileOptions) -> Reflection {
+        let mut reflection = Reflection::new();
+        let explicit_locations = options.get_glsl_version().explicit_uniform_locations();
+
+        for constant_buffer in self.constant_buffers.iter() {
+            reflection.push_constant_buffer(
+                constant_buffer.name().to_owned(),
+                constant_buffer.slot(),
+                constant_buffer.space(),
+                constant_buffer.cb_type().to_string(),
+                match explicit_locations {
+                    true => Some(constant_buffer.slot() + CONSTANT_BUFFER_INDEX),
+                    false => None,
+                },
+            );
+        }
+
+        for texture in self.textures.iter() {
+            reflection.push_texture(
+                texture,
+                match explicit_locations {
+                    true => Some(texture.slot() + options.textures_index()),
+                    false => None,
+                },
+            );
+        }
+
+        for buffer in self.buffers.iter() {
+            reflection.push_buffer(
+                buffer.name().to_owned(),
+                buffer.slot(),
+                buffer.space(),
+                buffer.element_type(),
+                buffer.writable(),
+                buffer.hlsl_register_index(options),
+            );
+        }
+
+        for storage_texture in self.storage_textures.iter() {
+            reflection.push_storage_texture(
+                storage_texture.name().to_owned(),
+                storage_texture.slot(),
+                storage_texture.space(),
+                storage_texture.texel_type().to_string(),
+                storage_texture.format().to_owned(),
+                storage_texture.hlsl_register_index(options),
+            );
+        }
+
+        for sampler in self.samplers.iter() {
+            let textures = self
+                .sampled_textures
+                .borrow()
+                .iter()
+                .filter(|(_, name)| name == sampler.name())
+                .map(|(texture, _)| texture.clone())
+                .collect();
+
+            reflection.push_sampler(sampler, sampler.hlsl_register_index(options), textures);
+        }
+
+        if let Some(Type::Struct(structure)) = &self.vertex_input_type {
+            let locations = structure.locations(options.get_semantic_locations());
+            for (i, (name, member_type)) in structure.members().iter().enumerate() {
+                if structure.semantic(i).is_some_and(is_vertex_system_value) {
+                    continue;
+                }
+
+                reflection.push_vertex_attribute(
+                    name.to_owned(),
+                    structure.semantic(i).unwrap_or("").to_owned(),
+                    locations[i],
+                    member_type.to_string(),
+                );
+            }
+        }
+
+        // System values passed between the stages are builtins
+        if let Some(Type::Struct(structure)) = &self.fragment_input_type {
+            let locations = structure.locations(options.get_semantic_locations());
+            for (i, (name, member_type)) in structure.members().iter().enumerate() {
+                let semantic = structure.semantic(i).unwrap();
+                if !Semantic::parse(semantic)
+                    .is_some_and(|parsed| parsed.is_located_in(Stage::Interstage))
+                {
+                    continue;
+                }
+
+                reflection.push_interstage_variable(
+                    name.to_owned(),
+                    semantic.to_owned(),
+                    locations[i],
+                    member_type.to_string(),
+                );
+            }
+        }
+
+        reflection
+    }
+
+    pub fn generate_hlsl(mut self, options: &CompileOptions) -> String {
+        let mut hlsl = options.header_comment();
+
+        if self.uses_thread_id() {
+            hlsl.push_str(&self.thread_id.generate_hlsl(options));
+            hlsl.push('\n');
+        }
+
+        let sized_textures = self.sized_textures.take();
+        let (hull_attributes, domain_attributes) = self.hlsl_tessellation_attributes();
 
         for declaration in self.declaration_order {
             match declaration {
                 DeclarationType::Function => {
-                    hlsl.push_str(&self.functions.pop_front().unwrap().generate_hlsl())
+                    let function = self.functions.pop_front().unwrap();
+                    match function.name() {
+                        pipeline::HULL_MAIN => hlsl.push_str(&hull_attributes),
+                        pipeline::DOMAIN_MAIN => hlsl.push_str(&domain_attributes),
+                        _ => {}
+                    }
+                    hlsl.push_str(&function.generate_hlsl(options))
                 }
                 DeclarationType::Struct => {
-                    hlsl.push_str(&self.structs.pop_front().unwrap().generate_hlsl())
+                    hlsl.push_str(&self.structs.pop_front().unwrap().generate_hlsl(options))
                 }
-                DeclarationType::ConstantBuffer(slot) => {
-                    hlsl.push_str(&self.constant_buffers[slot].take().unwrap().generate_hlsl())
+                DeclarationType::ConstantBuffer(binding) => {
+                    hlsl.push_str(&self.constant_buffers.take(binding).generate_hlsl(options))
                 }
-                DeclarationType::Texture(slot) => {
-                    hlsl.push_str(&self.textures[slot].take().unwrap().generate_hlsl())
+                DeclarationType::Texture(binding) => {
+                    let texture = self.textures.take(binding);
+                    let sized = sized_textures.iter().any(|name| name == texture.name());
+                    hlsl.push_str(&texture.generate_hlsl(sized))
+                }
+                DeclarationType::Buffer(binding) => {
+                    hlsl.push_str(&self.buffers.take(binding).generate_hlsl(options))
+                }
+                DeclarationType::StorageTexture(binding) => {
+                    hlsl.push_str(&self.storage_textures.take(binding).generate_hlsl(options))
+                }
+                DeclarationType::Sampler(binding) => {
+                    hlsl.push_str(&self.samplers.take(binding).generate_hlsl(options))
                 }
                 DeclarationType::Constant => {
                     hlsl.push_str(&self.constants.pop_front().unwrap().generate_hlsl())
                 }
+                DeclarationType::GroupShared => {
+                    hlsl.push_str(&self.group_shared.pop_front().unwrap().generate_hlsl())
+                }
             }
 
             hlsl.push('\n');
@@ -312,113 +1160,723 @@ impl AnnotatedSyntaxTree {
         hlsl
     }
 
-    pub fn generate_glsl(mut self) -> (String, String) {
-        // Write header
-        let mut glsl_vertex =
-            format!("#version 430 core\n\n// Generated from Alexandria Common Shader Language\n\n");
-        let mut glsl_frag = glsl_vertex.clone();
-
-        // Write fragment output
-        glsl_frag.push_str("out vec4 acsl_fragment_color;\n\n");
-
-        // Write vertex input
-        let vertex_input_type = self.vertex_input_type.unwrap();
-        match vertex_input_type {
-            Type::Struct(structure) => {
-                let members = structure.members();
-                for i in 0..members.len() {
-                    glsl_vertex.push_str(&format!(
-                        "layout (location = {}) in {} acsl_vertex_input_{};\n",
-                        i,
-                        members[i].1.glsl(),
-                        members[i].0
-                    ));
+    // Generates a separate shader for each stage, each with only the
+    // declarations its entry points use, the stages between "vertex_main()"
+    // and "fragment_main()" are empty unless the file defines them
+    pub fn generate_hlsl_stages(mut self, options: &CompileOptions) -> StageCode {
+        let stages: Vec<(String, Reachable)> = [
+            &["vertex_main"][..],
+            &[pipeline::HULL_MAIN, pipeline::HULL_CONSTANTS],
+            &[pipeline::DOMAIN_MAIN],
+            &[pipeline::GEOMETRY_MAIN],
+            &["fragment_main"],
+        ]
+        .iter()
+        .map(|entry_points| (entry_points[0].to_owned(), self.reachable(entry_points)))
+        .collect();
+
+        let mut stage_hlsl: Vec<String> = stages
+            .iter()
+            .map(|(entry_point, _)| {
+                let present = self.user_function(entry_point).is_some()
+                    || !pipeline::is_stage_entry_point(entry_point);
+                match present {
+                    true => options.header_comment(),
+                    false => String::new(),
+                }
+            })
+            .collect();
+        let sized_textures = self.sized_textures.take();
+        let (hull_attributes, domain_attributes) = self.hlsl_tessellation_attributes();
+
+        for declaration in self.declaration_order {
+            let (hlsl, used) = match declaration {
+                DeclarationType::Function => {
+                    let function = self.functions.pop_front().unwrap();
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.functions,
+                        function.name(),
+                    );
+                    let attributes = match function.name() {
+                        pipeline::HULL_MAIN => hull_attributes.as_str(),
+                        pipeline::DOMAIN_MAIN => domain_attributes.as_str(),
+                        _ => "",
+                    };
+                    (
+                        format!("{}{}", attributes, function.generate_hlsl(options)),
+                        used,
+                    )
+                }
+                DeclarationType::Struct => {
+                    let structure = self.structs.pop_front().unwrap();
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.structs,
+                        structure.name(),
+                    );
+                    (structure.generate_hlsl(options), used)
+                }
+                DeclarationType::ConstantBuffer(binding) => {
+                    let constant_buffer = self.constant_buffers.take(binding);
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.variables,
+                        constant_buffer.name(),
+                    );
+                    (constant_buffer.generate_hlsl(options), used)
+                }
+                DeclarationType::Texture(binding) => {
+                    let texture = self.textures.take(binding);
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.variables,
+                        texture.name(),
+                    );
+                    let sized = sized_textures.iter().any(|name| name == texture.name());
+                    (texture.generate_hlsl(sized), used)
+                }
+                DeclarationType::Buffer(binding) => {
+                    let buffer = self.buffers.take(binding);
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.variables,
+                        buffer.name(),
+                    );
+                    (buffer.generate_hlsl(options), used)
+                }
+                DeclarationType::StorageTexture(binding) => {
+                    let storage_texture = self.storage_textures.take(binding);
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.variables,
+                        storage_texture.name(),
+                    );
+                    (storage_texture.generate_hlsl(options), used)
                 }
+                DeclarationType::Sampler(binding) => {
+                    let sampler = self.samplers.take(binding);
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.variables,
+                        sampler.name(),
+                    );
+                    (sampler.generate_hlsl(options), used)
+                }
+                DeclarationType::Constant => {
+                    let constant = self.constants.pop_front().unwrap();
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.variables,
+                        constant.name(),
+                    );
+                    (constant.generate_hlsl(), used)
+                }
+                DeclarationType::GroupShared => {
+                    let group_shared = self.group_shared.pop_front().unwrap();
+                    let used = entry_points_using(
+                        &stages,
+                        |reachable| &reachable.variables,
+                        group_shared.name(),
+                    );
+                    (group_shared.generate_hlsl(), used)
+                }
+            };
 
-                glsl_vertex.push('\n');
+            for (stage, used) in stage_hlsl.iter_mut().zip(used) {
+                if used && !stage.is_empty() {
+                    stage.push_str(&hlsl);
+                    stage.push('\n');
+                }
             }
-            _ => panic!("Vertex input must be a structure"),
         }
 
-        // Write fragment input & vertex output
-        let fragment_input_type = self.fragment_input_type.unwrap();
-        let mut position_variable_name = None;
-        match fragment_input_type {
-            Type::Struct(structure) => {
-                let members = structure.members();
-                let semantics = structure.semantics();
-                for i in 0..members.len() {
-                    glsl_vertex.push_str(&format!(
-                        "out {} acsl_pixel_input_{};\n",
-                        members[i].1.glsl(),
-                        members[i].0
-                    ));
-                    glsl_frag.push_str(&format!(
-                        "in {} acsl_pixel_input_{};\n",
-                        members[i].1.glsl(),
-                        members[i].0
-                    ));
+        let mut stage_hlsl = stage_hlsl.into_iter();
+        StageCode {
+            vertex: stage_hlsl.next().unwrap(),
+            hull: stage_hlsl.next().unwrap(),
+            domain: stage_hlsl.next().unwrap(),
+            geometry: stage_hlsl.next().unwrap(),
+            fragment: stage_hlsl.next().unwrap(),
+        }
+    }
 
-                    if semantics[i] == "SV_POSITION" {
-                        position_variable_name = Some(members[i].0.to_string());
-                    }
+    pub fn generate_wgsl(mut self, options: &CompileOptions) -> String {
+        let mut wgsl = options.header_comment();
+
+        for declaration in self.declaration_order {
+            match declaration {
+                DeclarationType::Function => {
+                    wgsl.push_str(&self.functions.pop_front().unwrap().generate_wgsl(options))
+                }
+                DeclarationType::Struct => {
+                    wgsl.push_str(&self.structs.pop_front().unwrap().generate_wgsl(options))
+                }
+                DeclarationType::ConstantBuffer(binding) => {
+                    wgsl.push_str(&self.constant_buffers.take(binding).generate_wgsl())
+                }
+                DeclarationType::Texture(binding) => {
+                    wgsl.push_str(&self.textures.take(binding).generate_wgsl(options))
+                }
+                DeclarationType::Sampler(binding) => {
+                    wgsl.push_str(&self.samplers.take(binding).generate_wgsl(options))
+                }
+                DeclarationType::Constant => {
+                    wgsl.push_str(&self.constants.pop_front().unwrap().generate_wgsl())
+                }
+                DeclarationType::Buffer(_) => unreachable!("WGSL has no storage buffers"),
+                DeclarationType::StorageTexture(_) => {
+                    unreachable!("WGSL has no storage textures")
+                }
+                DeclarationType::GroupShared => unreachable!("WGSL has no compute shaders"),
+            }
+
+            wgsl.push('\n');
+        }
+
+        wgsl
+    }
+
+    pub fn generate_msl(mut self, options: &CompileOptions) -> String {
+        let mut msl = format!(
+            "{}#include <metal_stdlib>\n\nusing namespace metal;\n\n",
+            options.header_comment()
+        );
+
+        // Find the resources each function uses, callees are always declared
+        // before their callers so their resources are already known
+        let mut function_resources: Vec<(String, Vec<String>)> = Vec::new();
+        for function in &self.functions {
+            let mut variables = Vec::new();
+            let mut functions = Vec::new();
+            function.references(&mut variables, &mut functions);
+
+            for (name, resources) in &function_resources {
+                if functions.contains(name) {
+                    variables.extend(resources.iter().cloned());
                 }
+            }
+
+            function_resources.push((function.name().to_owned(), self.msl_resources(&variables)));
+        }
+
+        let resource_arguments: HashMap<String, String> = function_resources
+            .iter()
+            .map(|(name, resources)| {
+                let arguments: Vec<String> = resources
+                    .iter()
+                    .map(|resource| self.msl_resource_argument(resource))
+                    .collect();
+                (name.clone(), arguments.join(", "))
+            })
+            .collect();
+
+        let vertex_input_type = self.vertex_input_type.clone();
+        let mut function_resources = function_resources.into_iter();
+
+        for declaration in self.declaration_order {
+            match declaration {
+                DeclarationType::Function => {
+                    let function = self.functions.pop_front().unwrap();
+                    let (_, resources) = function_resources.next().unwrap();
+
+                    let entry_point = function.is_entry_point();
+                    let mut resource_parameters = Vec::new();
+                    for resource in &resources {
+                        for constant_buffer in self.constant_buffers.iter() {
+                            if constant_buffer.name() == resource {
+                                resource_parameters
+                                    .push(constant_buffer.msl_parameter(entry_point));
+                            }
+                        }
+
+                        for texture in self.textures.iter() {
+                            if texture.name() == resource {
+                                resource_parameters.push(texture.msl_parameter(entry_point));
+                            }
+                        }
+                    }
 
-                glsl_vertex.push('\n');
-                glsl_frag.push('\n');
+                    msl.push_str(&function.generate_msl(
+                        &resource_parameters,
+                        &resource_arguments,
+                        options,
+                    ))
+                }
+                DeclarationType::Struct => {
+                    let structure = self.structs.pop_front().unwrap();
+                    let vertex_input = match &vertex_input_type {
+                        Some(Type::Struct(vertex_input)) => Rc::ptr_eq(vertex_input, &structure),
+                        _ => false,
+                    };
+                    msl.push_str(&structure.generate_msl(vertex_input, options))
+                }
+                // Resources are passed as parameters
+                DeclarationType::ConstantBuffer(_) | DeclarationType::Texture(_) => continue,
+                DeclarationType::Sampler(binding) => {
+                    msl.push_str(&self.samplers.take(binding).generate_msl())
+                }
+                DeclarationType::Constant => {
+                    msl.push_str(&self.constants.pop_front().unwrap().generate_msl())
+                }
+                DeclarationType::Buffer(_) => unreachable!("MSL has no storage buffers"),
+                DeclarationType::StorageTexture(_) => unreachable!("MSL has no storage textures"),
+                DeclarationType::GroupShared => unreachable!("MSL has no compute shaders"),
             }
+
+            msl.push('\n');
+        }
+
+        msl
+    }
+
+    pub fn generate_spirv(self, options: &CompileOptions) -> Vec<u32> {
+        let mut context = Context::new();
+
+        for constant_buffer in self.constant_buffers.iter() {
+            constant_buffer.declare_spirv(&mut context);
+        }
+
+        for texture in self.textures.iter() {
+            texture.declare_spirv(&mut context);
+        }
+
+        for sampler in self.samplers.iter() {
+            sampler.declare_spirv(&mut context, options);
+        }
+
+        for constant in &self.constants {
+            context.define_constant(constant.name(), constant.expression());
+        }
+
+        for function in &self.functions {
+            function.declare_spirv(&mut context);
+        }
+
+        for function in &self.functions {
+            function.generate_spirv(&mut context);
+        }
+
+        let vertex_input = match &self.vertex_input_type {
+            Some(Type::Struct(structure)) => structure,
+            _ => panic!("Vertex input must be a structure"),
+        };
+
+        let fragment_input = match &self.fragment_input_type {
+            Some(Type::Struct(structure)) => structure,
             _ => panic!("Fragment input must be a structure"),
         };
 
-        let position_variable_name = match position_variable_name {
-            Some(position_variable_name) => position_variable_name,
-            None => panic!("Pixel input type must have position semantic"),
+        let fragment_output = match &self.fragment_output_type {
+            Some(Type::Struct(structure)) => Some(structure.as_ref()),
+            _ => None,
+        };
+
+        spirv_vertex_entry_point(&mut context, vertex_input, fragment_input, options);
+        spirv_fragment_entry_point(&mut context, fragment_input, fragment_output, options);
+
+        context.module.finish()
+    }
+
+    // Generates a shader for each graphics stage, empty without a graphics
+    // pipeline, along with a shader for every compute kernel
+    pub fn generate_glsl(mut self, options: &CompileOptions) -> (StageCode, Vec<(String, String)>) {
+        let version = options.get_glsl_version();
+
+        // Write header
+        let mut header = format!(
+            "#version {}\n\n{}",
+            version.directive(),
+            options.header_comment()
+        );
+
+        // Write default precisions
+        if version.is_es() {
+            header.push_str("precision highp float;\nprecision highp int;\n");
+
+            let mut sampler_types = Vec::new();
+            for texture in self.textures.iter() {
+                let sampler_type = texture.glsl_sampler_type();
+                if !sampler_types.contains(&sampler_type) {
+                    header.push_str(&format!("precision highp {};\n", sampler_type));
+                    sampler_types.push(sampler_type);
+                }
+            }
+
+            header.push('\n');
+        }
+
+        // Every compute kernel gets the declarations it uses
+        let kernels: Vec<(String, Reachable)> = self
+            .functions
+            .iter()
+            .filter(|function| function.is_compute_kernel())
+            .map(|function| {
+                (
+                    function.name().to_owned(),
+                    self.reachable(&[function.name()]),
+                )
+            })
+            .collect();
+
+        let mut glsl_compute: Vec<String> = Vec::new();
+        for function in self.functions.iter() {
+            if let Some((x, y, z)) = function.thread_group_size() {
+                let mut glsl = format!(
+                    "{}layout(local_size_x = {}, local_size_y = {}, local_size_z = {}) in;\n\n",
+                    header, x, y, z
+                );
+
+                if function.parameters().len() == 1 {
+                    glsl.push_str(&self.thread_id.generate_glsl(options));
+                    glsl.push('\n');
+                }
+
+                glsl_compute.push(glsl);
+            }
+        }
+
+        let mut glsl = StageCode {
+            vertex: header.clone(),
+            fragment: header.clone(),
+            ..StageCode::default()
         };
+        let mut position_variable_name = String::new();
+        let (declarations, mains, emit) = match self.glsl_stages(options) {
+            Some((declarations, mains, emit)) => (Some(declarations), Some(mains), emit),
+            None => (None, None, String::new()),
+        };
+
+        if let (Some(vertex_input_type), Some(fragment_input_type)) = (
+            self.vertex_input_type.take(),
+            self.fragment_input_type.take(),
+        ) {
+            // Write fragment output, every version can locate the outputs
+            // of multiple render targets, depth is written to "gl_FragDepth"
+            match &self.fragment_output_type {
+                Some(Type::Struct(structure)) => {
+                    for (i, (name, member_type)) in structure.members().iter().enumerate() {
+                        if let Some(target) = structure.semantic(i).and_then(render_target) {
+                            glsl.fragment.push_str(&format!(
+                                "layout(location = {}) out {} acsl_fragment_{};\n",
+                                target,
+                                member_type.glsl(),
+                                name
+                            ));
+                        }
+                    }
+
+                    glsl.fragment.push('\n');
+                }
+                _ if version.is_es() || version.is_vulkan() => glsl
+                    .fragment
+                    .push_str("layout(location = 0) out vec4 acsl_fragment_color;\n\n"),
+                _ => glsl.fragment.push_str("out vec4 acsl_fragment_color;\n\n"),
+            }
+
+            // Write vertex input, system values aren't attributes
+            match vertex_input_type {
+                Type::Struct(structure) => {
+                    let members = structure.members();
+                    let locations = structure.locations(options.get_semantic_locations());
+                    for i in 0..members.len() {
+                        if structure.semantic(i).is_some_and(is_vertex_system_value) {
+                            continue;
+                        }
+
+                        glsl.vertex.push_str(&format!(
+                            "layout (location = {}) in {} acsl_vertex_input_{};\n",
+                            locations[i],
+                            members[i].1.glsl(),
+                            members[i].0
+                        ));
+                    }
+
+                    glsl.vertex.push('\n');
+                }
+                _ => panic!("Vertex input must be a structure"),
+            }
+
+            // Write fragment input, "vertex_main()" writes it unless there are
+            // stages in between, system values come from the builtin variables
+            match fragment_input_type {
+                Type::Struct(structure) => {
+                    if let Some(i) = structure.find_semantic("SV_POSITION") {
+                        position_variable_name = structure.members()[i].0.clone();
+                    }
+
+                    glsl.fragment.push_str(&pipeline::glsl_interface(
+                        &structure, "in", "pixel", false, options,
+                    ));
+                    glsl.fragment.push('\n');
+
+                    if declarations.is_none() {
+                        glsl.vertex.push_str(&pipeline::glsl_interface(
+                            &structure, "out", "pixel", false, options,
+                        ));
+                        glsl.vertex.push('\n');
+                    }
+                }
+                _ => panic!("Fragment input must be a structure"),
+            };
+
+            // The tessellation and geometry shaders pass the vertices on
+            if let Some(declarations) = &declarations {
+                glsl.vertex.push_str(&declarations.vertex);
+                for (shader, declaration) in [
+                    (&mut glsl.hull, &declarations.hull),
+                    (&mut glsl.domain, &declarations.domain),
+                    (&mut glsl.geometry, &declarations.geometry),
+                ] {
+                    if !declaration.is_empty() {
+                        *shader = format!("{}{}", header, declaration);
+                    }
+                }
+            }
+        } else {
+            glsl.vertex.clear();
+            glsl.fragment.clear();
+        }
+
+        let graphics = !glsl.vertex.is_empty();
 
         // Write declarations
         for declaration in self.declaration_order {
-            match declaration {
+            // The code of each stage, then the code for the kernels which use
+            // the declaration
+            let (code, compute, used) = match declaration {
                 DeclarationType::Function => {
-                    let (vertex, frag) = self
-                        .functions
-                        .pop_front()
-                        .unwrap()
-                        .generate_glsl(&position_variable_name);
-                    glsl_vertex.push_str(&vertex);
-                    glsl_frag.push_str(&frag);
+                    let function = self.functions.pop_front().unwrap();
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.functions,
+                        function.name(),
+                    );
+
+                    if function.is_compute_kernel() {
+                        (None, function.generate_glsl_compute(options), used)
+                    } else if declarations.is_some()
+                        && (function.name() == "vertex_main"
+                            || pipeline::is_stage_entry_point(function.name()))
+                    {
+                        // The entry points are called by the "main()" of
+                        // their own shader
+                        let shader = match function.name() {
+                            "vertex_main" => &mut glsl.vertex,
+                            pipeline::HULL_MAIN | pipeline::HULL_CONSTANTS => &mut glsl.hull,
+                            pipeline::DOMAIN_MAIN => &mut glsl.domain,
+                            _ => {
+                                glsl.geometry.push_str(&emit);
+                                glsl.geometry.push('\n');
+                                &mut glsl.geometry
+                            }
+                        };
+
+                        let code = function.generate_glsl_stage_function(options);
+                        shader.push_str(&code);
+                        shader.push('\n');
+                        (None, code, used)
+                    } else {
+                        let (vertex, fragment) =
+                            function.generate_glsl(&position_variable_name, options);
+                        let compute = vertex.clone();
+                        let mut code = StageCode::shared(vertex);
+                        code.fragment = fragment;
+                        (Some(code), compute, used)
+                    }
                 }
                 DeclarationType::Struct => {
-                    let glsl = self.structs.pop_front().unwrap().generate_glsl();
-                    glsl_vertex.push_str(&glsl);
-                    glsl_frag.push_str(&glsl);
+                    let structure = self.structs.pop_front().unwrap();
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.structs,
+                        structure.name(),
+                    );
+                    let glsl = structure.generate_glsl(options);
+                    (Some(StageCode::shared(glsl.clone())), glsl, used)
+                }
+                DeclarationType::ConstantBuffer(binding) => {
+                    let constant_buffer = self.constant_buffers.take(binding);
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.variables,
+                        constant_buffer.name(),
+                    );
+                    let glsl = constant_buffer.generate_glsl(options);
+                    (Some(StageCode::shared(glsl.clone())), glsl, used)
+                }
+                DeclarationType::Texture(binding) => {
+                    let texture = self.textures.take(binding);
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.variables,
+                        texture.name(),
+                    );
+                    let glsl = texture.generate_glsl(options);
+                    (Some(StageCode::shared(glsl.clone())), glsl, used)
                 }
-                DeclarationType::ConstantBuffer(slot) => {
-                    let glsl = self.constant_buffers[slot].take().unwrap().generate_glsl();
-                    glsl_vertex.push_str(&glsl);
-                    glsl_frag.push_str(&glsl);
+                DeclarationType::Buffer(binding) => {
+                    let buffer = self.buffers.take(binding);
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.variables,
+                        buffer.name(),
+                    );
+                    let glsl = buffer.generate_glsl(options);
+                    (Some(StageCode::shared(glsl.clone())), glsl, used)
                 }
-                DeclarationType::Texture(slot) => {
-                    let glsl = self.textures[slot].take().unwrap().generate_glsl();
-                    glsl_vertex.push_str(&glsl);
-                    glsl_frag.push_str(&glsl);
+                DeclarationType::StorageTexture(binding) => {
+                    let storage_texture = self.storage_textures.take(binding);
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.variables,
+                        storage_texture.name(),
+                    );
+                    let glsl = storage_texture.generate_glsl(options);
+                    (Some(StageCode::shared(glsl.clone())), glsl, used)
+                }
+                DeclarationType::Sampler(binding) => {
+                    let sampler = self.samplers.take(binding);
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.variables,
+                        sampler.name(),
+                    );
+                    match sampler.generate_glsl(options) {
+                        Some(glsl) => (Some(StageCode::shared(glsl.clone())), glsl, used),
+                        None => continue,
+                    }
                 }
                 DeclarationType::Constant => {
-                    let glsl = self.constants.pop_front().unwrap().generate_glsl();
-                    glsl_vertex.push_str(&glsl);
-                    glsl_frag.push_str(&glsl);
+                    let constant = self.constants.pop_front().unwrap();
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.variables,
+                        constant.name(),
+                    );
+                    let glsl = constant.generate_glsl(options);
+                    (Some(StageCode::shared(glsl.clone())), glsl, used)
+                }
+                // Shared memory is only valid in compute shaders
+                DeclarationType::GroupShared => {
+                    let group_shared = self.group_shared.pop_front().unwrap();
+                    let used = entry_points_using(
+                        &kernels,
+                        |reachable| &reachable.variables,
+                        group_shared.name(),
+                    );
+                    (None, group_shared.generate_glsl(), used)
+                }
+            };
+
+            if let (Some(code), true) = (code, graphics) {
+                glsl.append(code);
+            }
+
+            for (glsl, used) in glsl_compute.iter_mut().zip(used) {
+                if used {
+                    glsl.push_str(&compute);
+                    glsl.push('\n');
+                }
+            }
+        }
+
+        // The "main()" of each shader calls its entry points
+        if let (Some(mains), true) = (mains, graphics) {
+            for (shader, main) in [
+                (&mut glsl.vertex, mains.vertex),
+                (&mut glsl.hull, mains.hull),
+                (&mut glsl.domain, mains.domain),
+                (&mut glsl.geometry, mains.geometry),
+            ] {
+                if !main.is_empty() {
+                    shader.push_str(&main);
+                    shader.push('\n');
                 }
             }
+        }
+
+        let compute = kernels
+            .into_iter()
+            .map(|(name, _)| name)
+            .zip(glsl_compute)
+            .collect();
+
+        (glsl, compute)
+    }
+
+    // Compute kernels taking their thread IDs need the "ThreadId" structure
+    fn uses_thread_id(&self) -> bool {
+        self.functions
+            .iter()
+            .any(|function| function.is_compute_kernel() && !function.parameters().is_empty())
+    }
+
+    // The entry points can be renamed by the options, the names are used as
+    // they are by every target besides GLSL, which always uses "main"
+    pub fn verify_entry_point_names(
+        &self,
+        options: &CompileOptions,
+    ) -> Result<(), SemanticAnalysisError> {
+        let vertex_entry_point = options.get_vertex_entry_point();
+        let fragment_entry_point = options.get_fragment_entry_point();
+
+        for (name, function) in [
+            (vertex_entry_point, "vertex_main"),
+            (fragment_entry_point, "fragment_main"),
+        ] {
+            if !is_identifier(name)
+                || name.starts_with("acsl_")
+                || name.starts_with("gl_")
+                || RESERVED_WORDS.contains(&name)
+                || MSL_SAMPLING_OPTIONS.contains(&name)
+            {
+                return Err(SemanticAnalysisError::InvalidEntryPointName(
+                    name.to_owned(),
+                ));
+            }
+
+            let in_use = self
+                .functions
+                .iter()
+                .any(|other| other.name() == name && other.name() != function)
+                || self
+                    .builtin_functions
+                    .iter()
+                    .any(|builtin| builtin.name() == name)
+                || self.global_scope.get_variable(name).is_ok()
+                || (self.user_function(name).is_none() && !self.verify_type_name(name));
+            if in_use {
+                return Err(SemanticAnalysisError::EntryPointNameInUse(name.to_owned()));
+            }
+        }
 
-            glsl_vertex.push('\n');
-            glsl_frag.push('\n');
+        if vertex_entry_point == fragment_entry_point {
+            return Err(SemanticAnalysisError::EntryPointNameInUse(
+                fragment_entry_point.to_owned(),
+            ));
         }
 
-        (glsl_vertex, glsl_frag)
+        Ok(())
     }
 
     fn verify_type_name(&self, name: &str) -> bool {
         const BUILTIN_TYPENAMES: &[&str] = &[
-            "float", "float1", "float2", "float3", "float4", "float4x4", "texture",
+            "float",
+            "float1",
+            "float2",
+            "float3",
+            "float4",
+            "float4x4",
+            "half",
+            "texture",
+            "texture1d",
+            "texture3d",
+            "texture_cube",
+            "texture2d_array",
+            "texture_cube_array",
+            "sampler",
+            "ThreadId",
         ];
 
         const RESERVED_TYPENAMES: &[&str] = &[
@@ -427,8 +1885,23 @@ impl AnnotatedSyntaxTree {
             "vec3",
             "vec4",
             "mat",
+            "sampler1D",
             "sampler2D",
+            "sampler3D",
+            "samplerCube",
+            "sampler2DArray",
+            "samplerCubeArray",
+            "Texture1D",
             "Texture2D",
+            "Texture3D",
+            "TextureCube",
+            "Texture2DArray",
+            "TextureCubeArray",
+            "StructuredBuffer",
+            "RWStructuredBuffer",
+            "RWTexture2D",
+            "image2D",
+            "SamplerState",
         ];
 
         for function in &self.functions {
@@ -464,3 +1937,552 @@ impl AnnotatedSyntaxTree {
         true
     }
 }
+
+// Each semantic of a structure entering or leaving a stage must be valid for
+// it and used once, the structure passed between the stages also needs the
+// position of its vertices and is the only one with interpolated members, no
+// two members can be placed at the same location
+fn verify_semantics(
+    structure: &Struct,
+    semantic_locations: &[(String, usize)],
+    stage: Stage,
+) -> Result<(), SemanticAnalysisError> {
+    let mut semantics = Vec::new();
+    let mut used_locations = Vec::new();
+    let locations = structure.locations(semantic_locations);
+
+    for (i, (name, _)) in structure.members().iter().enumerate() {
+        let semantic = structure.semantic(i).unwrap();
+        let parsed = match Semantic::parse(semantic) {
+            Some(parsed) if parsed.is_valid_in(stage) => {
+                if semantics.contains(&parsed) {
+                    return Err(SemanticAnalysisError::DuplicateSemantic(
+                        structure.name().to_owned(),
+                        semantic.to_owned(),
+                    ));
+                }
+                parsed
+            }
+            _ => {
+                return Err(SemanticAnalysisError::InvalidSemantic(
+                    structure.name().to_owned(),
+                    name.clone(),
+                    semantic.to_owned(),
+                    stage,
+                ))
+            }
+        };
+
+        if let Some(interpolation) = structure.interpolation_attribute(i) {
+            if stage != Stage::Interstage || !matches!(parsed, Semantic::User(_, _)) {
+                return Err(SemanticAnalysisError::InvalidInterpolation(
+                    structure.name().to_owned(),
+                    name.clone(),
+                    interpolation,
+                ));
+            }
+        }
+
+        if parsed.is_located_in(stage) {
+            if used_locations.contains(&locations[i]) {
+                return Err(SemanticAnalysisError::ReusingLocation(
+                    structure.name().to_owned(),
+                    locations[i],
+                ));
+            }
+            used_locations.push(locations[i]);
+        }
+
+        semantics.push(parsed);
+    }
+
+    let positions = semantics
+        .iter()
+        .filter(|semantic| **semantic == Semantic::Position)
+        .count();
+    if stage == Stage::Interstage && positions != 1 {
+        return Err(SemanticAnalysisError::PositionSemanticCount(
+            structure.name().to_owned(),
+            positions,
+        ));
+    }
+
+    Ok(())
+}
+
+// Every member of a fragment output structure writes its own render target,
+// or the depth of the fragment
+fn verify_render_targets(structure: &Struct) -> Result<(), SemanticAnalysisError> {
+    let mut targets = Vec::new();
+    let mut depth = false;
+
+    for (i, (name, member_type)) in structure.members().iter().enumerate() {
+        if structure.semantic(i) == Some("SV_DEPTH") {
+            if depth {
+                return Err(SemanticAnalysisError::DuplicateSemantic(
+                    structure.name().to_owned(),
+                    "SV_DEPTH".to_owned(),
+                ));
+            }
+            depth = true;
+            continue;
+        }
+
+        let target = match structure.semantic(i).and_then(render_target) {
+            Some(target) => target,
+            None => {
+                return Err(SemanticAnalysisError::InvalidRenderTarget(
+                    structure.name().to_owned(),
+                    name.clone(),
+                ))
+            }
+        };
+
+        if targets.contains(&target) {
+            return Err(SemanticAnalysisError::ReusingRenderTarget(target));
+        }
+        targets.push(target);
+
+        if !member_type.is_float() && !member_type.is_float_vector() && !member_type.is_integer() {
+            return Err(SemanticAnalysisError::InvalidRenderTargetType(
+                name.clone(),
+                member_type.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// The entry points wrap "vertex_main" and "fragment_main", moving the members
+// of their input and output structures through interface variables
+fn spirv_vertex_entry_point(
+    context: &mut Context,
+    input: &Struct,
+    output: &Struct,
+    options: &CompileOptions,
+) {
+    let mut interface = Vec::new();
+    let input_locations = input.locations(options.get_semantic_locations());
+    let output_locations = output.locations(options.get_semantic_locations());
+
+    let mut inputs = Vec::new();
+    for (i, (name, member_type)) in input.members().iter().enumerate() {
+        let variable = spirv_interface_variable(
+            context,
+            member_type,
+            spirv::STORAGE_CLASS_INPUT,
+            &format!("acsl_vertex_input_{}", name),
+        );
+        let module = &mut context.module;
+        match input.semantic(i) {
+            Some("SV_VERTEX_ID") => module.decorate(
+                variable,
+                spirv::DECORATION_BUILT_IN,
+                &[spirv::BUILT_IN_VERTEX_INDEX],
+            ),
+            Some("SV_INSTANCE_ID") => module.decorate(
+                variable,
+                spirv::DECORATION_BUILT_IN,
+                &[spirv::BUILT_IN_INSTANCE_INDEX],
+            ),
+            _ => module.decorate(
+                variable,
+                spirv::DECORATION_LOCATION,
+                &[input_locations[i] as u32],
+            ),
+        }
+
+        interface.push(variable);
+        inputs.push((variable, member_type.spirv(module)));
+    }
+
+    // The facing of a fragment comes from the rasterizer
+    let mut outputs = Vec::new();
+    for (i, (name, member_type)) in output.members().iter().enumerate() {
+        if output.semantic(i) == Some("SV_IS_FRONT_FACE") {
+            continue;
+        }
+
+        let variable = spirv_interface_variable(
+            context,
+            member_type,
+            spirv::STORAGE_CLASS_OUTPUT,
+            &format!("acsl_pixel_input_{}", name),
+        );
+        let position = output.semantic(i) == Some("SV_POSITION");
+        let module = &mut context.module;
+        if position {
+            module.decorate(
+                variable,
+                spirv::DECORATION_BUILT_IN,
+                &[spirv::BUILT_IN_POSITION],
+            );
+        } else {
+            module.decorate(
+                variable,
+                spirv::DECORATION_LOCATION,
+                &[output_locations[i] as u32],
+            );
+        }
+
+        interface.push(variable);
+        outputs.push((i, variable, member_type.spirv(module), position));
+    }
+
+    let function = spirv_begin_entry_point(context, "acsl_vertex_main");
+    let module = &mut context.module;
+
+    let values: Vec<Id> = inputs
+        .iter()
+        .map(|(variable, value_type)| module.emit_result(spirv::OP_LOAD, *value_type, &[*variable]))
+        .collect();
+    let input_type = input.spirv(module);
+    let input_value = module.emit_result(spirv::OP_COMPOSITE_CONSTRUCT, input_type, &values);
+
+    let (vertex_main, output_type) = context.function("vertex_main").unwrap();
+    let module = &mut context.module;
+    let output_value = module.emit_result(
+        spirv::OP_FUNCTION_CALL,
+        output_type,
+        &[vertex_main, input_value],
+    );
+
+    for (i, variable, value_type, position) in outputs {
+        let mut value = module.emit_result(
+            spirv::OP_COMPOSITE_EXTRACT,
+            value_type,
+            &[output_value, i as u32],
+        );
+
+        if position {
+            let float_type = module.type_float();
+
+            if options.get_flip_y() {
+                let y = module.emit_result(spirv::OP_COMPOSITE_EXTRACT, float_type, &[value, 1]);
+                let y = module.emit_result(spirv::OP_F_NEGATE, float_type, &[y]);
+                value = module.emit_result(spirv::OP_COMPOSITE_INSERT, value_type, &[y, value, 1]);
+            }
+
+            if options.get_fix_depth_range() {
+                let z = module.emit_result(spirv::OP_COMPOSITE_EXTRACT, float_type, &[value, 2]);
+                let w = module.emit_result(spirv::OP_COMPOSITE_EXTRACT, float_type, &[value, 3]);
+                let sum = module.emit_result(spirv::OP_F_ADD, float_type, &[z, w]);
+                let half = module.constant_float(0.5);
+                let z = module.emit_result(spirv::OP_F_MUL, float_type, &[sum, half]);
+                value = module.emit_result(spirv::OP_COMPOSITE_INSERT, value_type, &[z, value, 2]);
+            }
+        }
+
+        module.emit(spirv::OP_STORE, &[variable, value]);
+    }
+
+    spirv_end_entry_point(context);
+    context.module.entry_point(
+        spirv::EXECUTION_MODEL_VERTEX,
+        function,
+        options.get_vertex_entry_point(),
+        &interface,
+    );
+}
+
+// A structure output writes each member to the location of its render target
+fn spirv_fragment_entry_point(
+    context: &mut Context,
+    input: &Struct,
+    output: Option<&Struct>,
+    options: &CompileOptions,
+) {
+    let mut interface = Vec::new();
+
+    let mut inputs = Vec::new();
+    let mut front_facing = None;
+    let locations = input.locations(options.get_semantic_locations());
+    for (i, (name, member_type)) in input.members().iter().enumerate() {
+        // The facing is a boolean builtin, selected into a uint
+        if input.semantic(i) == Some("SV_IS_FRONT_FACE") {
+            let module = &mut context.module;
+            let bool_type = module.type_bool();
+            let pointer_type = module.type_pointer(spirv::STORAGE_CLASS_INPUT, bool_type);
+            let variable = module.global_variable(pointer_type, spirv::STORAGE_CLASS_INPUT);
+            module.name(variable, &format!("acsl_pixel_input_{}", name));
+            module.decorate(
+                variable,
+                spirv::DECORATION_BUILT_IN,
+                &[spirv::BUILT_IN_FRONT_FACING],
+            );
+
+            interface.push(variable);
+            inputs.push((variable, bool_type));
+            front_facing = Some(i);
+            continue;
+        }
+
+        let variable = spirv_interface_variable(
+            context,
+            member_type,
+            spirv::STORAGE_CLASS_INPUT,
+            &format!("acsl_pixel_input_{}", name),
+        );
+        let module = &mut context.module;
+        if input.semantic(i) == Some("SV_POSITION") {
+            module.decorate(
+                variable,
+                spirv::DECORATION_BUILT_IN,
+                &[spirv::BUILT_IN_FRAG_COORD],
+            );
+        } else {
+            module.decorate(variable, spirv::DECORATION_LOCATION, &[locations[i] as u32]);
+
+            if let Some(interpolation) = input.interpolation(i) {
+                if interpolation == Interpolation::Sample {
+                    module.capability(spirv::CAPABILITY_SAMPLE_RATE_SHADING);
+                }
+                module.decorate(variable, interpolation.spirv_decoration(), &[]);
+            }
+        }
+
+        interface.push(variable);
+        inputs.push((variable, member_type.spirv(module)));
+    }
+
+    let mut outputs = Vec::new();
+    match output {
+        Some(output) => {
+            for (i, (name, member_type)) in output.members().iter().enumerate() {
+                let variable = spirv_interface_variable(
+                    context,
+                    member_type,
+                    spirv::STORAGE_CLASS_OUTPUT,
+                    &format!("acsl_fragment_{}", name),
+                );
+                match output.semantic(i).and_then(render_target) {
+                    Some(target) => context.module.decorate(
+                        variable,
+                        spirv::DECORATION_LOCATION,
+                        &[target as u32],
+                    ),
+                    None => context.module.decorate(
+                        variable,
+                        spirv::DECORATION_BUILT_IN,
+                        &[spirv::BUILT_IN_FRAG_DEPTH],
+                    ),
+                }
+
+                interface.push(variable);
+                outputs.push(variable);
+            }
+        }
+        None => {
+            let color = spirv_interface_variable(
+                context,
+                &Type::float4(),
+                spirv::STORAGE_CLASS_OUTPUT,
+                "acsl_fragment_color",
+            );
+            context
+                .module
+                .decorate(color, spirv::DECORATION_LOCATION, &[0]);
+            interface.push(color);
+            outputs.push(color);
+        }
+    }
+
+    let function = spirv_begin_entry_point(context, "acsl_fragment_main");
+    let module = &mut context.module;
+
+    let mut values: Vec<Id> = inputs
+        .iter()
+        .map(|(variable, value_type)| module.emit_result(spirv::OP_LOAD, *value_type, &[*variable]))
+        .collect();
+    if let Some(i) = front_facing {
+        let uint_type = module.type_uint();
+        let one = module.constant_uint(1);
+        let zero = module.constant_uint(0);
+        values[i] = module.emit_result(spirv::OP_SELECT, uint_type, &[values[i], one, zero]);
+    }
+    let input_type = input.spirv(module);
+    let input_value = module.emit_result(spirv::OP_COMPOSITE_CONSTRUCT, input_type, &values);
+
+    let (fragment_main, output_type) = context.function("fragment_main").unwrap();
+    let module = &mut context.module;
+    let value = module.emit_result(
+        spirv::OP_FUNCTION_CALL,
+        output_type,
+        &[fragment_main, input_value],
+    );
+
+    match output {
+        Some(output) => {
+            for (i, (_, member_type)) in output.members().iter().enumerate() {
+                let member_type = member_type.spirv(module);
+                let member = module.emit_result(
+                    spirv::OP_COMPOSITE_EXTRACT,
+                    member_type,
+                    &[value, i as u32],
+                );
+                module.emit(spirv::OP_STORE, &[outputs[i], member]);
+            }
+        }
+        None => module.emit(spirv::OP_STORE, &[outputs[0], value]),
+    }
+
+    spirv_end_entry_point(context);
+    context.module.entry_point(
+        spirv::EXECUTION_MODEL_FRAGMENT,
+        function,
+        options.get_fragment_entry_point(),
+        &interface,
+    );
+    context
+        .module
+        .execution_mode(function, spirv::EXECUTION_MODE_ORIGIN_UPPER_LEFT);
+
+    if output.is_some_and(|output| output.find_semantic("SV_DEPTH").is_some()) {
+        context
+            .module
+            .execution_mode(function, spirv::EXECUTION_MODE_DEPTH_REPLACING);
+    }
+}
+
+fn spirv_interface_variable(
+    context: &mut Context,
+    variable_type: &Type,
+    storage_class: u32,
+    name: &str,
+) -> Id {
+    let module = &mut context.module;
+    let value_type = variable_type.spirv(module);
+    let pointer_type = module.type_pointer(storage_class, value_type);
+    let variable = module.global_variable(pointer_type, storage_class);
+    module.name(variable, name);
+    variable
+}
+
+fn spirv_begin_entry_point(context: &mut Context, name: &str) -> Id {
+    let module = &mut context.module;
+    let void_type = module.type_void();
+    let function_type = module.type_function(void_type, &[]);
+
+    let function = module.id();
+    module.name(function, name);
+    module.emit(
+        spirv::OP_FUNCTION,
+        &[
+            void_type,
+            function,
+            spirv::FUNCTION_CONTROL_NONE,
+            function_type,
+        ],
+    );
+
+    let label = module.id();
+    module.emit(spirv::OP_LABEL, &[label]);
+
+    function
+}
+
+fn spirv_end_entry_point(context: &mut Context) {
+    context.module.emit(spirv::OP_RETURN, &[]);
+    context.module.emit(spirv::OP_FUNCTION_END, &[]);
+}
+
+// Whether each compute kernel or stage uses the named declaration
+fn entry_points_using(
+    entry_points: &[(String, Reachable)],
+    names: fn(&Reachable) -> &Vec<String>,
+    name: &str,
+) -> Vec<bool> {
+    entry_points
+        .iter()
+        .map(|(_, reachable)| names(reachable).iter().any(|used| used == name))
+        .collect()
+}
+
+// Metal declares its sampling options as functions in the "metal" namespace,
+// which user identifiers of the same name would hide
+const MSL_SAMPLING_OPTIONS: &[&str] = &[
+    "bias",
+    "level",
+    "min_lod_clamp",
+    "gradient2d",
+    "gradient3d",
+    "gradientcube",
+];
+
+// Keywords of ACSL and of the targets, which can't name an entry point
+const RESERVED_WORDS: &[&str] = &[
+    "alias",
+    "bool",
+    "break",
+    "buffer",
+    "case",
+    "cbuffer",
+    "class",
+    "const",
+    "constant",
+    "continue",
+    "default",
+    "device",
+    "discard",
+    "do",
+    "else",
+    "enum",
+    "false",
+    "fn",
+    "for",
+    "fragment",
+    "groupshared",
+    "if",
+    "in",
+    "inout",
+    "int",
+    "kernel",
+    "layout",
+    "let",
+    "loop",
+    "main",
+    "namespace",
+    "out",
+    "precision",
+    "register",
+    "return",
+    "static",
+    "struct",
+    "switch",
+    "template",
+    "thread",
+    "threadgroup",
+    "true",
+    "type",
+    "uniform",
+    "using",
+    "var",
+    "vertex",
+    "void",
+    "while",
+];
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Identifiers can't start with "acsl_", so the renamed ones never clash
+pub fn msl_identifier(name: &str) -> String {
+    match MSL_SAMPLING_OPTIONS.contains(&name) {
+        true => format!("acsl_{}", name),
+        false => name.to_owned(),
+    }
+}
+
+// The register of an HLSL resource, the space is left out unless one was given
+fn hlsl_register(class: char, slot: usize, space: usize) -> String {
+    match space {
+        0 => format!("{}{}", class, slot),
+        space => format!("{}{}, space{}", class, slot, space),
+    }
+}
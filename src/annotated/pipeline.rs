@@ -0,0 +1,513 @@
+use super::{
+    function::Function,
+    structure::{glsl_fragment_input, glsl_vertex_input, Struct},
+    verify_semantics,
+};
+use crate::{
+    ast::SemanticAnalysisError,
+    options::CompileOptions,
+    semantic::{Domain, Stage},
+    types::{Topology, Type},
+};
+use std::rc::Rc;
+
+// The entry points of the stages between "vertex_main()" and "fragment_main()"
+pub const HULL_MAIN: &str = "hull_main";
+pub const HULL_CONSTANTS: &str = "hull_constants";
+pub const DOMAIN_MAIN: &str = "domain_main";
+pub const GEOMETRY_MAIN: &str = "geometry_main";
+
+pub const TESSELLATION_ENTRY_POINTS: [&str; 3] = [HULL_MAIN, HULL_CONSTANTS, DOMAIN_MAIN];
+
+// The code of each graphics stage, empty for the stages a file doesn't use
+#[derive(Default)]
+pub struct StageCode {
+    pub vertex: String,
+    pub hull: String,
+    pub domain: String,
+    pub geometry: String,
+    pub fragment: String,
+}
+
+impl StageCode {
+    // A declaration every stage includes
+    pub fn shared(code: String) -> Self {
+        StageCode {
+            vertex: code.clone(),
+            hull: code.clone(),
+            domain: code.clone(),
+            geometry: code.clone(),
+            fragment: code,
+        }
+    }
+
+    // Appends the code of each stage to the shaders of the stages in use,
+    // every shader but the vertex and fragment ones can be absent
+    pub fn append(&mut self, code: StageCode) {
+        self.vertex.push_str(&code.vertex);
+        self.vertex.push('\n');
+        self.fragment.push_str(&code.fragment);
+        self.fragment.push('\n');
+
+        for (glsl, code) in [
+            (&mut self.hull, code.hull),
+            (&mut self.domain, code.domain),
+            (&mut self.geometry, code.geometry),
+        ] {
+            if !glsl.is_empty() && !code.is_empty() {
+                glsl.push_str(&code);
+                glsl.push('\n');
+            }
+        }
+    }
+}
+
+pub fn is_stage_entry_point(name: &str) -> bool {
+    TESSELLATION_ENTRY_POINTS.contains(&name) || name == GEOMETRY_MAIN
+}
+
+// The structures passed between the stages carry a semantic on every member
+pub fn stage_struct(stage_type: &Type) -> Option<&Rc<Struct>> {
+    match stage_type {
+        Type::Struct(structure) if structure.has_semantics() => Some(structure),
+        _ => None,
+    }
+}
+
+// The domain is decided by the types of the tessellation factors
+pub fn patch_domain(constants: &Struct) -> Option<Domain> {
+    let edges = constants.find_semantic("SV_TESS_FACTOR")?;
+    let inside = constants.find_semantic("SV_INSIDE_TESS_FACTOR")?;
+    Domain::from_factors(
+        &constants.members()[edges].1,
+        &constants.members()[inside].1,
+    )
+}
+
+// "fn hull_main(control_points: patch<T, n>, id: uint) -> U" runs once for each
+// control point of the output patch
+pub fn verify_hull_main(
+    function: &Function,
+    semantic_locations: &[(String, usize)],
+) -> Result<(), SemanticAnalysisError> {
+    let output = match (function.parameters(), stage_struct(function.return_type())) {
+        ([control_points, id], Some(output))
+            if is_patch_of_structs(control_points.parameter_type())
+                && *id.parameter_type() == Type::uint() =>
+        {
+            output
+        }
+        _ => {
+            return Err(SemanticAnalysisError::InvalidEntryPoint(
+                HULL_MAIN.to_owned(),
+                "fn hull_main(control_points: patch<T, n>, id: uint) -> U",
+            ))
+        }
+    };
+
+    verify_semantics(output, semantic_locations, Stage::Intermediate)
+}
+
+// "fn hull_constants(control_points: patch<T, n>) -> U" runs once for each patch
+// and returns its tessellation factors
+pub fn verify_hull_constants(
+    function: &Function,
+    semantic_locations: &[(String, usize)],
+) -> Result<(), SemanticAnalysisError> {
+    let constants = match (function.parameters(), stage_struct(function.return_type())) {
+        ([control_points], Some(constants))
+            if is_patch_of_structs(control_points.parameter_type()) =>
+        {
+            constants
+        }
+        _ => {
+            return Err(SemanticAnalysisError::InvalidEntryPoint(
+                HULL_CONSTANTS.to_owned(),
+                "fn hull_constants(control_points: patch<T, n>) -> U",
+            ))
+        }
+    };
+
+    verify_semantics(constants, semantic_locations, Stage::PatchConstants)?;
+
+    match patch_domain(constants) {
+        Some(_) => Ok(()),
+        None => Err(SemanticAnalysisError::InvalidTessFactors(
+            constants.name().to_owned(),
+        )),
+    }
+}
+
+// "fn domain_main(constants: T, coordinates: float3, control_points: patch<U, n>) -> V"
+// runs once for each vertex of the tessellated patch, the coordinates in the
+// patch are a "float2" for quads
+pub fn verify_domain_main(function: &Function) -> Result<(), SemanticAnalysisError> {
+    match (function.parameters(), stage_struct(function.return_type())) {
+        ([constants, coordinates, control_points], Some(_))
+            if stage_struct(constants.parameter_type()).is_some()
+                && (*coordinates.parameter_type() == Type::float3()
+                    || *coordinates.parameter_type() == Type::float2())
+                && is_patch_of_structs(control_points.parameter_type()) =>
+        {
+            Ok(())
+        }
+        _ => Err(SemanticAnalysisError::InvalidEntryPoint(
+            DOMAIN_MAIN.to_owned(),
+            "fn domain_main(constants: T, coordinates: float3, control_points: patch<U, n>) -> V",
+        )),
+    }
+}
+
+// "#[geometry(n)] fn geometry_main(vertices: triangle<T>, stream: triangle_stream<U>)"
+// runs once for each primitive and emits up to n vertices
+pub fn verify_geometry_main(function: &Function) -> Result<(), SemanticAnalysisError> {
+    match function.parameters() {
+        [vertices, stream]
+            if function.max_vertex_count().is_some()
+                && *function.return_type() == Type::void()
+                && is_primitive_of_structs(vertices.parameter_type())
+                && matches!(stream.parameter_type(), Type::Stream(element, _) if stage_struct(element).is_some()) =>
+        {
+            Ok(())
+        }
+        _ => Err(SemanticAnalysisError::InvalidEntryPoint(
+            GEOMETRY_MAIN.to_owned(),
+            "#[geometry(n)] fn geometry_main(vertices: triangle<T>, stream: triangle_stream<U>)",
+        )),
+    }
+}
+
+fn is_patch_of_structs(patch_type: &Type) -> bool {
+    matches!(patch_type, Type::Patch(element, _) if stage_struct(element).is_some())
+}
+
+fn is_primitive_of_structs(primitive_type: &Type) -> bool {
+    matches!(primitive_type, Type::GeometryInput(element, _) if stage_struct(element).is_some())
+}
+
+// The interface variables carrying the members of a structure into the stage
+// named by "consumer", arrays of vertices for the tessellation and geometry
+// stages, positions and fragment system values are builtins instead
+pub fn glsl_interface(
+    structure: &Struct,
+    direction: &str,
+    consumer: &str,
+    arrayed: bool,
+    options: &CompileOptions,
+) -> String {
+    let version = options.get_glsl_version();
+    let locations = structure.locations(options.get_semantic_locations());
+
+    let mut glsl = String::new();
+    for (i, (name, member_type)) in structure.members().iter().enumerate() {
+        if structure
+            .semantic(i)
+            .and_then(glsl_fragment_input)
+            .is_some()
+        {
+            continue;
+        }
+
+        let mut layout = match version.explicit_interstage_locations() {
+            true => format!("layout(location = {}) ", locations[i]),
+            false => String::new(),
+        };
+
+        // Older versions need the same interpolation in both stages, only the
+        // values reaching the fragment shader are interpolated
+        if let (Some(interpolation), "pixel") = (structure.interpolation(i), consumer) {
+            layout.push_str(&format!("{} ", interpolation.glsl()));
+        }
+
+        glsl.push_str(&format!(
+            "{}{} {} acsl_{}_input_{}{};\n",
+            layout,
+            direction,
+            member_type.glsl(),
+            consumer,
+            name,
+            if arrayed { "[]" } else { "" }
+        ));
+    }
+
+    glsl
+}
+
+// "vertex_main()" passing its output on to a tessellation or geometry shader
+pub fn glsl_vertex_main(
+    input: &Struct,
+    output: &Struct,
+    consumer: &str,
+    options: &CompileOptions,
+) -> String {
+    let vulkan = options.get_glsl_version().is_vulkan();
+    let members: Vec<String> = input
+        .members()
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _))| {
+            match input
+                .semantic(i)
+                .and_then(|semantic| glsl_vertex_input(semantic, vulkan))
+            {
+                Some(builtin) => builtin.to_owned(),
+                None => format!("acsl_vertex_input_{}", name),
+            }
+        })
+        .collect();
+
+    format!(
+        "void main() {{\n{0}{1} acsl_input = {1}({2});\n{0}{3} acsl_output = vertex_main(acsl_input);\n{4}}}\n",
+        options.indent(1),
+        input.name(),
+        members.join(", "),
+        output.name(),
+        glsl_write_vertex(output, "acsl_output", consumer, "", "gl_Position", options)
+    )
+}
+
+// The tessellation control shader runs "hull_main()" for each output control
+// point, the first invocation also writes the tessellation levels of the patch
+pub fn glsl_hull_main(
+    input: &Struct,
+    input_control_points: usize,
+    output: &Struct,
+    constants: &Struct,
+    domain: Domain,
+    options: &CompileOptions,
+) -> String {
+    let indent = options.indent(1);
+    let inner_indent = options.indent(2);
+
+    let mut glsl = "void main() {\n".to_owned();
+    glsl.push_str(&glsl_read_vertices(
+        input,
+        "hull",
+        input_control_points,
+        "acsl_control_points",
+        options,
+    ));
+    glsl.push_str(&format!(
+        "{}{} acsl_output = {}(acsl_control_points, uint(gl_InvocationID));\n",
+        indent,
+        output.name(),
+        HULL_MAIN
+    ));
+    glsl.push_str(&glsl_write_vertex(
+        output,
+        "acsl_output",
+        "domain",
+        "[gl_InvocationID]",
+        "gl_out[gl_InvocationID].gl_Position",
+        options,
+    ));
+
+    glsl.push_str(&format!(
+        "\n{0}if (gl_InvocationID == 0) {{\n{1}{2} acsl_constants = {3}(acsl_control_points);\n",
+        indent,
+        inner_indent,
+        constants.name(),
+        HULL_CONSTANTS
+    ));
+    for (semantic, levels, count) in [
+        ("SV_TESS_FACTOR", "gl_TessLevelOuter", domain.edges()),
+        (
+            "SV_INSIDE_TESS_FACTOR",
+            "gl_TessLevelInner",
+            domain.inside(),
+        ),
+    ] {
+        let name = &constants.members()[constants.find_semantic(semantic).unwrap()].0;
+        for i in 0..count {
+            glsl.push_str(&format!(
+                "{}{}[{}] = acsl_constants.{}{};\n",
+                inner_indent,
+                levels,
+                i,
+                name,
+                match count {
+                    1 => "",
+                    _ => [".x", ".y", ".z", ".w"][i],
+                }
+            ));
+        }
+    }
+    glsl.push_str(&format!("{}}}\n}}\n", indent));
+
+    glsl
+}
+
+// The tessellation evaluation shader runs "domain_main()" for each vertex of
+// the tessellated patch, with the patch constants rebuilt from the
+// tessellation levels
+pub fn glsl_domain_main(
+    constants: &Struct,
+    domain: Domain,
+    control_point: &Struct,
+    control_points: usize,
+    output: &Struct,
+    consumer: &str,
+    options: &CompileOptions,
+) -> String {
+    let indent = options.indent(1);
+
+    let members: Vec<String> = constants
+        .members()
+        .iter()
+        .enumerate()
+        .map(|(i, (_, member_type))| {
+            let (levels, count) = match constants.semantic(i) {
+                Some("SV_TESS_FACTOR") => ("gl_TessLevelOuter", domain.edges()),
+                _ => ("gl_TessLevelInner", domain.inside()),
+            };
+            let levels: Vec<String> = (0..count)
+                .map(|level| format!("{}[{}]", levels, level))
+                .collect();
+
+            match count {
+                1 => levels[0].clone(),
+                _ => format!("{}({})", member_type.glsl(), levels.join(", ")),
+            }
+        })
+        .collect();
+
+    let mut glsl = format!(
+        "void main() {{\n{0}{1} acsl_constants = {1}({2});\n",
+        indent,
+        constants.name(),
+        members.join(", ")
+    );
+    glsl.push_str(&glsl_read_vertices(
+        control_point,
+        "domain",
+        control_points,
+        "acsl_control_points",
+        options,
+    ));
+    glsl.push_str(&format!(
+        "{}{} acsl_output = {}(acsl_constants, {}, acsl_control_points);\n",
+        indent,
+        output.name(),
+        DOMAIN_MAIN,
+        match domain {
+            Domain::Triangle => "gl_TessCoord",
+            Domain::Quad => "gl_TessCoord.xy",
+        }
+    ));
+    glsl.push_str(&glsl_write_vertex(
+        output,
+        "acsl_output",
+        consumer,
+        "",
+        "gl_Position",
+        options,
+    ));
+    glsl.push_str("}\n");
+
+    glsl
+}
+
+// "emit(stream, vertex)" writes the outputs of the geometry shader before
+// emitting the vertex
+pub fn glsl_emit(output: &Struct, options: &CompileOptions) -> String {
+    format!(
+        "void acsl_emit({} acsl_vertex) {{\n{}{}EmitVertex();\n}}\n",
+        output.name(),
+        glsl_write_vertex(output, "acsl_vertex", "pixel", "", "gl_Position", options),
+        options.indent(1)
+    )
+}
+
+// The geometry shader runs "geometry_main()" once for each primitive
+pub fn glsl_geometry_main(input: &Struct, topology: Topology, options: &CompileOptions) -> String {
+    format!(
+        "void main() {{\n{}{}{}(acsl_vertices);\n}}\n",
+        glsl_read_vertices(
+            input,
+            "geometry",
+            topology.vertices(),
+            "acsl_vertices",
+            options
+        ),
+        options.indent(1),
+        GEOMETRY_MAIN
+    )
+}
+
+// Gathers the vertices of a patch or primitive into an array, "gl_in" holds
+// their positions
+fn glsl_read_vertices(
+    structure: &Struct,
+    consumer: &str,
+    count: usize,
+    name: &str,
+    options: &CompileOptions,
+) -> String {
+    let vertices: Vec<String> = (0..count)
+        .map(|vertex| {
+            let members: Vec<String> = structure
+                .members()
+                .iter()
+                .enumerate()
+                .map(|(i, (member, _))| match structure.semantic(i) {
+                    Some("SV_POSITION") => format!("gl_in[{}].gl_Position", vertex),
+                    _ => format!("acsl_{}_input_{}[{}]", consumer, member, vertex),
+                })
+                .collect();
+
+            format!("{}({})", structure.name(), members.join(", "))
+        })
+        .collect();
+
+    format!(
+        "{0}{1} {2}[{3}] = {1}[{3}]({4});\n",
+        options.indent(1),
+        structure.name(),
+        name,
+        count,
+        vertices.join(", ")
+    )
+}
+
+// Writes a structure to the interface variables of the next stage, the last
+// stage before the rasterizer also moves the position into the clip space of
+// the API
+fn glsl_write_vertex(
+    structure: &Struct,
+    value: &str,
+    consumer: &str,
+    index: &str,
+    position: &str,
+    options: &CompileOptions,
+) -> String {
+    let indent = options.indent(1);
+
+    let mut glsl = String::new();
+    for (i, (name, _)) in structure.members().iter().enumerate() {
+        match structure.semantic(i) {
+            Some("SV_POSITION") => {
+                glsl.push_str(&format!("{}{} = {}.{};\n", indent, position, value, name))
+            }
+            Some(semantic) if glsl_fragment_input(semantic).is_some() => {}
+            _ => glsl.push_str(&format!(
+                "{}acsl_{}_input_{}{} = {}.{};\n",
+                indent, consumer, name, index, value, name
+            )),
+        }
+    }
+
+    if consumer == "pixel" {
+        if options.get_flip_y() {
+            glsl.push_str(&format!("{}gl_Position.y = -gl_Position.y;\n", indent));
+        }
+
+        if options.get_fix_depth_range() {
+            glsl.push_str(&format!(
+                "{}gl_Position.z = (gl_Position.z + gl_Position.w) * 0.5;\n",
+                indent
+            ));
+        }
+    }
+
+    glsl
+}
@@ -0,0 +1,267 @@
+use super::{hlsl_register, msl_identifier, VULKAN_SAMPLER_SET};
+use crate::{
+    ast::SemanticAnalysisError,
+    options::CompileOptions,
+    spirv::{
+        Context, Global, DECORATION_BINDING, DECORATION_DESCRIPTOR_SET,
+        STORAGE_CLASS_UNIFORM_CONSTANT,
+    },
+    types::Type,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Filter {
+    Point,
+    Linear,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AddressMode {
+    Wrap,
+    Clamp,
+    Mirror,
+}
+
+// The comparison of a comparison sampler, "sample_compare" passes when the
+// reference value compares to the texel with it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompareFunction {
+    Never,
+    Less,
+    Equal,
+    LessEqual,
+    Greater,
+    NotEqual,
+    GreaterEqual,
+    Always,
+}
+
+#[derive(Clone)]
+pub struct Sampler {
+    name: String,
+    slot: usize,
+    space: usize,
+    filter: Filter,
+    address: AddressMode,
+    compare: Option<CompareFunction>,
+}
+
+impl Sampler {
+    pub fn new(
+        name: String,
+        slot: usize,
+        space: usize,
+        filter: Filter,
+        address: AddressMode,
+        compare: Option<CompareFunction>,
+        max_samplers: usize,
+    ) -> Result<Self, SemanticAnalysisError> {
+        if slot >= max_samplers {
+            Err(SemanticAnalysisError::SlotOutOfRange(
+                "samplers",
+                slot,
+                max_samplers,
+            ))
+        } else {
+            Ok(Sampler {
+                name,
+                slot,
+                space,
+                filter,
+                address,
+                compare,
+            })
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    pub fn filter(&self) -> Filter {
+        self.filter
+    }
+
+    pub fn address(&self) -> AddressMode {
+        self.address
+    }
+
+    pub fn compare(&self) -> Option<CompareFunction> {
+        self.compare
+    }
+
+    // Samplers with a comparison can only be used by "sample_compare"
+    pub fn sampler_type(&self) -> Type {
+        match self.compare {
+            Some(_) => Type::comparison_sampler(),
+            None => Type::sampler(),
+        }
+    }
+
+    // Also the binding in the Vulkan sampler set
+    pub fn hlsl_register_index(&self, options: &CompileOptions) -> usize {
+        self.slot + options.sampler_states_index()
+    }
+
+    pub fn generate_hlsl(self, options: &CompileOptions) -> String {
+        format!(
+            "{} {} : register({});\n",
+            self.sampler_type().hlsl(),
+            self.name,
+            hlsl_register('s', self.hlsl_register_index(options), self.space)
+        )
+    }
+
+    // OpenGL has no sampler objects in GLSL, the texture is sampled directly
+    // and the sampler state is left to the reflection
+    pub fn generate_glsl(self, options: &CompileOptions) -> Option<String> {
+        if options.get_glsl_version().is_vulkan() {
+            Some(format!(
+                "layout(set = {}, binding = {}) uniform {} {};\n",
+                VULKAN_SAMPLER_SET,
+                self.hlsl_register_index(options),
+                self.sampler_type().glsl(),
+                self.name
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn generate_wgsl(self, options: &CompileOptions) -> String {
+        format!(
+            "@group(0) @binding({}) var {}: {};\n",
+            self.slot + options.samplers_index() + options.sampler_states_index(),
+            self.name,
+            self.sampler_type().wgsl()
+        )
+    }
+
+    // Metal samplers can be declared with their state in the shader
+    pub fn generate_msl(self) -> String {
+        let filter = match self.filter {
+            Filter::Point => "nearest",
+            Filter::Linear => "linear",
+        };
+        let address = match self.address {
+            AddressMode::Wrap => "repeat",
+            AddressMode::Clamp => "clamp_to_edge",
+            AddressMode::Mirror => "mirrored_repeat",
+        };
+
+        let compare = match self.compare {
+            Some(compare) => format!(", compare_func::{}", compare.name()),
+            None => String::new(),
+        };
+
+        format!(
+            "constexpr sampler {}(filter::{1}, mip_filter::{1}, address::{2}{3});\n",
+            msl_identifier(&self.name),
+            filter,
+            address,
+            compare
+        )
+    }
+
+    pub fn declare_spirv(&self, context: &mut Context, options: &CompileOptions) {
+        let module = &mut context.module;
+
+        let sampler_type = module.type_sampler();
+        let sampler_pointer_type =
+            module.type_pointer(STORAGE_CLASS_UNIFORM_CONSTANT, sampler_type);
+        let sampler = module.global_variable(sampler_pointer_type, STORAGE_CLASS_UNIFORM_CONSTANT);
+        module.name(sampler, &self.name);
+        module.decorate(
+            sampler,
+            DECORATION_DESCRIPTOR_SET,
+            &[VULKAN_SAMPLER_SET as u32],
+        );
+        module.decorate(
+            sampler,
+            DECORATION_BINDING,
+            &[self.hlsl_register_index(options) as u32],
+        );
+
+        context.define_global(
+            &self.name,
+            Global::Sampler {
+                sampler,
+                sampler_type,
+            },
+        );
+    }
+}
+
+impl Filter {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "point" => Some(Filter::Point),
+            "linear" => Some(Filter::Linear),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Filter::Point => "point",
+            Filter::Linear => "linear",
+        }
+    }
+}
+
+impl AddressMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wrap" => Some(AddressMode::Wrap),
+            "clamp" => Some(AddressMode::Clamp),
+            "mirror" => Some(AddressMode::Mirror),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AddressMode::Wrap => "wrap",
+            AddressMode::Clamp => "clamp",
+            AddressMode::Mirror => "mirror",
+        }
+    }
+}
+
+impl CompareFunction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "never" => Some(CompareFunction::Never),
+            "less" => Some(CompareFunction::Less),
+            "equal" => Some(CompareFunction::Equal),
+            "less_equal" => Some(CompareFunction::LessEqual),
+            "greater" => Some(CompareFunction::Greater),
+            "not_equal" => Some(CompareFunction::NotEqual),
+            "greater_equal" => Some(CompareFunction::GreaterEqual),
+            "always" => Some(CompareFunction::Always),
+            _ => None,
+        }
+    }
+
+    // Also the name of the Metal "compare_func"
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompareFunction::Never => "never",
+            CompareFunction::Less => "less",
+            CompareFunction::Equal => "equal",
+            CompareFunction::LessEqual => "less_equal",
+            CompareFunction::Greater => "greater",
+            CompareFunction::NotEqual => "not_equal",
+            CompareFunction::GreaterEqual => "greater_equal",
+            CompareFunction::Always => "always",
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::ast::SemanticAnalysisError;
+use std::collections::BTreeMap;
+
+// The slot and register space of a resource
+pub type Binding = (usize, usize);
+
+// The resources of one kind, ordered by slot then register space
+pub struct Slots<T> {
+    class: &'static str,
+    max: usize,
+    resources: BTreeMap<Binding, T>,
+}
+
+impl<T> Slots<T> {
+    pub fn new(class: &'static str, max: usize) -> Self {
+        Slots {
+            class,
+            max,
+            resources: BTreeMap::new(),
+        }
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    pub fn verify_unused(&self, binding: Binding) -> Result<(), SemanticAnalysisError> {
+        match self.resources.contains_key(&binding) {
+            true => Err(SemanticAnalysisError::ReusingSlot(
+                self.class, binding.0, binding.1,
+            )),
+            false => Ok(()),
+        }
+    }
+
+    pub fn insert(&mut self, binding: Binding, resource: T) {
+        self.resources.insert(binding, resource);
+    }
+
+    // Only HLSL has register spaces, the other targets would bind every
+    // resource using the slot at the same place
+    pub fn is_shared(&self, slot: usize) -> bool {
+        self.resources.range((slot, 0)..=(slot, usize::MAX)).count() > 1
+    }
+
+    pub fn take(&mut self, binding: Binding) -> T {
+        self.resources.remove(&binding).unwrap()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.resources.values()
+    }
+}
@@ -1,19 +1,71 @@
-use super::expression::Expression;
-use crate::types::Type;
+use super::{
+    expression::{hlsl_atomic, is_atomic, push_unique, Expression},
+    msl_identifier,
+    structure::glsl_fragment_input,
+};
+use crate::{
+    options::CompileOptions,
+    spirv::{Context, OP_KILL, OP_RETURN, OP_RETURN_VALUE},
+    types::Type,
+};
+use std::collections::HashMap;
 
 pub enum Statement {
     Return(Expression, Type),
+    // Only valid in the fragment stage, in "fragment_main" or the functions
+    // only it calls, the fragment is thrown away
+    Discard,
     Assignment(String, Expression),
+    IndexAssignment(String, Expression, Expression),
     VariableDefinition(String, Expression, Type),
+    FunctionCall(Expression),
 }
 
 impl Statement {
-    pub fn hlsl(self) -> String {
+    pub fn hlsl(self, indentation: &str, options: &CompileOptions) -> String {
         match self {
+            Statement::VariableDefinition(
+                name,
+                Expression::FunctionCall(function, parameters, _),
+                variable_type,
+            ) if is_atomic(&function) => {
+                format!(
+                    "{} {};\n{}{}",
+                    variable_type.hlsl(),
+                    name,
+                    indentation,
+                    hlsl_atomic(&function, parameters, Some(&name))
+                )
+            }
+            // The previous value is discarded in its own scope
+            Statement::FunctionCall(Expression::FunctionCall(
+                function,
+                parameters,
+                return_type,
+            )) if function == "atomic_exchange" => {
+                let inner_indentation = format!("{}{}", indentation, options.indent(1));
+                format!(
+                    "{{\n{1}{2} acsl_original;\n{1}{3}{0}}}\n",
+                    indentation,
+                    inner_indentation,
+                    return_type.hlsl(),
+                    hlsl_atomic(&function, parameters, Some("acsl_original"))
+                )
+            }
+            Statement::FunctionCall(Expression::FunctionCall(function, parameters, _))
+                if is_atomic(&function) =>
+            {
+                hlsl_atomic(&function, parameters, None)
+            }
+            Statement::Return(Expression::Empty, _) => "return;\n".to_owned(),
             Statement::Return(expression, _) => format!("return {};\n", expression.hlsl()),
+            Statement::Discard => "discard;\n".to_owned(),
             Statement::Assignment(name, expression) => {
                 format!("{} = {};\n", name, expression.hlsl())
             }
+            Statement::IndexAssignment(name, index, expression) => {
+                format!("{}[{}] = {};\n", name, index.hlsl(), expression.hlsl())
+            }
             Statement::VariableDefinition(name, expression, variable_type) => {
                 format!(
                     "{} {} = {};\n",
@@ -22,70 +74,234 @@ impl Statement {
                     expression.hlsl()
                 )
             }
+            Statement::FunctionCall(expression) => format!("{};\n", expression.hlsl()),
         }
     }
 
     pub fn glsl(
         self,
-        indent: usize,
+        indentation: &str,
         in_vertex_main: bool,
         in_fragment_main: bool,
         position_output_name: &str,
+        options: &CompileOptions,
     ) -> String {
         match self {
+            Statement::Return(Expression::Empty, _) => "return;\n".to_owned(),
             Statement::Return(expression, expression_type) => {
                 if in_vertex_main {
                     let mut glsl = format!(
                         "{} acsl_vertex_output = {};\n",
                         expression_type.glsl(),
-                        expression.glsl(),
+                        expression.glsl(options),
                     );
 
-                    for (name, _) in expression_type.members() {
-                        for _ in 0..indent * 4 {
-                            glsl.push(' ');
+                    // System values aren't passed to the fragment shader
+                    for (i, (name, _)) in expression_type.members().iter().enumerate() {
+                        let system_value = match &expression_type {
+                            Type::Struct(structure) => structure
+                                .semantic(i)
+                                .and_then(glsl_fragment_input)
+                                .is_some(),
+                            _ => false,
+                        };
+
+                        if !system_value {
+                            glsl.push_str(indentation);
+                            glsl.push_str(&format!(
+                                "acsl_pixel_input_{} = acsl_vertex_output.{};\n",
+                                name, name
+                            ));
                         }
-                        glsl.push_str(&format!(
-                            "acsl_pixel_input_{} = acsl_vertex_output.{};\n",
-                            name, name
-                        ))
                     }
 
-                    for _ in 0..indent * 4 {
-                        glsl.push(' ');
-                    }
+                    glsl.push_str(indentation);
 
                     glsl.push_str(&format!(
-                        "gl_Position = acsl_pixel_input_{};\n",
+                        "gl_Position = acsl_vertex_output.{};\n",
                         position_output_name
                     ));
 
-                    for _ in 0..indent * 4 {
-                        glsl.push(' ');
+                    if options.get_flip_y() {
+                        glsl.push_str(indentation);
+                        glsl.push_str("gl_Position.y = -gl_Position.y;\n");
                     }
 
+                    if options.get_fix_depth_range() {
+                        glsl.push_str(indentation);
+                        glsl.push_str("gl_Position.z = (gl_Position.z + gl_Position.w) * 0.5;\n");
+                    }
+
+                    glsl.push_str(indentation);
+
                     glsl.push_str("return;\n");
 
                     glsl
                 } else if in_fragment_main {
-                    let mut glsl = format!("acsl_fragment_color = {};\n", expression.glsl());
-                    for _ in 0..indent * 4 {
-                        glsl.push(' ');
-                    }
+                    let mut glsl = match &expression_type {
+                        // Each member is written to its own render target
+                        Type::Struct(structure) => {
+                            let mut glsl = format!(
+                                "{} acsl_fragment_output = {};\n",
+                                expression_type.glsl(),
+                                expression.glsl(options),
+                            );
+
+                            for (i, (name, _)) in structure.members().iter().enumerate() {
+                                let output = match structure.semantic(i) {
+                                    Some("SV_DEPTH") => "gl_FragDepth".to_owned(),
+                                    _ => format!("acsl_fragment_{}", name),
+                                };
+
+                                glsl.push_str(indentation);
+                                glsl.push_str(&format!(
+                                    "{} = acsl_fragment_output.{};\n",
+                                    output, name
+                                ));
+                            }
+
+                            glsl
+                        }
+                        _ => format!("acsl_fragment_color = {};\n", expression.glsl(options)),
+                    };
+                    glsl.push_str(indentation);
                     glsl.push_str("return;\n");
                     glsl
                 } else {
-                    format!("return {};\n", expression.glsl())
+                    format!("return {};\n", expression.glsl(options))
                 }
             }
+            Statement::Discard => "discard;\n".to_owned(),
             Statement::VariableDefinition(name, expression, variable_type) => format!(
                 "{} {} = {};\n",
                 variable_type.glsl(),
                 name,
-                expression.glsl()
+                expression.glsl(options)
             ),
             Statement::Assignment(name, expression) => {
-                format!("{} = {};\n", name, expression.glsl())
+                format!("{} = {};\n", name, expression.glsl(options))
+            }
+            Statement::IndexAssignment(name, index, expression) => format!(
+                "{}[{}] = {};\n",
+                name,
+                index.glsl(options),
+                expression.glsl(options)
+            ),
+            // Shared memory needs its own barrier to be visible to the group
+            Statement::FunctionCall(Expression::FunctionCall(name, _, _))
+                if name == "group_barrier" =>
+            {
+                format!("memoryBarrierShared();\n{}barrier();\n", indentation)
+            }
+            Statement::FunctionCall(expression) => format!("{};\n", expression.glsl(options)),
+        }
+    }
+
+    pub fn wgsl(self) -> String {
+        match self {
+            Statement::Return(expression, _) => format!("return {};\n", expression.wgsl()),
+            Statement::Discard => "discard;\n".to_owned(),
+            Statement::Assignment(name, expression) => {
+                format!("{} = {};\n", name, expression.wgsl())
+            }
+            Statement::IndexAssignment(name, index, expression) => {
+                format!("{}[{}] = {};\n", name, index.wgsl(), expression.wgsl())
+            }
+            Statement::VariableDefinition(name, expression, variable_type) => {
+                format!(
+                    "var {}: {} = {};\n",
+                    name,
+                    variable_type.wgsl(),
+                    expression.wgsl()
+                )
+            }
+            Statement::FunctionCall(expression) => format!("{};\n", expression.wgsl()),
+        }
+    }
+
+    pub fn msl(self, resource_arguments: &HashMap<String, String>) -> String {
+        match self {
+            Statement::Return(expression, _) => {
+                format!("return {};\n", expression.msl(resource_arguments))
+            }
+            Statement::Discard => "discard_fragment();\n".to_owned(),
+            Statement::Assignment(name, expression) => format!(
+                "{} = {};\n",
+                msl_identifier(&name),
+                expression.msl(resource_arguments)
+            ),
+            Statement::IndexAssignment(name, index, expression) => format!(
+                "{}[{}] = {};\n",
+                msl_identifier(&name),
+                index.msl(resource_arguments),
+                expression.msl(resource_arguments)
+            ),
+            Statement::VariableDefinition(name, expression, variable_type) => {
+                format!(
+                    "{} {} = {};\n",
+                    variable_type.msl(),
+                    msl_identifier(&name),
+                    expression.msl(resource_arguments)
+                )
+            }
+            Statement::FunctionCall(expression) => {
+                format!("{};\n", expression.msl(resource_arguments))
+            }
+        }
+    }
+
+    pub fn is_discard(&self) -> bool {
+        matches!(self, Statement::Discard)
+    }
+
+    // Returns true when the statement terminates the block
+    pub fn spirv(&self, context: &mut Context) -> bool {
+        match self {
+            Statement::Return(Expression::Empty, _) => {
+                context.module.emit(OP_RETURN, &[]);
+                true
+            }
+            Statement::Return(expression, _) => {
+                let value = expression.spirv(context);
+                context.module.emit(OP_RETURN_VALUE, &[value]);
+                true
+            }
+            Statement::Discard => {
+                context.module.emit(OP_KILL, &[]);
+                true
+            }
+            Statement::Assignment(name, expression)
+            | Statement::VariableDefinition(name, expression, _) => {
+                let value = expression.spirv(context);
+                if !context.module.is_constant(value) {
+                    context.module.name(value, name);
+                }
+
+                context.set_local(name, value);
+                false
+            }
+            Statement::IndexAssignment(_, _, _) => panic!("SPIR-V output has no storage buffers"),
+            Statement::FunctionCall(expression) => {
+                expression.spirv(context);
+                false
+            }
+        }
+    }
+
+    pub fn references(&self, variables: &mut Vec<String>, functions: &mut Vec<String>) {
+        match self {
+            Statement::Discard => {}
+            Statement::Return(expression, _)
+            | Statement::VariableDefinition(_, expression, _)
+            | Statement::FunctionCall(expression) => expression.references(variables, functions),
+            Statement::Assignment(name, expression) => {
+                push_unique(variables, name);
+                expression.references(variables, functions);
+            }
+            Statement::IndexAssignment(name, index, expression) => {
+                push_unique(variables, name);
+                index.references(variables, functions);
+                expression.references(variables, functions);
             }
         }
     }
@@ -0,0 +1,187 @@
+use super::{hlsl_register, VULKAN_STORAGE_TEXTURE_SET};
+use crate::{ast::SemanticAnalysisError, options::CompileOptions, types::Type};
+
+// The GLSL image formats, with their component count and whether they hold
+// unsigned integers
+const FORMATS: &[(&str, usize, bool)] = &[
+    ("rgba32f", 4, false),
+    ("rgba16f", 4, false),
+    ("rgba16", 4, false),
+    ("rgba8", 4, false),
+    ("rgba8_snorm", 4, false),
+    ("rg32f", 2, false),
+    ("rg16f", 2, false),
+    ("rg16", 2, false),
+    ("rg8", 2, false),
+    ("r32f", 1, false),
+    ("r16f", 1, false),
+    ("r16", 1, false),
+    ("r8", 1, false),
+    ("rgba32ui", 4, true),
+    ("rgba16ui", 4, true),
+    ("rgba8ui", 4, true),
+    ("rg32ui", 2, true),
+    ("rg16ui", 2, true),
+    ("rg8ui", 2, true),
+    ("r32ui", 1, true),
+    ("r16ui", 1, true),
+    ("r8ui", 1, true),
+];
+
+#[derive(Clone)]
+pub struct StorageTexture {
+    name: String,
+    slot: usize,
+    space: usize,
+    texel_type: Type,
+    format: String,
+}
+
+impl StorageTexture {
+    // Without a format the texels are stored as 32-bit values
+    pub fn new(
+        name: String,
+        slot: usize,
+        space: usize,
+        texel_type: Type,
+        format: Option<String>,
+        max_storage_textures: usize,
+    ) -> Result<Self, SemanticAnalysisError> {
+        let (components, uint) = match texel_components(&texel_type) {
+            Some(components) => components,
+            None => {
+                return Err(SemanticAnalysisError::InvalidStorageTextureType(
+                    texel_type.to_string(),
+                ))
+            }
+        };
+
+        let format = match format {
+            Some(format) => {
+                if !FORMATS.contains(&(format.as_str(), components, uint)) {
+                    return Err(SemanticAnalysisError::InvalidStorageTextureFormat(
+                        format,
+                        texel_type.to_string(),
+                    ));
+                }
+                format
+            }
+            None => match (components, uint) {
+                (1, true) => "r32ui",
+                (1, false) => "r32f",
+                (2, _) => "rg32f",
+                _ => "rgba32f",
+            }
+            .to_owned(),
+        };
+
+        if slot >= max_storage_textures {
+            Err(SemanticAnalysisError::SlotOutOfRange(
+                "storage textures",
+                slot,
+                max_storage_textures,
+            ))
+        } else {
+            Ok(StorageTexture {
+                name,
+                slot,
+                space,
+                texel_type,
+                format,
+            })
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    pub fn texel_type(&self) -> &Type {
+        &self.texel_type
+    }
+
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    pub fn storage_texture_type(&self) -> Type {
+        Type::storage_texture(self.texel_type.clone())
+    }
+
+    pub fn hlsl_register_index(&self, options: &CompileOptions) -> usize {
+        self.slot + options.storage_textures_index()
+    }
+
+    pub fn generate_hlsl(self, options: &CompileOptions) -> String {
+        format!(
+            "{} {} : register({});\n",
+            self.storage_texture_type().hlsl(),
+            self.name,
+            hlsl_register('u', self.hlsl_register_index(options), self.space)
+        )
+    }
+
+    // Image units have their own bindings, so the slot is used as is
+    pub fn generate_glsl(self, options: &CompileOptions) -> String {
+        let binding = if options.get_glsl_version().is_vulkan() {
+            format!(
+                "set = {}, binding = {}",
+                VULKAN_STORAGE_TEXTURE_SET, self.slot
+            )
+        } else {
+            format!("binding = {}", self.slot)
+        };
+
+        format!(
+            "layout({}, {}) uniform {} {};\n",
+            self.format,
+            binding,
+            self.storage_texture_type().glsl(),
+            self.name
+        )
+    }
+}
+
+// Images always hold four components in GLSL, the texel type is the first
+// components of those
+pub fn glsl_texel_swizzle(texel_type: &Type) -> &'static str {
+    match texel_components(texel_type) {
+        Some((1, _)) => ".x",
+        Some((2, _)) => ".xy",
+        _ => "",
+    }
+}
+
+// Widens a texel to the four components images are stored with
+pub fn glsl_texel_vec4(texel_type: &Type, texel: &str) -> String {
+    match texel_components(texel_type) {
+        Some((1, true)) => format!("uvec4({})", texel),
+        Some((1, false)) => format!("vec4({})", texel),
+        Some((2, _)) => format!("vec4({}, 0.0, 0.0)", texel),
+        _ => texel.to_owned(),
+    }
+}
+
+// The component count of the texel type and whether it is unsigned, "None"
+// for types with no image format
+fn texel_components(texel_type: &Type) -> Option<(usize, bool)> {
+    if texel_type.is_float() {
+        Some((1, false))
+    } else if texel_type.is_uint() {
+        Some((1, true))
+    } else if *texel_type == Type::float2() {
+        Some((2, false))
+    } else if *texel_type == Type::float4() {
+        Some((4, false))
+    } else {
+        None
+    }
+}
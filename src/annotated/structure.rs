@@ -1,20 +1,105 @@
-use crate::types::Type;
+use crate::{
+    options::CompileOptions,
+    semantic::{Interpolation, Semantic},
+    spirv::{Id, Module},
+    types::Type,
+};
+
+// The index of a "SV_TARGET0" to "SV_TARGET7" semantic
+pub fn render_target(semantic: &str) -> Option<usize> {
+    match Semantic::parse(semantic)? {
+        Semantic::Target(target) => Some(target),
+        _ => None,
+    }
+}
+
+// The builtin GLSL reads a fragment input system value from, these members
+// aren't passed between the stages
+pub fn glsl_fragment_input(semantic: &str) -> Option<&'static str> {
+    match semantic {
+        "SV_POSITION" => Some("gl_FragCoord"),
+        "SV_IS_FRONT_FACE" => Some("uint(gl_FrontFacing)"),
+        _ => None,
+    }
+}
+
+// Vertex inputs generated by the input assembler instead of read from a
+// vertex buffer
+pub fn is_vertex_system_value(semantic: &str) -> bool {
+    matches!(
+        Semantic::parse(semantic),
+        Some(Semantic::VertexId | Semantic::InstanceId)
+    )
+}
+
+// The builtin GLSL reads a vertex input system value from, Vulkan renamed them
+// as they include the base vertex and instance
+pub fn glsl_vertex_input(semantic: &str, vulkan: bool) -> Option<&'static str> {
+    match (semantic, vulkan) {
+        ("SV_VERTEX_ID", false) => Some("uint(gl_VertexID)"),
+        ("SV_VERTEX_ID", true) => Some("uint(gl_VertexIndex)"),
+        ("SV_INSTANCE_ID", false) => Some("uint(gl_InstanceID)"),
+        ("SV_INSTANCE_ID", true) => Some("uint(gl_InstanceIndex)"),
+        _ => None,
+    }
+}
+
+// HLSL spells some system values differently, the facing is a parameter of
+// the fragment entry point as vertices can't write it, the member is passed
+// between the stages like any other value
+fn hlsl_semantic(semantic: &str) -> &str {
+    match semantic {
+        "SV_IS_FRONT_FACE" => "ACSL_IS_FRONT_FACE",
+        "SV_VERTEX_ID" => "SV_VertexID",
+        "SV_INSTANCE_ID" => "SV_InstanceID",
+        "SV_TESS_FACTOR" => "SV_TessFactor",
+        "SV_INSIDE_TESS_FACTOR" => "SV_InsideTessFactor",
+        semantic => semantic,
+    }
+}
 
 pub struct Struct {
     name: String,
     members: Vec<(String, Type)>,
     semantics: Option<Vec<String>>,
+    interpolations: Vec<Option<Interpolation>>,
 }
 
 impl Struct {
-    pub fn new(name: String, members: Vec<(String, Type)>, semantics: Option<Vec<String>>) -> Self {
+    pub fn new(
+        name: String,
+        members: Vec<(String, Type)>,
+        semantics: Option<Vec<String>>,
+        interpolations: Vec<Option<Interpolation>>,
+    ) -> Self {
         Struct {
             name,
             members,
             semantics,
+            interpolations,
         }
     }
 
+    // The thread IDs a compute kernel is dispatched with
+    pub fn thread_id() -> Self {
+        Struct::new(
+            "ThreadId".to_owned(),
+            vec![
+                ("dispatch_thread".to_owned(), Type::uint3()),
+                ("group".to_owned(), Type::uint3()),
+                ("group_thread".to_owned(), Type::uint3()),
+                ("group_index".to_owned(), Type::uint()),
+            ],
+            Some(vec![
+                "SV_DispatchThreadID".to_owned(),
+                "SV_GroupID".to_owned(),
+                "SV_GroupThreadID".to_owned(),
+                "SV_GroupIndex".to_owned(),
+            ]),
+            vec![None; 4],
+        )
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -23,26 +108,123 @@ impl Struct {
         &self.members
     }
 
-    pub fn semantics(&self) -> &[String] {
-        self.semantics.as_ref().unwrap()
+    pub fn has_semantics(&self) -> bool {
+        self.semantics.is_some()
+    }
+
+    pub fn semantic(&self, index: usize) -> Option<&str> {
+        self.semantics
+            .as_ref()
+            .map(|semantics| semantics[index].as_str())
+    }
+
+    // Integers can't be interpolated, so they're flat without an attribute
+    pub fn interpolation(&self, index: usize) -> Option<Interpolation> {
+        match self.interpolations[index] {
+            None if self.members[index].1.is_integer() => Some(Interpolation::Flat),
+            interpolation => interpolation,
+        }
+    }
+
+    // The interpolation attribute of the member, if it has one
+    pub fn interpolation_attribute(&self, index: usize) -> Option<Interpolation> {
+        self.interpolations[index]
+    }
+
+    // The location of a member, placed by the semantic locations of the
+    // options or at the position of the member in the structure
+    pub fn location(&self, index: usize, semantic_locations: &[(String, usize)]) -> usize {
+        let semantic = self.semantic(index).and_then(Semantic::parse);
+        semantic_locations
+            .iter()
+            .rev()
+            .find(|(placed, _)| semantic.is_some() && Semantic::parse(placed) == semantic)
+            .map_or(index, |(_, location)| *location)
     }
 
-    pub fn generate_hlsl(&self) -> String {
+    pub fn locations(&self, semantic_locations: &[(String, usize)]) -> Vec<usize> {
+        (0..self.members.len())
+            .map(|i| self.location(i, semantic_locations))
+            .collect()
+    }
+
+    // The index of the member with the semantic
+    pub fn find_semantic(&self, semantic: &str) -> Option<usize> {
+        self.semantics
+            .as_ref()?
+            .iter()
+            .position(|member_semantic| member_semantic == semantic)
+    }
+
+    // HLSL declares the tessellation factors as arrays of "float", the number
+    // of elements of a factor member held in a vector
+    pub fn hlsl_factor_elements(&self, member: &str) -> Option<usize> {
+        let index = self.members.iter().position(|(name, _)| name == member)?;
+        match self.semantic(index).and_then(Semantic::parse)? {
+            Semantic::TessFactor | Semantic::InsideTessFactor => {
+                match self.members[index].1.members().len() {
+                    1 if self.members[index].1.is_float() => None,
+                    elements => Some(elements),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn has_vertex_system_values(&self) -> bool {
+        self.semantics
+            .iter()
+            .flatten()
+            .any(|semantic| is_vertex_system_value(semantic))
+    }
+
+    pub fn has_vertex_attributes(&self) -> bool {
+        match &self.semantics {
+            Some(semantics) => !semantics
+                .iter()
+                .all(|semantic| is_vertex_system_value(semantic)),
+            None => !self.members.is_empty(),
+        }
+    }
+
+    pub fn generate_hlsl(&self, options: &CompileOptions) -> String {
+        let indent = options.indent(1);
+
         let mut struct_hlsl = format!("struct {} {{\n", self.name);
         let mut constructor_declaration_hlsl = format!("{} acsl_create_{}(", self.name, self.name);
-        let mut constructor_body_hlsl = format!("    {} output;\n", self.name);
+        let mut constructor_body_hlsl = format!("{}{} output;\n", indent, self.name);
 
         let mut i = 0;
         for (name, member_type) in &self.members {
-            struct_hlsl.push_str(&format!("    {} {}", member_type.hlsl(), name));
-            constructor_body_hlsl.push_str(&format!("    output.{} = {};\n", name, name));
+            struct_hlsl.push_str(&indent);
+            if let Some(interpolation) = self.interpolations[i] {
+                struct_hlsl.push_str(&format!("{} ", interpolation.hlsl()));
+            }
+            match self.hlsl_factor_elements(name) {
+                Some(elements) => {
+                    struct_hlsl.push_str(&format!("float {}[{}]", name, elements));
+                    for (element, (component, _)) in member_type.members().iter().enumerate() {
+                        constructor_body_hlsl.push_str(&format!(
+                            "{}output.{}[{}] = {}.{};\n",
+                            indent, name, element, name, component
+                        ));
+                    }
+                }
+                None => {
+                    struct_hlsl.push_str(&format!("{} {}", member_type.hlsl(), name));
+                    constructor_body_hlsl
+                        .push_str(&format!("{}output.{} = {};\n", indent, name, name));
+                }
+            }
             constructor_declaration_hlsl.push_str(&format!("{} {}", member_type.hlsl(), name));
             if i != self.members.len() - 1 {
                 constructor_declaration_hlsl.push_str(", ");
             }
 
             match &self.semantics {
-                Some(semantics) => struct_hlsl.push_str(&format!(": {}", semantics[i])),
+                Some(semantics) => {
+                    struct_hlsl.push_str(&format!(": {}", hlsl_semantic(&semantics[i])))
+                }
                 None => {}
             }
 
@@ -53,7 +235,7 @@ impl Struct {
 
         struct_hlsl.push_str("};\n");
         constructor_declaration_hlsl.push_str(") {\n");
-        constructor_body_hlsl.push_str("    return output;\n}\n");
+        constructor_body_hlsl.push_str(&format!("{}return output;\n}}\n", indent));
 
         format!(
             "{}{}{}",
@@ -61,12 +243,13 @@ impl Struct {
         )
     }
 
-    pub fn generate_glsl(&self) -> String {
+    pub fn generate_glsl(&self, options: &CompileOptions) -> String {
         let mut glsl = format!("struct {} {{\n", self.name);
 
         for i in 0..self.members.len() {
             glsl.push_str(&format!(
-                "    {} {};\n",
+                "{}{} {};\n",
+                options.indent(1),
                 self.members[i].1.glsl(),
                 self.members[i].0
             ));
@@ -76,4 +259,207 @@ impl Struct {
 
         glsl
     }
+
+    pub fn generate_wgsl(&self, options: &CompileOptions) -> String {
+        let mut wgsl = format!("struct {} {{\n", self.name);
+
+        for i in 0..self.members.len() {
+            let (name, member_type) = &self.members[i];
+
+            wgsl.push_str(&options.indent(1));
+            match self.semantic(i) {
+                Some("SV_POSITION") => wgsl.push_str("@builtin(position) "),
+                Some("SV_DEPTH") => wgsl.push_str("@builtin(frag_depth) "),
+                Some("SV_VERTEX_ID") => wgsl.push_str("@builtin(vertex_index) "),
+                Some("SV_INSTANCE_ID") => wgsl.push_str("@builtin(instance_index) "),
+                // Fragment outputs are located at their render target
+                Some(semantic) if render_target(semantic).is_some() => {
+                    let target = render_target(semantic).unwrap();
+                    wgsl.push_str(&format!("@location({}) ", target));
+                }
+                Some(_) => {
+                    let location = self.location(i, options.get_semantic_locations());
+                    wgsl.push_str(&format!("@location({}) ", location));
+                    if let Some(interpolation) = self.interpolation(i) {
+                        wgsl.push_str(&format!("@interpolate({}) ", interpolation.wgsl()));
+                    }
+                }
+                None => {}
+            }
+
+            wgsl.push_str(&format!("{}: {},\n", name, member_type.wgsl()));
+        }
+
+        wgsl.push_str("}\n");
+
+        wgsl
+    }
+
+    // Vertex system values are parameters of the entry point in MSL, so the
+    // attributes of a vertex input with them get a structure of their own
+    pub fn generate_msl(&self, vertex_input: bool, options: &CompileOptions) -> String {
+        if vertex_input && self.has_vertex_system_values() {
+            let mut msl = format!("struct {} {{\n", self.name);
+            for (name, member_type) in &self.members {
+                msl.push_str(&format!(
+                    "{}{} {};\n",
+                    options.indent(1),
+                    member_type.msl(),
+                    name
+                ));
+            }
+            msl.push_str("};\n");
+
+            if self.has_vertex_attributes() {
+                msl.push('\n');
+                msl.push_str(&self.msl_struct(
+                    &format!("acsl_{}_attributes", self.name),
+                    true,
+                    options,
+                ));
+            }
+
+            msl
+        } else {
+            self.msl_struct(&self.name, vertex_input, options)
+        }
+    }
+
+    fn msl_struct(&self, name: &str, vertex_input: bool, options: &CompileOptions) -> String {
+        let mut msl = format!("struct {} {{\n", name);
+
+        for i in 0..self.members.len() {
+            let (name, member_type) = &self.members[i];
+            if vertex_input && self.semantic(i).is_some_and(is_vertex_system_value) {
+                continue;
+            }
+
+            msl.push_str(&format!(
+                "{}{} {}",
+                options.indent(1),
+                member_type.msl(),
+                name
+            ));
+
+            match self.semantic(i) {
+                Some(_) if vertex_input => {
+                    let location = self.location(i, options.get_semantic_locations());
+                    msl.push_str(&format!(" [[attribute({})]]", location))
+                }
+                Some("SV_POSITION") => msl.push_str(" [[position]]"),
+                Some("SV_DEPTH") => msl.push_str(" [[depth(any)]]"),
+                Some(semantic) if render_target(semantic).is_some() => {
+                    let target = render_target(semantic).unwrap();
+                    msl.push_str(&format!(" [[color({})]]", target));
+                }
+                Some(_) if self.interpolation(i).is_some() => {
+                    let interpolation = self.interpolation(i).unwrap();
+                    msl.push_str(&format!(" [[{}]]", interpolation.msl()));
+                }
+                _ => {}
+            }
+
+            msl.push_str(";\n");
+        }
+
+        msl.push_str("};\n");
+
+        msl
+    }
+
+    pub fn spirv(&self, module: &mut Module) -> Id {
+        let members: Vec<Id> = self
+            .members
+            .iter()
+            .map(|(_, member_type)| member_type.spirv(module))
+            .collect();
+
+        let (id, declared) = module.type_struct(&self.name, &members);
+        if declared {
+            module.name(id, &self.name);
+
+            let offsets = self.std140_offsets();
+            for (i, (name, member_type)) in self.members.iter().enumerate() {
+                module.member_name(id, i as u32, name);
+                module.member_layout(id, i as u32, offsets[i], member_type.is_float_matrix());
+            }
+        }
+
+        id
+    }
+
+    pub fn std140_layout(&self) -> (usize, usize) {
+        let end = match self.members.last() {
+            Some((_, member_type)) => {
+                self.std140_offsets().last().unwrap() + member_type.std140_layout().0
+            }
+            None => 0,
+        };
+
+        (end.next_multiple_of(16), 16)
+    }
+
+    fn std140_offsets(&self) -> Vec<usize> {
+        self.offsets(Type::std140_layout)
+    }
+
+    // Structures are aligned to their largest member, which is also how far
+    // their size rounds up
+    pub fn std430_layout(&self) -> (usize, usize) {
+        let offsets = self.offsets(Type::std430_layout);
+        let alignment = self
+            .members
+            .iter()
+            .map(|(_, member_type)| member_type.std430_layout().1)
+            .max()
+            .unwrap_or(1);
+        let end = match self.members.last() {
+            Some((_, member_type)) => offsets.last().unwrap() + member_type.std430_layout().0,
+            None => 0,
+        };
+
+        (end.next_multiple_of(alignment), alignment)
+    }
+
+    pub fn packed_size(&self) -> usize {
+        self.members
+            .iter()
+            .map(|(_, member_type)| member_type.packed_size())
+            .sum()
+    }
+
+    // Whether std430 places every member right after the one before it
+    pub fn is_packed_in_std430(&self) -> bool {
+        let mut packed_offset = 0;
+        for ((_, member_type), offset) in self.members.iter().zip(self.offsets(Type::std430_layout))
+        {
+            let nested = match member_type {
+                Type::Struct(structure) => structure.is_packed_in_std430(),
+                _ => true,
+            };
+            if offset != packed_offset
+                || member_type.std430_layout().0 != member_type.packed_size()
+                || !nested
+            {
+                return false;
+            }
+            packed_offset += member_type.packed_size();
+        }
+
+        true
+    }
+
+    fn offsets(&self, layout: fn(&Type) -> (usize, usize)) -> Vec<usize> {
+        let mut offsets = Vec::with_capacity(self.members.len());
+
+        let mut offset: usize = 0;
+        for (_, member_type) in &self.members {
+            let (size, alignment) = layout(member_type);
+            offset = offset.next_multiple_of(alignment);
+            offsets.push(offset);
+            offset += size;
+        }
+
+        offsets
+    }
 }
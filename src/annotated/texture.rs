@@ -1,30 +1,48 @@
-use super::{MAX_CONSTANT_BUFFERS, TEXTURES_INDEX};
-use crate::{ast::SemanticAnalysisError, types::Type};
+use super::{hlsl_register, msl_identifier, VULKAN_SAMPLER_SET, VULKAN_TEXTURE_SET};
+use crate::{
+    ast::SemanticAnalysisError,
+    options::CompileOptions,
+    spirv::{
+        Context, Global, DECORATION_BINDING, DECORATION_DESCRIPTOR_SET,
+        STORAGE_CLASS_UNIFORM_CONSTANT,
+    },
+    types::{TextureDimension, Type},
+};
 
 #[derive(Clone)]
 pub struct Texture {
     name: String,
     slot: usize,
+    space: usize,
     texture_type: Type,
+    dimension: TextureDimension,
+    depth: bool,
 }
 
 impl Texture {
     pub fn new(
         name: String,
         slot: usize,
+        space: usize,
         texture_type: Type,
+        dimension: TextureDimension,
+        depth: bool,
+        max_textures: usize,
     ) -> Result<Self, SemanticAnalysisError> {
-        if slot >= MAX_CONSTANT_BUFFERS {
+        if slot >= max_textures {
             Err(SemanticAnalysisError::SlotOutOfRange(
                 "textures",
                 slot,
-                MAX_CONSTANT_BUFFERS,
+                max_textures,
             ))
         } else {
             Ok(Texture {
                 name,
                 slot,
+                space,
                 texture_type,
+                dimension,
+                depth,
             })
         }
     }
@@ -37,19 +55,566 @@ impl Texture {
         self.slot
     }
 
-    pub fn generate_hlsl(self) -> String {
-        format!(
-            "Texture2D<{}> {} : register(t{});\nSamplerState acsl_{}_sampler_state : register(s{});\n",
-            self.texture_type, self.name, self.slot, self.name, self.slot
-        )
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    pub fn texture_type(&self) -> &Type {
+        &self.texture_type
+    }
+
+    pub fn dimension(&self) -> TextureDimension {
+        self.dimension
     }
 
-    pub fn generate_glsl(self) -> String {
+    pub fn is_depth(&self) -> bool {
+        self.depth
+    }
+
+    pub fn resource_type(&self) -> Type {
+        match self.depth {
+            true => Type::depth_texture(self.dimension),
+            false => Type::texture(self.texture_type.clone(), self.dimension),
+        }
+    }
+
+    // The texture's own sampler compares when it samples depth
+    fn sampler_type(&self) -> Type {
+        match self.depth {
+            true => Type::comparison_sampler(),
+            false => Type::sampler(),
+        }
+    }
+
+    // "sized" textures are queried by "texture_size", which calls a function
+    // wrapping "GetDimensions"
+    pub fn generate_hlsl(self, sized: bool) -> String {
+        let mut hlsl = format!(
+            "{} {} : register({});\n{} acsl_{}_sampler_state : register({});\n",
+            self.resource_type().hlsl(),
+            self.name,
+            hlsl_register('t', self.slot, self.space),
+            self.sampler_type().hlsl(),
+            self.name,
+            hlsl_register('s', self.slot, self.space)
+        );
+
+        if sized {
+            let size: &[&str] = match self.dimension {
+                TextureDimension::D1 => &["width"],
+                TextureDimension::D2 | TextureDimension::Cube => &["width", "height"],
+                TextureDimension::D3 => &["width", "height", "depth"],
+                TextureDimension::D2Array | TextureDimension::CubeArray => {
+                    &["width", "height", "elements"]
+                }
+            };
+            let size_type = match size.len() {
+                1 => Type::uint(),
+                n => Type::uintn(n),
+            };
+
+            hlsl.push_str(&format!(
+                "{0} acsl_{1}_size() {{\n    uint {2};\n    {1}.GetDimensions({2});\n    return {0}({2});\n}}\n",
+                size_type.hlsl(),
+                self.name,
+                size.join(", ")
+            ));
+        }
+
+        hlsl
+    }
+
+    pub fn glsl_sampler_type(&self) -> String {
+        self.resource_type().glsl()
+    }
+
+    pub fn generate_glsl(self, options: &CompileOptions) -> String {
+        if options.get_glsl_version().is_vulkan() {
+            format!(
+                "layout(set = {}, binding = {}) uniform {}texture{} {};\nlayout(set = {}, binding = {}) uniform {} acsl_{}_sampler_state;\n",
+                VULKAN_TEXTURE_SET,
+                self.slot,
+                glsl_prefix(&self.texture_type),
+                self.dimension.glsl(),
+                self.name,
+                VULKAN_SAMPLER_SET,
+                self.slot,
+                self.sampler_type().glsl(),
+                self.name
+            )
+        } else if options.get_glsl_version().explicit_uniform_locations() {
+            format!(
+                "layout(location = {}) uniform {} {};\n",
+                self.slot + options.textures_index(),
+                self.glsl_sampler_type(),
+                self.name
+            )
+        } else {
+            format!("uniform {} {};\n", self.glsl_sampler_type(), self.name)
+        }
+    }
+
+    pub fn generate_wgsl(self, options: &CompileOptions) -> String {
         format!(
-            "layout(location = {}) uniform {}sampler2D {};\n",
-            self.slot + TEXTURES_INDEX,
-            if self.texture_type.is_uint() { "u" } else { "" },
-            self.name
+            "@group(0) @binding({}) var {}: {};\n@group(0) @binding({}) var acsl_{}_sampler_state: {};\n",
+            self.slot + options.textures_index(),
+            self.name,
+            self.resource_type().wgsl(),
+            self.slot + options.samplers_index(),
+            self.name,
+            self.sampler_type().wgsl()
         )
     }
+
+    pub fn msl_parameter(&self, entry_point: bool) -> String {
+        let texture_type = self.resource_type().msl();
+        let name = msl_identifier(&self.name);
+
+        if entry_point {
+            format!(
+                "{} {} [[texture({})]], sampler acsl_{}_sampler_state [[sampler({})]]",
+                texture_type, name, self.slot, name, self.slot
+            )
+        } else {
+            format!(
+                "{} {}, sampler acsl_{}_sampler_state",
+                texture_type, name, name
+            )
+        }
+    }
+
+    pub fn msl_argument(&self) -> String {
+        let name = msl_identifier(&self.name);
+        format!("{}, acsl_{}_sampler_state", name, name)
+    }
+
+    // Declared as a separate image and sampler, like the Vulkan GLSL output
+    pub fn declare_spirv(&self, context: &mut Context) {
+        let module = &mut context.module;
+        let uint = self.texture_type.is_uint();
+
+        let sampled_type = if uint {
+            module.type_uint()
+        } else {
+            module.type_float()
+        };
+        let image_type = module.type_image(sampled_type, self.dimension, self.depth);
+        let image_pointer_type = module.type_pointer(STORAGE_CLASS_UNIFORM_CONSTANT, image_type);
+        let image = module.global_variable(image_pointer_type, STORAGE_CLASS_UNIFORM_CONSTANT);
+        module.name(image, &self.name);
+        module.decorate(
+            image,
+            DECORATION_DESCRIPTOR_SET,
+            &[VULKAN_TEXTURE_SET as u32],
+        );
+        module.decorate(image, DECORATION_BINDING, &[self.slot as u32]);
+
+        let sampler_type = module.type_sampler();
+        let sampler_pointer_type =
+            module.type_pointer(STORAGE_CLASS_UNIFORM_CONSTANT, sampler_type);
+        let sampler = module.global_variable(sampler_pointer_type, STORAGE_CLASS_UNIFORM_CONSTANT);
+        module.name(sampler, &format!("acsl_{}_sampler_state", self.name));
+        module.decorate(
+            sampler,
+            DECORATION_DESCRIPTOR_SET,
+            &[VULKAN_SAMPLER_SET as u32],
+        );
+        module.decorate(sampler, DECORATION_BINDING, &[self.slot as u32]);
+
+        context.define_global(
+            &self.name,
+            Global::Texture {
+                image,
+                image_type,
+                sampler,
+                sampler_type,
+                uint,
+            },
+        );
+    }
+}
+
+// Unsigned integer samplers and textures are prefixed with "u"
+pub fn glsl_prefix(texture_type: &Type) -> &'static str {
+    if texture_type.is_uint() {
+        "u"
+    } else {
+        ""
+    }
+}
+
+// WGSL and MSL take the array layer as a separate integer
+pub fn wgsl_sample_coordinates(dimension: TextureDimension, coordinates: &str) -> String {
+    match dimension {
+        TextureDimension::D2Array => format!("{0}.xy, i32(round({0}.z))", coordinates),
+        TextureDimension::CubeArray => format!("{0}.xyz, i32(round({0}.w))", coordinates),
+        _ => coordinates.to_owned(),
+    }
+}
+
+pub fn msl_sample_coordinates(dimension: TextureDimension, coordinates: &str) -> String {
+    match dimension {
+        TextureDimension::D2Array => format!("{0}.xy, uint(round({0}.z))", coordinates),
+        TextureDimension::CubeArray => format!("{0}.xyz, uint(round({0}.w))", coordinates),
+        _ => coordinates.to_owned(),
+    }
+}
+
+// A call of one of the texture functions with its arguments written in the
+// target language. "sampler" is only set when a sampler declaration follows
+// the texture, the texture's own sampler is used otherwise.
+pub struct TextureCall {
+    pub name: String,
+    pub texture_type: Type,
+    pub texture: String,
+    pub sampler: Option<String>,
+    pub arguments: Vec<String>,
+    // The whole texel offset of "sample_offset"
+    pub offset: Vec<i64>,
+}
+
+impl TextureCall {
+    fn dimension(&self) -> TextureDimension {
+        self.texture_type.texture_dimension().unwrap()
+    }
+
+    fn element_type(&self) -> Type {
+        self.texture_type.texture_element().unwrap()
+    }
+
+    fn sampler_state(&self) -> String {
+        match &self.sampler {
+            Some(sampler) => sampler.clone(),
+            None => format!("acsl_{}_sampler_state", self.texture),
+        }
+    }
+
+    // Only HLSL samples typed textures, the other targets sample four
+    // components. Comparisons already return a "float" and gathers a "float4".
+    fn sample_swizzle(&self) -> &'static str {
+        match self.name.as_str() {
+            "gather" | "sample_compare" | "sample_compare_level_zero" => "",
+            _ => element_swizzle(&self.element_type()),
+        }
+    }
+
+    fn offset(&self, vector_type: &str) -> String {
+        let components: Vec<String> = self.offset.iter().map(|c| c.to_string()).collect();
+        format!("{}({})", vector_type, components.join(", "))
+    }
+
+    pub fn hlsl(self) -> String {
+        let dimension = self.dimension();
+        let mut arguments = self.arguments.clone();
+
+        match self.name.as_str() {
+            "texture_size" => format!("acsl_{}_size()", self.texture),
+            // The mip level is the last component of the location
+            "load" => {
+                let coordinates = arguments.remove(0);
+                let level = arguments.pop().unwrap_or_else(|| "0".to_owned());
+                format!(
+                    "{}.Load(uint{}({}, {}))",
+                    self.texture,
+                    dimension.load_coordinates().unwrap() + 1,
+                    coordinates,
+                    level
+                )
+            }
+            name => {
+                let method = match name {
+                    "sample_level" => "SampleLevel",
+                    "sample_bias" => "SampleBias",
+                    "sample_grad" => "SampleGrad",
+                    "sample_compare" => "SampleCmp",
+                    "sample_compare_level_zero" => "SampleCmpLevelZero",
+                    "gather" => "Gather",
+                    _ => "Sample",
+                };
+                if !self.offset.is_empty() {
+                    arguments.push(self.offset(&format!("int{}", self.offset.len())));
+                }
+
+                format!(
+                    "{}.{}({}, {})",
+                    self.texture,
+                    method,
+                    self.sampler_state(),
+                    arguments.join(", ")
+                )
+            }
+        }
+    }
+
+    // Vulkan combines the separate texture and sampler at the call, OpenGL
+    // samples the texture with the state of its texture unit
+    pub fn glsl(self, options: &CompileOptions) -> String {
+        let dimension = self.dimension();
+        let comparison =
+            self.texture_type.is_depth_texture() || self.name.starts_with("sample_compare");
+
+        let sampler = if options.get_glsl_version().is_vulkan() {
+            format!(
+                "{}sampler{}{}({}, {})",
+                glsl_prefix(&self.element_type()),
+                dimension.glsl(),
+                if comparison { "Shadow" } else { "" },
+                self.texture,
+                self.sampler_state()
+            )
+        } else {
+            self.texture.clone()
+        };
+        let mut arguments = self.arguments.clone();
+
+        match self.name.as_str() {
+            "texture_size" => {
+                let size_type = match dimension.size_components() {
+                    1 => Type::uint(),
+                    n => Type::uintn(n),
+                };
+                format!("{}(textureSize({}, 0))", size_type.glsl(), sampler)
+            }
+            "load" => {
+                let count = dimension.load_coordinates().unwrap();
+                let coordinates = match count {
+                    1 => format!("int({})", arguments[0]),
+                    n => format!("ivec{}({})", n, arguments[0]),
+                };
+                let level = match arguments.get(1) {
+                    Some(level) => format!("int({})", level),
+                    None => "0".to_owned(),
+                };
+                format!(
+                    "texelFetch({}, {}, {}){}",
+                    sampler,
+                    coordinates,
+                    level,
+                    element_swizzle(&self.element_type())
+                )
+            }
+            // The reference value is part of the coordinates, unless they
+            // already hold four components. Only 2D shadow samplers take a
+            // level of detail, the others are sampled with zero gradients.
+            "sample_compare" | "sample_compare_level_zero" => {
+                let reference = arguments.pop().unwrap();
+                let coordinates = match dimension {
+                    TextureDimension::D2 => format!("vec3({}, {})", arguments[0], reference),
+                    TextureDimension::CubeArray => format!("{}, {}", arguments[0], reference),
+                    _ => format!("vec4({}, {})", arguments[0], reference),
+                };
+                match (self.name.as_str(), dimension) {
+                    ("sample_compare", _) => format!("texture({}, {})", sampler, coordinates),
+                    (_, TextureDimension::D2) => {
+                        format!("textureLod({}, {}, 0.0)", sampler, coordinates)
+                    }
+                    _ => {
+                        let gradient = match dimension.gradient_components() {
+                            2 => "vec2(0.0)",
+                            _ => "vec3(0.0)",
+                        };
+                        format!(
+                            "textureGrad({}, {}, {2}, {2})",
+                            sampler, coordinates, gradient
+                        )
+                    }
+                }
+            }
+            name => {
+                let function = match name {
+                    "sample_level" => "textureLod",
+                    "sample_grad" => "textureGrad",
+                    "sample_offset" => "textureOffset",
+                    "gather" => "textureGather",
+                    _ => "texture",
+                };
+                if !self.offset.is_empty() {
+                    arguments.push(self.offset(&format!("ivec{}", self.offset.len())));
+                }
+
+                format!(
+                    "{}({}, {}){}",
+                    function,
+                    sampler,
+                    arguments.join(", "),
+                    self.sample_swizzle()
+                )
+            }
+        }
+    }
+
+    // WebGPU 1D textures have a single mip level, so they are always sampled
+    // without a level of detail
+    pub fn wgsl(self) -> String {
+        let dimension = self.dimension();
+        let mut arguments = self.arguments.clone();
+
+        match self.name.as_str() {
+            "texture_size" if dimension.is_array() => format!(
+                "vec3<u32>(textureDimensions({0}), textureNumLayers({0}))",
+                self.texture
+            ),
+            "texture_size" => format!("textureDimensions({})", self.texture),
+            "load" => {
+                let coordinates = &arguments[0];
+                let coordinates = match dimension {
+                    TextureDimension::D1 => format!("i32({})", coordinates),
+                    TextureDimension::D2Array => {
+                        format!("vec2<i32>({0}.xy), i32({0}.z)", coordinates)
+                    }
+                    _ => format!(
+                        "vec{}<i32>({})",
+                        dimension.load_coordinates().unwrap(),
+                        coordinates
+                    ),
+                };
+                let level = match arguments.get(1) {
+                    Some(level) => format!("i32({})", level),
+                    None => "0".to_owned(),
+                };
+                format!(
+                    "textureLoad({}, {}, {}){}",
+                    self.texture,
+                    coordinates,
+                    level,
+                    element_swizzle(&self.element_type())
+                )
+            }
+            name => {
+                let function = match name {
+                    "sample_level" | "sample_bias" | "sample_grad"
+                        if dimension == TextureDimension::D1 =>
+                    {
+                        arguments.truncate(1);
+                        "textureSample"
+                    }
+                    "sample_level" => "textureSampleLevel",
+                    "sample_bias" => "textureSampleBias",
+                    "sample_grad" => "textureSampleGrad",
+                    "sample_compare" => "textureSampleCompare",
+                    "sample_compare_level_zero" => "textureSampleCompareLevel",
+                    "gather" => "textureGather",
+                    _ => "textureSample",
+                };
+                arguments[0] = wgsl_sample_coordinates(dimension, &arguments[0]);
+                if !self.offset.is_empty() {
+                    arguments.push(self.offset(&format!("vec{}<i32>", self.offset.len())));
+                }
+
+                // Gathers of color textures start with the component to gather
+                let component = match name {
+                    "gather" => "0, ",
+                    _ => "",
+                };
+
+                format!(
+                    "{}({}{}, {}, {}){}",
+                    function,
+                    component,
+                    self.texture,
+                    self.sampler_state(),
+                    arguments.join(", "),
+                    self.sample_swizzle()
+                )
+            }
+        }
+    }
+
+    // Metal 1D textures have no mip levels, so they are always sampled
+    // without a level of detail
+    pub fn msl(self) -> String {
+        let dimension = self.dimension();
+        let mut arguments = self.arguments.clone();
+
+        match self.name.as_str() {
+            "texture_size" => {
+                let size = match dimension {
+                    TextureDimension::D1 => return format!("{}.get_width()", self.texture),
+                    TextureDimension::D2 | TextureDimension::Cube => {
+                        "uint2({0}.get_width(), {0}.get_height())"
+                    }
+                    TextureDimension::D3 => {
+                        "uint3({0}.get_width(), {0}.get_height(), {0}.get_depth())"
+                    }
+                    TextureDimension::D2Array | TextureDimension::CubeArray => {
+                        "uint3({0}.get_width(), {0}.get_height(), {0}.get_array_size())"
+                    }
+                };
+                size.replace("{0}", &self.texture)
+            }
+            "load" => {
+                let coordinates = &arguments[0];
+                let level = match arguments.get(1) {
+                    Some(level) => level.clone(),
+                    None => "0".to_owned(),
+                };
+                let arguments = match dimension {
+                    TextureDimension::D1 => coordinates.clone(),
+                    TextureDimension::D2Array => {
+                        format!("{0}.xy, {0}.z, {1}", coordinates, level)
+                    }
+                    _ => format!("{}, {}", coordinates, level),
+                };
+                format!(
+                    "{}.read({}){}",
+                    self.texture,
+                    arguments,
+                    element_swizzle(&self.element_type())
+                )
+            }
+            name => {
+                let method = match name {
+                    "sample_compare" | "sample_compare_level_zero" => "sample_compare",
+                    "gather" => "gather",
+                    _ => "sample",
+                };
+                arguments[0] = msl_sample_coordinates(dimension, &arguments[0]);
+
+                // Metal passes the level of detail as an option
+                if dimension == TextureDimension::D1 {
+                    arguments.truncate(1);
+                } else if name == "sample_level" {
+                    let level = arguments.pop().unwrap();
+                    arguments.push(format!("level({})", level));
+                } else if name == "sample_compare_level_zero" {
+                    arguments.push("level(0)".to_owned());
+                } else if name == "sample_bias" {
+                    let bias = arguments.pop().unwrap();
+                    arguments.push(format!("bias({})", bias));
+                } else if name == "sample_grad" {
+                    let gradient = match dimension {
+                        TextureDimension::D3 => "gradient3d",
+                        TextureDimension::Cube | TextureDimension::CubeArray => "gradientcube",
+                        _ => "gradient2d",
+                    };
+                    let ddy = arguments.pop().unwrap();
+                    let ddx = arguments.pop().unwrap();
+                    arguments.push(format!("{}({}, {})", gradient, ddx, ddy));
+                }
+                if !self.offset.is_empty() {
+                    arguments.push(self.offset(&format!("int{}", self.offset.len())));
+                }
+
+                format!(
+                    "{}.{}({}, {}){}",
+                    self.texture,
+                    method,
+                    self.sampler_state(),
+                    arguments.join(", "),
+                    self.sample_swizzle()
+                )
+            }
+        }
+    }
+}
+
+// Texel fetches return four components, narrowed to the element type of the
+// texture, scalars have no members
+fn element_swizzle(element_type: &Type) -> &'static str {
+    match element_type.members().len() {
+        0 | 1 => ".x",
+        2 => ".xy",
+        3 => ".xyz",
+        _ => "",
+    }
 }
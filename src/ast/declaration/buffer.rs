@@ -0,0 +1,67 @@
+use super::{parse_binding, Declaration};
+use crate::{
+    annotated::{self, AnnotatedSyntaxTree},
+    ast::SemanticAnalysisError,
+    feature::Feature,
+    next_token,
+    parser::ParserError,
+    stream::Stream,
+    tokens::TokenClass,
+    types::Type,
+};
+
+// Parses "<type> name : slot;" following "buffer" or "rwbuffer"
+pub fn parse_buffer(stream: &mut Stream, writable: bool) -> Result<Declaration, ParserError> {
+    next_token!(stream, TokenClass::LeftAngleBracket => {});
+    let element_type =
+        next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+    next_token!(stream, TokenClass::RightAngleBracket => {});
+
+    let name = next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+
+    next_token!(stream, TokenClass::Colon => {});
+
+    let (slot, space) = parse_binding(stream)?;
+
+    Ok(Declaration::Buffer(
+        name,
+        slot,
+        space,
+        element_type,
+        writable,
+    ))
+}
+
+pub fn semantic_analysis(
+    name: String,
+    slot: usize,
+    space: usize,
+    element_type: String,
+    writable: bool,
+    output_tree: &AnnotatedSyntaxTree,
+) -> Result<annotated::buffer::Buffer, SemanticAnalysisError> {
+    let element_type = Type::from_name(&element_type, output_tree)?;
+
+    // Every target has to agree on where each element starts
+    if !element_type.is_packed_in_std430() {
+        return Err(SemanticAnalysisError::UnpackedBufferElement(
+            name,
+            element_type.to_string(),
+        ));
+    }
+
+    if space != 0 {
+        output_tree.require_feature(Feature::RegisterSpace);
+    }
+
+    output_tree.require_feature(Feature::StorageBuffers);
+
+    annotated::buffer::Buffer::new(
+        name,
+        slot,
+        space,
+        element_type,
+        writable,
+        output_tree.max_buffers(),
+    )
+}
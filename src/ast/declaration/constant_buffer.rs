@@ -1,7 +1,8 @@
-use super::Declaration;
+use super::{parse_binding, Declaration};
 use crate::{
     annotated::{self, AnnotatedSyntaxTree},
     ast::SemanticAnalysisError,
+    feature::Feature,
     next_token,
     parser::ParserError,
     stream::Stream,
@@ -19,22 +20,27 @@ pub fn parse_constant_buffer(stream: &mut Stream) -> Result<Declaration, ParserE
 
     next_token!(stream, TokenClass::Colon => {});
 
-    let slot = next_token!(stream, TokenClass::IntegerLiteral(slot) => {*slot});
+    let (slot, space) = parse_binding(stream)?;
 
-    next_token!(stream, TokenClass::SemiColon => {});
-
-    Ok(Declaration::ConstantBuffer(name, slot, type_name))
+    Ok(Declaration::ConstantBuffer(name, slot, space, type_name))
 }
 
 pub fn semantic_analysis(
     output_tree: &AnnotatedSyntaxTree,
     name: String,
     slot: usize,
+    space: usize,
     type_name: String,
 ) -> Result<annotated::constant_buffer::ConstantBuffer, SemanticAnalysisError> {
+    if space != 0 {
+        output_tree.require_feature(Feature::RegisterSpace);
+    }
+
     annotated::constant_buffer::ConstantBuffer::new(
         name,
         slot,
+        space,
         Type::from_name(&type_name, output_tree)?,
+        output_tree.max_constant_buffers(),
     )
 }
@@ -1,14 +1,60 @@
 use super::Declaration;
 use crate::{
-    annotated::{self, function::FunctionParameter, AnnotatedSyntaxTree},
+    annotated::{
+        self,
+        function::{FunctionAttribute, FunctionParameter},
+        AnnotatedSyntaxTree,
+    },
     ast::{code_block::CodeBlock, SemanticAnalysisError},
     lexer, next_token,
     parser::ParserError,
     stream::Stream,
-    tokens::TokenClass,
+    tokens::{Token, TokenClass},
     types::Type,
 };
 
+// Parses an attribute following its "#" along with the function it applies to,
+// "[name(a, b, ...)] fn ..."
+pub fn parse_attributed_function(stream: &mut Stream) -> Result<Declaration, ParserError> {
+    next_token!(stream, TokenClass::OpenSquareBracket => {});
+
+    let name = next_token!(stream, TokenClass::Identifier(name) => { name.to_owned() });
+
+    let mut arguments = Vec::new();
+    let has_arguments = next_token!(stream,
+        TokenClass::OpenParenthesis => {true},
+        TokenClass::CloseSquareBracket => {false}
+    );
+
+    if has_arguments {
+        loop {
+            arguments.push(next_token!(stream, TokenClass::IntegerLiteral(value) => {*value}));
+
+            next_token!(stream,
+                TokenClass::Comma => {},
+                TokenClass::CloseParenthesis => {break}
+            );
+        }
+
+        next_token!(stream, TokenClass::CloseSquareBracket => {});
+    }
+
+    next_token!(stream, TokenClass::Fn => {});
+
+    match parse_function(stream)? {
+        Declaration::Function(function_name, parameters, return_type, code_block, _) => {
+            Ok(Declaration::Function(
+                function_name,
+                parameters,
+                return_type,
+                code_block,
+                Some((name, arguments)),
+            ))
+        }
+        _ => unreachable!(),
+    }
+}
+
 pub fn parse_function(stream: &mut Stream) -> Result<Declaration, ParserError> {
     let name = next_token!(stream, TokenClass::Identifier(name) => { name.to_owned() });
 
@@ -40,15 +86,15 @@ pub fn parse_function(stream: &mut Stream) -> Result<Declaration, ParserError> {
 
         next_token!(stream, TokenClass::Colon => {});
 
-        let type_name =
-            next_token!(stream, TokenClass::Identifier(type_name) => {type_name.to_owned()});
+        let (type_name, next_token) = parse_parameter_type(stream)?;
 
         parameters.push((name, type_name, mutable));
 
-        next_token!(stream,
-            TokenClass::CloseParenthesis => {break},
-            TokenClass::Comma => {name_token = lexer::next_token(stream)?}
-        );
+        match next_token.class() {
+            TokenClass::CloseParenthesis => break,
+            TokenClass::Comma => name_token = lexer::next_token(stream)?,
+            _ => return Err(ParserError::UnexpectedToken(next_token)),
+        }
     }
 
     // Parse return type
@@ -69,16 +115,60 @@ pub fn parse_function(stream: &mut Stream) -> Result<Declaration, ParserError> {
         parameters,
         return_type,
         code_block,
+        None,
     ))
 }
 
+// Parses the type of a parameter and returns the token which follows, the
+// types of the stage entry points are generic as in "patch<T, 3>"
+fn parse_parameter_type(stream: &mut Stream) -> Result<(String, Token), ParserError> {
+    let mut type_name =
+        next_token!(stream, TokenClass::Identifier(type_name) => {type_name.to_owned()});
+
+    let token = match lexer::next_token(stream)? {
+        Some(token) => token,
+        None => return Err(ParserError::UnexpectedEOF),
+    };
+    match token.class() {
+        TokenClass::LeftAngleBracket => {}
+        _ => return Ok((type_name, token)),
+    }
+
+    let element_name =
+        next_token!(stream, TokenClass::Identifier(element_name) => {element_name.to_owned()});
+    type_name.push_str(&format!("<{}", element_name));
+
+    let has_count = next_token!(stream,
+        TokenClass::Comma => {true},
+        TokenClass::RightAngleBracket => {false}
+    );
+    if has_count {
+        let count = next_token!(stream, TokenClass::IntegerLiteral(count) => {*count});
+        type_name.push_str(&format!(", {}", count));
+
+        next_token!(stream, TokenClass::RightAngleBracket => {});
+    }
+    type_name.push('>');
+
+    match lexer::next_token(stream)? {
+        Some(token) => Ok((type_name, token)),
+        None => Err(ParserError::UnexpectedEOF),
+    }
+}
+
 pub fn semantic_analysis(
     output_tree: &AnnotatedSyntaxTree,
     name: String,
     parameters: Vec<(String, String, bool)>,
     return_type: Option<String>,
     code_block: CodeBlock,
+    attribute: Option<(String, Vec<usize>)>,
 ) -> Result<annotated::function::Function, SemanticAnalysisError> {
+    let attribute = match attribute {
+        Some((attribute, arguments)) => Some(function_attribute(attribute, arguments)?),
+        None => None,
+    };
+
     let mut f_parameters = Vec::with_capacity(parameters.len());
 
     let mut scope = output_tree.global_scope().new_child();
@@ -100,5 +190,32 @@ pub fn semantic_analysis(
         f_parameters,
         return_type,
         code_block,
+        attribute,
     ))
 }
+
+// "#[compute(x, y, z)]" marks a compute kernel, omitted sizes default to one,
+// "#[geometry(n)]" gives the maximum number of vertices "geometry_main()" emits
+fn function_attribute(
+    attribute: String,
+    arguments: Vec<usize>,
+) -> Result<FunctionAttribute, SemanticAnalysisError> {
+    match attribute.as_str() {
+        "compute" => {
+            if arguments.is_empty() || arguments.len() > 3 || arguments.contains(&0) {
+                return Err(SemanticAnalysisError::InvalidThreadGroupSize(arguments));
+            }
+
+            Ok(FunctionAttribute::Compute(
+                arguments[0],
+                arguments.get(1).copied().unwrap_or(1),
+                arguments.get(2).copied().unwrap_or(1),
+            ))
+        }
+        "geometry" => match arguments[..] {
+            [max_vertices] if max_vertices != 0 => Ok(FunctionAttribute::Geometry(max_vertices)),
+            _ => Err(SemanticAnalysisError::InvalidMaxVertexCount(arguments)),
+        },
+        _ => Err(SemanticAnalysisError::UnknownAttribute(attribute)),
+    }
+}
@@ -0,0 +1,46 @@
+use super::Declaration;
+use crate::{
+    annotated::{self, AnnotatedSyntaxTree},
+    ast::SemanticAnalysisError,
+    feature::Feature,
+    next_token,
+    parser::ParserError,
+    stream::Stream,
+    tokens::TokenClass,
+    types::Type,
+};
+
+pub fn parse_group_shared(stream: &mut Stream) -> Result<Declaration, ParserError> {
+    let name = next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+
+    next_token!(stream, TokenClass::Colon => {});
+
+    let type_name =
+        next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+
+    next_token!(stream, TokenClass::SemiColon => {});
+
+    Ok(Declaration::GroupShared(name, type_name))
+}
+
+pub fn semantic_analysis(
+    output_tree: &AnnotatedSyntaxTree,
+    name: String,
+    type_name: String,
+) -> Result<annotated::group_shared::GroupShared, SemanticAnalysisError> {
+    let variable_type = Type::from_name(&type_name, output_tree)?;
+    if variable_type.texture_dimension().is_some()
+        || variable_type.is_sampler()
+        || variable_type == Type::void()
+    {
+        return Err(SemanticAnalysisError::InvalidGroupSharedType(type_name));
+    }
+
+    // Memory shared by a thread group only exists in compute kernels
+    output_tree.require_feature(Feature::ComputeShaders);
+
+    Ok(annotated::group_shared::GroupShared::new(
+        name,
+        variable_type,
+    ))
+}
@@ -1,9 +1,20 @@
 use super::{code_block::CodeBlock, expression::Expression, SemanticAnalysisError};
-use crate::annotated::AnnotatedSyntaxTree;
+use crate::{
+    annotated::AnnotatedSyntaxTree,
+    lexer, next_token,
+    parser::ParserError,
+    stream::Stream,
+    tokens::{Token, TokenClass},
+    types::TextureDimension,
+};
 
+pub mod buffer;
 pub mod constant;
 pub mod constant_buffer;
 pub mod function;
+pub mod group_shared;
+pub mod sampler;
+pub mod storage_texture;
 pub mod structure;
 pub mod texture;
 pub mod type_alias;
@@ -14,12 +25,21 @@ pub enum Declaration {
         Vec<(String, String, bool)>,
         Option<String>,
         CodeBlock,
+        Option<(String, Vec<usize>)>,
     ),
-    Struct(String, Vec<(String, String, Option<String>)>),
-    ConstantBuffer(String, usize, String),
-    Texture(String, usize, String),
+    Struct(
+        String,
+        Vec<(String, String, Option<String>, Option<String>)>,
+    ),
+    ConstantBuffer(String, usize, usize, String),
+    Texture(String, usize, usize, String, TextureDimension),
+    DepthTexture(String, usize, usize, TextureDimension),
+    Buffer(String, usize, usize, String, bool),
+    StorageTexture(String, usize, usize, String, Option<String>),
+    Sampler(String, usize, usize, Vec<(String, String)>),
     TypeAlias(String, String),
     Constant(String, Expression),
+    GroupShared(String, String),
 }
 
 impl Declaration {
@@ -28,28 +48,68 @@ impl Declaration {
         output_tree: &mut AnnotatedSyntaxTree,
     ) -> Result<(), SemanticAnalysisError> {
         match self {
-            Declaration::Function(name, parameters, return_type, code_block) => output_tree
-                .push_function(function::semantic_analysis(
+            Declaration::Function(name, parameters, return_type, code_block, attribute) => {
+                output_tree.push_function(function::semantic_analysis(
                     output_tree,
                     name,
                     parameters,
                     return_type,
                     code_block,
-                )?),
+                    attribute,
+                )?)
+            }
             Declaration::Struct(name, members) => {
                 output_tree.push_struct(structure::semantic_analysis(output_tree, name, members)?)
             }
-            Declaration::ConstantBuffer(name, slot, type_name) => output_tree.push_constant_buffer(
-                constant_buffer::semantic_analysis(output_tree, name, slot, type_name)?,
+            Declaration::ConstantBuffer(name, slot, space, type_name) => output_tree
+                .push_constant_buffer(constant_buffer::semantic_analysis(
+                    output_tree,
+                    name,
+                    slot,
+                    space,
+                    type_name,
+                )?),
+            Declaration::Texture(name, slot, space, texture_type, dimension) => output_tree
+                .push_texture(texture::semantic_analysis(
+                    name,
+                    slot,
+                    space,
+                    texture_type,
+                    dimension,
+                    output_tree,
+                )?),
+            Declaration::DepthTexture(name, slot, space, dimension) => output_tree.push_texture(
+                texture::depth_semantic_analysis(name, slot, space, dimension, output_tree)?,
             ),
-            Declaration::Texture(name, slot, texture_type) => output_tree.push_texture(
-                texture::semantic_analysis(name, slot, texture_type, &output_tree)?,
+            Declaration::Buffer(name, slot, space, element_type, writable) => output_tree
+                .push_buffer(buffer::semantic_analysis(
+                    name,
+                    slot,
+                    space,
+                    element_type,
+                    writable,
+                    output_tree,
+                )?),
+            Declaration::StorageTexture(name, slot, space, texel_type, format) => output_tree
+                .push_storage_texture(storage_texture::semantic_analysis(
+                    name,
+                    slot,
+                    space,
+                    texel_type,
+                    format,
+                    output_tree,
+                )?),
+            Declaration::Sampler(name, slot, space, states) => output_tree.push_sampler(
+                sampler::semantic_analysis(name, slot, space, states, output_tree)?,
             ),
             Declaration::TypeAlias(name, type_name) => {
                 type_alias::semantic_analysis(output_tree, name, type_name)
             }
             Declaration::Constant(name, expression) => output_tree
                 .push_constant(constant::semantic_analysis(output_tree, name, expression)?),
+            Declaration::GroupShared(name, type_name) => output_tree.push_group_shared(
+                group_shared::semantic_analysis(output_tree, name, type_name)?,
+            ),
         }
     }
 }
@@ -59,7 +119,11 @@ impl std::fmt::Display for Declaration {
         write!(f, "  - ")?;
 
         match self {
-            Declaration::Function(name, parameters, return_type, code_block) => {
+            Declaration::Function(name, parameters, return_type, code_block, attribute) => {
+                if let Some((attribute, arguments)) = attribute {
+                    write!(f, "#[{}{:?}] ", attribute, arguments)?;
+                }
+
                 write!(f, "Function {}(", name)?;
                 for i in 0..parameters.len() {
                     write!(f, "{}: {}", parameters[i].0, parameters[i].1)?;
@@ -79,8 +143,12 @@ impl std::fmt::Display for Declaration {
             Declaration::Struct(name, members) => {
                 writeln!(f, "Structure \"{}\"", name)?;
 
-                for (name, type_name, semantic) in members {
-                    write!(f, "      - {}: {}", name, type_name)?;
+                for (name, type_name, semantic, attribute) in members {
+                    write!(f, "      - ")?;
+                    if let Some(attribute) = attribute {
+                        write!(f, "#[{}] ", attribute)?;
+                    }
+                    write!(f, "{}: {}", name, type_name)?;
                     match semantic {
                         Some(semantic) => write!(f, " : {}", semantic)?,
                         None => {}
@@ -90,11 +158,52 @@ impl std::fmt::Display for Declaration {
 
                 Ok(())
             }
-            Declaration::ConstantBuffer(name, slot, type_name) => {
-                writeln!(f, "Constant Buffer \"{}\" @ {} ({})", name, slot, type_name)
-            }
-            Declaration::Texture(name, slot, texture_type) => {
-                writeln!(f, "Texture<{}> \"{}\" @ {}", texture_type, name, slot)
+            Declaration::ConstantBuffer(name, slot, space, type_name) => writeln!(
+                f,
+                "Constant Buffer \"{}\" @ {}, space {} ({})",
+                name, slot, space, type_name
+            ),
+            Declaration::Texture(name, slot, space, texture_type, dimension) => writeln!(
+                f,
+                "Texture {}<{}> \"{}\" @ {}, space {}",
+                dimension.name(),
+                texture_type,
+                name,
+                slot,
+                space
+            ),
+            Declaration::DepthTexture(name, slot, space, dimension) => writeln!(
+                f,
+                "Depth Texture {} \"{}\" @ {}, space {}",
+                dimension.name(),
+                name,
+                slot,
+                space
+            ),
+            Declaration::Buffer(name, slot, space, element_type, writable) => writeln!(
+                f,
+                "{}<{}> \"{}\" @ {}, space {}",
+                if *writable { "RW Buffer" } else { "Buffer" },
+                element_type,
+                name,
+                slot,
+                space
+            ),
+            Declaration::StorageTexture(name, slot, space, texel_type, format) => writeln!(
+                f,
+                "RW Texture<{}, {}> \"{}\" @ {}, space {}",
+                texel_type,
+                format.as_deref().unwrap_or("default"),
+                name,
+                slot,
+                space
+            ),
+            Declaration::Sampler(name, slot, space, states) => {
+                write!(f, "Sampler \"{}\" @ {}, space {}", name, slot, space)?;
+                for (state, value) in states {
+                    write!(f, " {}: {}", state, value)?;
+                }
+                writeln!(f)
             }
             Declaration::TypeAlias(name, type_name) => {
                 writeln!(f, "Type Alias {} = {}", name, type_name)
@@ -102,6 +211,48 @@ impl std::fmt::Display for Declaration {
             Declaration::Constant(name, expression) => {
                 writeln!(f, "Constant \"{}\" = {}", name, expression)
             }
+            Declaration::GroupShared(name, type_name) => {
+                writeln!(f, "Group Shared \"{}\": {}", name, type_name)
+            }
         }
     }
 }
+
+// Parses the binding of a resource following its colon, "slot [, space n];"
+fn parse_binding(stream: &mut Stream) -> Result<(usize, usize), ParserError> {
+    let (slot, space, token) = parse_partial_binding(stream)?;
+
+    match token.class() {
+        TokenClass::SemiColon => Ok((slot, space)),
+        _ => Err(ParserError::UnexpectedToken(token)),
+    }
+}
+
+// Parses "slot [, space n]" and returns the token which follows
+fn parse_partial_binding(stream: &mut Stream) -> Result<(usize, usize, Token), ParserError> {
+    let slot = next_token!(stream, TokenClass::IntegerLiteral(slot) => {*slot});
+
+    let token = match lexer::next_token(stream)? {
+        Some(token) => token,
+        None => return Err(ParserError::UnexpectedEOF),
+    };
+    match token.class() {
+        TokenClass::Comma => {}
+        _ => return Ok((slot, 0, token)),
+    }
+
+    match lexer::next_token(stream)? {
+        Some(token) => match token.class() {
+            TokenClass::Identifier(identifier) if identifier == "space" => {}
+            _ => return Err(ParserError::UnexpectedToken(token)),
+        },
+        None => return Err(ParserError::UnexpectedEOF),
+    }
+
+    let space = next_token!(stream, TokenClass::IntegerLiteral(space) => {*space});
+
+    match lexer::next_token(stream)? {
+        Some(token) => Ok((slot, space, token)),
+        None => Err(ParserError::UnexpectedEOF),
+    }
+}
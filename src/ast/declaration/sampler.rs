@@ -0,0 +1,117 @@
+use super::{parse_partial_binding, Declaration};
+use crate::{
+    annotated::{
+        self,
+        sampler::{AddressMode, CompareFunction, Filter},
+        AnnotatedSyntaxTree,
+    },
+    ast::SemanticAnalysisError,
+    feature::Feature,
+    next_token,
+    parser::ParserError,
+    stream::Stream,
+    tokens::TokenClass,
+};
+
+// Parses "name : slot { state: value, ... };" following "sampler", the block of
+// sampler states is optional
+pub fn parse_sampler(stream: &mut Stream) -> Result<Declaration, ParserError> {
+    let name = next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+
+    next_token!(stream, TokenClass::Colon => {});
+
+    let (slot, space, token) = parse_partial_binding(stream)?;
+
+    let mut states = Vec::new();
+    match token.class() {
+        TokenClass::SemiColon => return Ok(Declaration::Sampler(name, slot, space, states)),
+        TokenClass::OpenCurlyBrace => {}
+        _ => return Err(ParserError::UnexpectedToken(token)),
+    }
+
+    loop {
+        let state = next_token!(stream,
+            TokenClass::CloseCurlyBrace => {break},
+            TokenClass::Identifier(identifier) => {identifier.to_owned()}
+        );
+
+        next_token!(stream, TokenClass::Colon => {});
+
+        let value =
+            next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+        states.push((state, value));
+
+        next_token!(stream,
+            TokenClass::CloseCurlyBrace => {break},
+            TokenClass::Comma => {}
+        );
+    }
+
+    next_token!(stream, TokenClass::SemiColon => {});
+
+    Ok(Declaration::Sampler(name, slot, space, states))
+}
+
+// Samplers filter linearly and wrap their coordinates unless stated otherwise,
+// they only compare when given a "compare" function
+pub fn semantic_analysis(
+    name: String,
+    slot: usize,
+    space: usize,
+    states: Vec<(String, String)>,
+    output_tree: &AnnotatedSyntaxTree,
+) -> Result<annotated::sampler::Sampler, SemanticAnalysisError> {
+    let mut filter = Filter::Linear;
+    let mut address = AddressMode::Wrap;
+    let mut compare = None;
+
+    for (state, value) in states {
+        match state.as_str() {
+            "filter" => match Filter::from_name(&value) {
+                Some(value) => filter = value,
+                None => {
+                    return Err(SemanticAnalysisError::InvalidSamplerState(
+                        state,
+                        value,
+                        "\"point\" or \"linear\"",
+                    ))
+                }
+            },
+            "address" => match AddressMode::from_name(&value) {
+                Some(value) => address = value,
+                None => {
+                    return Err(SemanticAnalysisError::InvalidSamplerState(
+                        state,
+                        value,
+                        "\"wrap\", \"clamp\" or \"mirror\"",
+                    ))
+                }
+            },
+            "compare" => match CompareFunction::from_name(&value) {
+                Some(value) => compare = Some(value),
+                None => {
+                    return Err(SemanticAnalysisError::InvalidSamplerState(
+                        state,
+                        value,
+                        "\"never\", \"less\", \"equal\", \"less_equal\", \"greater\", \"not_equal\", \"greater_equal\" or \"always\"",
+                    ))
+                }
+            },
+            _ => return Err(SemanticAnalysisError::UnknownSamplerState(state)),
+        }
+    }
+
+    if space != 0 {
+        output_tree.require_feature(Feature::RegisterSpace);
+    }
+
+    annotated::sampler::Sampler::new(
+        name,
+        slot,
+        space,
+        filter,
+        address,
+        compare,
+        output_tree.max_samplers(),
+    )
+}
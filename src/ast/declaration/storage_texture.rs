@@ -0,0 +1,62 @@
+use super::{parse_binding, Declaration};
+use crate::{
+    annotated::{self, AnnotatedSyntaxTree},
+    ast::SemanticAnalysisError,
+    feature::Feature,
+    next_token,
+    parser::ParserError,
+    stream::Stream,
+    tokens::TokenClass,
+    types::Type,
+};
+
+// Parses "<type[, format]> name : slot;" following "rwtexture"
+pub fn parse_storage_texture(stream: &mut Stream) -> Result<Declaration, ParserError> {
+    next_token!(stream, TokenClass::LeftAngleBracket => {});
+    let texel_type =
+        next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+    let format = next_token!(stream,
+        TokenClass::RightAngleBracket => {None},
+        TokenClass::Comma => {
+            let format = next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+            next_token!(stream, TokenClass::RightAngleBracket => {});
+            Some(format)
+        }
+    );
+
+    let name = next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+
+    next_token!(stream, TokenClass::Colon => {});
+
+    let (slot, space) = parse_binding(stream)?;
+
+    Ok(Declaration::StorageTexture(
+        name, slot, space, texel_type, format,
+    ))
+}
+
+pub fn semantic_analysis(
+    name: String,
+    slot: usize,
+    space: usize,
+    texel_type: String,
+    format: Option<String>,
+    output_tree: &AnnotatedSyntaxTree,
+) -> Result<annotated::storage_texture::StorageTexture, SemanticAnalysisError> {
+    let texel_type = Type::from_name(&texel_type, output_tree)?;
+
+    if space != 0 {
+        output_tree.require_feature(Feature::RegisterSpace);
+    }
+
+    output_tree.require_feature(Feature::StorageTextures);
+
+    annotated::storage_texture::StorageTexture::new(
+        name,
+        slot,
+        space,
+        texel_type,
+        format,
+        output_tree.max_storage_textures(),
+    )
+}
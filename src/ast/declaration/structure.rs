@@ -4,6 +4,7 @@ use crate::{
     ast::SemanticAnalysisError,
     next_token,
     parser::ParserError,
+    semantic::{Interpolation, Semantic},
     stream::Stream,
     tokens::TokenClass,
     types::Type,
@@ -16,8 +17,19 @@ pub fn parse_struct(stream: &mut Stream) -> Result<Declaration, ParserError> {
 
     let mut members = Vec::new();
     loop {
-        let name = next_token!(stream,
-            TokenClass::Identifier(identifier) => {identifier.to_owned()},
+        // Members can have an attribute, as in "#[flat]"
+        let (name, attribute) = next_token!(stream,
+            TokenClass::Identifier(identifier) => {(identifier.to_owned(), None)},
+            TokenClass::Hash => {
+                next_token!(stream, TokenClass::OpenSquareBracket => {});
+                let attribute =
+                    next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+                next_token!(stream, TokenClass::CloseSquareBracket => {});
+
+                let name =
+                    next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+                (name, Some(attribute))
+            },
             TokenClass::CloseCurlyBrace => {break}
         );
 
@@ -28,11 +40,11 @@ pub fn parse_struct(stream: &mut Stream) -> Result<Declaration, ParserError> {
 
         next_token!(stream,
             TokenClass::CloseCurlyBrace => {
-                members.push((name, type_name, None));
+                members.push((name, type_name, None, attribute));
                 break;
             },
             TokenClass::Comma => {
-                members.push((name, type_name, None));
+                members.push((name, type_name, None, attribute));
                 continue;
             },
             TokenClass::Colon => {}
@@ -41,7 +53,7 @@ pub fn parse_struct(stream: &mut Stream) -> Result<Declaration, ParserError> {
         let semantic =
             next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
 
-        members.push((name, type_name, Some(semantic)));
+        members.push((name, type_name, Some(semantic), attribute));
 
         next_token!(stream,
             TokenClass::CloseCurlyBrace => {break},
@@ -55,11 +67,12 @@ pub fn parse_struct(stream: &mut Stream) -> Result<Declaration, ParserError> {
 pub fn semantic_analysis(
     output_tree: &AnnotatedSyntaxTree,
     name: String,
-    members: Vec<(String, String, Option<String>)>,
+    members: Vec<(String, String, Option<String>, Option<String>)>,
 ) -> Result<annotated::structure::Struct, SemanticAnalysisError> {
     // Verify unique names & types
     let mut s_members = Vec::with_capacity(members.len());
     let mut semantics: Option<Vec<String>> = None;
+    let mut interpolations = Vec::with_capacity(members.len());
 
     for i in 0..members.len() {
         for j in i + 1..members.len() {
@@ -70,6 +83,52 @@ pub fn semantic_analysis(
 
         let member_type = Type::from_name(&members[i].1, output_tree)?;
 
+        // The pipeline reads and writes system values with a fixed type
+        let system_value_type = members[i]
+            .2
+            .as_deref()
+            .and_then(Semantic::parse)
+            .and_then(|semantic| semantic.value_type());
+        if let Some((expected_type, expected)) = system_value_type {
+            if member_type != expected_type {
+                return Err(SemanticAnalysisError::InvalidSystemValueType(
+                    members[i].2.clone().unwrap(),
+                    member_type.to_string(),
+                    expected,
+                ));
+            }
+        }
+
+        // Only members with a semantic can be passed between the stages to be
+        // interpolated, integers never are
+        let interpolation = match &members[i].3 {
+            Some(attribute) => {
+                let interpolation = Interpolation::parse(attribute)
+                    .ok_or_else(|| SemanticAnalysisError::UnknownAttribute(attribute.clone()))?;
+                if members[i].2.is_none() {
+                    return Err(SemanticAnalysisError::InvalidInterpolation(
+                        name,
+                        members[i].0.clone(),
+                        interpolation,
+                    ));
+                }
+                if member_type.is_integer() && interpolation != Interpolation::Flat {
+                    return Err(SemanticAnalysisError::IntegerInterpolation(
+                        name,
+                        members[i].0.clone(),
+                        interpolation,
+                    ));
+                }
+                if let Some(feature) = interpolation.feature() {
+                    output_tree.require_feature(feature);
+                }
+
+                Some(interpolation)
+            }
+            None => None,
+        };
+        interpolations.push(interpolation);
+
         s_members.push((members[i].0.clone(), member_type));
 
         match &members[i].2 {
@@ -92,6 +151,9 @@ pub fn semantic_analysis(
     }
 
     Ok(annotated::structure::Struct::new(
-        name, s_members, semantics,
+        name,
+        s_members,
+        semantics,
+        interpolations,
     ))
 }
@@ -1,15 +1,21 @@
-use super::Declaration;
+use super::{parse_binding, Declaration};
 use crate::{
     annotated::{self, AnnotatedSyntaxTree},
     ast::SemanticAnalysisError,
+    feature::Feature,
     next_token,
     parser::ParserError,
     stream::Stream,
     tokens::TokenClass,
-    types::Type,
+    types::{TextureDimension, Type},
 };
 
-pub fn parse_texture(stream: &mut Stream) -> Result<Declaration, ParserError> {
+// Parses "<type> name : slot;" following "texture" or one of the other
+// dimensions, the type defaults to "float4"
+pub fn parse_texture(
+    stream: &mut Stream,
+    dimension: TextureDimension,
+) -> Result<Declaration, ParserError> {
     let (name, texture_type) = next_token!(stream,
         TokenClass::Identifier(identifier) => {(identifier.to_owned(), "float4".to_owned())},
         TokenClass::LeftAngleBracket => {
@@ -21,20 +27,41 @@ pub fn parse_texture(stream: &mut Stream) -> Result<Declaration, ParserError> {
 
     next_token!(stream, TokenClass::Colon => {});
 
-    let slot = next_token!(stream, TokenClass::IntegerLiteral(slot) => {*slot});
+    let (slot, space) = parse_binding(stream)?;
 
-    next_token!(stream, TokenClass::SemiColon => {});
+    Ok(Declaration::Texture(
+        name,
+        slot,
+        space,
+        texture_type,
+        dimension,
+    ))
+}
+
+// Parses "name : slot;" following "depth_texture" or one of the other depth
+// dimensions, depth textures always hold a single "float"
+pub fn parse_depth_texture(
+    stream: &mut Stream,
+    dimension: TextureDimension,
+) -> Result<Declaration, ParserError> {
+    let name = next_token!(stream, TokenClass::Identifier(identifier) => {identifier.to_owned()});
+
+    next_token!(stream, TokenClass::Colon => {});
+
+    let (slot, space) = parse_binding(stream)?;
 
-    Ok(Declaration::Texture(name, slot, texture_type))
+    Ok(Declaration::DepthTexture(name, slot, space, dimension))
 }
 
 pub fn semantic_analysis(
     name: String,
     slot: usize,
+    space: usize,
     texture_type: String,
+    dimension: TextureDimension,
     output_tree: &AnnotatedSyntaxTree,
 ) -> Result<annotated::texture::Texture, SemanticAnalysisError> {
-    let texture_type = Type::from_name(&texture_type, &output_tree)?;
+    let texture_type = Type::from_name(&texture_type, output_tree)?;
 
     if !texture_type.is_float_vector() && !texture_type.is_uint() && !texture_type.is_float() {
         return Err(SemanticAnalysisError::InvalidTextureType(
@@ -42,5 +69,43 @@ pub fn semantic_analysis(
         ));
     }
 
-    annotated::texture::Texture::new(name, slot, texture_type)
+    if space != 0 {
+        output_tree.require_feature(Feature::RegisterSpace);
+    }
+
+    if let Some(feature) = dimension.required_feature() {
+        output_tree.require_feature(feature);
+    }
+
+    annotated::texture::Texture::new(
+        name,
+        slot,
+        space,
+        texture_type,
+        dimension,
+        false,
+        output_tree.max_textures(),
+    )
+}
+
+pub fn depth_semantic_analysis(
+    name: String,
+    slot: usize,
+    space: usize,
+    dimension: TextureDimension,
+    output_tree: &AnnotatedSyntaxTree,
+) -> Result<annotated::texture::Texture, SemanticAnalysisError> {
+    if space != 0 {
+        output_tree.require_feature(Feature::RegisterSpace);
+    }
+
+    annotated::texture::Texture::new(
+        name,
+        slot,
+        space,
+        Type::float(),
+        dimension,
+        true,
+        output_tree.max_textures(),
+    )
 }
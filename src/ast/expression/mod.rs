@@ -12,8 +12,10 @@ pub enum Expression {
     Variable(String),
     FunctionCall(String, Vec<Expression>),
     FloatLiteral(f64),
+    UintLiteral(usize),
     StructCreation(String, Vec<(String, Expression)>),
     MemberAccess(Box<Expression>, String),
+    Index(Box<Expression>, Box<Expression>),
     Empty,
 
     // Multiplicative Expressions
@@ -33,6 +35,29 @@ impl Expression {
         additive::parse(stream)
     }
 
+    // Parses the parameters of a call whose name and "(" were already read
+    pub fn parse_function_call(
+        stream: &mut Stream,
+        name: &str,
+    ) -> Result<(Self, Token), ParserError> {
+        primary::function_call::parse(stream, name)
+    }
+
+    // Parses the index of an element whose "[" was already read, through the "]"
+    pub fn parse_index(stream: &mut Stream) -> Result<Self, ParserError> {
+        primary::index::parse_index(stream)
+    }
+
+    // The type of the element of "buffer_type" at "index"
+    pub fn index_type(
+        output_tree: &AnnotatedSyntaxTree,
+        scope: &Scope,
+        buffer_type: &Type,
+        index: &Expression,
+    ) -> Result<Type, SemanticAnalysisError> {
+        primary::index::element_type(output_tree, scope, buffer_type, index)
+    }
+
     pub fn get_type(
         &self,
         output_tree: &AnnotatedSyntaxTree,
@@ -43,14 +68,21 @@ impl Expression {
             Expression::Variable(name) => scope
                 .get_variable(name)
                 .map(|variable_type| variable_type.0.clone()),
-            Expression::FunctionCall(name, _) => {
-                Ok(output_tree.get_function(name)?.return_type().clone())
+            Expression::FunctionCall(name, parameters) => {
+                primary::function_call::return_type(output_tree, scope, name, parameters)
             }
             Expression::FloatLiteral(_) => Ok(Type::float()),
+            Expression::UintLiteral(_) => Ok(Type::uint()),
             Expression::StructCreation(name, _) => output_tree.get_type(name),
             Expression::MemberAccess(expression, member) => {
                 expression.get_type(output_tree, scope)?.member_type(member)
             }
+            Expression::Index(expression, index) => primary::index::element_type(
+                output_tree,
+                scope,
+                &expression.get_type(output_tree, scope)?,
+                index,
+            ),
             Expression::Multiply(left_expression, right_expression) => left_expression
                 .get_type(output_tree, scope)?
                 .product_type(&right_expression.get_type(output_tree, scope)?),
@@ -63,6 +95,28 @@ impl Expression {
         }
     }
 
+    // Atomic operations are statements in HLSL, so they are only valid on their
+    // own or as the value of a variable definition
+    pub fn statement_semantic_analysis(
+        self,
+        output_tree: &AnnotatedSyntaxTree,
+        scope: &Scope,
+    ) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+        match self {
+            Expression::FunctionCall(name, parameters)
+                if primary::function_call::is_atomic(&name) =>
+            {
+                primary::function_call::atomic_semantic_analysis(
+                    output_tree,
+                    scope,
+                    name,
+                    parameters,
+                )
+            }
+            expression => expression.semantic_analysis(output_tree, scope),
+        }
+    }
+
     pub fn semantic_analysis(
         self,
         output_tree: &AnnotatedSyntaxTree,
@@ -72,6 +126,7 @@ impl Expression {
             Expression::Empty => primary::empty::semantic_analysis(),
             Expression::Variable(variable) => primary::variable::semantic_analysis(scope, variable),
             Expression::FloatLiteral(value) => primary::float_literal::semantic_analysis(value),
+            Expression::UintLiteral(value) => primary::uint_literal::semantic_analysis(value),
             Expression::FunctionCall(name, parameters) => {
                 primary::function_call::semantic_analysis(output_tree, scope, name, parameters)
             }
@@ -86,6 +141,9 @@ impl Expression {
                     member_name,
                 )
             }
+            Expression::Index(expression, index) => {
+                primary::index::semantic_analysis(output_tree, scope, *expression, *index)
+            }
             Expression::Multiply(left_expression, right_expression) => {
                 multiplicative::multiply_semantic_analysis(
                     output_tree,
@@ -131,6 +189,7 @@ impl std::fmt::Display for Expression {
                 write!(f, ")")
             }
             Expression::FloatLiteral(value) => write!(f, "{}", value),
+            Expression::UintLiteral(value) => write!(f, "{}", value),
             Expression::StructCreation(name, members) => {
                 write!(f, "{} {{", name)?;
 
@@ -147,6 +206,7 @@ impl std::fmt::Display for Expression {
             Expression::MemberAccess(variable_name, member_name) => {
                 write!(f, "{}.{}", variable_name, member_name)
             }
+            Expression::Index(expression, index) => write!(f, "{}[{}]", expression, index),
             Expression::Multiply(left_expression, right_expression) => {
                 write!(f, "({} * {})", left_expression, right_expression)
             }
@@ -1,10 +1,12 @@
 use crate::{
     annotated::{self, AnnotatedSyntaxTree},
     ast::{expression::Expression, scope::Scope, SemanticAnalysisError},
+    feature::Feature,
     lexer,
     parser::ParserError,
     stream::Stream,
     tokens::{Token, TokenClass},
+    types::{TextureDimension, Type},
 };
 
 pub fn parse(stream: &mut Stream, identifier: &str) -> Result<(Expression, Token), ParserError> {
@@ -13,7 +15,11 @@ pub fn parse(stream: &mut Stream, identifier: &str) -> Result<(Expression, Token
     loop {
         let (parameter, next_token) = Expression::parse(stream)?;
 
-        parameters.push(parameter);
+        // An empty argument list has no parameters
+        match parameter {
+            Expression::Empty if parameters.is_empty() => {}
+            parameter => parameters.push(parameter),
+        }
 
         match next_token.class() {
             TokenClass::Comma => {}
@@ -31,12 +37,57 @@ pub fn parse(stream: &mut Stream, identifier: &str) -> Result<(Expression, Token
     }
 }
 
+pub fn return_type(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    name: &str,
+    parameters: &[Expression],
+) -> Result<Type, SemanticAnalysisError> {
+    if name == "store" || is_stream_function(name) {
+        Ok(Type::void())
+    } else if is_atomic(name) {
+        // The previous value of the target
+        match parameters.first() {
+            Some(target) => target.get_type(output_tree, scope),
+            None => Ok(Type::uint()),
+        }
+    } else if name == "load" && is_storage_texture_call(output_tree, scope, parameters)? {
+        let storage_texture_type = parameters[0].get_type(output_tree, scope)?;
+        Ok(storage_texture_type.storage_texel().unwrap().clone())
+    } else if let Some(texture_type) = texture_call_type(output_tree, scope, name, parameters)? {
+        verify_texture_operation(name, &texture_type)?;
+        Ok(texture_return_type(name, &texture_type))
+    } else {
+        Ok(output_tree.get_function(name)?.return_type().clone())
+    }
+}
+
 pub fn semantic_analysis(
     output_tree: &AnnotatedSyntaxTree,
     scope: &Scope,
     name: String,
     parameters: Vec<Expression>,
 ) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+    // Storage textures are generic over their texel type
+    if name == "store"
+        || (name == "load" && is_storage_texture_call(output_tree, scope, &parameters)?)
+    {
+        return storage_texture_call(output_tree, scope, name, parameters);
+    }
+
+    if is_atomic(&name) {
+        return Err(SemanticAnalysisError::AtomicInExpression(name));
+    }
+
+    if is_stream_function(&name) {
+        return stream_call(output_tree, scope, name, parameters);
+    }
+
+    // The parameters depend on the dimension and element type of the texture
+    if let Some(texture_type) = texture_call_type(output_tree, scope, &name, &parameters)? {
+        return texture_call(output_tree, scope, name, parameters, texture_type);
+    }
+
     // Verify function existance
     let function = output_tree.get_function(&name)?;
 
@@ -69,9 +120,543 @@ pub fn semantic_analysis(
         i += 1;
     }
 
+    if let Some(feature) = function.required_feature() {
+        output_tree.require_feature(feature);
+    }
+
     Ok(annotated::expression::Expression::FunctionCall(
         name,
         annoted_parameters,
         function.return_type().clone(),
     ))
 }
+
+// How a texture function takes a sampler declaration after the texture
+#[derive(Clone, Copy, PartialEq)]
+enum SamplerParameter {
+    None,
+    Optional,
+    Required,
+    Comparison,
+}
+
+// The parameters of a texture function following the texture and sampler
+#[derive(Clone, Copy, PartialEq)]
+enum TextureParameter {
+    // "float" coordinates, with the array layer last
+    Coordinates,
+    Float,
+    // A derivative of the coordinates, without the array layer
+    Gradient,
+    // A constant texel offset
+    Offset,
+    // "uint" texel coordinates, with the array layer last
+    TexelCoordinates,
+    // An optional "uint" mip level, the last parameter
+    MipLevel,
+}
+
+fn texture_function(name: &str) -> Option<(SamplerParameter, &'static [TextureParameter])> {
+    use TextureParameter::*;
+
+    Some(match name {
+        "sample_texture" => (SamplerParameter::None, &[Coordinates]),
+        "sample" => (SamplerParameter::Required, &[Coordinates]),
+        "sample_level" | "sample_bias" => (SamplerParameter::Optional, &[Coordinates, Float]),
+        "sample_grad" => (
+            SamplerParameter::Optional,
+            &[Coordinates, Gradient, Gradient],
+        ),
+        "sample_offset" => (SamplerParameter::Optional, &[Coordinates, Offset]),
+        "sample_compare" | "sample_compare_level_zero" => {
+            (SamplerParameter::Comparison, &[Coordinates, Float])
+        }
+        "gather" => (SamplerParameter::Optional, &[Coordinates]),
+        "texture_size" => (SamplerParameter::None, &[]),
+        "load" => (SamplerParameter::None, &[TexelCoordinates, MipLevel]),
+        _ => return None,
+    })
+}
+
+// The type of the texture passed to a texture function, user functions can
+// share the names of texture functions as textures can't be passed to them
+fn texture_call_type(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    name: &str,
+    parameters: &[Expression],
+) -> Result<Option<Type>, SemanticAnalysisError> {
+    if texture_function(name).is_none() {
+        return Ok(None);
+    }
+
+    let texture_type = match parameters.first() {
+        Some(parameter) => parameter.get_type(output_tree, scope)?,
+        None => Type::void(),
+    };
+
+    if texture_type.texture_dimension().is_some() {
+        Ok(Some(texture_type))
+    } else if output_tree.get_function(name).is_ok() {
+        Ok(None)
+    } else {
+        Err(SemanticAnalysisError::InvalidParameterType(
+            name.to_owned(),
+            0,
+            texture_type.to_string(),
+            "texture".to_owned(),
+        ))
+    }
+}
+
+// Depth textures can only be compared against, the other checks depend on
+// the dimension and element type of the texture
+fn verify_texture_operation(name: &str, texture_type: &Type) -> Result<(), SemanticAnalysisError> {
+    let dimension = texture_type.texture_dimension().unwrap();
+    let element_type = texture_type.texture_element().unwrap();
+    let depth = texture_type.is_depth_texture();
+
+    if depth
+        && !matches!(
+            name,
+            "sample_compare" | "sample_compare_level_zero" | "texture_size"
+        )
+    {
+        return Err(SemanticAnalysisError::UnsupportedTextureOperation(
+            name.to_owned(),
+            texture_type.to_string(),
+        ));
+    }
+
+    // GLSL has no way to sample cube map arrays at a level with a comparison
+    let valid_dimension = match name {
+        "load" => dimension.load_coordinates().is_some(),
+        "sample_offset" => dimension.offset_components().is_some(),
+        "gather" | "sample_compare" => dimension.is_2d_or_cube(),
+        "sample_compare_level_zero" => {
+            dimension.is_2d_or_cube() && dimension != TextureDimension::CubeArray
+        }
+        _ => true,
+    };
+    if !valid_dimension {
+        return Err(SemanticAnalysisError::UnsupportedTextureOperation(
+            name.to_owned(),
+            dimension.keyword().to_owned(),
+        ));
+    }
+
+    // Filtering needs float elements, comparisons read the first one
+    let valid_element = match name {
+        "load" | "texture_size" => true,
+        _ => element_type.is_float() || element_type.is_float_vector(),
+    };
+    if !valid_element {
+        return Err(SemanticAnalysisError::UnsupportedTextureOperation(
+            name.to_owned(),
+            texture_type.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Sampling and "load" return the element type, gathers return the first
+// component of four texels
+fn texture_return_type(name: &str, texture_type: &Type) -> Type {
+    let dimension = texture_type.texture_dimension().unwrap();
+
+    match name {
+        "gather" => Type::float4(),
+        "sample_compare" | "sample_compare_level_zero" => Type::float(),
+        "texture_size" => uint_vector(dimension.size_components()),
+        _ => texture_type.texture_element().unwrap(),
+    }
+}
+
+fn uint_vector(components: usize) -> Type {
+    match components {
+        1 => Type::uint(),
+        n => Type::uintn(n),
+    }
+}
+
+// Texture functions take the texture first, the "sample" functions may then
+// take a sampler declaration instead of the texture's own sampler. Depth
+// textures come with a comparison sampler and can only be compared against.
+fn texture_call(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    name: String,
+    parameters: Vec<Expression>,
+    texture_type: Type,
+) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+    let (sampler_parameter, texture_parameters) = texture_function(&name).unwrap();
+    let dimension = texture_type.texture_dimension().unwrap();
+    let depth = texture_type.is_depth_texture();
+
+    verify_texture_operation(&name, &texture_type)?;
+
+    let explicit_sampler = match sampler_parameter {
+        SamplerParameter::None => false,
+        SamplerParameter::Optional => match parameters.get(1) {
+            Some(parameter) => parameter.get_type(output_tree, scope)?.is_sampler(),
+            None => false,
+        },
+        SamplerParameter::Comparison if depth => match parameters.get(1) {
+            Some(parameter) => parameter.get_type(output_tree, scope)?.is_sampler(),
+            None => false,
+        },
+        SamplerParameter::Required | SamplerParameter::Comparison => true,
+    };
+
+    let mut expected_types = vec![texture_type.clone()];
+    if explicit_sampler {
+        expected_types.push(match sampler_parameter {
+            SamplerParameter::Comparison => Type::comparison_sampler(),
+            _ => Type::sampler(),
+        });
+    }
+    let mut offset_index = None;
+    for texture_parameter in texture_parameters {
+        let expected_type = match texture_parameter {
+            TextureParameter::Coordinates => float_vector(dimension.sample_coordinates()),
+            TextureParameter::Float => Type::float(),
+            TextureParameter::Gradient => float_vector(dimension.gradient_components()),
+            TextureParameter::Offset => {
+                offset_index = Some(expected_types.len());
+                float_vector(dimension.offset_components().unwrap())
+            }
+            TextureParameter::TexelCoordinates => {
+                uint_vector(dimension.load_coordinates().unwrap())
+            }
+            TextureParameter::MipLevel => Type::uint(),
+        };
+        expected_types.push(expected_type);
+    }
+
+    let optional_count = match texture_parameters.last() {
+        Some(TextureParameter::MipLevel) => 1,
+        _ => 0,
+    };
+    if parameters.len() > expected_types.len()
+        || parameters.len() + optional_count < expected_types.len()
+    {
+        return Err(SemanticAnalysisError::InvalidParameterCount(
+            name,
+            parameters.len(),
+            expected_types.len(),
+        ));
+    }
+
+    // The reflection lists the textures sampled with each sampler, a texture
+    // sampled without one uses the sampler declared alongside it, which is a
+    // comparison sampler for depth textures
+    match &parameters[..] {
+        [Expression::Variable(texture), Expression::Variable(sampler), ..] if explicit_sampler => {
+            output_tree.use_sampler(texture, sampler)
+        }
+        [Expression::Variable(texture), ..]
+            if !matches!(name.as_str(), "load" | "texture_size") =>
+        {
+            output_tree.use_sampler(texture, &format!("acsl_{}_sampler_state", texture))
+        }
+        _ => {}
+    }
+
+    if name == "texture_size" {
+        if let Expression::Variable(texture) = &parameters[0] {
+            output_tree.use_texture_size(texture);
+        }
+    }
+
+    let mut annotated_parameters = Vec::new();
+    for (i, parameter) in parameters.into_iter().enumerate() {
+        let parameter_type = parameter.get_type(output_tree, scope)?;
+
+        if parameter_type != expected_types[i] {
+            return Err(SemanticAnalysisError::InvalidParameterType(
+                name,
+                i,
+                parameter_type.to_string(),
+                expected_types[i].to_string(),
+            ));
+        }
+
+        if offset_index == Some(i) {
+            annotated_parameters.push(constant_offset(&parameter, parameter_type)?);
+        } else {
+            annotated_parameters.push(parameter.semantic_analysis(output_tree, scope)?);
+        }
+    }
+
+    match name.as_str() {
+        "gather" => output_tree.require_feature(Feature::TextureGather),
+        "sample_compare" | "sample_compare_level_zero" if !depth => {
+            output_tree.require_feature(Feature::ColorTextureComparison)
+        }
+        _ => {}
+    }
+
+    let return_type = texture_return_type(&name, &texture_type);
+    Ok(annotated::expression::Expression::FunctionCall(
+        name,
+        annotated_parameters,
+        return_type,
+    ))
+}
+
+fn float_vector(components: usize) -> Type {
+    match components {
+        1 => Type::float(),
+        n => Type::floatn(n),
+    }
+}
+
+// Texel offsets are immediate operands on every target, so they must be built
+// from literals and are folded into whole numbers
+fn constant_offset(
+    offset: &Expression,
+    offset_type: Type,
+) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+    let components = match offset {
+        Expression::FunctionCall(_, components) => components
+            .iter()
+            .map(constant_value)
+            .collect::<Option<Vec<f64>>>(),
+        _ => None,
+    };
+
+    match components {
+        Some(components)
+            if components
+                .iter()
+                .all(|component| component.fract() == 0.0 && (-8.0..=7.0).contains(component)) =>
+        {
+            Ok(annotated::expression::Expression::FunctionCall(
+                offset_type.to_string(),
+                components
+                    .into_iter()
+                    .map(annotated::expression::Expression::FloatLiteral)
+                    .collect(),
+                offset_type,
+            ))
+        }
+        _ => Err(SemanticAnalysisError::InvalidTextureOffset(
+            offset.to_string(),
+        )),
+    }
+}
+
+fn constant_value(expression: &Expression) -> Option<f64> {
+    match expression {
+        Expression::FloatLiteral(value) => Some(*value),
+        Expression::Add(left, right) => Some(constant_value(left)? + constant_value(right)?),
+        Expression::Subtract(left, right) => Some(constant_value(left)? - constant_value(right)?),
+        Expression::Multiply(left, right) => Some(constant_value(left)? * constant_value(right)?),
+        _ => None,
+    }
+}
+
+fn is_storage_texture_call(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    parameters: &[Expression],
+) -> Result<bool, SemanticAnalysisError> {
+    match parameters.first() {
+        Some(parameter) => Ok(parameter
+            .get_type(output_tree, scope)?
+            .storage_texel()
+            .is_some()),
+        None => Ok(false),
+    }
+}
+
+// "load(texture, coordinates)" returns the texel and "store(texture,
+// coordinates, texel)" writes it, the coordinates are a "uint2"
+fn storage_texture_call(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    name: String,
+    parameters: Vec<Expression>,
+) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+    let expected_count = if name == "store" { 3 } else { 2 };
+    if parameters.len() != expected_count {
+        return Err(SemanticAnalysisError::InvalidParameterCount(
+            name,
+            parameters.len(),
+            expected_count,
+        ));
+    }
+
+    let storage_texture_type = parameters[0].get_type(output_tree, scope)?;
+    let texel_type = match storage_texture_type.storage_texel() {
+        Some(texel_type) => texel_type.clone(),
+        None => {
+            return Err(SemanticAnalysisError::InvalidParameterType(
+                name,
+                0,
+                storage_texture_type.to_string(),
+                "rwtexture".to_owned(),
+            ))
+        }
+    };
+
+    let expected_types = [storage_texture_type, Type::uintn(2), texel_type.clone()];
+
+    let mut annotated_parameters = Vec::new();
+    for (i, parameter) in parameters.into_iter().enumerate() {
+        let parameter_type = parameter.get_type(output_tree, scope)?;
+
+        if parameter_type != expected_types[i] {
+            return Err(SemanticAnalysisError::InvalidParameterType(
+                name,
+                i,
+                parameter_type.to_string(),
+                expected_types[i].to_string(),
+            ));
+        }
+
+        annotated_parameters.push(parameter.semantic_analysis(output_tree, scope)?);
+    }
+
+    let return_type = if name == "store" {
+        Type::void()
+    } else {
+        texel_type
+    };
+
+    Ok(annotated::expression::Expression::FunctionCall(
+        name,
+        annotated_parameters,
+        return_type,
+    ))
+}
+
+// "emit(stream, vertex)" appends a vertex to the primitive being built by
+// "geometry_main()", "end_primitive(stream)" starts the next one
+fn stream_call(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    name: String,
+    parameters: Vec<Expression>,
+) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+    let expected_count = if name == "emit" { 2 } else { 1 };
+    if parameters.len() != expected_count {
+        return Err(SemanticAnalysisError::InvalidParameterCount(
+            name,
+            parameters.len(),
+            expected_count,
+        ));
+    }
+
+    let stream_type = parameters[0].get_type(output_tree, scope)?;
+    let vertex_type = match &stream_type {
+        Type::Stream(vertex_type, _) => vertex_type.as_ref().clone(),
+        _ => {
+            return Err(SemanticAnalysisError::InvalidParameterType(
+                name,
+                0,
+                stream_type.to_string(),
+                "triangle_stream".to_owned(),
+            ))
+        }
+    };
+
+    let expected_types = [stream_type, vertex_type];
+
+    let mut annotated_parameters = Vec::new();
+    for (i, parameter) in parameters.into_iter().enumerate() {
+        let parameter_type = parameter.get_type(output_tree, scope)?;
+
+        if parameter_type != expected_types[i] {
+            return Err(SemanticAnalysisError::InvalidParameterType(
+                name,
+                i,
+                parameter_type.to_string(),
+                expected_types[i].to_string(),
+            ));
+        }
+
+        annotated_parameters.push(parameter.semantic_analysis(output_tree, scope)?);
+    }
+
+    Ok(annotated::expression::Expression::FunctionCall(
+        name,
+        annotated_parameters,
+        Type::void(),
+    ))
+}
+
+fn is_stream_function(name: &str) -> bool {
+    name == "emit" || name == "end_primitive"
+}
+
+pub fn is_atomic(name: &str) -> bool {
+    annotated::expression::is_atomic(name)
+}
+
+// Atomic operations take the target followed by their values and return the
+// previous value of the target, which must be a "uint" or "int" element of a
+// "rwbuffer" or a "uint" or "int" group shared variable
+pub fn atomic_semantic_analysis(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    name: String,
+    parameters: Vec<Expression>,
+) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+    let expected_count = if name == "atomic_compare_exchange" {
+        3
+    } else {
+        2
+    };
+    if parameters.len() != expected_count {
+        return Err(SemanticAnalysisError::InvalidParameterCount(
+            name,
+            parameters.len(),
+            expected_count,
+        ));
+    }
+
+    let target_type = match &parameters[0] {
+        Expression::Index(buffer, _) => match buffer.get_type(output_tree, scope)?.buffer_element()
+        {
+            Some((element_type, true)) => Some(element_type.clone()),
+            _ => None,
+        },
+        Expression::Variable(variable) if output_tree.is_group_shared(variable) => {
+            Some(scope.get_variable(variable)?.0.clone())
+        }
+        _ => None,
+    };
+    let target_type = match target_type {
+        Some(target_type) if target_type.is_uint() || target_type.is_int() => target_type,
+        _ => {
+            return Err(SemanticAnalysisError::InvalidAtomicTarget(
+                name,
+                parameters[0].to_string(),
+            ))
+        }
+    };
+
+    let mut annotated_parameters = Vec::new();
+    for (i, parameter) in parameters.into_iter().enumerate() {
+        let parameter_type = parameter.get_type(output_tree, scope)?;
+
+        if parameter_type != target_type {
+            return Err(SemanticAnalysisError::InvalidParameterType(
+                name,
+                i,
+                parameter_type.to_string(),
+                target_type.to_string(),
+            ));
+        }
+
+        annotated_parameters.push(parameter.semantic_analysis(output_tree, scope)?);
+    }
+
+    Ok(annotated::expression::Expression::FunctionCall(
+        name,
+        annotated_parameters,
+        target_type,
+    ))
+}
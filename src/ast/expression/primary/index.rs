@@ -0,0 +1,78 @@
+use crate::{
+    annotated::{self, AnnotatedSyntaxTree},
+    ast::{expression::Expression, scope::Scope, SemanticAnalysisError},
+    lexer,
+    parser::ParserError,
+    stream::Stream,
+    tokens::{Token, TokenClass},
+    types::Type,
+};
+
+// Parses the index following "[" up to and including the "]"
+pub fn parse_index(stream: &mut Stream) -> Result<Expression, ParserError> {
+    let (index, next_token) = Expression::parse(stream)?;
+
+    match next_token.class() {
+        TokenClass::CloseSquareBracket => Ok(index),
+        _ => Err(ParserError::UnexpectedToken(next_token)),
+    }
+}
+
+pub fn parse(
+    stream: &mut Stream,
+    expression: Expression,
+) -> Result<(Expression, Token), ParserError> {
+    let index = parse_index(stream)?;
+
+    let next_token = match lexer::next_token(stream)? {
+        Some(token) => token,
+        None => return Err(ParserError::UnexpectedEOF),
+    };
+
+    Ok((
+        Expression::Index(Box::new(expression), Box::new(index)),
+        next_token,
+    ))
+}
+
+// The type of the element, verifying the expression is a buffer, patch or
+// primitive and the index is a "uint"
+pub fn element_type(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    buffer_type: &Type,
+    index: &Expression,
+) -> Result<Type, SemanticAnalysisError> {
+    let element_type = match buffer_type.buffer_element() {
+        Some((element_type, _)) => element_type.clone(),
+        None => match buffer_type.stage_element() {
+            Some(element_type) => element_type.clone(),
+            None => return Err(SemanticAnalysisError::NotIndexable(buffer_type.to_string())),
+        },
+    };
+
+    let index_type = index.get_type(output_tree, scope)?;
+    if !index_type.is_uint() {
+        return Err(SemanticAnalysisError::InvalidIndexType(
+            index_type.to_string(),
+        ));
+    }
+
+    Ok(element_type)
+}
+
+pub fn semantic_analysis(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    expression: Expression,
+    index: Expression,
+) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+    let buffer_type = expression.get_type(output_tree, scope)?;
+    let element_type = element_type(output_tree, scope, &buffer_type, &index)?;
+
+    Ok(annotated::expression::Expression::Index(
+        Box::new(expression.semantic_analysis(output_tree, scope)?),
+        Box::new(index.semantic_analysis(output_tree, scope)?),
+        element_type,
+    ))
+}
@@ -9,8 +9,10 @@ use crate::{
 pub mod empty;
 pub mod float_literal;
 pub mod function_call;
+pub mod index;
 pub mod member_access;
 pub mod struct_creation;
+pub mod uint_literal;
 pub mod variable;
 
 pub fn parse(stream: &mut Stream) -> Result<(Expression, Token), ParserError> {
@@ -29,6 +31,7 @@ pub fn parse(stream: &mut Stream) -> Result<(Expression, Token), ParserError> {
                 }
             }
             TokenClass::FloatLiteral(value) => float_literal::parse(stream, *value),
+            TokenClass::IntegerLiteral(value) => uint_literal::parse(stream, *value),
             TokenClass::Identifier(identifier) => match lexer::next_token(stream)? {
                 Some(next_token) => match next_token.class() {
                     TokenClass::OpenParenthesis => function_call::parse(stream, identifier),
@@ -48,6 +51,10 @@ pub fn parse(stream: &mut Stream) -> Result<(Expression, Token), ParserError> {
     loop {
         match next_token.class() {
             TokenClass::Period => {}
+            TokenClass::OpenSquareBracket => {
+                (expression, next_token) = index::parse(stream, expression)?;
+                continue;
+            }
             _ => return Ok((expression, next_token)),
         }
 
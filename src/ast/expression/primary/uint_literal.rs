@@ -0,0 +1,25 @@
+use crate::{
+    annotated,
+    ast::{expression::Expression, SemanticAnalysisError},
+    lexer,
+    parser::ParserError,
+    stream::Stream,
+    tokens::Token,
+};
+
+pub fn parse(stream: &mut Stream, value: usize) -> Result<(Expression, Token), ParserError> {
+    match lexer::next_token(stream)? {
+        Some(next_token) => Ok((Expression::UintLiteral(value), next_token)),
+        None => Err(ParserError::UnexpectedEOF),
+    }
+}
+
+pub fn semantic_analysis(
+    value: usize,
+) -> Result<annotated::expression::Expression, SemanticAnalysisError> {
+    if value > u32::MAX as usize {
+        return Err(SemanticAnalysisError::UintLiteralOutOfRange(value));
+    }
+
+    Ok(annotated::expression::Expression::UintLiteral(value as u32))
+}
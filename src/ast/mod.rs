@@ -1,5 +1,10 @@
 use self::declaration::Declaration;
-use crate::annotated::AnnotatedSyntaxTree;
+use crate::{
+    annotated::AnnotatedSyntaxTree,
+    options::CompileOptions,
+    semantic::{Interpolation, Stage},
+    CompilationError,
+};
 
 pub mod code_block;
 pub mod declaration;
@@ -34,14 +39,57 @@ pub enum SemanticAnalysisError {
     InvalidVariableName(String),
     InvalidOperation(String, &'static str, String),
     VariableTypeMismatch(String, String, String),
-    ReusingSlot(&'static str, usize),
+    ReusingSlot(&'static str, usize, usize),
     SlotOutOfRange(&'static str, usize, usize),
     AssigningImmutableVariable(String),
     InvalidTextureType(String),
+    UnsupportedFeature(String, String),
+    UnimplementedFeature(String, String),
+    UnknownAttribute(String),
+    InvalidThreadGroupSize(Vec<usize>),
+    InvalidComputeParameters(String),
+    InvalidComputeReturnType(String, String),
+    InvalidGroupSharedType(String),
+    NotIndexable(String),
+    InvalidIndexType(String),
+    WritingReadOnlyBuffer(String),
+    UnpackedBufferElement(String, String),
+    InvalidStorageTextureType(String),
+    InvalidStorageTextureFormat(String, String),
+    InvalidAtomicTarget(String, String),
+    AtomicInExpression(String),
+    UnsupportedTextureOperation(String, String),
+    UnknownSamplerState(String),
+    InvalidSamplerState(String, String, &'static str),
+    InvalidTextureOffset(String),
+    InvalidRenderTarget(String, String),
+    ReusingRenderTarget(usize),
+    InvalidRenderTargetType(String, String),
+    InvalidSystemValueType(String, String, &'static str),
+    DiscardOutsideFragmentMain(String),
+    InvalidSemantic(String, String, String, Stage),
+    DuplicateSemantic(String, String),
+    PositionSemanticCount(String, usize),
+    InvalidInterpolation(String, String, Interpolation),
+    IntegerInterpolation(String, String, Interpolation),
+    ReusingLocation(String, usize),
+    UintLiteralOutOfRange(usize),
+    InvalidPatchSize(usize),
+    InvalidEntryPoint(String, &'static str),
+    MissingEntryPoint(String, String),
+    InvalidEntryPointName(String),
+    EntryPointNameInUse(String),
+    StageTypeMismatch(String, String, String, String),
+    StageTypeOutsideEntryPoint(String, String),
+    MisplacedAttribute(String, String),
+    InvalidMaxVertexCount(Vec<usize>),
+    InvalidTessFactors(String),
 }
 
+// Each declaration keeps the line and column it starts at, so errors found while
+// analysing it can point back into the source
 pub struct AbstractSyntaxTree {
-    declarations: Vec<Declaration>,
+    declarations: Vec<(Declaration, (usize, usize))>,
 }
 
 impl AbstractSyntaxTree {
@@ -51,18 +99,26 @@ impl AbstractSyntaxTree {
         }
     }
 
-    pub fn push(&mut self, declaration: Declaration) {
-        self.declarations.push(declaration)
+    pub fn push(&mut self, declaration: Declaration, location: (usize, usize)) {
+        self.declarations.push((declaration, location))
     }
 
-    pub fn semantic_analysis(self) -> Result<AnnotatedSyntaxTree, SemanticAnalysisError> {
-        let mut output_tree = AnnotatedSyntaxTree::new();
+    pub fn semantic_analysis(
+        self,
+        options: &CompileOptions,
+    ) -> Result<AnnotatedSyntaxTree, CompilationError> {
+        let mut output_tree = AnnotatedSyntaxTree::new(options);
 
-        for declaration in self.declarations {
-            declaration.semantic_analysis(&mut output_tree)?;
+        for (declaration, location) in self.declarations {
+            declaration
+                .semantic_analysis(&mut output_tree)
+                .map_err(|error| {
+                    CompilationError::SemanticAnalysisError(Box::new(error), Some(location))
+                })?;
         }
 
         output_tree.verify_graphics_functions()?;
+        output_tree.verify_discards()?;
 
         Ok(output_tree)
     }
@@ -72,7 +128,7 @@ impl std::fmt::Display for AbstractSyntaxTree {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Abstract Syntax Tree:")?;
 
-        for declaration in &self.declarations {
+        for (declaration, _) in &self.declarations {
             write!(f, "{}", declaration)?;
         }
 
@@ -120,16 +176,58 @@ impl std::fmt::Display for SemanticAnalysisError {
             SemanticAnalysisError::VertexMainReturnTypeMismatch(actual, expected) => write!(f, "The return type of \"vertex_main()\" must be the same as the parameter type \"fragment_main()\" (\"{}\"), instead it is \"{}\"", expected, actual),
             SemanticAnalysisError::FragmentMainParameterCount => write!(f, "\"fragment_main()\" must take one parameter"),
             SemanticAnalysisError::FragmentMainParameterTypeMismatch(actual, expected) => write!(f, "The parameter for \"fragment_main()\" must be the same as the return type of \"vertex_main()\" (\"{}\"), instead it is \"{}\"", expected, actual),
-            SemanticAnalysisError::InvalidFragmentMainReturnType(actual) => write!(f, "\"fragment_main()\" must return a float4, instead it is \"{}\"", actual),
+            SemanticAnalysisError::InvalidFragmentMainReturnType(actual) => write!(f, "\"fragment_main()\" must return a float4 or a structure of render targets, instead it is \"{}\"", actual),
             SemanticAnalysisError::InvalidFragmentMainParameterType(actual) => write!(f, "The parameter for \"fragment_main()\" must be a structure with semantics, instead it is \"{}\"", actual),
             SemanticAnalysisError::AllFieldsNeedSemantics(structure_name) => write!(f, "All fields require semantics when they are defined, and \"{}\" is missing some", structure_name),
             SemanticAnalysisError::InvalidVariableName(name) => write!(f, "Invalid variable name \"{}\", variables beginning with \"gl_\" or \"acsl_\" are reserved.", name),
             SemanticAnalysisError::InvalidOperation(left_type, op, right_type) => write!(f, "Unable to perform operation \"{}\" on types \"{}\" and \"{}\"", op, left_type, right_type),
             SemanticAnalysisError::VariableTypeMismatch(name, actual, expected) => write!(f, "Attempting to set \"{}\" to type \"{}\" when it has a type of \"{}\"", name, actual, expected),
-            SemanticAnalysisError::ReusingSlot(class, slot) => write!(f, "Reusing {} slot {}", class, slot),
+            SemanticAnalysisError::ReusingSlot(class, slot, 0) => write!(f, "Reusing {} slot {}", class, slot),
+            SemanticAnalysisError::ReusingSlot(class, slot, space) => write!(f, "Reusing {} slot {} in space {}", class, slot, space),
             SemanticAnalysisError::SlotOutOfRange(class, slot, max) => write!(f, "Slot {} is beyond the max slot of {} for {}", slot, max, class),
             SemanticAnalysisError::AssigningImmutableVariable(name) => write!(f, "Attempting to modify immutable variable \"{}\"", name),
             SemanticAnalysisError::InvalidTextureType(name) => write!(f, "Textures is of type \"{}\" but can only be of type \"float\", \"floatn\", or \"uint\"", name),
+            SemanticAnalysisError::UnsupportedFeature(feature, target) => write!(f, "{} does not support {}", target, feature),
+            SemanticAnalysisError::UnimplementedFeature(feature, target) => write!(f, "The {} backend does not yet support {}", target, feature),
+            SemanticAnalysisError::UnknownAttribute(name) => write!(f, "Unknown attribute \"{}\"", name),
+            SemanticAnalysisError::InvalidThreadGroupSize(sizes) => write!(f, "Invalid thread group size {:?}, \"compute\" takes one to three non-zero sizes", sizes),
+            SemanticAnalysisError::InvalidComputeParameters(name) => write!(f, "Compute kernel \"{}\" must take no parameters or a single \"ThreadId\"", name),
+            SemanticAnalysisError::InvalidComputeReturnType(name, actual) => write!(f, "Compute kernel \"{}\" must not return a value, instead it returns \"{}\"", name, actual),
+            SemanticAnalysisError::InvalidGroupSharedType(type_name) => write!(f, "Group shared variables can't be of type \"{}\"", type_name),
+            SemanticAnalysisError::NotIndexable(type_name) => write!(f, "Only buffers, patches and primitives can be indexed, found type \"{}\"", type_name),
+            SemanticAnalysisError::InvalidIndexType(type_name) => write!(f, "Indices must be of type \"uint\", instead found \"{}\"", type_name),
+            SemanticAnalysisError::InvalidStorageTextureType(type_name) => write!(f, "Storage textures are of type \"{}\" but can only be of type \"float\", \"float2\", \"float4\" or \"uint\"", type_name),
+            SemanticAnalysisError::InvalidStorageTextureFormat(format, type_name) => write!(f, "Format \"{}\" can't hold texels of type \"{}\"", format, type_name),
+            SemanticAnalysisError::InvalidAtomicTarget(name, target) => write!(f, "\"{}\" can only operate on a \"uint\" or \"int\" element of a \"rwbuffer\" or group shared variable, found \"{}\"", name, target),
+            SemanticAnalysisError::AtomicInExpression(name) => write!(f, "\"{}\" can only be called on its own or to define a variable", name),
+            SemanticAnalysisError::UnsupportedTextureOperation(name, type_name) => write!(f, "\"{}\" can't be used on textures of type \"{}\"", name, type_name),
+            SemanticAnalysisError::UnknownSamplerState(name) => write!(f, "Unknown sampler state \"{}\", expected \"filter\", \"address\" or \"compare\"", name),
+            SemanticAnalysisError::InvalidSamplerState(name, value, expected) => write!(f, "Sampler state \"{}\" can't be \"{}\", expected {}", name, value, expected),
+            SemanticAnalysisError::WritingReadOnlyBuffer(name) => write!(f, "Attempting to write to read-only buffer \"{}\", only \"rwbuffer\" can be written", name),
+            SemanticAnalysisError::UnpackedBufferElement(name, type_name) => write!(f, "Buffer \"{}\" has elements of type \"{}\", which std430 pads unlike HLSL, use four component vectors and matrix columns", name, type_name),
+            SemanticAnalysisError::InvalidTextureOffset(offset) => write!(f, "Texture offsets must be built from literals with whole values between -8 and 7, found \"{}\"", offset),
+            SemanticAnalysisError::InvalidRenderTarget(structure_name, member_name) => write!(f, "Member \"{}\" of \"{}\" must have a render target semantic from \"SV_TARGET0\" to \"SV_TARGET7\" or \"SV_DEPTH\"", member_name, structure_name),
+            SemanticAnalysisError::ReusingRenderTarget(target) => write!(f, "Reusing render target {}", target),
+            SemanticAnalysisError::InvalidRenderTargetType(member_name, member_type) => write!(f, "Render target \"{}\" can't be of type \"{}\", expected a float or uint scalar or vector", member_name, member_type),
+            SemanticAnalysisError::InvalidSystemValueType(semantic, actual, expected) => write!(f, "Semantic \"{}\" must be of type \"{}\", instead it is \"{}\"", semantic, expected, actual),
+            SemanticAnalysisError::DiscardOutsideFragmentMain(name) => write!(f, "\"discard\" can only be used in \"fragment_main()\" and the functions only it calls, not in \"{}()\"", name),
+            SemanticAnalysisError::InvalidSemantic(structure_name, member_name, semantic, stage) => write!(f, "Member \"{}\" of \"{}\" has the semantic \"{}\", which can't be used in {}", member_name, structure_name, semantic, stage),
+            SemanticAnalysisError::DuplicateSemantic(structure_name, semantic) => write!(f, "Semantic \"{}\" is used more than once in \"{}\"", semantic, structure_name),
+            SemanticAnalysisError::PositionSemanticCount(structure_name, count) => write!(f, "\"{}\" must have exactly one member with the \"SV_POSITION\" semantic, found {}", structure_name, count),
+            SemanticAnalysisError::InvalidInterpolation(structure_name, member_name, interpolation) => write!(f, "Member \"{}\" of \"{}\" can't be \"{}\", only members with a user semantic passed from \"vertex_main()\" to \"fragment_main()\" are interpolated", member_name, structure_name, interpolation),
+            SemanticAnalysisError::IntegerInterpolation(structure_name, member_name, interpolation) => write!(f, "Member \"{}\" of \"{}\" can't be \"{}\", integers are always \"flat\"", member_name, structure_name, interpolation),
+            SemanticAnalysisError::ReusingLocation(structure_name, location) => write!(f, "Location {} is used by more than one member of \"{}\"", location, structure_name),
+            SemanticAnalysisError::UintLiteralOutOfRange(value) => write!(f, "Integer literal {} is too large for a \"uint\"", value),
+            SemanticAnalysisError::InvalidPatchSize(control_points) => write!(f, "Patches hold 1 to 32 control points, found {}", control_points),
+            SemanticAnalysisError::InvalidEntryPoint(name, signature) => write!(f, "\"{}()\" must be declared as \"{}\"", name, signature),
+            SemanticAnalysisError::MissingEntryPoint(name, required_by) => write!(f, "No \"{}()\" function defined, it is required by \"{}()\"", name, required_by),
+            SemanticAnalysisError::InvalidEntryPointName(name) => write!(f, "Invalid entry point name \"{}\", expected an identifier which is not a reserved word and doesn't begin with \"gl_\" or \"acsl_\"", name),
+            SemanticAnalysisError::EntryPointNameInUse(name) => write!(f, "Entry point name \"{}\" is already used by another declaration", name),
+            SemanticAnalysisError::StageTypeMismatch(name, actual, producer, expected) => write!(f, "The input of \"{}()\" must be the output of \"{}()\" (\"{}\"), instead it is \"{}\"", name, producer, expected, actual),
+            SemanticAnalysisError::StageTypeOutsideEntryPoint(name, type_name) => write!(f, "Only the parameters of the stage entry points can be a \"{}\", not \"{}\"", type_name, name),
+            SemanticAnalysisError::MisplacedAttribute(attribute, name) => write!(f, "\"#[{}]\" can't be used on \"{}()\"", attribute, name),
+            SemanticAnalysisError::InvalidMaxVertexCount(counts) => write!(f, "Invalid vertex count {:?}, \"geometry\" takes one non-zero maximum number of vertices", counts),
+            SemanticAnalysisError::InvalidTessFactors(structure_name) => write!(f, "\"{}\" must have a \"float3\" \"SV_TESS_FACTOR\" and a \"float\" \"SV_INSIDE_TESS_FACTOR\" for triangles, or a \"float4\" and a \"float2\" for quads", structure_name),
         }
     }
 }
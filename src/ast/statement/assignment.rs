@@ -8,13 +8,30 @@ use crate::{
     tokens::TokenClass,
 };
 
+// Parses the value following the "=" of an assignment
 pub fn parse(stream: &mut Stream, name: &str) -> Result<Statement, ParserError> {
+    let (expression, next_token) = Expression::parse(stream)?;
+
+    match next_token.class() {
+        TokenClass::SemiColon => Ok(Statement::Assignment(name.to_owned(), expression)),
+        _ => Err(ParserError::UnexpectedToken(next_token)),
+    }
+}
+
+// Parses "index] = value;" following the "[" of an element assignment
+pub fn parse_index(stream: &mut Stream, name: &str) -> Result<Statement, ParserError> {
+    let index = Expression::parse_index(stream)?;
+
     next_token!(stream, TokenClass::Equal => {});
 
     let (expression, next_token) = Expression::parse(stream)?;
 
     match next_token.class() {
-        TokenClass::SemiColon => Ok(Statement::Assignment(name.to_owned(), expression)),
+        TokenClass::SemiColon => Ok(Statement::IndexAssignment(
+            name.to_owned(),
+            index,
+            expression,
+        )),
         _ => Err(ParserError::UnexpectedToken(next_token)),
     }
 }
@@ -48,3 +65,37 @@ pub fn semantic_analysis(
         ))
     }
 }
+
+pub fn index_semantic_analysis(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    name: String,
+    index: Expression,
+    expression: Expression,
+) -> Result<annotated::statement::Statement, SemanticAnalysisError> {
+    // Verify the variable is a buffer which can be written
+    let (buffer_type, _) = scope.get_variable(&name)?;
+    let element_type = Expression::index_type(output_tree, scope, buffer_type, &index)?;
+    match buffer_type.buffer_element() {
+        Some((_, true)) => {}
+        Some((_, false)) => return Err(SemanticAnalysisError::WritingReadOnlyBuffer(name)),
+        // Patches and primitives are inputs of their stage
+        None => return Err(SemanticAnalysisError::AssigningImmutableVariable(name)),
+    }
+
+    // Verify the type
+    let expression_type = expression.get_type(output_tree, scope)?;
+    if expression_type != element_type {
+        Err(SemanticAnalysisError::VariableTypeMismatch(
+            format!("{}[{}]", name, index),
+            expression_type.to_string(),
+            element_type.to_string(),
+        ))
+    } else {
+        Ok(annotated::statement::Statement::IndexAssignment(
+            name,
+            index.semantic_analysis(output_tree, scope)?,
+            expression.semantic_analysis(output_tree, scope)?,
+        ))
+    }
+}
@@ -0,0 +1,28 @@
+use super::Statement;
+use crate::{
+    annotated::{self, AnnotatedSyntaxTree},
+    ast::{expression::Expression, scope::Scope, SemanticAnalysisError},
+    parser::ParserError,
+    stream::Stream,
+    tokens::TokenClass,
+};
+
+// Parses a call made for its side effects, "name(parameters);"
+pub fn parse(stream: &mut Stream, name: &str) -> Result<Statement, ParserError> {
+    let (expression, next_token) = Expression::parse_function_call(stream, name)?;
+
+    match next_token.class() {
+        TokenClass::SemiColon => Ok(Statement::FunctionCall(expression)),
+        _ => Err(ParserError::UnexpectedToken(next_token)),
+    }
+}
+
+pub fn semantic_analysis(
+    output_tree: &AnnotatedSyntaxTree,
+    scope: &Scope,
+    expression: Expression,
+) -> Result<annotated::statement::Statement, SemanticAnalysisError> {
+    Ok(annotated::statement::Statement::FunctionCall(
+        expression.statement_semantic_analysis(output_tree, scope)?,
+    ))
+}
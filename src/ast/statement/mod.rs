@@ -1,6 +1,7 @@
 use super::{expression::Expression, scope::Scope, SemanticAnalysisError};
 use crate::{
     annotated::{self, AnnotatedSyntaxTree},
+    lexer, next_token,
     parser::ParserError,
     stream::Stream,
     tokens::{Token, TokenClass},
@@ -8,20 +9,36 @@ use crate::{
 };
 
 mod assignment;
+mod function_call;
 mod return_statement;
 mod variable_definition;
 
 pub enum Statement {
     Return(Expression),
+    Discard,
     VariableDefinition(String, Expression, bool),
     Assignment(String, Expression),
+    IndexAssignment(String, Expression, Expression),
+    FunctionCall(Expression),
 }
 
 impl Statement {
     pub fn parse(stream: &mut Stream, first_token: Token) -> Result<Self, ParserError> {
         match first_token.class() {
             TokenClass::Return => return_statement::parse(stream),
-            TokenClass::Identifier(name) => assignment::parse(stream, name),
+            TokenClass::Discard => {
+                next_token!(stream, TokenClass::SemiColon => {});
+                Ok(Statement::Discard)
+            }
+            TokenClass::Identifier(name) => match lexer::next_token(stream)? {
+                Some(token) => match token.class() {
+                    TokenClass::Equal => assignment::parse(stream, name),
+                    TokenClass::OpenSquareBracket => assignment::parse_index(stream, name),
+                    TokenClass::OpenParenthesis => function_call::parse(stream, name),
+                    _ => Err(ParserError::UnexpectedToken(token)),
+                },
+                None => Err(ParserError::UnexpectedEOF),
+            },
             TokenClass::Let => variable_definition::parse(stream),
             _ => Err(ParserError::UnexpectedToken(first_token)),
         }
@@ -40,9 +57,13 @@ impl Statement {
                 function_return_type,
                 expression,
             ),
+            Statement::Discard => Ok(annotated::statement::Statement::Discard),
             Statement::Assignment(name, expression) => {
                 assignment::semantic_analysis(output_tree, scope, name, expression)
             }
+            Statement::IndexAssignment(name, index, expression) => {
+                assignment::index_semantic_analysis(output_tree, scope, name, index, expression)
+            }
             Statement::VariableDefinition(name, expression, mutable) => {
                 variable_definition::semantic_analysis(
                     output_tree,
@@ -52,6 +73,9 @@ impl Statement {
                     mutable,
                 )
             }
+            Statement::FunctionCall(expression) => {
+                function_call::semantic_analysis(output_tree, scope, expression)
+            }
         }
     }
 }
@@ -60,7 +84,12 @@ impl std::fmt::Display for Statement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Statement::Return(expression) => writeln!(f, "return {}", expression),
+            Statement::Discard => writeln!(f, "discard"),
             Statement::Assignment(name, expression) => writeln!(f, "{} = {}", name, expression),
+            Statement::IndexAssignment(name, index, expression) => {
+                writeln!(f, "{}[{}] = {}", name, index, expression)
+            }
+            Statement::FunctionCall(expression) => writeln!(f, "{}", expression),
             Statement::VariableDefinition(name, expression, mutable) => {
                 writeln!(
                     f,
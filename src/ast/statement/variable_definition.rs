@@ -32,12 +32,18 @@ pub fn semantic_analysis(
     mutable: bool,
 ) -> Result<annotated::statement::Statement, SemanticAnalysisError> {
     let expression_type = expression.get_type(output_tree, scope)?;
+    if expression_type.is_stage_type() {
+        return Err(SemanticAnalysisError::StageTypeOutsideEntryPoint(
+            name,
+            expression_type.to_string(),
+        ));
+    }
 
     scope.define_variable(name.clone(), expression_type.clone(), mutable)?;
 
     Ok(annotated::statement::Statement::VariableDefinition(
         name,
-        expression.semantic_analysis(output_tree, scope)?,
+        expression.statement_semantic_analysis(output_tree, scope)?,
         expression_type,
     ))
 }
@@ -0,0 +1,555 @@
+use acsl::{GlslVersion, ShaderModel};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum ArgumentError {
+    MissingInput,
+    MultipleInputs(String),
+    MissingValue(String),
+    UnknownOption(String),
+    UnknownTarget(String),
+    UnknownGlslVersion(String),
+    UnknownShaderModel(String),
+    InvalidLocation(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Hlsl,
+    Glsl,
+    Wgsl,
+    Msl,
+    Spirv,
+}
+
+pub struct Arguments {
+    input: PathBuf,
+    target: Target,
+    glsl_version: GlslVersion,
+    shader_model: ShaderModel,
+    flip_y: bool,
+    fix_depth_range: bool,
+    separate_stages: bool,
+    vertex_entry_point: Option<String>,
+    fragment_entry_point: Option<String>,
+    locations: Vec<(String, usize)>,
+    output: Option<PathBuf>,
+    vertex_output: Option<PathBuf>,
+    fragment_output: Option<PathBuf>,
+    reflect: Option<PathBuf>,
+    check: bool,
+    watch: bool,
+    on_build: Option<String>,
+}
+
+pub const USAGE: &str = "Usage: acslc <input> [options]
+
+Options:
+    --target <language>       Output language, one of hlsl, glsl, wgsl, msl or spirv (default: hlsl)
+    --glsl-version <version>  GLSL version, one of 330, 430, 450, 300es or vulkan (default: 430)
+    --shader-model <version>  HLSL shader model, 5.0, 5.1 or 6.0 to 6.6 (default: 5.0)
+    --flip-y                  Negate the Y coordinate of the GLSL vertex position
+    --fix-depth-range         Remap the GLSL vertex position depth from [-1, 1] to [0, 1]
+    --separate-stages         Write a separate HLSL shader for each stage
+    --vertex-entry <name>     Name of the vertex shader entry point
+    --fragment-entry <name>   Name of the fragment shader entry point
+    --location <semantic=n>   Location of the vertex attribute and interstage value with the semantic
+    -o, --output <path>       Output path, for separate stages the base path of the pair
+    --vertex-output <path>    Output path for the separate vertex shader
+    --fragment-output <path>  Output path for the separate fragment shader
+    --reflect <path>          Write resource reflection as JSON to <path>
+    --check                   Compile for the target without writing any output
+    --watch                   Recompile whenever the input changes, polled every 250 ms outside Linux
+    --on-build <command>      Run <command> through the shell after each successful build
+    -h, --help                Print this message
+
+Exit codes:
+    0    Success
+    1    The shader failed to compile
+    2    Invalid command line arguments
+    3    Unable to read the input or write an output
+";
+
+impl Arguments {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Option<Self>, ArgumentError> {
+        let mut input = None;
+        let mut target = Target::Hlsl;
+        let mut glsl_version = GlslVersion::Glsl430;
+        let mut shader_model = ShaderModel::Sm50;
+        let mut flip_y = false;
+        let mut fix_depth_range = false;
+        let mut separate_stages = false;
+        let mut vertex_entry_point = None;
+        let mut fragment_entry_point = None;
+        let mut locations = Vec::new();
+        let mut output = None;
+        let mut vertex_output = None;
+        let mut fragment_output = None;
+        let mut reflect = None;
+        let mut check = false;
+        let mut watch = false;
+        let mut on_build = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--target" => {
+                    target = match next_value(&mut args, &arg)?.as_str() {
+                        "hlsl" => Target::Hlsl,
+                        "glsl" => Target::Glsl,
+                        "wgsl" => Target::Wgsl,
+                        "msl" => Target::Msl,
+                        "spirv" => Target::Spirv,
+                        target => return Err(ArgumentError::UnknownTarget(target.to_owned())),
+                    }
+                }
+                "--glsl-version" => {
+                    glsl_version = match next_value(&mut args, &arg)?.as_str() {
+                        "330" => GlslVersion::Glsl330,
+                        "430" => GlslVersion::Glsl430,
+                        "450" => GlslVersion::Glsl450,
+                        "300es" => GlslVersion::Essl300,
+                        "vulkan" => GlslVersion::Vulkan,
+                        version => {
+                            return Err(ArgumentError::UnknownGlslVersion(version.to_owned()))
+                        }
+                    }
+                }
+                "--shader-model" => {
+                    shader_model = match next_value(&mut args, &arg)?.as_str() {
+                        "5.0" => ShaderModel::Sm50,
+                        "5.1" => ShaderModel::Sm51,
+                        "6.0" => ShaderModel::Sm60,
+                        "6.1" => ShaderModel::Sm61,
+                        "6.2" => ShaderModel::Sm62,
+                        "6.3" => ShaderModel::Sm63,
+                        "6.4" => ShaderModel::Sm64,
+                        "6.5" => ShaderModel::Sm65,
+                        "6.6" => ShaderModel::Sm66,
+                        version => {
+                            return Err(ArgumentError::UnknownShaderModel(version.to_owned()))
+                        }
+                    }
+                }
+                "--flip-y" => flip_y = true,
+                "--fix-depth-range" => fix_depth_range = true,
+                "--separate-stages" => separate_stages = true,
+                "--vertex-entry" => vertex_entry_point = Some(next_value(&mut args, &arg)?),
+                "--fragment-entry" => fragment_entry_point = Some(next_value(&mut args, &arg)?),
+                "--location" => {
+                    let value = next_value(&mut args, &arg)?;
+                    let location = value
+                        .split_once('=')
+                        .and_then(|(semantic, location)| {
+                            Some((semantic.to_owned(), location.parse().ok()?))
+                        })
+                        .ok_or(ArgumentError::InvalidLocation(value))?;
+                    locations.push(location);
+                }
+                "-o" | "--output" => output = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+                "--vertex-output" => {
+                    vertex_output = Some(PathBuf::from(next_value(&mut args, &arg)?))
+                }
+                "--fragment-output" => {
+                    fragment_output = Some(PathBuf::from(next_value(&mut args, &arg)?))
+                }
+                "--reflect" => reflect = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+                "--check" => check = true,
+                "--watch" => watch = true,
+                "--on-build" => on_build = Some(next_value(&mut args, &arg)?),
+                _ => {
+                    if arg.starts_with('-') && arg.len() > 1 {
+                        return Err(ArgumentError::UnknownOption(arg));
+                    }
+
+                    if input.is_some() {
+                        return Err(ArgumentError::MultipleInputs(arg));
+                    }
+
+                    input = Some(PathBuf::from(arg));
+                }
+            }
+        }
+
+        let input = match input {
+            Some(input) => input,
+            None => return Err(ArgumentError::MissingInput),
+        };
+
+        Ok(Some(Arguments {
+            input,
+            target,
+            glsl_version,
+            shader_model,
+            flip_y,
+            fix_depth_range,
+            separate_stages,
+            vertex_entry_point,
+            fragment_entry_point,
+            locations,
+            output,
+            vertex_output,
+            fragment_output,
+            reflect,
+            check,
+            watch,
+            on_build,
+        }))
+    }
+
+    pub fn input(&self) -> &Path {
+        &self.input
+    }
+
+    pub fn target(&self) -> Target {
+        self.target
+    }
+
+    pub fn glsl_version(&self) -> GlslVersion {
+        self.glsl_version
+    }
+
+    pub fn shader_model(&self) -> ShaderModel {
+        self.shader_model
+    }
+
+    pub fn flip_y(&self) -> bool {
+        self.flip_y
+    }
+
+    pub fn fix_depth_range(&self) -> bool {
+        self.fix_depth_range
+    }
+
+    pub fn separate_stages(&self) -> bool {
+        self.separate_stages
+    }
+
+    pub fn vertex_entry_point(&self) -> Option<&str> {
+        self.vertex_entry_point.as_deref()
+    }
+
+    pub fn fragment_entry_point(&self) -> Option<&str> {
+        self.fragment_entry_point.as_deref()
+    }
+
+    pub fn locations(&self) -> &[(String, usize)] {
+        &self.locations
+    }
+
+    pub fn reflect(&self) -> Option<&Path> {
+        self.reflect.as_deref()
+    }
+
+    pub fn check(&self) -> bool {
+        self.check
+    }
+
+    pub fn watch(&self) -> bool {
+        self.watch
+    }
+
+    pub fn on_build(&self) -> Option<&str> {
+        self.on_build.as_deref()
+    }
+
+    pub fn hlsl_output(&self) -> PathBuf {
+        match &self.output {
+            Some(output) => output.clone(),
+            None => self.input.with_extension("hlsl"),
+        }
+    }
+
+    pub fn wgsl_output(&self) -> PathBuf {
+        match &self.output {
+            Some(output) => output.clone(),
+            None => self.input.with_extension("wgsl"),
+        }
+    }
+
+    pub fn msl_output(&self) -> PathBuf {
+        match &self.output {
+            Some(output) => output.clone(),
+            None => self.input.with_extension("metal"),
+        }
+    }
+
+    pub fn spirv_output(&self) -> PathBuf {
+        match &self.output {
+            Some(output) => output.clone(),
+            None => self.input.with_extension("spv"),
+        }
+    }
+
+    pub fn glsl_outputs(&self) -> (PathBuf, PathBuf) {
+        self.stage_outputs("_vertex.glsl", "_frag.glsl")
+    }
+
+    // Each compute kernel is written next to the other stages
+    pub fn glsl_compute_output(&self, kernel: &str) -> PathBuf {
+        self.extra_stage_output(&format!("_{}_comp.glsl", kernel))
+    }
+
+    // The tessellation and geometry stages are only written when used, they
+    // can't be renamed like the vertex and fragment outputs
+    pub fn extra_stage_output(&self, suffix: &str) -> PathBuf {
+        let base = match &self.output {
+            Some(output) => output.with_extension(""),
+            None => self.input.with_extension(""),
+        };
+
+        suffixed(&base, suffix)
+    }
+
+    pub fn hlsl_stage_outputs(&self) -> (PathBuf, PathBuf) {
+        self.stage_outputs("_vertex.hlsl", "_pixel.hlsl")
+    }
+
+    fn stage_outputs(&self, vertex_suffix: &str, fragment_suffix: &str) -> (PathBuf, PathBuf) {
+        let base = match &self.output {
+            Some(output) => output.with_extension(""),
+            None => self.input.with_extension(""),
+        };
+
+        let vertex_output = match &self.vertex_output {
+            Some(vertex_output) => vertex_output.clone(),
+            None => suffixed(&base, vertex_suffix),
+        };
+
+        let fragment_output = match &self.fragment_output {
+            Some(fragment_output) => fragment_output.clone(),
+            None => suffixed(&base, fragment_suffix),
+        };
+
+        (vertex_output, fragment_output)
+    }
+}
+
+fn next_value<I: Iterator<Item = String>>(
+    args: &mut I,
+    option: &str,
+) -> Result<String, ArgumentError> {
+    args.next()
+        .ok_or_else(|| ArgumentError::MissingValue(option.to_owned()))
+}
+
+fn suffixed(base: &Path, suffix: &str) -> PathBuf {
+    let mut path = base.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+impl std::error::Error for ArgumentError {}
+
+impl std::fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgumentError::MissingInput => write!(f, "No input file provided"),
+            ArgumentError::MultipleInputs(input) => {
+                write!(f, "Unexpected second input file \"{}\"", input)
+            }
+            ArgumentError::MissingValue(option) => {
+                write!(f, "Option \"{}\" requires a value", option)
+            }
+            ArgumentError::UnknownOption(option) => write!(f, "Unknown option \"{}\"", option),
+            ArgumentError::UnknownTarget(target) => write!(
+                f,
+                "Unknown target \"{}\", expected \"hlsl\", \"glsl\", \"wgsl\", \"msl\" or \"spirv\"",
+                target
+            ),
+            ArgumentError::UnknownGlslVersion(version) => write!(
+                f,
+                "Unknown GLSL version \"{}\", expected \"330\", \"430\", \"450\", \"300es\" or \"vulkan\"",
+                version
+            ),
+            ArgumentError::UnknownShaderModel(version) => write!(
+                f,
+                "Unknown shader model \"{}\", expected \"5.0\", \"5.1\" or \"6.0\" to \"6.6\"",
+                version
+            ),
+            ArgumentError::InvalidLocation(location) => write!(
+                f,
+                "Invalid location \"{}\", expected a semantic and a location as in \"TEXCOORD0=1\"",
+                location
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Arguments>, ArgumentError> {
+        Arguments::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn parsed(args: &[&str]) -> Arguments {
+        parse(args).unwrap().unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        let arguments = parsed(&["shader.acsl"]);
+        assert_eq!(arguments.input(), Path::new("shader.acsl"));
+        assert!(arguments.target() == Target::Hlsl);
+        assert!(arguments.glsl_version() == GlslVersion::Glsl430);
+        assert!(arguments.shader_model() == ShaderModel::Sm50);
+        assert!(!arguments.separate_stages() && !arguments.check() && !arguments.watch());
+        assert_eq!(arguments.vertex_entry_point(), None);
+        assert_eq!(arguments.reflect(), None);
+        assert_eq!(arguments.on_build(), None);
+    }
+
+    #[test]
+    fn options() {
+        let arguments = parsed(&[
+            "--target",
+            "glsl",
+            "--glsl-version",
+            "vulkan",
+            "--shader-model",
+            "6.6",
+            "shader.acsl",
+            "--flip-y",
+            "--fix-depth-range",
+            "--vertex-entry",
+            "vs",
+            "--fragment-entry",
+            "fs",
+            "--location",
+            "TEXCOORD0=1",
+            "--location",
+            "COLOR=3",
+            "--reflect",
+            "shader.json",
+            "--check",
+            "--on-build",
+            "make reload",
+        ]);
+        assert!(arguments.target() == Target::Glsl);
+        assert!(arguments.glsl_version() == GlslVersion::Vulkan);
+        assert!(arguments.shader_model() == ShaderModel::Sm66);
+        assert!(arguments.flip_y() && arguments.fix_depth_range() && arguments.check());
+        assert_eq!(arguments.vertex_entry_point(), Some("vs"));
+        assert_eq!(arguments.fragment_entry_point(), Some("fs"));
+        assert_eq!(
+            arguments.locations(),
+            [("TEXCOORD0".to_owned(), 1), ("COLOR".to_owned(), 3)]
+        );
+        assert_eq!(arguments.reflect(), Some(Path::new("shader.json")));
+        assert_eq!(arguments.on_build(), Some("make reload"));
+    }
+
+    #[test]
+    fn help() {
+        assert!(parse(&["shader.acsl", "--help"]).unwrap().is_none());
+        assert!(parse(&["-h"]).unwrap().is_none());
+    }
+
+    #[test]
+    fn errors() {
+        let error = |args: &[&str]| parse(args).err().unwrap().to_string();
+
+        assert_eq!(error(&[]), "No input file provided");
+        assert_eq!(
+            error(&["a.acsl", "b.acsl"]),
+            "Unexpected second input file \"b.acsl\""
+        );
+        assert_eq!(
+            error(&["shader.acsl", "--target"]),
+            "Option \"--target\" requires a value"
+        );
+        assert_eq!(
+            error(&["shader.acsl", "--optimize"]),
+            "Unknown option \"--optimize\""
+        );
+        assert!(error(&["shader.acsl", "--target", "dxil"]).starts_with("Unknown target \"dxil\""));
+        assert!(error(&["shader.acsl", "--glsl-version", "460"])
+            .starts_with("Unknown GLSL version \"460\""));
+        assert!(error(&["shader.acsl", "--shader-model", "4.0"])
+            .starts_with("Unknown shader model \"4.0\""));
+        for location in ["TEXCOORD0", "TEXCOORD0=", "TEXCOORD0=one"] {
+            assert!(error(&["shader.acsl", "--location", location])
+                .starts_with(&format!("Invalid location \"{}\"", location)));
+        }
+    }
+
+    #[test]
+    fn default_outputs() {
+        let arguments = parsed(&["shaders/shader.acsl"]);
+        assert_eq!(arguments.hlsl_output(), Path::new("shaders/shader.hlsl"));
+        assert_eq!(arguments.wgsl_output(), Path::new("shaders/shader.wgsl"));
+        assert_eq!(arguments.msl_output(), Path::new("shaders/shader.metal"));
+        assert_eq!(arguments.spirv_output(), Path::new("shaders/shader.spv"));
+        assert_eq!(
+            arguments.glsl_outputs(),
+            (
+                PathBuf::from("shaders/shader_vertex.glsl"),
+                PathBuf::from("shaders/shader_frag.glsl")
+            )
+        );
+        assert_eq!(
+            arguments.hlsl_stage_outputs(),
+            (
+                PathBuf::from("shaders/shader_vertex.hlsl"),
+                PathBuf::from("shaders/shader_pixel.hlsl")
+            )
+        );
+        assert_eq!(
+            arguments.extra_stage_output("_geom.glsl"),
+            Path::new("shaders/shader_geom.glsl")
+        );
+        assert_eq!(
+            arguments.glsl_compute_output("clear"),
+            Path::new("shaders/shader_clear_comp.glsl")
+        );
+    }
+
+    #[test]
+    fn named_outputs() {
+        // Stage outputs drop the extension of "--output" and keep the rest of the name
+        let arguments = parsed(&["shader.acsl", "-o", "out/lit.v2.hlsl"]);
+        assert_eq!(arguments.hlsl_output(), Path::new("out/lit.v2.hlsl"));
+        assert_eq!(
+            arguments.hlsl_stage_outputs(),
+            (
+                PathBuf::from("out/lit.v2_vertex.hlsl"),
+                PathBuf::from("out/lit.v2_pixel.hlsl")
+            )
+        );
+        assert_eq!(
+            arguments.extra_stage_output("_hull.hlsl"),
+            Path::new("out/lit.v2_hull.hlsl")
+        );
+
+        // Each stage can be renamed on its own
+        let arguments = parsed(&[
+            "shader.acsl",
+            "--output",
+            "out/lit",
+            "--fragment-output",
+            "frag.glsl",
+        ]);
+        assert_eq!(
+            arguments.glsl_outputs(),
+            (
+                PathBuf::from("out/lit_vertex.glsl"),
+                PathBuf::from("frag.glsl")
+            )
+        );
+        assert_eq!(
+            arguments.extra_stage_output("_tesc.glsl"),
+            Path::new("out/lit_tesc.glsl")
+        );
+
+        let arguments = parsed(&["shader.acsl", "--vertex-output", "vertex.hlsl"]);
+        assert_eq!(
+            arguments.hlsl_stage_outputs(),
+            (
+                PathBuf::from("vertex.hlsl"),
+                PathBuf::from("shader_pixel.hlsl")
+            )
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use acsl::CompilationError;
+use std::path::Path;
+
+pub fn render(path: &Path, code: &str, error: &CompilationError) -> String {
+    let mut diagnostic = format!("error: {}\n", error);
+
+    let (line, column) = match error.location() {
+        Some(location) => location,
+        None => {
+            diagnostic.push_str(&format!(" --> {}\n", path.display()));
+            return diagnostic;
+        }
+    };
+
+    diagnostic.push_str(&format!(" --> {}:{}:{}\n", path.display(), line, column));
+
+    let source_line = match code.lines().nth(line - 1) {
+        Some(source_line) => expand_tabs(source_line),
+        None => return diagnostic,
+    };
+
+    let gutter = line.to_string();
+    let padding = " ".repeat(gutter.len());
+
+    diagnostic.push_str(&format!("{} |\n", padding));
+    diagnostic.push_str(&format!("{} | {}\n", gutter, source_line));
+    diagnostic.push_str(&format!(
+        "{} | {}^\n",
+        padding,
+        " ".repeat(column.saturating_sub(1))
+    ));
+
+    diagnostic
+}
+
+// Tabs are expanded the same way the lexer counts columns so the caret lines up
+fn expand_tabs(line: &str) -> String {
+    let mut expanded = String::with_capacity(line.len());
+
+    for c in line.chars() {
+        match c {
+            '\t' => {
+                expanded.push(' ');
+                while !expanded.chars().count().is_multiple_of(4) {
+                    expanded.push(' ');
+                }
+            }
+            _ => expanded.push(c),
+        }
+    }
+
+    expanded
+}
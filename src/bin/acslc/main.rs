@@ -0,0 +1,236 @@
+use acsl::{CompileOptions, Compiler};
+use args::{ArgumentError, Arguments, Target, USAGE};
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+mod args;
+mod diagnostic;
+mod watch;
+
+const EXIT_COMPILATION_ERROR: u8 = 1;
+const EXIT_ARGUMENT_ERROR: u8 = 2;
+const EXIT_IO_ERROR: u8 = 3;
+
+enum Error {
+    Read(String, std::io::Error),
+    Write(String, std::io::Error),
+    Compilation(String),
+    Watch(std::io::Error),
+}
+
+fn main() -> ExitCode {
+    let arguments = match Arguments::parse(std::env::args().skip(1)) {
+        Ok(Some(arguments)) => arguments,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => return argument_error(error),
+    };
+
+    if arguments.watch() {
+        return match watch(&arguments) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprint!("{}", error);
+                ExitCode::from(error.exit_code())
+            }
+        };
+    }
+
+    match compile(&arguments) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprint!("{}", error);
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+fn watch(arguments: &Arguments) -> Result<(), Error> {
+    let sources = vec![arguments.input().to_owned()];
+    let mut watcher = watch::Watcher::new(arguments.input()).map_err(Error::Watch)?;
+
+    build(arguments);
+    println!("Watching \"{}\" for changes", arguments.input().display());
+
+    loop {
+        let mut rebuild = false;
+        for changed in watcher.wait().map_err(Error::Watch)? {
+            rebuild |= !watch::dependents(&changed, &sources).is_empty();
+        }
+
+        if rebuild {
+            build(arguments);
+        }
+    }
+}
+
+// Compiles the input once while watching, errors are reported without stopping
+fn build(arguments: &Arguments) {
+    if let Err(error) = compile(arguments) {
+        eprint!("{}", error);
+        return;
+    }
+
+    println!("Compiled \"{}\"", arguments.input().display());
+
+    let hook = match arguments.on_build() {
+        Some(hook) => hook,
+        None => return,
+    };
+
+    let status = if cfg!(windows) {
+        Command::new("cmd").arg("/C").arg(hook).status()
+    } else {
+        Command::new("sh").arg("-c").arg(hook).status()
+    };
+
+    match status {
+        Ok(status) if !status.success() => {
+            eprintln!("error: Post-build command \"{}\" failed ({})", hook, status)
+        }
+        Ok(_) => {}
+        Err(error) => eprintln!(
+            "error: Unable to run post-build command \"{}\" - {}",
+            hook, error
+        ),
+    }
+}
+
+fn argument_error(error: ArgumentError) -> ExitCode {
+    eprintln!("error: {}\n\n{}", error, USAGE);
+    ExitCode::from(EXIT_ARGUMENT_ERROR)
+}
+
+fn compile(arguments: &Arguments) -> Result<(), Error> {
+    let input = arguments.input();
+    let code = std::fs::read_to_string(input)
+        .map_err(|error| Error::Read(input.display().to_string(), error))?;
+
+    let render = |error| Error::Compilation(diagnostic::render(input, &code, &error));
+
+    let mut options = CompileOptions::new()
+        .glsl_version(arguments.glsl_version())
+        .shader_model(arguments.shader_model())
+        .flip_y(arguments.flip_y())
+        .fix_depth_range(arguments.fix_depth_range());
+    if let Some(vertex_entry_point) = arguments.vertex_entry_point() {
+        options = options.vertex_entry_point(vertex_entry_point);
+    }
+    if let Some(fragment_entry_point) = arguments.fragment_entry_point() {
+        options = options.fragment_entry_point(fragment_entry_point);
+    }
+    for (semantic, location) in arguments.locations() {
+        options = options.semantic_location(semantic, *location);
+    }
+
+    let compiler = Compiler::new(options);
+
+    // The outputs are only written once every stage compiled, "--check"
+    // compiles for the target without writing them
+    let mut outputs: Vec<(PathBuf, Vec<u8>)> = Vec::new();
+    match arguments.target() {
+        Target::Hlsl if arguments.separate_stages() => {
+            let hlsl = compiler.compile_hlsl_stages(&code).map_err(render)?;
+            let (vertex_output, pixel_output) = arguments.hlsl_stage_outputs();
+            outputs.push((vertex_output, hlsl.vertex().into()));
+            outputs.push((pixel_output, hlsl.pixel().into()));
+
+            for (suffix, code) in [
+                ("_hull.hlsl", hlsl.hull()),
+                ("_domain.hlsl", hlsl.domain()),
+                ("_geometry.hlsl", hlsl.geometry()),
+            ] {
+                if !code.is_empty() {
+                    outputs.push((arguments.extra_stage_output(suffix), code.into()));
+                }
+            }
+        }
+        Target::Hlsl => {
+            let hlsl = compiler.compile_hlsl(&code).map_err(render)?;
+            outputs.push((arguments.hlsl_output(), hlsl.code().into()));
+        }
+        Target::Glsl => {
+            let glsl = compiler.compile_glsl(&code).map_err(render)?;
+            // Files with only compute kernels have no vertex or fragment shader
+            if !glsl.vertex().is_empty() {
+                let (vertex_output, fragment_output) = arguments.glsl_outputs();
+                outputs.push((vertex_output, glsl.vertex().into()));
+                outputs.push((fragment_output, glsl.fragment().into()));
+            }
+
+            for (suffix, code) in [
+                ("_tesc.glsl", glsl.tess_control()),
+                ("_tese.glsl", glsl.tess_evaluation()),
+                ("_geom.glsl", glsl.geometry()),
+            ] {
+                if !code.is_empty() {
+                    outputs.push((arguments.extra_stage_output(suffix), code.into()));
+                }
+            }
+
+            for (kernel, code) in glsl.compute() {
+                outputs.push((arguments.glsl_compute_output(kernel), code.as_str().into()));
+            }
+        }
+        Target::Wgsl => {
+            let wgsl = compiler.compile_wgsl(&code).map_err(render)?;
+            outputs.push((arguments.wgsl_output(), wgsl.code().into()));
+        }
+        Target::Msl => {
+            let msl = compiler.compile_msl(&code).map_err(render)?;
+            outputs.push((arguments.msl_output(), msl.code().into()));
+        }
+        Target::Spirv => {
+            let spirv = compiler.compile_spirv(&code).map_err(render)?;
+            outputs.push((arguments.spirv_output(), spirv.to_bytes()));
+        }
+    }
+
+    if let Some(reflect) = arguments.reflect() {
+        let reflection = compiler.reflect(&code).map_err(render)?;
+        outputs.push((reflect.to_owned(), reflection.to_json().into()));
+    }
+
+    if arguments.check() {
+        return Ok(());
+    }
+
+    for (path, contents) in outputs {
+        write(&path, contents)?;
+    }
+
+    Ok(())
+}
+
+fn write<C: AsRef<[u8]>>(path: &Path, contents: C) -> Result<(), Error> {
+    std::fs::write(path, contents).map_err(|error| Error::Write(path.display().to_string(), error))
+}
+
+impl Error {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Read(_, _) | Error::Write(_, _) => EXIT_IO_ERROR,
+            Error::Compilation(_) => EXIT_COMPILATION_ERROR,
+            Error::Watch(_) => EXIT_IO_ERROR,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Read(path, error) => {
+                writeln!(f, "error: Unable to read \"{}\" - {}", path, error)
+            }
+            Error::Write(path, error) => {
+                writeln!(f, "error: Unable to write \"{}\" - {}", path, error)
+            }
+            Error::Compilation(diagnostic) => write!(f, "{}", diagnostic),
+            Error::Watch(error) => writeln!(f, "error: Unable to watch for changes - {}", error),
+        }
+    }
+}
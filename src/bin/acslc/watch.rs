@@ -0,0 +1,252 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(target_os = "linux")]
+pub use inotify::Watcher;
+#[cfg(not(target_os = "linux"))]
+pub use poll::Watcher;
+
+// Returns the files which need to be recompiled when "changed" is modified
+pub fn dependents(changed: &Path, sources: &[PathBuf]) -> Vec<PathBuf> {
+    let changed = canonical(changed);
+    sources
+        .iter()
+        .filter(|source| canonical(source) == changed)
+        .cloned()
+        .collect()
+}
+
+// Editors may briefly remove a file while saving it, so a path which no longer
+// exists is resolved through its directory instead
+fn canonical(path: &Path) -> PathBuf {
+    if let Ok(path) = path.canonicalize() {
+        return path;
+    }
+
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    match (directory.canonicalize(), path.file_name()) {
+        (Ok(directory), Some(file_name)) => directory.join(file_name),
+        _ => path.to_owned(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::{
+        ffi::CString,
+        os::{
+            raw::{c_char, c_int, c_void},
+            unix::ffi::OsStrExt,
+        },
+        path::{Path, PathBuf},
+    };
+
+    const IN_CLOEXEC: c_int = 0o2000000;
+
+    const IN_CLOSE_WRITE: u32 = 0x008;
+    const IN_MOVED_TO: u32 = 0x080;
+
+    const EVENT_HEADER_SIZE: usize = 16;
+
+    extern "C" {
+        fn inotify_init1(flags: c_int) -> c_int;
+        fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: u32) -> c_int;
+        fn read(fd: c_int, buf: *mut c_void, count: usize) -> isize;
+        fn close(fd: c_int) -> c_int;
+    }
+
+    pub struct Watcher {
+        fd: c_int,
+        directory: PathBuf,
+        buffer: Vec<u8>,
+    }
+
+    impl Watcher {
+        pub fn new(file: &Path) -> std::io::Result<Self> {
+            let directory = match file.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_owned(),
+                _ => PathBuf::from("."),
+            };
+
+            let fd = unsafe { inotify_init1(IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+
+            // Watch the directory so editors which replace the file on save are noticed
+            let pathname = CString::new(directory.as_os_str().as_bytes())
+                .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+            if unsafe { inotify_add_watch(fd, pathname.as_ptr(), IN_CLOSE_WRITE | IN_MOVED_TO) } < 0
+            {
+                let error = std::io::Error::last_os_error();
+                unsafe { close(fd) };
+                return Err(error);
+            }
+
+            Ok(Watcher {
+                fd,
+                directory,
+                buffer: vec![0; 4096],
+            })
+        }
+
+        // Blocks until at least one file in the watched directory changes
+        pub fn wait(&mut self) -> std::io::Result<Vec<PathBuf>> {
+            let length = unsafe {
+                read(
+                    self.fd,
+                    self.buffer.as_mut_ptr() as *mut c_void,
+                    self.buffer.len(),
+                )
+            };
+            if length < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+
+            let mut changed: Vec<PathBuf> = Vec::new();
+            let mut offset = 0;
+            while offset + EVENT_HEADER_SIZE <= length as usize {
+                let name_length =
+                    u32::from_ne_bytes(self.buffer[offset + 12..offset + 16].try_into().unwrap())
+                        as usize;
+
+                let name = &self.buffer
+                    [offset + EVENT_HEADER_SIZE..offset + EVENT_HEADER_SIZE + name_length];
+                let name = match name.iter().position(|byte| *byte == 0) {
+                    Some(end) => &name[..end],
+                    None => name,
+                };
+
+                let path = self.directory.join(std::ffi::OsStr::from_bytes(name));
+                if !changed.contains(&path) {
+                    changed.push(path);
+                }
+
+                offset += EVENT_HEADER_SIZE + name_length;
+            }
+
+            Ok(changed)
+        }
+    }
+
+    impl Drop for Watcher {
+        fn drop(&mut self) {
+            unsafe { close(self.fd) };
+        }
+    }
+}
+
+// Other platforms poll the modification time of the file instead of using
+// their native notifications
+#[cfg(not(target_os = "linux"))]
+mod poll {
+    use std::{
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime},
+    };
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    pub struct Watcher {
+        file: PathBuf,
+        modified: Option<SystemTime>,
+    }
+
+    impl Watcher {
+        pub fn new(file: &Path) -> std::io::Result<Self> {
+            Ok(Watcher {
+                file: file.to_owned(),
+                modified: modified(file),
+            })
+        }
+
+        // Blocks until the watched file changes
+        pub fn wait(&mut self) -> std::io::Result<Vec<PathBuf>> {
+            loop {
+                std::thread::sleep(POLL_INTERVAL);
+
+                let modified = modified(&self.file);
+                if modified != self.modified {
+                    self.modified = modified;
+                    return Ok(vec![self.file.clone()]);
+                }
+            }
+        }
+    }
+
+    fn modified(file: &Path) -> Option<SystemTime> {
+        std::fs::metadata(file)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A scratch directory holding an "a/shader.acsl" and a "b/shader.acsl"
+    fn directory(test_name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join("acslc_watch").join(test_name);
+        let _ = std::fs::remove_dir_all(&directory);
+        for name in ["a", "b"] {
+            std::fs::create_dir_all(directory.join(name)).unwrap();
+            std::fs::write(directory.join(name).join("shader.acsl"), "").unwrap();
+        }
+        directory
+    }
+
+    #[test]
+    fn same_file() {
+        let directory = directory("same_file");
+        let source = directory.join("a").join("shader.acsl");
+        let sources = vec![source.clone()];
+
+        assert_eq!(dependents(&source, &sources), sources);
+        assert_eq!(
+            dependents(&directory.join("b/../a/./shader.acsl"), &sources),
+            sources
+        );
+    }
+
+    #[test]
+    fn other_files() {
+        let directory = directory("other_files");
+        let sources = vec![directory.join("a").join("shader.acsl")];
+
+        // A file with the same name in another directory is not the source
+        assert!(dependents(&directory.join("b").join("shader.acsl"), &sources).is_empty());
+        assert!(dependents(&directory.join("a").join("other.acsl"), &sources).is_empty());
+        assert!(dependents(&directory.join("a"), &sources).is_empty());
+    }
+
+    #[test]
+    fn removed_file() {
+        let directory = directory("removed_file");
+        let source = directory.join("a").join("shader.acsl");
+        let sources = vec![source.clone()];
+        std::fs::remove_file(&source).unwrap();
+
+        assert_eq!(
+            dependents(&directory.join("b/../a/shader.acsl"), &sources),
+            sources
+        );
+        assert!(dependents(&directory.join("b").join("shader.acsl"), &sources).is_empty());
+    }
+
+    #[test]
+    fn relative_source() {
+        let sources = vec![PathBuf::from("tests/simple.acsl")];
+        let absolute = std::env::current_dir().unwrap().join("tests/simple.acsl");
+
+        assert_eq!(dependents(&absolute, &sources), sources);
+        assert_eq!(
+            dependents(Path::new("./tests/simple.acsl"), &sources),
+            sources
+        );
+        assert!(dependents(Path::new("simple.acsl"), &sources).is_empty());
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{
+    annotated::AnnotatedSyntaxTree, parser, reflection::Reflection, spirv, target::Target,
+    CompilationError, CompileOptions,
+};
+
+pub struct Compiler {
+    options: CompileOptions,
+}
+
+pub struct HlslOutput {
+    code: String,
+}
+
+pub struct HlslStagesOutput {
+    vertex: String,
+    hull: String,
+    domain: String,
+    geometry: String,
+    pixel: String,
+}
+
+pub struct WgslOutput {
+    code: String,
+}
+
+pub struct MslOutput {
+    code: String,
+}
+
+pub struct SpirvOutput {
+    words: Vec<u32>,
+}
+
+pub struct GlslOutput {
+    vertex: String,
+    tess_control: String,
+    tess_evaluation: String,
+    geometry: String,
+    fragment: String,
+    compute: Vec<(String, String)>,
+}
+
+impl Compiler {
+    pub fn new(options: CompileOptions) -> Self {
+        Compiler { options }
+    }
+
+    pub fn options(&self) -> &CompileOptions {
+        &self.options
+    }
+
+    pub fn compile_hlsl<S: AsRef<str>>(&self, code: S) -> Result<HlslOutput, CompilationError> {
+        // Compile
+        let ast = self.analyze(code)?;
+        ast.verify_features(Target::Hlsl(self.options.get_shader_model()))?;
+
+        // Generate code
+        Ok(HlslOutput {
+            code: ast.generate_hlsl(&self.options),
+        })
+    }
+
+    pub fn compile_hlsl_stages<S: AsRef<str>>(
+        &self,
+        code: S,
+    ) -> Result<HlslStagesOutput, CompilationError> {
+        // Compile
+        let ast = self.analyze(code)?;
+        ast.verify_features(Target::Hlsl(self.options.get_shader_model()))?;
+
+        // Generate code
+        let stages = ast.generate_hlsl_stages(&self.options);
+        Ok(HlslStagesOutput {
+            vertex: stages.vertex,
+            hull: stages.hull,
+            domain: stages.domain,
+            geometry: stages.geometry,
+            pixel: stages.fragment,
+        })
+    }
+
+    pub fn compile_glsl<S: AsRef<str>>(&self, code: S) -> Result<GlslOutput, CompilationError> {
+        // Compile
+        let ast = self.analyze(code)?;
+        ast.verify_features(Target::Glsl(self.options.get_glsl_version()))?;
+
+        // Generate code
+        let (stages, compute) = ast.generate_glsl(&self.options);
+        Ok(GlslOutput {
+            vertex: stages.vertex,
+            tess_control: stages.hull,
+            tess_evaluation: stages.domain,
+            geometry: stages.geometry,
+            fragment: stages.fragment,
+            compute,
+        })
+    }
+
+    pub fn compile_wgsl<S: AsRef<str>>(&self, code: S) -> Result<WgslOutput, CompilationError> {
+        // Compile
+        let ast = self.analyze(code)?;
+        ast.verify_features(Target::Wgsl)?;
+
+        // Generate code
+        Ok(WgslOutput {
+            code: ast.generate_wgsl(&self.options),
+        })
+    }
+
+    pub fn compile_msl<S: AsRef<str>>(&self, code: S) -> Result<MslOutput, CompilationError> {
+        // Compile
+        let ast = self.analyze(code)?;
+        ast.verify_features(Target::Msl)?;
+
+        // Generate code
+        Ok(MslOutput {
+            code: ast.generate_msl(&self.options),
+        })
+    }
+
+    pub fn compile_spirv<S: AsRef<str>>(&self, code: S) -> Result<SpirvOutput, CompilationError> {
+        // Compile
+        let ast = self.analyze(code)?;
+        ast.verify_features(Target::Spirv)?;
+
+        // Generate code
+        Ok(SpirvOutput {
+            words: ast.generate_spirv(&self.options),
+        })
+    }
+
+    pub fn reflect<S: AsRef<str>>(&self, code: S) -> Result<Reflection, CompilationError> {
+        // Compile
+        let ast = self.analyze(code)?;
+
+        // Collect resources
+        Ok(ast.reflect(&self.options))
+    }
+
+    fn analyze<S: AsRef<str>>(&self, code: S) -> Result<AnnotatedSyntaxTree, CompilationError> {
+        // Parse into AST
+        let ast = parser::parse(code.as_ref())?;
+
+        // Perform semantic analysis
+        let annotated_ast = ast.semantic_analysis(&self.options)?;
+        annotated_ast.verify_entry_point_names(&self.options)?;
+
+        Ok(annotated_ast)
+    }
+}
+
+impl Default for Compiler {
+    fn default() -> Self {
+        Compiler::new(CompileOptions::default())
+    }
+}
+
+impl HlslOutput {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn into_code(self) -> String {
+        self.code
+    }
+}
+
+impl HlslStagesOutput {
+    pub fn vertex(&self) -> &str {
+        &self.vertex
+    }
+
+    pub fn pixel(&self) -> &str {
+        &self.pixel
+    }
+
+    // The tessellation and geometry stages are empty when they aren't used
+    pub fn hull(&self) -> &str {
+        &self.hull
+    }
+
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    pub fn geometry(&self) -> &str {
+        &self.geometry
+    }
+
+    pub fn into_code(self) -> (String, String) {
+        (self.vertex, self.pixel)
+    }
+}
+
+impl WgslOutput {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn into_code(self) -> String {
+        self.code
+    }
+}
+
+impl MslOutput {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn into_code(self) -> String {
+        self.code
+    }
+}
+
+impl SpirvOutput {
+    pub fn words(&self) -> &[u32] {
+        &self.words
+    }
+
+    pub fn into_words(self) -> Vec<u32> {
+        self.words
+    }
+
+    // The module as little-endian bytes, as stored in a ".spv" file
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+
+    // A textual listing of the module in the style of spirv-dis
+    pub fn disassemble(&self) -> String {
+        spirv::disassemble(&self.words)
+    }
+}
+
+impl GlslOutput {
+    pub fn vertex(&self) -> &str {
+        &self.vertex
+    }
+
+    pub fn fragment(&self) -> &str {
+        &self.fragment
+    }
+
+    // The tessellation and geometry shaders are empty when they aren't used
+    pub fn tess_control(&self) -> &str {
+        &self.tess_control
+    }
+
+    pub fn tess_evaluation(&self) -> &str {
+        &self.tess_evaluation
+    }
+
+    pub fn geometry(&self) -> &str {
+        &self.geometry
+    }
+
+    // A shader for each compute kernel, paired with the name of the kernel
+    pub fn compute(&self) -> &[(String, String)] {
+        &self.compute
+    }
+
+    pub fn into_code(self) -> (String, String) {
+        (self.vertex, self.fragment)
+    }
+}
@@ -0,0 +1,72 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Feature {
+    SingleComponentVector,
+    SingleComponentMatrix,
+    RegisterSpace,
+    SharedSlots,
+    SixteenBitTypes,
+    WaveIntrinsics,
+    ComputeShaders,
+    StorageBuffers,
+    StorageTextures,
+    OneDimensionalTextures,
+    CubeArrayTextures,
+    TextureGather,
+    ColorTextureComparison,
+    MultipleSamplersPerTexture,
+    NoPerspectiveInterpolation,
+    SampleInterpolation,
+    TessellationShaders,
+    GeometryShaders,
+}
+
+impl std::fmt::Display for Feature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Feature::SingleComponentVector => write!(f, "single component vectors (\"float1\")"),
+            Feature::SingleComponentMatrix => {
+                write!(
+                    f,
+                    "matrices with a single row or column (\"float1xn\", \"floatnx1\")"
+                )
+            }
+            Feature::RegisterSpace => write!(f, "register spaces (\"space\")"),
+            Feature::SharedSlots => {
+                write!(
+                    f,
+                    "slots shared by resources in different spaces (\"space\")"
+                )
+            }
+            Feature::SixteenBitTypes => write!(f, "16-bit types (\"half\")"),
+            Feature::WaveIntrinsics => write!(f, "wave intrinsics (\"wave_*\")"),
+            Feature::ComputeShaders => write!(f, "compute shaders (\"#[compute]\")"),
+            Feature::StorageBuffers => write!(f, "storage buffers (\"buffer\", \"rwbuffer\")"),
+            Feature::StorageTextures => write!(f, "storage textures (\"rwtexture\")"),
+            Feature::OneDimensionalTextures => write!(f, "1D textures (\"texture1d\")"),
+            Feature::CubeArrayTextures => {
+                write!(f, "cube map arrays (\"texture_cube_array\")")
+            }
+            Feature::TextureGather => write!(f, "texture gathers (\"gather\")"),
+            Feature::ColorTextureComparison => {
+                write!(
+                    f,
+                    "comparison sampling of color textures (\"sample_compare\")"
+                )
+            }
+            Feature::MultipleSamplersPerTexture => {
+                write!(
+                    f,
+                    "sampling a texture with more than one sampler (\"sample\")"
+                )
+            }
+            Feature::NoPerspectiveInterpolation => {
+                write!(f, "linear interpolation (\"#[noperspective]\")")
+            }
+            Feature::SampleInterpolation => write!(f, "per sample interpolation (\"#[sample]\")"),
+            Feature::TessellationShaders => {
+                write!(f, "tessellation shaders (\"hull_main\", \"domain_main\")")
+            }
+            Feature::GeometryShaders => write!(f, "geometry shaders (\"#[geometry]\")"),
+        }
+    }
+}
@@ -112,6 +112,15 @@ fn parse_exponent(
     ))
 }
 
+impl LexerError {
+    pub fn location(&self) -> (usize, usize) {
+        match self {
+            LexerError::UnknownCharacter(_, column, line) => (*line, *column),
+            LexerError::NoExponentialDigits(column, line) => (*line, *column),
+        }
+    }
+}
+
 pub fn next_token(stream: &mut Stream) -> Result<Option<Token>, LexerError> {
     while let Some(c) = stream.next() {
         if !c.is_whitespace() {
@@ -131,6 +140,7 @@ pub fn next_token(stream: &mut Stream) -> Result<Option<Token>, LexerError> {
                     "fn" => TokenClass::Fn,
                     "struct" => TokenClass::Struct,
                     "return" => TokenClass::Return,
+                    "discard" => TokenClass::Discard,
                     "cbuffer" => TokenClass::CBuffer,
                     "const" => TokenClass::Const,
                     "type" => TokenClass::Type,
@@ -172,6 +182,9 @@ pub fn next_token(stream: &mut Stream) -> Result<Option<Token>, LexerError> {
                     '=' => TokenClass::Equal,
                     '*' => TokenClass::Asterick,
                     '+' => TokenClass::Plus,
+                    '#' => TokenClass::Hash,
+                    '[' => TokenClass::OpenSquareBracket,
+                    ']' => TokenClass::CloseSquareBracket,
                     _ => return Err(LexerError::UnknownCharacter(c, column, line)),
                 }
             }
@@ -1,43 +1,88 @@
 mod annotated;
 mod ast;
+mod compiler;
+mod feature;
 mod lexer;
+mod options;
 mod parser;
+mod reflection;
+mod semantic;
+mod spirv;
 mod stream;
+mod target;
 mod tokens;
 mod types;
 
-pub use annotated::{CONSTANT_BUFFER_INDEX, TEXTURES_INDEX};
+pub use annotated::{
+    BUFFERS_INDEX, CONSTANT_BUFFER_INDEX, MAX_BUFFERS, MAX_CONSTANT_BUFFERS, MAX_SAMPLERS,
+    MAX_STORAGE_TEXTURES, MAX_TEXTURES, SAMPLER_STATES_INDEX, STORAGE_TEXTURES_INDEX,
+    TEXTURES_INDEX, VULKAN_BUFFER_SET, VULKAN_CONSTANT_BUFFER_SET, VULKAN_SAMPLER_SET,
+    VULKAN_STORAGE_TEXTURE_SET, VULKAN_TEXTURE_SET,
+};
+pub use compiler::{
+    Compiler, GlslOutput, HlslOutput, HlslStagesOutput, MslOutput, SpirvOutput, WgslOutput,
+};
+pub use options::CompileOptions;
+pub use reflection::{
+    BufferReflection, ConstantBufferReflection, InterstageVariableReflection, Reflection,
+    SamplerReflection, StorageTextureReflection, TextureReflection, VertexAttributeReflection,
+};
+pub use target::{GlslVersion, ShaderModel};
 
 #[derive(Debug)]
 pub enum CompilationError {
     ParserError(parser::ParserError),
-    SemanticAnalysisError(ast::SemanticAnalysisError),
+    // The location of the declaration being analysed, errors about the file as
+    // a whole have none
+    SemanticAnalysisError(Box<ast::SemanticAnalysisError>, Option<(usize, usize)>),
 }
 
-fn compile<S: AsRef<str>>(code: S) -> Result<annotated::AnnotatedSyntaxTree, CompilationError> {
-    // Parse into AST
-    let ast = parser::parse(code.as_ref())?;
+pub fn compile_hlsl<S: AsRef<str>>(code: S) -> Result<String, CompilationError> {
+    Compiler::default()
+        .compile_hlsl(code)
+        .map(|output| output.into_code())
+}
 
-    // Perform semantic analysis
-    let annotated_ast = ast.semantic_analysis()?;
+pub fn compile_glsl<S: AsRef<str>>(code: S) -> Result<(String, String), CompilationError> {
+    Compiler::default()
+        .compile_glsl(code)
+        .map(|output| output.into_code())
+}
 
-    Ok(annotated_ast)
+pub fn compile_wgsl<S: AsRef<str>>(code: S) -> Result<String, CompilationError> {
+    Compiler::default()
+        .compile_wgsl(code)
+        .map(|output| output.into_code())
 }
 
-pub fn compile_hlsl<S: AsRef<str>>(code: S) -> Result<String, CompilationError> {
-    // Compile
-    let ast = compile(code)?;
+pub fn compile_msl<S: AsRef<str>>(code: S) -> Result<String, CompilationError> {
+    Compiler::default()
+        .compile_msl(code)
+        .map(|output| output.into_code())
+}
 
-    // Generate code
-    Ok(ast.generate_hlsl())
+pub fn compile_spirv<S: AsRef<str>>(code: S) -> Result<Vec<u32>, CompilationError> {
+    Compiler::default()
+        .compile_spirv(code)
+        .map(|output| output.into_words())
 }
 
-pub fn compile_glsl<S: AsRef<str>>(code: S) -> Result<(String, String), CompilationError> {
-    // Compile
-    let ast = compile(code)?;
+// Renders a SPIR-V module as text, mainly for inspecting and testing output
+pub fn disassemble_spirv(words: &[u32]) -> String {
+    spirv::disassemble(words)
+}
+
+pub fn reflect<S: AsRef<str>>(code: S) -> Result<Reflection, CompilationError> {
+    Compiler::default().reflect(code)
+}
 
-    // Generate code
-    Ok(ast.generate_glsl())
+impl CompilationError {
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            CompilationError::ParserError(error) => error.location(),
+            CompilationError::SemanticAnalysisError(_, location) => *location,
+        }
+    }
 }
 
 impl std::error::Error for CompilationError {}
@@ -46,7 +91,7 @@ impl std::fmt::Display for CompilationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CompilationError::ParserError(error) => write!(f, "{}", error),
-            CompilationError::SemanticAnalysisError(error) => write!(f, "{}", error),
+            CompilationError::SemanticAnalysisError(error, _) => write!(f, "{}", error),
         }
     }
 }
@@ -59,6 +104,6 @@ impl From<parser::ParserError> for CompilationError {
 
 impl From<ast::SemanticAnalysisError> for CompilationError {
     fn from(error: ast::SemanticAnalysisError) -> Self {
-        CompilationError::SemanticAnalysisError(error)
+        CompilationError::SemanticAnalysisError(Box::new(error), None)
     }
 }
@@ -0,0 +1,229 @@
+use crate::{
+    annotated::{
+        MAX_BUFFERS, MAX_CONSTANT_BUFFERS, MAX_SAMPLERS, MAX_STORAGE_TEXTURES, MAX_TEXTURES,
+    },
+    target::{GlslVersion, ShaderModel},
+};
+
+#[derive(Clone)]
+pub struct CompileOptions {
+    glsl_version: GlslVersion,
+    shader_model: ShaderModel,
+    header: Option<String>,
+    vertex_entry_point: String,
+    fragment_entry_point: String,
+    max_constant_buffers: usize,
+    max_textures: usize,
+    max_buffers: usize,
+    max_storage_textures: usize,
+    max_samplers: usize,
+    indentation: String,
+    flip_y: bool,
+    fix_depth_range: bool,
+    semantic_locations: Vec<(String, usize)>,
+}
+
+const DEFAULT_HEADER: &str = "Generated from Alexandria Common Shader Language";
+
+impl CompileOptions {
+    pub fn new() -> Self {
+        CompileOptions {
+            glsl_version: GlslVersion::Glsl430,
+            shader_model: ShaderModel::Sm50,
+            header: Some(DEFAULT_HEADER.to_owned()),
+            vertex_entry_point: "vertex_main".to_owned(),
+            fragment_entry_point: "fragment_main".to_owned(),
+            max_constant_buffers: MAX_CONSTANT_BUFFERS,
+            max_textures: MAX_TEXTURES,
+            max_buffers: MAX_BUFFERS,
+            max_storage_textures: MAX_STORAGE_TEXTURES,
+            max_samplers: MAX_SAMPLERS,
+            indentation: "    ".to_owned(),
+            flip_y: false,
+            fix_depth_range: false,
+            semantic_locations: Vec::new(),
+        }
+    }
+
+    pub fn glsl_version(mut self, glsl_version: GlslVersion) -> Self {
+        self.glsl_version = glsl_version;
+        self
+    }
+
+    // Selects the HLSL shader model, which decides the features available
+    pub fn shader_model(mut self, shader_model: ShaderModel) -> Self {
+        self.shader_model = shader_model;
+        self
+    }
+
+    // Sets the comment written at the top of every output, "None" removes it
+    pub fn header<S: Into<String>>(mut self, header: Option<S>) -> Self {
+        self.header = header.map(|header| header.into());
+        self
+    }
+
+    // Sets the name of the vertex entry point, GLSL output always uses "main"
+    pub fn vertex_entry_point<S: Into<String>>(mut self, vertex_entry_point: S) -> Self {
+        self.vertex_entry_point = vertex_entry_point.into();
+        self
+    }
+
+    // Sets the name of the fragment entry point, GLSL output always uses "main"
+    pub fn fragment_entry_point<S: Into<String>>(mut self, fragment_entry_point: S) -> Self {
+        self.fragment_entry_point = fragment_entry_point.into();
+        self
+    }
+
+    pub fn max_constant_buffers(mut self, max_constant_buffers: usize) -> Self {
+        self.max_constant_buffers = max_constant_buffers;
+        self
+    }
+
+    pub fn max_textures(mut self, max_textures: usize) -> Self {
+        self.max_textures = max_textures;
+        self
+    }
+
+    pub fn max_buffers(mut self, max_buffers: usize) -> Self {
+        self.max_buffers = max_buffers;
+        self
+    }
+
+    pub fn max_storage_textures(mut self, max_storage_textures: usize) -> Self {
+        self.max_storage_textures = max_storage_textures;
+        self
+    }
+
+    pub fn max_samplers(mut self, max_samplers: usize) -> Self {
+        self.max_samplers = max_samplers;
+        self
+    }
+
+    // Sets the string used for one level of indentation
+    pub fn indentation<S: Into<String>>(mut self, indentation: S) -> Self {
+        self.indentation = indentation.into();
+        self
+    }
+
+    // Negates "gl_Position.y" at the end of the GLSL vertex shader
+    pub fn flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+
+    // Remaps "gl_Position.z" from the [-1, 1] depth range to [0, 1] at the end
+    // of the GLSL vertex shader
+    pub fn fix_depth_range(mut self, fix_depth_range: bool) -> Self {
+        self.fix_depth_range = fix_depth_range;
+        self
+    }
+
+    // Places the vertex attribute or the value passed between the stages with
+    // the semantic at the location, instead of the position of the member in
+    // its structure
+    pub fn semantic_location<S: Into<String>>(mut self, semantic: S, location: usize) -> Self {
+        self.semantic_locations.push((semantic.into(), location));
+        self
+    }
+
+    pub fn get_glsl_version(&self) -> GlslVersion {
+        self.glsl_version
+    }
+
+    pub fn get_shader_model(&self) -> ShaderModel {
+        self.shader_model
+    }
+
+    pub fn get_header(&self) -> Option<&str> {
+        self.header.as_deref()
+    }
+
+    pub fn get_vertex_entry_point(&self) -> &str {
+        &self.vertex_entry_point
+    }
+
+    pub fn get_fragment_entry_point(&self) -> &str {
+        &self.fragment_entry_point
+    }
+
+    pub fn get_max_constant_buffers(&self) -> usize {
+        self.max_constant_buffers
+    }
+
+    pub fn get_max_textures(&self) -> usize {
+        self.max_textures
+    }
+
+    pub fn get_max_buffers(&self) -> usize {
+        self.max_buffers
+    }
+
+    pub fn get_max_storage_textures(&self) -> usize {
+        self.max_storage_textures
+    }
+
+    pub fn get_max_samplers(&self) -> usize {
+        self.max_samplers
+    }
+
+    pub fn get_indentation(&self) -> &str {
+        &self.indentation
+    }
+
+    pub fn get_flip_y(&self) -> bool {
+        self.flip_y
+    }
+
+    pub fn get_fix_depth_range(&self) -> bool {
+        self.fix_depth_range
+    }
+
+    pub fn get_semantic_locations(&self) -> &[(String, usize)] {
+        &self.semantic_locations
+    }
+
+    // The first GLSL uniform location used by textures
+    pub fn textures_index(&self) -> usize {
+        self.max_constant_buffers
+    }
+
+    // The first HLSL "t" register used by read-only buffers, which share the
+    // registers with textures
+    pub fn buffers_index(&self) -> usize {
+        self.max_textures
+    }
+
+    // The first HLSL "u" register used by storage textures, which share the
+    // registers with writable buffers
+    pub fn storage_textures_index(&self) -> usize {
+        self.max_buffers
+    }
+
+    // The first WGSL binding used by samplers
+    pub fn samplers_index(&self) -> usize {
+        self.max_constant_buffers + self.max_textures
+    }
+
+    // The first HLSL "s" register and Vulkan binding used by sampler
+    // declarations, which follow the samplers of the textures
+    pub fn sampler_states_index(&self) -> usize {
+        self.max_textures
+    }
+
+    pub(crate) fn indent(&self, level: usize) -> String {
+        self.indentation.repeat(level)
+    }
+
+    pub(crate) fn header_comment(&self) -> String {
+        match &self.header {
+            Some(header) => format!("// {}\n\n", header),
+            None => String::new(),
+        }
+    }
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        CompileOptions::new()
+    }
+}
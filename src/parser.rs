@@ -1,8 +1,15 @@
 use crate::{
     ast::{
         declaration::{
-            constant::parse_constant, constant_buffer::parse_constant_buffer,
-            function::parse_function, structure::parse_struct, texture::parse_texture,
+            buffer::parse_buffer,
+            constant::parse_constant,
+            constant_buffer::parse_constant_buffer,
+            function::{parse_attributed_function, parse_function},
+            group_shared::parse_group_shared,
+            sampler::parse_sampler,
+            storage_texture::parse_storage_texture,
+            structure::parse_struct,
+            texture::{parse_depth_texture, parse_texture},
             type_alias::parse_type_alias,
         },
         AbstractSyntaxTree,
@@ -10,6 +17,7 @@ use crate::{
     lexer,
     stream::Stream,
     tokens::{Token, TokenClass},
+    types::TextureDimension,
 };
 
 #[derive(Debug)]
@@ -37,23 +45,52 @@ pub fn parse(code: &str) -> Result<AbstractSyntaxTree, ParserError> {
     let mut stream = Stream::new(code);
 
     while let Some(token) = lexer::next_token(&mut stream)? {
-        ast.push(match token.class() {
+        let location = (token.line(), token.column());
+        let declaration = match token.class() {
             TokenClass::Fn => parse_function(&mut stream)?,
             TokenClass::Struct => parse_struct(&mut stream)?,
             TokenClass::CBuffer => parse_constant_buffer(&mut stream)?,
             TokenClass::Type => parse_type_alias(&mut stream)?,
             TokenClass::Const => parse_constant(&mut stream)?,
+            TokenClass::Hash => parse_attributed_function(&mut stream)?,
             TokenClass::Identifier(identifier) => match identifier.as_str() {
-                "texture" => parse_texture(&mut stream)?,
+                "texture" => parse_texture(&mut stream, TextureDimension::D2)?,
+                "texture1d" => parse_texture(&mut stream, TextureDimension::D1)?,
+                "texture3d" => parse_texture(&mut stream, TextureDimension::D3)?,
+                "texture_cube" => parse_texture(&mut stream, TextureDimension::Cube)?,
+                "texture2d_array" => parse_texture(&mut stream, TextureDimension::D2Array)?,
+                "texture_cube_array" => parse_texture(&mut stream, TextureDimension::CubeArray)?,
+                "depth_texture" => parse_depth_texture(&mut stream, TextureDimension::D2)?,
+                "depth_texture_cube" => parse_depth_texture(&mut stream, TextureDimension::Cube)?,
+                "depth_texture2d_array" => {
+                    parse_depth_texture(&mut stream, TextureDimension::D2Array)?
+                }
+                "buffer" => parse_buffer(&mut stream, false)?,
+                "rwbuffer" => parse_buffer(&mut stream, true)?,
+                "rwtexture" => parse_storage_texture(&mut stream)?,
+                "groupshared" => parse_group_shared(&mut stream)?,
+                "sampler" => parse_sampler(&mut stream)?,
                 _ => return Err(ParserError::UnexpectedToken(token)),
             },
             _ => return Err(ParserError::UnexpectedToken(token)),
-        })
+        };
+
+        ast.push(declaration, location);
     }
 
     Ok(ast)
 }
 
+impl ParserError {
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            ParserError::LexerError(error) => Some(error.location()),
+            ParserError::UnexpectedToken(token) => Some((token.line(), token.column())),
+            ParserError::UnexpectedEOF => None,
+        }
+    }
+}
+
 impl std::error::Error for ParserError {}
 
 impl std::fmt::Display for ParserError {
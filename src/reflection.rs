@@ -0,0 +1,675 @@
+use crate::{
+    annotated::{sampler::Sampler, texture::Texture},
+    types::Type,
+};
+
+pub struct Reflection {
+    constant_buffers: Vec<ConstantBufferReflection>,
+    textures: Vec<TextureReflection>,
+    buffers: Vec<BufferReflection>,
+    storage_textures: Vec<StorageTextureReflection>,
+    samplers: Vec<SamplerReflection>,
+    vertex_attributes: Vec<VertexAttributeReflection>,
+    interstage_variables: Vec<InterstageVariableReflection>,
+}
+
+pub struct ConstantBufferReflection {
+    name: String,
+    slot: usize,
+    space: usize,
+    type_name: String,
+    glsl_location: Option<usize>,
+}
+
+pub struct TextureReflection {
+    name: String,
+    slot: usize,
+    space: usize,
+    type_name: String,
+    dimension: String,
+    depth: bool,
+    glsl_location: Option<usize>,
+}
+
+pub struct BufferReflection {
+    name: String,
+    slot: usize,
+    space: usize,
+    type_name: String,
+    stride: usize,
+    writable: bool,
+    hlsl_register: usize,
+}
+
+pub struct StorageTextureReflection {
+    name: String,
+    slot: usize,
+    space: usize,
+    type_name: String,
+    format: String,
+    hlsl_register: usize,
+}
+
+pub struct SamplerReflection {
+    name: String,
+    slot: usize,
+    space: usize,
+    filter: String,
+    address: String,
+    compare: Option<String>,
+    hlsl_register: usize,
+    textures: Vec<String>,
+}
+
+pub struct VertexAttributeReflection {
+    name: String,
+    semantic: String,
+    location: usize,
+    type_name: String,
+}
+
+// A value passed from "vertex_main" to "fragment_main" through a location
+pub struct InterstageVariableReflection {
+    name: String,
+    semantic: String,
+    location: usize,
+    type_name: String,
+}
+
+struct JsonWriter {
+    json: String,
+    indent: usize,
+    first: bool,
+}
+
+impl Reflection {
+    pub(crate) fn new() -> Self {
+        Reflection {
+            constant_buffers: Vec::new(),
+            textures: Vec::new(),
+            buffers: Vec::new(),
+            storage_textures: Vec::new(),
+            samplers: Vec::new(),
+            vertex_attributes: Vec::new(),
+            interstage_variables: Vec::new(),
+        }
+    }
+
+    pub fn constant_buffers(&self) -> &[ConstantBufferReflection] {
+        &self.constant_buffers
+    }
+
+    pub fn textures(&self) -> &[TextureReflection] {
+        &self.textures
+    }
+
+    pub fn buffers(&self) -> &[BufferReflection] {
+        &self.buffers
+    }
+
+    pub fn storage_textures(&self) -> &[StorageTextureReflection] {
+        &self.storage_textures
+    }
+
+    pub fn samplers(&self) -> &[SamplerReflection] {
+        &self.samplers
+    }
+
+    pub fn vertex_attributes(&self) -> &[VertexAttributeReflection] {
+        &self.vertex_attributes
+    }
+
+    pub fn interstage_variables(&self) -> &[InterstageVariableReflection] {
+        &self.interstage_variables
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut writer = JsonWriter::new();
+
+        writer.begin_object(None);
+
+        writer.begin_array("constant_buffers");
+        for constant_buffer in &self.constant_buffers {
+            writer.begin_object(None);
+            writer.string("name", &constant_buffer.name);
+            writer.number("slot", constant_buffer.slot);
+            writer.number("space", constant_buffer.space);
+            writer.string("type", &constant_buffer.type_name);
+            writer.optional_number("glsl_location", constant_buffer.glsl_location);
+            writer.end_object();
+        }
+        writer.end_array();
+
+        writer.begin_array("textures");
+        for texture in &self.textures {
+            writer.begin_object(None);
+            writer.string("name", &texture.name);
+            writer.number("slot", texture.slot);
+            writer.number("space", texture.space);
+            writer.string("type", &texture.type_name);
+            writer.string("dimension", &texture.dimension);
+            writer.boolean("depth", texture.depth);
+            writer.optional_number("glsl_location", texture.glsl_location);
+            writer.end_object();
+        }
+        writer.end_array();
+
+        writer.begin_array("buffers");
+        for buffer in &self.buffers {
+            writer.begin_object(None);
+            writer.string("name", &buffer.name);
+            writer.number("slot", buffer.slot);
+            writer.number("space", buffer.space);
+            writer.string("type", &buffer.type_name);
+            writer.number("stride", buffer.stride);
+            writer.boolean("writable", buffer.writable);
+            writer.number("hlsl_register", buffer.hlsl_register);
+            writer.end_object();
+        }
+        writer.end_array();
+
+        writer.begin_array("storage_textures");
+        for storage_texture in &self.storage_textures {
+            writer.begin_object(None);
+            writer.string("name", &storage_texture.name);
+            writer.number("slot", storage_texture.slot);
+            writer.number("space", storage_texture.space);
+            writer.string("type", &storage_texture.type_name);
+            writer.string("format", &storage_texture.format);
+            writer.number("hlsl_register", storage_texture.hlsl_register);
+            writer.end_object();
+        }
+        writer.end_array();
+
+        writer.begin_array("samplers");
+        for sampler in &self.samplers {
+            writer.begin_object(None);
+            writer.string("name", &sampler.name);
+            writer.number("slot", sampler.slot);
+            writer.number("space", sampler.space);
+            writer.string("filter", &sampler.filter);
+            writer.string("address", &sampler.address);
+            writer.optional_string("compare", sampler.compare.as_deref());
+            writer.number("hlsl_register", sampler.hlsl_register);
+            writer.strings("textures", &sampler.textures);
+            writer.end_object();
+        }
+        writer.end_array();
+
+        writer.begin_array("vertex_attributes");
+        for vertex_attribute in &self.vertex_attributes {
+            writer.begin_object(None);
+            writer.string("name", &vertex_attribute.name);
+            writer.string("semantic", &vertex_attribute.semantic);
+            writer.number("location", vertex_attribute.location);
+            writer.string("type", &vertex_attribute.type_name);
+            writer.end_object();
+        }
+        writer.end_array();
+
+        writer.begin_array("interstage_variables");
+        for interstage_variable in &self.interstage_variables {
+            writer.begin_object(None);
+            writer.string("name", &interstage_variable.name);
+            writer.string("semantic", &interstage_variable.semantic);
+            writer.number("location", interstage_variable.location);
+            writer.string("type", &interstage_variable.type_name);
+            writer.end_object();
+        }
+        writer.end_array();
+
+        writer.end_object();
+
+        writer.finish()
+    }
+
+    pub(crate) fn push_constant_buffer(
+        &mut self,
+        name: String,
+        slot: usize,
+        space: usize,
+        type_name: String,
+        glsl_location: Option<usize>,
+    ) {
+        self.constant_buffers.push(ConstantBufferReflection {
+            name,
+            slot,
+            space,
+            type_name,
+            glsl_location,
+        })
+    }
+
+    // The declaration holds the binding and shape of the texture
+    pub(crate) fn push_texture(&mut self, texture: &Texture, glsl_location: Option<usize>) {
+        self.textures.push(TextureReflection {
+            name: texture.name().to_owned(),
+            slot: texture.slot(),
+            space: texture.space(),
+            type_name: texture.texture_type().to_string(),
+            dimension: texture.dimension().name().to_owned(),
+            depth: texture.is_depth(),
+            glsl_location,
+        })
+    }
+
+    pub(crate) fn push_buffer(
+        &mut self,
+        name: String,
+        slot: usize,
+        space: usize,
+        element_type: &Type,
+        writable: bool,
+        hlsl_register: usize,
+    ) {
+        self.buffers.push(BufferReflection {
+            name,
+            slot,
+            space,
+            type_name: element_type.to_string(),
+            stride: element_type.packed_size(),
+            writable,
+            hlsl_register,
+        })
+    }
+
+    pub(crate) fn push_storage_texture(
+        &mut self,
+        name: String,
+        slot: usize,
+        space: usize,
+        type_name: String,
+        format: String,
+        hlsl_register: usize,
+    ) {
+        self.storage_textures.push(StorageTextureReflection {
+            name,
+            slot,
+            space,
+            type_name,
+            format,
+            hlsl_register,
+        })
+    }
+
+    // The declaration holds the binding and state of the sampler
+    pub(crate) fn push_sampler(
+        &mut self,
+        sampler: &Sampler,
+        hlsl_register: usize,
+        textures: Vec<String>,
+    ) {
+        self.samplers.push(SamplerReflection {
+            name: sampler.name().to_owned(),
+            slot: sampler.slot(),
+            space: sampler.space(),
+            filter: sampler.filter().name().to_owned(),
+            address: sampler.address().name().to_owned(),
+            compare: sampler.compare().map(|compare| compare.name().to_owned()),
+            hlsl_register,
+            textures,
+        })
+    }
+
+    pub(crate) fn push_vertex_attribute(
+        &mut self,
+        name: String,
+        semantic: String,
+        location: usize,
+        type_name: String,
+    ) {
+        self.vertex_attributes.push(VertexAttributeReflection {
+            name,
+            semantic,
+            location,
+            type_name,
+        })
+    }
+
+    pub(crate) fn push_interstage_variable(
+        &mut self,
+        name: String,
+        semantic: String,
+        location: usize,
+        type_name: String,
+    ) {
+        self.interstage_variables
+            .push(InterstageVariableReflection {
+                name,
+                semantic,
+                location,
+                type_name,
+            })
+    }
+}
+
+impl ConstantBufferReflection {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    // The HLSL register space, 0 unless declared with "space"
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    // "None" when the GLSL version has no explicit uniform locations, the
+    // uniform must then be located by name
+    pub fn glsl_location(&self) -> Option<usize> {
+        self.glsl_location
+    }
+}
+
+impl TextureReflection {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    // The HLSL register space, 0 unless declared with "space"
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    // "1d", "2d", "3d", "cube", "2d_array" or "cube_array"
+    pub fn dimension(&self) -> &str {
+        &self.dimension
+    }
+
+    // Depth textures are sampled with a comparison sampler, which OpenGL
+    // enables on the texture with "GL_TEXTURE_COMPARE_MODE"
+    pub fn depth(&self) -> bool {
+        self.depth
+    }
+
+    // "None" when the GLSL version has no explicit uniform locations, the
+    // sampler must then be located by name and set to texture unit "slot"
+    pub fn glsl_location(&self) -> Option<usize> {
+        self.glsl_location
+    }
+}
+
+impl BufferReflection {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Also the GLSL binding of the shader storage block
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    // The HLSL register space, 0 unless declared with "space"
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    // The type of each element
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    // The bytes from the start of one element to the next, the same for
+    // every target
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn writable(&self) -> bool {
+        self.writable
+    }
+
+    // The "u" register of writable buffers, otherwise the "t" register
+    pub fn hlsl_register(&self) -> usize {
+        self.hlsl_register
+    }
+}
+
+impl StorageTextureReflection {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Also the GLSL image binding
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    // The HLSL register space, 0 unless declared with "space"
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    // The type of each texel
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    // The GLSL image format, such as "rgba8"
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    // The "u" register
+    pub fn hlsl_register(&self) -> usize {
+        self.hlsl_register
+    }
+}
+
+impl SamplerReflection {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    // The HLSL register space, 0 unless declared with "space"
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    // "point" or "linear", for minification, magnification and mip levels
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    // "wrap", "clamp" or "mirror", for every coordinate
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    // The comparison of a comparison sampler, as in "less_equal"
+    pub fn compare(&self) -> Option<&str> {
+        self.compare.as_deref()
+    }
+
+    // The "s" register, also the binding in the Vulkan sampler set
+    pub fn hlsl_register(&self) -> usize {
+        self.hlsl_register
+    }
+
+    // The textures sampled with the sampler, OpenGL has no separate samplers
+    // in GLSL so the sampler state must be applied to their texture units
+    pub fn textures(&self) -> &[String] {
+        &self.textures
+    }
+}
+
+impl VertexAttributeReflection {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn semantic(&self) -> &str {
+        &self.semantic
+    }
+
+    pub fn location(&self) -> usize {
+        self.location
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+impl InterstageVariableReflection {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn semantic(&self) -> &str {
+        &self.semantic
+    }
+
+    pub fn location(&self) -> usize {
+        self.location
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+impl JsonWriter {
+    fn new() -> Self {
+        JsonWriter {
+            json: String::new(),
+            indent: 0,
+            first: true,
+        }
+    }
+
+    fn begin_object(&mut self, key: Option<&str>) {
+        self.key(key);
+        self.json.push('{');
+        self.indent += 1;
+        self.first = true;
+    }
+
+    fn end_object(&mut self) {
+        self.close('}');
+    }
+
+    fn begin_array(&mut self, key: &str) {
+        self.key(Some(key));
+        self.json.push('[');
+        self.indent += 1;
+        self.first = true;
+    }
+
+    fn end_array(&mut self) {
+        self.close(']');
+    }
+
+    fn string(&mut self, key: &str, value: &str) {
+        self.key(Some(key));
+        self.quoted(value);
+    }
+
+    fn strings(&mut self, key: &str, values: &[String]) {
+        self.begin_array(key);
+        for value in values {
+            self.key(None);
+            self.quoted(value);
+        }
+        self.end_array();
+    }
+
+    fn quoted(&mut self, value: &str) {
+        self.json.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => self.json.push_str("\\\""),
+                '\\' => self.json.push_str("\\\\"),
+                '\n' => self.json.push_str("\\n"),
+                '\t' => self.json.push_str("\\t"),
+                c if (c as u32) < 0x20 => self.json.push_str(&format!("\\u{:04x}", c as u32)),
+                c => self.json.push(c),
+            }
+        }
+        self.json.push('"');
+    }
+
+    fn number(&mut self, key: &str, value: usize) {
+        self.key(Some(key));
+        self.json.push_str(&value.to_string());
+    }
+
+    fn boolean(&mut self, key: &str, value: bool) {
+        self.key(Some(key));
+        self.json.push_str(if value { "true" } else { "false" });
+    }
+
+    fn optional_number(&mut self, key: &str, value: Option<usize>) {
+        match value {
+            Some(value) => self.number(key, value),
+            None => {
+                self.key(Some(key));
+                self.json.push_str("null");
+            }
+        }
+    }
+
+    fn optional_string(&mut self, key: &str, value: Option<&str>) {
+        match value {
+            Some(value) => self.string(key, value),
+            None => {
+                self.key(Some(key));
+                self.json.push_str("null");
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.json.push('\n');
+        self.json
+    }
+
+    fn key(&mut self, key: Option<&str>) {
+        if self.indent > 0 {
+            if !self.first {
+                self.json.push(',');
+            }
+            self.newline();
+        }
+        self.first = false;
+
+        if let Some(key) = key {
+            self.json.push_str(&format!("\"{}\": ", key));
+        }
+    }
+
+    fn close(&mut self, c: char) {
+        self.indent -= 1;
+        if !self.first {
+            self.newline();
+        }
+        self.json.push(c);
+        self.first = false;
+    }
+
+    fn newline(&mut self) {
+        self.json.push('\n');
+        for _ in 0..self.indent * 2 {
+            self.json.push(' ');
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{feature::Feature, spirv, types::Type};
+
+// The semantic of a structure member, system values are read or written by
+// the pipeline while user semantics only link the stages together
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Semantic {
+    Position,
+    Depth,
+    Target(usize),
+    IsFrontFace,
+    VertexId,
+    InstanceId,
+    // The tessellation factors of the edges and the inside of a patch
+    TessFactor,
+    InsideTessFactor,
+    // "TEXCOORD1" is "TEXCOORD" with the index 1, no index is the index 0,
+    // names are compared without case like HLSL
+    User(String, usize),
+}
+
+// Where a structure with semantics enters or leaves a stage
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stage {
+    VertexInput,
+    // Passed between the stages ahead of the rasterizer, as the control points
+    // of a patch or the vertices of a primitive
+    Intermediate,
+    PatchConstants,
+    Interstage,
+    FragmentOutput,
+}
+
+// The shape tessellated patches are subdivided as, decided by the type of the
+// "SV_TESS_FACTOR" of the patch constants
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Domain {
+    Triangle,
+    Quad,
+}
+
+// How a value passed from "vertex_main" to "fragment_main" is interpolated
+// across the primitive, "#[flat]" takes the value of the provoking vertex
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Interpolation {
+    Flat,
+    NoPerspective,
+    Centroid,
+    Sample,
+}
+
+impl Semantic {
+    // Unknown or misspelled system values are None
+    pub fn parse(semantic: &str) -> Option<Self> {
+        let split = semantic.len()
+            - semantic
+                .bytes()
+                .rev()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+        let (name, index) = semantic.split_at(split);
+        let index = match index {
+            "" => 0,
+            index => index.parse().ok()?,
+        };
+
+        // System values are spelled in capitals, HLSL would still read other
+        // spellings as system values
+        if name.to_ascii_uppercase().starts_with("SV_") {
+            return match (name, index) {
+                ("SV_TARGET", index) if index < 8 => Some(Semantic::Target(index)),
+                (_, 0) if name != semantic => None,
+                ("SV_POSITION", 0) => Some(Semantic::Position),
+                ("SV_DEPTH", 0) => Some(Semantic::Depth),
+                ("SV_IS_FRONT_FACE", 0) => Some(Semantic::IsFrontFace),
+                ("SV_VERTEX_ID", 0) => Some(Semantic::VertexId),
+                ("SV_INSTANCE_ID", 0) => Some(Semantic::InstanceId),
+                ("SV_TESS_FACTOR", 0) => Some(Semantic::TessFactor),
+                ("SV_INSIDE_TESS_FACTOR", 0) => Some(Semantic::InsideTessFactor),
+                _ => None,
+            };
+        }
+
+        Some(Semantic::User(name.to_ascii_uppercase(), index))
+    }
+
+    // The type the pipeline reads or writes a system value with
+    pub fn value_type(&self) -> Option<(Type, &'static str)> {
+        match self {
+            Semantic::Position => Some((Type::float4(), "float4")),
+            Semantic::Depth => Some((Type::float(), "float")),
+            Semantic::IsFrontFace | Semantic::VertexId | Semantic::InstanceId => {
+                Some((Type::uint(), "uint"))
+            }
+            // The tessellation factors depend on the domain
+            Semantic::Target(_)
+            | Semantic::User(_, _)
+            | Semantic::TessFactor
+            | Semantic::InsideTessFactor => None,
+        }
+    }
+
+    // Whether the value goes through a location rather than a builtin
+    pub fn is_located_in(&self, stage: Stage) -> bool {
+        match self {
+            Semantic::User(_, _) => !matches!(stage, Stage::PatchConstants | Stage::FragmentOutput),
+            Semantic::Position => stage == Stage::VertexInput,
+            _ => false,
+        }
+    }
+
+    pub fn is_valid_in(&self, stage: Stage) -> bool {
+        match self {
+            Semantic::User(_, _) => !matches!(stage, Stage::PatchConstants | Stage::FragmentOutput),
+            Semantic::VertexId | Semantic::InstanceId => stage == Stage::VertexInput,
+            // A vertex input position is read from a vertex buffer like any
+            // other attribute
+            Semantic::Position => !matches!(stage, Stage::PatchConstants | Stage::FragmentOutput),
+            Semantic::IsFrontFace => stage == Stage::Interstage,
+            Semantic::Target(_) | Semantic::Depth => stage == Stage::FragmentOutput,
+            Semantic::TessFactor | Semantic::InsideTessFactor => stage == Stage::PatchConstants,
+        }
+    }
+}
+
+impl Interpolation {
+    pub fn parse(attribute: &str) -> Option<Self> {
+        match attribute {
+            "flat" => Some(Interpolation::Flat),
+            "noperspective" => Some(Interpolation::NoPerspective),
+            "centroid" => Some(Interpolation::Centroid),
+            "sample" => Some(Interpolation::Sample),
+            _ => None,
+        }
+    }
+
+    pub fn feature(&self) -> Option<Feature> {
+        match self {
+            Interpolation::NoPerspective => Some(Feature::NoPerspectiveInterpolation),
+            Interpolation::Sample => Some(Feature::SampleInterpolation),
+            Interpolation::Flat | Interpolation::Centroid => None,
+        }
+    }
+
+    pub fn hlsl(&self) -> &'static str {
+        match self {
+            Interpolation::Flat => "nointerpolation",
+            Interpolation::NoPerspective => "noperspective",
+            Interpolation::Centroid => "centroid",
+            Interpolation::Sample => "sample",
+        }
+    }
+
+    pub fn glsl(&self) -> &'static str {
+        match self {
+            Interpolation::Flat => "flat",
+            Interpolation::NoPerspective => "noperspective",
+            Interpolation::Centroid => "centroid",
+            Interpolation::Sample => "sample",
+        }
+    }
+
+    pub fn wgsl(&self) -> &'static str {
+        match self {
+            Interpolation::Flat => "flat",
+            Interpolation::NoPerspective => "linear",
+            Interpolation::Centroid => "perspective, centroid",
+            Interpolation::Sample => "perspective, sample",
+        }
+    }
+
+    pub fn msl(&self) -> &'static str {
+        match self {
+            Interpolation::Flat => "flat",
+            Interpolation::NoPerspective => "center_no_perspective",
+            Interpolation::Centroid => "centroid_perspective",
+            Interpolation::Sample => "sample_perspective",
+        }
+    }
+
+    pub fn spirv_decoration(&self) -> u32 {
+        match self {
+            Interpolation::Flat => spirv::DECORATION_FLAT,
+            Interpolation::NoPerspective => spirv::DECORATION_NO_PERSPECTIVE,
+            Interpolation::Centroid => spirv::DECORATION_CENTROID,
+            Interpolation::Sample => spirv::DECORATION_SAMPLE,
+        }
+    }
+}
+
+impl Domain {
+    // The domain of the "SV_TESS_FACTOR" and "SV_INSIDE_TESS_FACTOR" types
+    pub fn from_factors(edges: &Type, inside: &Type) -> Option<Self> {
+        if *edges == Type::float3() && *inside == Type::float() {
+            Some(Domain::Triangle)
+        } else if *edges == Type::float4() && *inside == Type::float2() {
+            Some(Domain::Quad)
+        } else {
+            None
+        }
+    }
+
+    pub fn edges(&self) -> usize {
+        match self {
+            Domain::Triangle => 3,
+            Domain::Quad => 4,
+        }
+    }
+
+    pub fn inside(&self) -> usize {
+        match self {
+            Domain::Triangle => 1,
+            Domain::Quad => 2,
+        }
+    }
+
+    // The type of the location "domain_main" is called at, barycentric
+    // coordinates for triangles
+    pub fn location_type(&self) -> Type {
+        match self {
+            Domain::Triangle => Type::float3(),
+            Domain::Quad => Type::float2(),
+        }
+    }
+
+    pub fn hlsl(&self) -> &'static str {
+        match self {
+            Domain::Triangle => "tri",
+            Domain::Quad => "quad",
+        }
+    }
+
+    pub fn glsl(&self) -> &'static str {
+        match self {
+            Domain::Triangle => "triangles",
+            Domain::Quad => "quads",
+        }
+    }
+}
+
+impl std::fmt::Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::VertexInput => write!(f, "the input of \"vertex_main()\""),
+            Stage::Intermediate => write!(
+                f,
+                "the control points or vertices passed between the stages before \"fragment_main()\""
+            ),
+            Stage::PatchConstants => write!(f, "the output of \"hull_constants()\""),
+            Stage::Interstage => write!(
+                f,
+                "the structure passed from \"vertex_main()\" to \"fragment_main()\""
+            ),
+            Stage::FragmentOutput => write!(f, "the output of \"fragment_main()\""),
+        }
+    }
+}
+
+impl std::fmt::Display for Interpolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Interpolation::Flat => write!(f, "flat"),
+            Interpolation::NoPerspective => write!(f, "noperspective"),
+            Interpolation::Centroid => write!(f, "centroid"),
+            Interpolation::Sample => write!(f, "sample"),
+        }
+    }
+}
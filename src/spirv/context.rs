@@ -0,0 +1,111 @@
+use super::{Id, Module, OP_ACCESS_CHAIN, OP_LOAD, STORAGE_CLASS_UNIFORM};
+use crate::{annotated::expression::Expression, types::Type};
+use std::collections::HashMap;
+
+// Resources declared as global variables
+pub enum Global {
+    ConstantBuffer {
+        variable: Id,
+    },
+    Texture {
+        image: Id,
+        image_type: Id,
+        sampler: Id,
+        sampler_type: Id,
+        uint: bool,
+    },
+    Sampler {
+        sampler: Id,
+        sampler_type: Id,
+    },
+}
+
+// State shared while generating the functions of a module. ACSL has no
+// control flow, so locals are tracked as the id of their current value.
+pub struct Context<'a> {
+    pub module: Module,
+
+    globals: HashMap<String, Global>,
+    constants: HashMap<String, &'a Expression>,
+    functions: HashMap<String, (Id, Id)>,
+    locals: HashMap<String, Id>,
+}
+
+impl<'a> Context<'a> {
+    pub fn new() -> Self {
+        Context {
+            module: Module::new(),
+            globals: HashMap::new(),
+            constants: HashMap::new(),
+            functions: HashMap::new(),
+            locals: HashMap::new(),
+        }
+    }
+
+    pub fn define_global(&mut self, name: &str, global: Global) {
+        self.globals.insert(name.to_owned(), global);
+    }
+
+    // Constants are generated where they are used, constant expressions are
+    // folded into constant instructions by the module
+    pub fn define_constant(&mut self, name: &str, expression: &'a Expression) {
+        self.constants.insert(name.to_owned(), expression);
+    }
+
+    pub fn define_function(&mut self, name: &str, function: Id, return_type: Id) {
+        self.functions
+            .insert(name.to_owned(), (function, return_type));
+    }
+
+    pub fn function(&self, name: &str) -> Option<(Id, Id)> {
+        self.functions.get(name).cloned()
+    }
+
+    pub fn texture(&self, name: &str) -> Option<&Global> {
+        match self.globals.get(name) {
+            Some(texture @ Global::Texture { .. }) => Some(texture),
+            _ => None,
+        }
+    }
+
+    pub fn sampler(&self, name: &str) -> Option<&Global> {
+        match self.globals.get(name) {
+            Some(sampler @ Global::Sampler { .. }) => Some(sampler),
+            _ => None,
+        }
+    }
+
+    pub fn set_local(&mut self, name: &str, value: Id) {
+        self.locals.insert(name.to_owned(), value);
+    }
+
+    pub fn clear_locals(&mut self) {
+        self.locals.clear();
+    }
+
+    // Returns the id holding the current value of a variable
+    pub fn variable(&mut self, name: &str, variable_type: &Type) -> Id {
+        if let Some(value) = self.locals.get(name) {
+            return *value;
+        }
+
+        if let Some(expression) = self.constants.get(name).cloned() {
+            return expression.spirv(self);
+        }
+
+        match self.globals.get(name) {
+            Some(Global::ConstantBuffer { variable }) => {
+                let variable = *variable;
+                let value_type = variable_type.spirv(&mut self.module);
+                let pointer_type = self.module.type_pointer(STORAGE_CLASS_UNIFORM, value_type);
+                let member = self.module.constant_int(0);
+
+                let pointer =
+                    self.module
+                        .emit_result(OP_ACCESS_CHAIN, pointer_type, &[variable, member]);
+                self.module.emit_result(OP_LOAD, value_type, &[pointer])
+            }
+            _ => panic!("\"{}\" cannot be used as a value in SPIR-V", name),
+        }
+    }
+}
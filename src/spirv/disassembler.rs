@@ -0,0 +1,382 @@
+use super::*;
+
+#[derive(Clone, Copy)]
+enum Operand {
+    Id,
+    Literal,
+    String,
+    Capability,
+    AddressingModel,
+    MemoryModel,
+    ExecutionModel,
+    ExecutionMode,
+    StorageClass,
+    Decoration,
+    Dim,
+    FunctionControl,
+    ImageOperands,
+    ExtInstruction,
+}
+
+// Name, whether the instruction has a result type and result id, and its
+// remaining operands. The last operand repeats until the instruction ends.
+struct Instruction {
+    name: &'static str,
+    result_type: bool,
+    result: bool,
+    operands: &'static [Operand],
+}
+
+const fn instruction(
+    name: &'static str,
+    result_type: bool,
+    result: bool,
+    operands: &'static [Operand],
+) -> Instruction {
+    Instruction {
+        name,
+        result_type,
+        result,
+        operands,
+    }
+}
+
+fn lookup(opcode: u16) -> Option<Instruction> {
+    use Operand::*;
+
+    Some(match opcode {
+        OP_NAME => instruction("OpName", false, false, &[Id, String]),
+        OP_MEMBER_NAME => instruction("OpMemberName", false, false, &[Id, Literal, String]),
+        OP_EXT_INST_IMPORT => instruction("OpExtInstImport", false, true, &[String]),
+        OP_EXT_INST => instruction("OpExtInst", true, true, &[Id, ExtInstruction, Id]),
+        OP_MEMORY_MODEL => instruction(
+            "OpMemoryModel",
+            false,
+            false,
+            &[AddressingModel, MemoryModel],
+        ),
+        OP_ENTRY_POINT => instruction(
+            "OpEntryPoint",
+            false,
+            false,
+            &[ExecutionModel, Id, String, Id],
+        ),
+        OP_EXECUTION_MODE => instruction("OpExecutionMode", false, false, &[Id, ExecutionMode]),
+        OP_CAPABILITY => instruction("OpCapability", false, false, &[Capability]),
+        OP_TYPE_VOID => instruction("OpTypeVoid", false, true, &[]),
+        OP_TYPE_BOOL => instruction("OpTypeBool", false, true, &[]),
+        OP_TYPE_INT => instruction("OpTypeInt", false, true, &[Literal, Literal]),
+        OP_TYPE_FLOAT => instruction("OpTypeFloat", false, true, &[Literal]),
+        OP_TYPE_VECTOR => instruction("OpTypeVector", false, true, &[Id, Literal]),
+        OP_TYPE_MATRIX => instruction("OpTypeMatrix", false, true, &[Id, Literal]),
+        OP_TYPE_IMAGE => instruction(
+            "OpTypeImage",
+            false,
+            true,
+            &[Id, Dim, Literal, Literal, Literal, Literal, Literal],
+        ),
+        OP_TYPE_SAMPLER => instruction("OpTypeSampler", false, true, &[]),
+        OP_TYPE_SAMPLED_IMAGE => instruction("OpTypeSampledImage", false, true, &[Id]),
+        OP_TYPE_STRUCT => instruction("OpTypeStruct", false, true, &[Id]),
+        OP_TYPE_POINTER => instruction("OpTypePointer", false, true, &[StorageClass, Id]),
+        OP_TYPE_FUNCTION => instruction("OpTypeFunction", false, true, &[Id]),
+        OP_CONSTANT => instruction("OpConstant", true, true, &[Literal]),
+        OP_CONSTANT_COMPOSITE => instruction("OpConstantComposite", true, true, &[Id]),
+        OP_FUNCTION => instruction("OpFunction", true, true, &[FunctionControl, Id]),
+        OP_FUNCTION_PARAMETER => instruction("OpFunctionParameter", true, true, &[]),
+        OP_FUNCTION_END => instruction("OpFunctionEnd", false, false, &[]),
+        OP_FUNCTION_CALL => instruction("OpFunctionCall", true, true, &[Id]),
+        OP_VARIABLE => instruction("OpVariable", true, true, &[StorageClass]),
+        OP_LOAD => instruction("OpLoad", true, true, &[Id]),
+        OP_STORE => instruction("OpStore", false, false, &[Id, Id]),
+        OP_ACCESS_CHAIN => instruction("OpAccessChain", true, true, &[Id]),
+        OP_DECORATE => instruction("OpDecorate", false, false, &[Id, Decoration, Literal]),
+        OP_MEMBER_DECORATE => instruction(
+            "OpMemberDecorate",
+            false,
+            false,
+            &[Id, Literal, Decoration, Literal],
+        ),
+        OP_VECTOR_SHUFFLE => instruction("OpVectorShuffle", true, true, &[Id, Id, Literal]),
+        OP_COMPOSITE_CONSTRUCT => instruction("OpCompositeConstruct", true, true, &[Id]),
+        OP_COMPOSITE_EXTRACT => instruction("OpCompositeExtract", true, true, &[Id, Literal]),
+        OP_COMPOSITE_INSERT => instruction("OpCompositeInsert", true, true, &[Id, Id, Literal]),
+        OP_SAMPLED_IMAGE => instruction("OpSampledImage", true, true, &[Id, Id]),
+        OP_IMAGE_SAMPLE_IMPLICIT_LOD => instruction(
+            "OpImageSampleImplicitLod",
+            true,
+            true,
+            &[Id, Id, ImageOperands, Id],
+        ),
+        OP_IMAGE_SAMPLE_EXPLICIT_LOD => instruction(
+            "OpImageSampleExplicitLod",
+            true,
+            true,
+            &[Id, Id, ImageOperands, Id],
+        ),
+        OP_IMAGE_SAMPLE_DREF_IMPLICIT_LOD => {
+            instruction("OpImageSampleDrefImplicitLod", true, true, &[Id, Id, Id])
+        }
+        OP_IMAGE_SAMPLE_DREF_EXPLICIT_LOD => instruction(
+            "OpImageSampleDrefExplicitLod",
+            true,
+            true,
+            &[Id, Id, Id, ImageOperands, Id],
+        ),
+        OP_IMAGE_FETCH => instruction("OpImageFetch", true, true, &[Id, Id, ImageOperands, Id]),
+        OP_IMAGE_GATHER => instruction("OpImageGather", true, true, &[Id, Id, Id]),
+        OP_IMAGE_QUERY_SIZE_LOD => instruction("OpImageQuerySizeLod", true, true, &[Id, Id]),
+        OP_CONVERT_F_TO_U => instruction("OpConvertFToU", true, true, &[Id]),
+        OP_CONVERT_F_TO_S => instruction("OpConvertFToS", true, true, &[Id]),
+        OP_CONVERT_S_TO_F => instruction("OpConvertSToF", true, true, &[Id]),
+        OP_CONVERT_U_TO_F => instruction("OpConvertUToF", true, true, &[Id]),
+        OP_F_CONVERT => instruction("OpFConvert", true, true, &[Id]),
+        OP_BITCAST => instruction("OpBitcast", true, true, &[Id]),
+        OP_F_NEGATE => instruction("OpFNegate", true, true, &[Id]),
+        OP_I_ADD => instruction("OpIAdd", true, true, &[Id, Id]),
+        OP_F_ADD => instruction("OpFAdd", true, true, &[Id, Id]),
+        OP_I_SUB => instruction("OpISub", true, true, &[Id, Id]),
+        OP_F_SUB => instruction("OpFSub", true, true, &[Id, Id]),
+        OP_I_MUL => instruction("OpIMul", true, true, &[Id, Id]),
+        OP_F_MUL => instruction("OpFMul", true, true, &[Id, Id]),
+        OP_VECTOR_TIMES_SCALAR => instruction("OpVectorTimesScalar", true, true, &[Id, Id]),
+        OP_MATRIX_TIMES_SCALAR => instruction("OpMatrixTimesScalar", true, true, &[Id, Id]),
+        OP_VECTOR_TIMES_MATRIX => instruction("OpVectorTimesMatrix", true, true, &[Id, Id]),
+        OP_MATRIX_TIMES_VECTOR => instruction("OpMatrixTimesVector", true, true, &[Id, Id]),
+        OP_MATRIX_TIMES_MATRIX => instruction("OpMatrixTimesMatrix", true, true, &[Id, Id]),
+        OP_SELECT => instruction("OpSelect", true, true, &[Id, Id, Id]),
+        OP_LABEL => instruction("OpLabel", false, true, &[]),
+        OP_KILL => instruction("OpKill", false, false, &[]),
+        OP_RETURN => instruction("OpReturn", false, false, &[]),
+        OP_RETURN_VALUE => instruction("OpReturnValue", false, false, &[Id]),
+        OP_UNREACHABLE => instruction("OpUnreachable", false, false, &[]),
+        _ => return None,
+    })
+}
+
+// Renders a module as text in the style of "spirv-dis", only the
+// instructions emitted by this crate are known by name
+pub fn disassemble(words: &[u32]) -> String {
+    if words.len() < 5 || words[0] != MAGIC_NUMBER {
+        return "; Not a SPIR-V module\n".to_owned();
+    }
+
+    let mut text = format!(
+        "; SPIR-V\n; Version: {}.{}\n; Generator: {}\n; Bound: {}\n; Schema: {}\n",
+        (words[1] >> 16) & 0xFF,
+        (words[1] >> 8) & 0xFF,
+        words[2],
+        words[3],
+        words[4]
+    );
+
+    // Float constants are printed as numbers rather than their bits
+    let mut float_types = Vec::new();
+
+    let mut i = 5;
+    while i < words.len() {
+        let word_count = (words[i] >> 16) as usize;
+        let opcode = (words[i] & 0xFFFF) as u16;
+        if word_count == 0 || i + word_count > words.len() {
+            text.push_str(&format!("; Invalid instruction at word {}\n", i));
+            break;
+        }
+
+        let operands = &words[i + 1..i + word_count];
+        if opcode == OP_TYPE_FLOAT && !operands.is_empty() {
+            float_types.push(operands[0]);
+        }
+
+        if opcode == OP_CONSTANT && operands.len() == 3 && float_types.contains(&operands[0]) {
+            text.push_str(&format!(
+                "{:>12} = OpConstant %{} {}",
+                format!("%{}", operands[1]),
+                operands[0],
+                f32::from_bits(operands[2])
+            ));
+        } else {
+            text.push_str(&disassemble_instruction(opcode, operands));
+        }
+        text.push('\n');
+
+        i += word_count;
+    }
+
+    text
+}
+
+fn disassemble_instruction(opcode: u16, mut operands: &[u32]) -> String {
+    let instruction = match lookup(opcode) {
+        Some(instruction) => instruction,
+        None => {
+            let operands: Vec<String> = operands.iter().map(|word| word.to_string()).collect();
+            return format!("{:>15}Op{} {}", "", opcode, operands.join(" "));
+        }
+    };
+
+    let mut result_type = None;
+    if instruction.result_type && !operands.is_empty() {
+        result_type = Some(operands[0]);
+        operands = &operands[1..];
+    }
+
+    let mut line = if instruction.result && !operands.is_empty() {
+        let line = format!("{:>12} = {}", format!("%{}", operands[0]), instruction.name);
+        operands = &operands[1..];
+        line
+    } else {
+        format!("{:>15}{}", "", instruction.name)
+    };
+
+    if let Some(result_type) = result_type {
+        line.push_str(&format!(" %{}", result_type));
+    }
+
+    let mut kinds = instruction.operands.iter();
+    let mut kind = Operand::Literal;
+    while !operands.is_empty() {
+        if let Some(next) = kinds.next() {
+            kind = *next;
+        }
+
+        let (text, used) = disassemble_operand(kind, operands);
+        line.push(' ');
+        line.push_str(&text);
+        operands = &operands[used..];
+    }
+
+    line
+}
+
+fn disassemble_operand(kind: Operand, operands: &[u32]) -> (String, usize) {
+    let word = operands[0];
+
+    let text = match kind {
+        Operand::Id => format!("%{}", word),
+        Operand::Literal => word.to_string(),
+        Operand::String => {
+            let mut bytes = Vec::new();
+            let mut used = 0;
+            'words: for word in operands {
+                used += 1;
+                for byte in word.to_le_bytes() {
+                    if byte == 0 {
+                        break 'words;
+                    }
+                    bytes.push(byte);
+                }
+            }
+
+            return (
+                format!("\"{}\"", std::string::String::from_utf8_lossy(&bytes)),
+                used,
+            );
+        }
+        Operand::Capability => enumerant(
+            word,
+            &[
+                (CAPABILITY_SHADER, "Shader"),
+                (CAPABILITY_SAMPLE_RATE_SHADING, "SampleRateShading"),
+                (CAPABILITY_SAMPLED_1D, "Sampled1D"),
+                (CAPABILITY_SAMPLED_CUBE_ARRAY, "SampledCubeArray"),
+                (CAPABILITY_IMAGE_QUERY, "ImageQuery"),
+            ],
+        ),
+        Operand::AddressingModel => enumerant(word, &[(ADDRESSING_MODEL_LOGICAL, "Logical")]),
+        Operand::MemoryModel => enumerant(word, &[(MEMORY_MODEL_GLSL450, "GLSL450")]),
+        Operand::ExecutionModel => enumerant(
+            word,
+            &[
+                (EXECUTION_MODEL_VERTEX, "Vertex"),
+                (EXECUTION_MODEL_FRAGMENT, "Fragment"),
+            ],
+        ),
+        Operand::ExecutionMode => enumerant(
+            word,
+            &[
+                (EXECUTION_MODE_ORIGIN_UPPER_LEFT, "OriginUpperLeft"),
+                (EXECUTION_MODE_DEPTH_REPLACING, "DepthReplacing"),
+            ],
+        ),
+        Operand::StorageClass => enumerant(
+            word,
+            &[
+                (STORAGE_CLASS_UNIFORM_CONSTANT, "UniformConstant"),
+                (STORAGE_CLASS_INPUT, "Input"),
+                (STORAGE_CLASS_UNIFORM, "Uniform"),
+                (STORAGE_CLASS_OUTPUT, "Output"),
+            ],
+        ),
+        Operand::Decoration => {
+            let name = enumerant(
+                word,
+                &[
+                    (DECORATION_BLOCK, "Block"),
+                    (DECORATION_COL_MAJOR, "ColMajor"),
+                    (DECORATION_MATRIX_STRIDE, "MatrixStride"),
+                    (DECORATION_BUILT_IN, "BuiltIn"),
+                    (DECORATION_NO_PERSPECTIVE, "NoPerspective"),
+                    (DECORATION_FLAT, "Flat"),
+                    (DECORATION_CENTROID, "Centroid"),
+                    (DECORATION_SAMPLE, "Sample"),
+                    (DECORATION_LOCATION, "Location"),
+                    (DECORATION_BINDING, "Binding"),
+                    (DECORATION_DESCRIPTOR_SET, "DescriptorSet"),
+                    (DECORATION_OFFSET, "Offset"),
+                ],
+            );
+
+            if word == DECORATION_BUILT_IN && operands.len() > 1 {
+                let built_in = enumerant(
+                    operands[1],
+                    &[
+                        (BUILT_IN_POSITION, "Position"),
+                        (BUILT_IN_FRAG_COORD, "FragCoord"),
+                        (BUILT_IN_FRONT_FACING, "FrontFacing"),
+                        (BUILT_IN_FRAG_DEPTH, "FragDepth"),
+                        (BUILT_IN_VERTEX_INDEX, "VertexIndex"),
+                        (BUILT_IN_INSTANCE_INDEX, "InstanceIndex"),
+                    ],
+                );
+                return (format!("{} {}", name, built_in), 2);
+            }
+
+            name
+        }
+        Operand::Dim => enumerant(
+            word,
+            &[
+                (DIM_1D, "1D"),
+                (DIM_2D, "2D"),
+                (DIM_3D, "3D"),
+                (DIM_CUBE, "Cube"),
+            ],
+        ),
+        Operand::FunctionControl => enumerant(word, &[(FUNCTION_CONTROL_NONE, "None")]),
+        Operand::ImageOperands => enumerant(
+            word,
+            &[
+                (IMAGE_OPERANDS_BIAS, "Bias"),
+                (IMAGE_OPERANDS_LOD, "Lod"),
+                (IMAGE_OPERANDS_GRAD, "Grad"),
+                (IMAGE_OPERANDS_CONST_OFFSET, "ConstOffset"),
+            ],
+        ),
+        Operand::ExtInstruction => enumerant(
+            word,
+            &[(GLSL_STD_450_FLOOR, "Floor"), (GLSL_STD_450_FRACT, "Fract")],
+        ),
+    };
+
+    (text, 1)
+}
+
+fn enumerant(value: u32, names: &[(u32, &str)]) -> String {
+    for (enumerant, name) in names {
+        if *enumerant == value {
+            return (*name).to_owned();
+        }
+    }
+
+    value.to_string()
+}
@@ -0,0 +1,465 @@
+use crate::types::TextureDimension;
+use std::collections::HashMap;
+
+mod context;
+mod disassembler;
+
+pub use context::{Context, Global};
+pub use disassembler::disassemble;
+
+pub type Id = u32;
+
+pub const MAGIC_NUMBER: u32 = 0x07230203;
+pub const VERSION: u32 = 0x00010000;
+
+// Opcodes
+pub const OP_NAME: u16 = 5;
+pub const OP_MEMBER_NAME: u16 = 6;
+pub const OP_EXT_INST_IMPORT: u16 = 11;
+pub const OP_EXT_INST: u16 = 12;
+pub const OP_MEMORY_MODEL: u16 = 14;
+pub const OP_ENTRY_POINT: u16 = 15;
+pub const OP_EXECUTION_MODE: u16 = 16;
+pub const OP_CAPABILITY: u16 = 17;
+pub const OP_TYPE_VOID: u16 = 19;
+pub const OP_TYPE_BOOL: u16 = 20;
+pub const OP_TYPE_INT: u16 = 21;
+pub const OP_TYPE_FLOAT: u16 = 22;
+pub const OP_TYPE_VECTOR: u16 = 23;
+pub const OP_TYPE_MATRIX: u16 = 24;
+pub const OP_TYPE_IMAGE: u16 = 25;
+pub const OP_TYPE_SAMPLER: u16 = 26;
+pub const OP_TYPE_SAMPLED_IMAGE: u16 = 27;
+pub const OP_TYPE_STRUCT: u16 = 30;
+pub const OP_TYPE_POINTER: u16 = 32;
+pub const OP_TYPE_FUNCTION: u16 = 33;
+pub const OP_CONSTANT: u16 = 43;
+pub const OP_CONSTANT_COMPOSITE: u16 = 44;
+pub const OP_FUNCTION: u16 = 54;
+pub const OP_FUNCTION_PARAMETER: u16 = 55;
+pub const OP_FUNCTION_END: u16 = 56;
+pub const OP_FUNCTION_CALL: u16 = 57;
+pub const OP_VARIABLE: u16 = 59;
+pub const OP_LOAD: u16 = 61;
+pub const OP_STORE: u16 = 62;
+pub const OP_ACCESS_CHAIN: u16 = 65;
+pub const OP_DECORATE: u16 = 71;
+pub const OP_MEMBER_DECORATE: u16 = 72;
+pub const OP_VECTOR_SHUFFLE: u16 = 79;
+pub const OP_COMPOSITE_CONSTRUCT: u16 = 80;
+pub const OP_COMPOSITE_EXTRACT: u16 = 81;
+pub const OP_COMPOSITE_INSERT: u16 = 82;
+pub const OP_SAMPLED_IMAGE: u16 = 86;
+pub const OP_IMAGE_SAMPLE_IMPLICIT_LOD: u16 = 87;
+pub const OP_IMAGE_SAMPLE_EXPLICIT_LOD: u16 = 88;
+pub const OP_IMAGE_SAMPLE_DREF_IMPLICIT_LOD: u16 = 89;
+pub const OP_IMAGE_SAMPLE_DREF_EXPLICIT_LOD: u16 = 90;
+pub const OP_IMAGE_FETCH: u16 = 95;
+pub const OP_IMAGE_GATHER: u16 = 96;
+pub const OP_IMAGE_QUERY_SIZE_LOD: u16 = 103;
+pub const OP_CONVERT_F_TO_U: u16 = 109;
+pub const OP_CONVERT_F_TO_S: u16 = 110;
+pub const OP_CONVERT_S_TO_F: u16 = 111;
+pub const OP_CONVERT_U_TO_F: u16 = 112;
+pub const OP_F_CONVERT: u16 = 115;
+pub const OP_BITCAST: u16 = 124;
+pub const OP_F_NEGATE: u16 = 127;
+pub const OP_I_ADD: u16 = 128;
+pub const OP_F_ADD: u16 = 129;
+pub const OP_I_SUB: u16 = 130;
+pub const OP_F_SUB: u16 = 131;
+pub const OP_I_MUL: u16 = 132;
+pub const OP_F_MUL: u16 = 133;
+pub const OP_VECTOR_TIMES_SCALAR: u16 = 142;
+pub const OP_MATRIX_TIMES_SCALAR: u16 = 143;
+pub const OP_VECTOR_TIMES_MATRIX: u16 = 144;
+pub const OP_MATRIX_TIMES_VECTOR: u16 = 145;
+pub const OP_MATRIX_TIMES_MATRIX: u16 = 146;
+pub const OP_SELECT: u16 = 169;
+pub const OP_LABEL: u16 = 248;
+pub const OP_KILL: u16 = 252;
+pub const OP_RETURN: u16 = 253;
+pub const OP_RETURN_VALUE: u16 = 254;
+pub const OP_UNREACHABLE: u16 = 255;
+
+// Enumerants
+pub const CAPABILITY_SHADER: u32 = 1;
+pub const CAPABILITY_SAMPLE_RATE_SHADING: u32 = 35;
+pub const CAPABILITY_SAMPLED_1D: u32 = 43;
+pub const CAPABILITY_SAMPLED_CUBE_ARRAY: u32 = 45;
+pub const CAPABILITY_IMAGE_QUERY: u32 = 50;
+pub const ADDRESSING_MODEL_LOGICAL: u32 = 0;
+pub const MEMORY_MODEL_GLSL450: u32 = 1;
+pub const EXECUTION_MODEL_VERTEX: u32 = 0;
+pub const EXECUTION_MODEL_FRAGMENT: u32 = 4;
+pub const EXECUTION_MODE_ORIGIN_UPPER_LEFT: u32 = 7;
+pub const EXECUTION_MODE_DEPTH_REPLACING: u32 = 12;
+pub const STORAGE_CLASS_UNIFORM_CONSTANT: u32 = 0;
+pub const STORAGE_CLASS_INPUT: u32 = 1;
+pub const STORAGE_CLASS_UNIFORM: u32 = 2;
+pub const STORAGE_CLASS_OUTPUT: u32 = 3;
+pub const DECORATION_BLOCK: u32 = 2;
+pub const DECORATION_COL_MAJOR: u32 = 5;
+pub const DECORATION_MATRIX_STRIDE: u32 = 7;
+pub const DECORATION_BUILT_IN: u32 = 11;
+pub const DECORATION_NO_PERSPECTIVE: u32 = 13;
+pub const DECORATION_FLAT: u32 = 14;
+pub const DECORATION_CENTROID: u32 = 16;
+pub const DECORATION_SAMPLE: u32 = 17;
+pub const DECORATION_LOCATION: u32 = 30;
+pub const DECORATION_BINDING: u32 = 33;
+pub const DECORATION_DESCRIPTOR_SET: u32 = 34;
+pub const DECORATION_OFFSET: u32 = 35;
+pub const BUILT_IN_POSITION: u32 = 0;
+pub const BUILT_IN_FRAG_COORD: u32 = 15;
+pub const BUILT_IN_FRONT_FACING: u32 = 17;
+pub const BUILT_IN_FRAG_DEPTH: u32 = 22;
+pub const BUILT_IN_VERTEX_INDEX: u32 = 42;
+pub const BUILT_IN_INSTANCE_INDEX: u32 = 43;
+pub const DIM_1D: u32 = 0;
+pub const DIM_2D: u32 = 1;
+pub const DIM_3D: u32 = 2;
+pub const DIM_CUBE: u32 = 3;
+pub const IMAGE_OPERANDS_BIAS: u32 = 0x1;
+pub const IMAGE_OPERANDS_LOD: u32 = 0x2;
+pub const IMAGE_OPERANDS_GRAD: u32 = 0x4;
+pub const IMAGE_OPERANDS_CONST_OFFSET: u32 = 0x8;
+pub const FUNCTION_CONTROL_NONE: u32 = 0;
+
+// GLSL.std.450 extended instructions
+pub const GLSL_STD_450_FLOOR: u32 = 8;
+pub const GLSL_STD_450_FRACT: u32 = 10;
+
+// A SPIR-V module built section by section in the order required by the
+// logical layout, types and constants are created once and reused
+pub struct Module {
+    bound: Id,
+
+    declared_capabilities: Vec<u32>,
+    capabilities: Vec<u32>,
+    extension_imports: Vec<u32>,
+    memory_model: Vec<u32>,
+    entry_points: Vec<u32>,
+    execution_modes: Vec<u32>,
+    debug: Vec<u32>,
+    annotations: Vec<u32>,
+    globals: Vec<u32>,
+    functions: Vec<u32>,
+
+    types: HashMap<(u16, Vec<u32>), Id>,
+    struct_types: HashMap<String, Id>,
+    constants: HashMap<(u16, Vec<u32>), Id>,
+    glsl_std_450: Id,
+}
+
+impl Module {
+    pub fn new() -> Self {
+        let mut module = Module {
+            bound: 1,
+            declared_capabilities: Vec::new(),
+            capabilities: Vec::new(),
+            extension_imports: Vec::new(),
+            memory_model: Vec::new(),
+            entry_points: Vec::new(),
+            execution_modes: Vec::new(),
+            debug: Vec::new(),
+            annotations: Vec::new(),
+            globals: Vec::new(),
+            functions: Vec::new(),
+            types: HashMap::new(),
+            struct_types: HashMap::new(),
+            constants: HashMap::new(),
+            glsl_std_450: 0,
+        };
+
+        module.capability(CAPABILITY_SHADER);
+
+        module.glsl_std_450 = module.id();
+        let mut operands = vec![module.glsl_std_450];
+        operands.extend(string("GLSL.std.450"));
+        instruction(&mut module.extension_imports, OP_EXT_INST_IMPORT, &operands);
+
+        instruction(
+            &mut module.memory_model,
+            OP_MEMORY_MODEL,
+            &[ADDRESSING_MODEL_LOGICAL, MEMORY_MODEL_GLSL450],
+        );
+
+        module
+    }
+
+    pub fn id(&mut self) -> Id {
+        let id = self.bound;
+        self.bound += 1;
+        id
+    }
+
+    // Declares a capability on first use
+    pub fn capability(&mut self, capability: u32) {
+        if !self.declared_capabilities.contains(&capability) {
+            self.declared_capabilities.push(capability);
+            instruction(&mut self.capabilities, OP_CAPABILITY, &[capability]);
+        }
+    }
+
+    pub fn glsl_std_450(&self) -> Id {
+        self.glsl_std_450
+    }
+
+    pub fn entry_point(
+        &mut self,
+        execution_model: u32,
+        function: Id,
+        name: &str,
+        interface: &[Id],
+    ) {
+        let mut operands = vec![execution_model, function];
+        operands.extend(string(name));
+        operands.extend_from_slice(interface);
+        instruction(&mut self.entry_points, OP_ENTRY_POINT, &operands);
+    }
+
+    pub fn execution_mode(&mut self, function: Id, mode: u32) {
+        instruction(
+            &mut self.execution_modes,
+            OP_EXECUTION_MODE,
+            &[function, mode],
+        );
+    }
+
+    pub fn name(&mut self, target: Id, name: &str) {
+        let mut operands = vec![target];
+        operands.extend(string(name));
+        instruction(&mut self.debug, OP_NAME, &operands);
+    }
+
+    pub fn member_name(&mut self, structure: Id, member: u32, name: &str) {
+        let mut operands = vec![structure, member];
+        operands.extend(string(name));
+        instruction(&mut self.debug, OP_MEMBER_NAME, &operands);
+    }
+
+    pub fn decorate(&mut self, target: Id, decoration: u32, literals: &[u32]) {
+        let mut operands = vec![target, decoration];
+        operands.extend_from_slice(literals);
+        instruction(&mut self.annotations, OP_DECORATE, &operands);
+    }
+
+    pub fn member_decorate(
+        &mut self,
+        structure: Id,
+        member: u32,
+        decoration: u32,
+        literals: &[u32],
+    ) {
+        let mut operands = vec![structure, member, decoration];
+        operands.extend_from_slice(literals);
+        instruction(&mut self.annotations, OP_MEMBER_DECORATE, &operands);
+    }
+
+    // Decorates a struct member for use in a uniform buffer
+    pub fn member_layout(&mut self, structure: Id, member: u32, offset: usize, matrix: bool) {
+        self.member_decorate(structure, member, DECORATION_OFFSET, &[offset as u32]);
+        if matrix {
+            self.member_decorate(structure, member, DECORATION_COL_MAJOR, &[]);
+            self.member_decorate(structure, member, DECORATION_MATRIX_STRIDE, &[16]);
+        }
+    }
+
+    // Returns the id of a non-aggregate type, declaring it on first use
+    pub fn type_id(&mut self, opcode: u16, operands: &[u32]) -> Id {
+        let key = (opcode, operands.to_vec());
+        if let Some(id) = self.types.get(&key) {
+            return *id;
+        }
+
+        let id = self.id();
+        let mut instruction_operands = vec![id];
+        instruction_operands.extend_from_slice(operands);
+        instruction(&mut self.globals, opcode, &instruction_operands);
+
+        self.types.insert(key, id);
+        id
+    }
+
+    pub fn type_void(&mut self) -> Id {
+        self.type_id(OP_TYPE_VOID, &[])
+    }
+
+    pub fn type_bool(&mut self) -> Id {
+        self.type_id(OP_TYPE_BOOL, &[])
+    }
+
+    pub fn type_float(&mut self) -> Id {
+        self.type_id(OP_TYPE_FLOAT, &[32])
+    }
+
+    pub fn type_uint(&mut self) -> Id {
+        self.type_id(OP_TYPE_INT, &[32, 0])
+    }
+
+    pub fn type_int(&mut self) -> Id {
+        self.type_id(OP_TYPE_INT, &[32, 1])
+    }
+
+    pub fn type_vector(&mut self, component_type: Id, count: u32) -> Id {
+        self.type_id(OP_TYPE_VECTOR, &[component_type, count])
+    }
+
+    pub fn type_pointer(&mut self, storage_class: u32, pointee: Id) -> Id {
+        self.type_id(OP_TYPE_POINTER, &[storage_class, pointee])
+    }
+
+    pub fn type_function(&mut self, return_type: Id, parameters: &[Id]) -> Id {
+        let mut operands = vec![return_type];
+        operands.extend_from_slice(parameters);
+        self.type_id(OP_TYPE_FUNCTION, &operands)
+    }
+
+    pub fn type_matrix(&mut self, column_type: Id, count: u32) -> Id {
+        self.type_id(OP_TYPE_MATRIX, &[column_type, count])
+    }
+
+    pub fn type_image(&mut self, sampled_type: Id, dimension: TextureDimension, depth: bool) -> Id {
+        let dim = match dimension {
+            TextureDimension::D1 => {
+                self.capability(CAPABILITY_SAMPLED_1D);
+                DIM_1D
+            }
+            TextureDimension::D2 | TextureDimension::D2Array => DIM_2D,
+            TextureDimension::D3 => DIM_3D,
+            TextureDimension::Cube => DIM_CUBE,
+            TextureDimension::CubeArray => {
+                self.capability(CAPABILITY_SAMPLED_CUBE_ARRAY);
+                DIM_CUBE
+            }
+        };
+        let arrayed = dimension.is_array() as u32;
+
+        self.type_id(
+            OP_TYPE_IMAGE,
+            &[sampled_type, dim, depth as u32, arrayed, 0, 1, 0],
+        )
+    }
+
+    pub fn type_sampler(&mut self) -> Id {
+        self.type_id(OP_TYPE_SAMPLER, &[])
+    }
+
+    pub fn type_sampled_image(&mut self, image_type: Id) -> Id {
+        self.type_id(OP_TYPE_SAMPLED_IMAGE, &[image_type])
+    }
+
+    // Structs are distinct by name, the flag is set when the struct was
+    // declared by this call and still needs its names and decorations
+    pub fn type_struct(&mut self, name: &str, members: &[Id]) -> (Id, bool) {
+        if let Some(id) = self.struct_types.get(name) {
+            return (*id, false);
+        }
+
+        let id = self.id();
+        let mut operands = vec![id];
+        operands.extend_from_slice(members);
+        instruction(&mut self.globals, OP_TYPE_STRUCT, &operands);
+
+        self.struct_types.insert(name.to_owned(), id);
+        (id, true)
+    }
+
+    pub fn constant_float(&mut self, value: f32) -> Id {
+        let float_type = self.type_float();
+        self.constant(OP_CONSTANT, &[float_type, value.to_bits()])
+    }
+
+    pub fn constant_int(&mut self, value: i32) -> Id {
+        let int_type = self.type_int();
+        self.constant(OP_CONSTANT, &[int_type, value as u32])
+    }
+
+    pub fn constant_uint(&mut self, value: u32) -> Id {
+        let uint_type = self.type_uint();
+        self.constant(OP_CONSTANT, &[uint_type, value])
+    }
+
+    pub fn constant_composite(&mut self, composite_type: Id, constituents: &[Id]) -> Id {
+        let mut operands = vec![composite_type];
+        operands.extend_from_slice(constituents);
+        self.constant(OP_CONSTANT_COMPOSITE, &operands)
+    }
+
+    pub fn is_constant(&self, id: Id) -> bool {
+        self.constants.values().any(|constant| *constant == id)
+    }
+
+    pub fn global_variable(&mut self, pointer_type: Id, storage_class: u32) -> Id {
+        let id = self.id();
+        instruction(
+            &mut self.globals,
+            OP_VARIABLE,
+            &[pointer_type, id, storage_class],
+        );
+        id
+    }
+
+    // Appends an instruction to the function bodies
+    pub fn emit(&mut self, opcode: u16, operands: &[u32]) {
+        instruction(&mut self.functions, opcode, operands);
+    }
+
+    // Appends an instruction with a result type and id to the function bodies
+    pub fn emit_result(&mut self, opcode: u16, result_type: Id, operands: &[u32]) -> Id {
+        let id = self.id();
+        let mut instruction_operands = vec![result_type, id];
+        instruction_operands.extend_from_slice(operands);
+        instruction(&mut self.functions, opcode, &instruction_operands);
+        id
+    }
+
+    pub fn finish(self) -> Vec<u32> {
+        let mut words = vec![MAGIC_NUMBER, VERSION, 0, self.bound, 0];
+        words.extend(self.capabilities);
+        words.extend(self.extension_imports);
+        words.extend(self.memory_model);
+        words.extend(self.entry_points);
+        words.extend(self.execution_modes);
+        words.extend(self.debug);
+        words.extend(self.annotations);
+        words.extend(self.globals);
+        words.extend(self.functions);
+        words
+    }
+
+    fn constant(&mut self, opcode: u16, operands: &[u32]) -> Id {
+        let key = (opcode, operands.to_vec());
+        if let Some(id) = self.constants.get(&key) {
+            return *id;
+        }
+
+        let id = self.id();
+        let mut instruction_operands = vec![operands[0], id];
+        instruction_operands.extend_from_slice(&operands[1..]);
+        instruction(&mut self.globals, opcode, &instruction_operands);
+
+        self.constants.insert(key, id);
+        id
+    }
+}
+
+fn instruction(section: &mut Vec<u32>, opcode: u16, operands: &[u32]) {
+    section.push(((operands.len() as u32 + 1) << 16) | opcode as u32);
+    section.extend_from_slice(operands);
+}
+
+// Encodes a nul-terminated UTF-8 string padded to a whole word
+fn string(string: &str) -> Vec<u32> {
+    let mut bytes = string.as_bytes().to_vec();
+    bytes.push(0);
+    while !bytes.len().is_multiple_of(4) {
+        bytes.push(0);
+    }
+
+    bytes
+        .chunks(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
@@ -0,0 +1,249 @@
+use crate::feature::Feature;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GlslVersion {
+    Glsl330,
+    Glsl430,
+    Glsl450,
+    Essl300,
+    Vulkan,
+}
+
+impl GlslVersion {
+    // The text following "#version"
+    pub fn directive(&self) -> &'static str {
+        match self {
+            GlslVersion::Glsl330 => "330 core",
+            GlslVersion::Glsl430 => "430 core",
+            GlslVersion::Glsl450 => "450 core",
+            GlslVersion::Essl300 => "300 es",
+            GlslVersion::Vulkan => "450",
+        }
+    }
+
+    pub fn is_es(&self) -> bool {
+        *self == GlslVersion::Essl300
+    }
+
+    pub fn is_vulkan(&self) -> bool {
+        *self == GlslVersion::Vulkan
+    }
+
+    // "layout(location = n)" on uniforms requires GLSL 4.30, Vulkan uses
+    // descriptor sets and bindings instead
+    pub fn explicit_uniform_locations(&self) -> bool {
+        match self {
+            GlslVersion::Glsl430 | GlslVersion::Glsl450 => true,
+            GlslVersion::Glsl330 | GlslVersion::Essl300 | GlslVersion::Vulkan => false,
+        }
+    }
+
+    // "layout(location = n)" on interstage variables requires GLSL 4.10, which
+    // also lets separate programs match them by location
+    pub fn explicit_interstage_locations(&self) -> bool {
+        match self {
+            GlslVersion::Glsl430 | GlslVersion::Glsl450 | GlslVersion::Vulkan => true,
+            GlslVersion::Glsl330 | GlslVersion::Essl300 => false,
+        }
+    }
+
+    pub fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::SingleComponentVector
+            | Feature::SingleComponentMatrix
+            | Feature::SixteenBitTypes
+            | Feature::WaveIntrinsics => false,
+            // Spaces only exist in HLSL, other targets ignore them
+            Feature::RegisterSpace => true,
+            Feature::SharedSlots => false,
+            // Shader storage blocks arrived with GLSL 4.30
+            Feature::ComputeShaders | Feature::StorageBuffers | Feature::StorageTextures => {
+                match self {
+                    GlslVersion::Glsl430 | GlslVersion::Glsl450 | GlslVersion::Vulkan => true,
+                    GlslVersion::Glsl330 | GlslVersion::Essl300 => false,
+                }
+            }
+            Feature::OneDimensionalTextures => *self != GlslVersion::Essl300,
+            // Cube map arrays and gathers arrived with GLSL 4.00
+            Feature::CubeArrayTextures | Feature::TextureGather => match self {
+                GlslVersion::Glsl430 | GlslVersion::Glsl450 | GlslVersion::Vulkan => true,
+                GlslVersion::Glsl330 | GlslVersion::Essl300 => false,
+            },
+            // Only separate samplers can turn a color texture into a shadow
+            // sampler, OpenGL needs it declared as one
+            Feature::ColorTextureComparison => self.is_vulkan(),
+            // OpenGL samples a texture with the state of its texture unit
+            Feature::MultipleSamplersPerTexture => self.is_vulkan(),
+            Feature::NoPerspectiveInterpolation => !self.is_es(),
+            // Sample interpolation and tessellation arrived with GLSL 4.00
+            Feature::SampleInterpolation | Feature::TessellationShaders => match self {
+                GlslVersion::Glsl430 | GlslVersion::Glsl450 | GlslVersion::Vulkan => true,
+                GlslVersion::Glsl330 | GlslVersion::Essl300 => false,
+            },
+            Feature::GeometryShaders => !self.is_es(),
+        }
+    }
+}
+
+impl std::fmt::Display for GlslVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GlslVersion::Glsl330 => write!(f, "GLSL 3.30"),
+            GlslVersion::Glsl430 => write!(f, "GLSL 4.30"),
+            GlslVersion::Glsl450 => write!(f, "GLSL 4.50"),
+            GlslVersion::Essl300 => write!(f, "GLSL ES 3.00"),
+            GlslVersion::Vulkan => write!(f, "Vulkan GLSL 4.50"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ShaderModel {
+    Sm50,
+    Sm51,
+    Sm60,
+    Sm61,
+    Sm62,
+    Sm63,
+    Sm64,
+    Sm65,
+    Sm66,
+}
+
+impl ShaderModel {
+    // The suffix of the DXC/FXC target profile, as in "vs_6_0"
+    pub fn profile(&self) -> &'static str {
+        match self {
+            ShaderModel::Sm50 => "5_0",
+            ShaderModel::Sm51 => "5_1",
+            ShaderModel::Sm60 => "6_0",
+            ShaderModel::Sm61 => "6_1",
+            ShaderModel::Sm62 => "6_2",
+            ShaderModel::Sm63 => "6_3",
+            ShaderModel::Sm64 => "6_4",
+            ShaderModel::Sm65 => "6_5",
+            ShaderModel::Sm66 => "6_6",
+        }
+    }
+
+    // 16-bit types also require compiling with "-enable-16bit-types"
+    pub fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::SingleComponentVector | Feature::SingleComponentMatrix => true,
+            Feature::RegisterSpace | Feature::SharedSlots => *self >= ShaderModel::Sm51,
+            Feature::WaveIntrinsics => *self >= ShaderModel::Sm60,
+            Feature::SixteenBitTypes => *self >= ShaderModel::Sm62,
+            Feature::ComputeShaders | Feature::StorageBuffers | Feature::StorageTextures => true,
+            Feature::OneDimensionalTextures | Feature::CubeArrayTextures => true,
+            Feature::TextureGather | Feature::ColorTextureComparison => true,
+            Feature::MultipleSamplersPerTexture => true,
+            Feature::NoPerspectiveInterpolation | Feature::SampleInterpolation => true,
+            Feature::TessellationShaders | Feature::GeometryShaders => true,
+        }
+    }
+}
+
+impl std::fmt::Display for ShaderModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Shader Model {}", self.profile().replace('_', "."))
+    }
+}
+
+// Every output language, used to report features a target cannot express
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Target {
+    Hlsl(ShaderModel),
+    Glsl(GlslVersion),
+    Wgsl,
+    Msl,
+    Spirv,
+}
+
+impl Target {
+    pub fn supports(&self, feature: Feature) -> bool {
+        match self {
+            Target::Hlsl(shader_model) => shader_model.supports(feature),
+            Target::Glsl(version) => version.supports(feature),
+            Target::Wgsl | Target::Spirv => match feature {
+                Feature::SingleComponentVector
+                | Feature::SingleComponentMatrix
+                | Feature::SixteenBitTypes
+                | Feature::WaveIntrinsics
+                | Feature::ComputeShaders
+                | Feature::StorageBuffers
+                | Feature::StorageTextures
+                | Feature::TessellationShaders
+                | Feature::GeometryShaders
+                | Feature::SharedSlots => false,
+                Feature::RegisterSpace
+                | Feature::OneDimensionalTextures
+                | Feature::CubeArrayTextures
+                | Feature::TextureGather
+                | Feature::MultipleSamplersPerTexture
+                | Feature::NoPerspectiveInterpolation
+                | Feature::SampleInterpolation => true,
+                // WGSL compares depth textures only
+                Feature::ColorTextureComparison => *self == Target::Spirv,
+            },
+            Target::Msl => match feature {
+                Feature::SingleComponentVector
+                | Feature::SingleComponentMatrix
+                | Feature::WaveIntrinsics
+                | Feature::ComputeShaders
+                | Feature::StorageBuffers
+                | Feature::StorageTextures
+                | Feature::ColorTextureComparison
+                | Feature::TessellationShaders
+                | Feature::GeometryShaders
+                | Feature::SharedSlots => false,
+                Feature::SixteenBitTypes
+                | Feature::RegisterSpace
+                | Feature::OneDimensionalTextures
+                | Feature::CubeArrayTextures
+                | Feature::TextureGather
+                | Feature::MultipleSamplersPerTexture
+                | Feature::NoPerspectiveInterpolation
+                | Feature::SampleInterpolation => true,
+            },
+        }
+    }
+
+    // Features the target language has, which this compiler can't generate
+    // for it yet
+    pub fn is_unimplemented(&self, feature: Feature) -> bool {
+        match self {
+            Target::Hlsl(_) | Target::Glsl(_) => false,
+            Target::Wgsl => matches!(
+                feature,
+                Feature::ComputeShaders | Feature::StorageBuffers | Feature::StorageTextures
+            ),
+            Target::Msl => matches!(
+                feature,
+                Feature::ComputeShaders
+                    | Feature::StorageBuffers
+                    | Feature::StorageTextures
+                    | Feature::TessellationShaders
+            ),
+            Target::Spirv => matches!(
+                feature,
+                Feature::ComputeShaders
+                    | Feature::StorageBuffers
+                    | Feature::StorageTextures
+                    | Feature::TessellationShaders
+                    | Feature::GeometryShaders
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Hlsl(shader_model) => write!(f, "HLSL {}", shader_model),
+            Target::Glsl(version) => version.fmt(f),
+            Target::Wgsl => write!(f, "WGSL"),
+            Target::Msl => write!(f, "MSL"),
+            Target::Spirv => write!(f, "SPIR-V"),
+        }
+    }
+}
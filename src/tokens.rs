@@ -6,6 +6,7 @@ pub enum TokenClass {
     Type,
     Const,
     Return,
+    Discard,
     Let,
     Mut,
     Identifier(String),
@@ -26,6 +27,9 @@ pub enum TokenClass {
     Equal,
     Asterick,
     Plus,
+    Hash,
+    OpenSquareBracket,
+    CloseSquareBracket,
 }
 
 #[derive(Debug)]
@@ -47,6 +51,14 @@ impl Token {
     pub fn class(&self) -> &TokenClass {
         &self.class
     }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
 }
 
 impl std::fmt::Display for Token {
@@ -64,6 +76,7 @@ impl std::fmt::Display for TokenClass {
             TokenClass::Type => write!(f, "type"),
             TokenClass::Const => write!(f, "const"),
             TokenClass::Return => write!(f, "return"),
+            TokenClass::Discard => write!(f, "discard"),
             TokenClass::Let => write!(f, "let"),
             TokenClass::Mut => write!(f, "mut"),
             TokenClass::Identifier(identifier) => write!(f, "\"{}\"", identifier),
@@ -84,6 +97,9 @@ impl std::fmt::Display for TokenClass {
             TokenClass::Equal => write!(f, "="),
             TokenClass::Asterick => write!(f, "*"),
             TokenClass::Plus => write!(f, "+"),
+            TokenClass::Hash => write!(f, "#"),
+            TokenClass::OpenSquareBracket => write!(f, "["),
+            TokenClass::CloseSquareBracket => write!(f, "]"),
         }
     }
 }
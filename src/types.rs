@@ -1,6 +1,8 @@
 use crate::{
     annotated::{structure::Struct, AnnotatedSyntaxTree},
     ast::SemanticAnalysisError,
+    feature::Feature,
+    spirv::{Id, Module, OP_TYPE_FLOAT},
 };
 use std::{rc::Rc, sync::Once};
 
@@ -9,6 +11,21 @@ pub enum Type {
     Primitive(Primitive),
     Struct(Rc<Struct>),
     Alias(Box<Type>),
+    // A runtime sized array of elements, writable for "rwbuffer"
+    Buffer(Box<Type>, bool),
+    // A texture which can be written, holding texels of the inner type
+    StorageTexture(Box<Type>),
+    // A texture which can be sampled, holding elements of the inner type
+    Texture(Box<Type>, TextureDimension),
+    // A texture of depth values, sampled with a comparison
+    DepthTexture(TextureDimension),
+    // The control points of a tessellated patch, only taken by the entry points
+    // of the tessellation stages
+    Patch(Box<Type>, usize),
+    // The vertices of the primitive a geometry shader reads
+    GeometryInput(Box<Type>, Topology),
+    // The primitives a geometry shader writes, vertex by vertex
+    Stream(Box<Type>, Topology),
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -18,7 +35,30 @@ pub enum Primitive {
     FloatVec(usize),
     FloatMatrix(usize, usize),
     Uint,
-    Texture,
+    UintVec(usize),
+    Int,
+    Half,
+    Sampler,
+    ComparisonSampler,
+}
+
+// The shape of a texture, which decides the coordinates it is accessed with
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TextureDimension {
+    D1,
+    D2,
+    D3,
+    Cube,
+    D2Array,
+    CubeArray,
+}
+
+// The primitives of a geometry shader
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Topology {
+    Point,
+    Line,
+    Triangle,
 }
 
 static INIT_MEMBERS: Once = Once::new();
@@ -28,6 +68,9 @@ static mut FLOAT_MEMBERS: Option<Rc<Vec<(String, Type)>>> = None;
 static mut FLOAT2_MEMBERS: Option<Rc<Vec<(String, Type)>>> = None;
 static mut FLOAT3_MEMBERS: Option<Rc<Vec<(String, Type)>>> = None;
 static mut FLOAT4_MEMBERS: Option<Rc<Vec<(String, Type)>>> = None;
+static mut UINT2_MEMBERS: Option<Rc<Vec<(String, Type)>>> = None;
+static mut UINT3_MEMBERS: Option<Rc<Vec<(String, Type)>>> = None;
+static mut UINT4_MEMBERS: Option<Rc<Vec<(String, Type)>>> = None;
 
 impl Type {
     pub fn alias(inner_type: Type) -> Self {
@@ -136,13 +179,67 @@ impl Type {
         Type::Primitive(Primitive::Uint)
     }
 
-    pub fn texture() -> Self {
-        Type::Primitive(Primitive::Texture)
+    pub fn uintn(n: usize) -> Self {
+        assert!((2..=4).contains(&n));
+        Type::Primitive(Primitive::UintVec(n))
+    }
+
+    pub fn uint3() -> Self {
+        Type::uintn(3)
+    }
+
+    pub fn int() -> Self {
+        Type::Primitive(Primitive::Int)
+    }
+
+    pub fn half() -> Self {
+        Type::Primitive(Primitive::Half)
+    }
+
+    pub fn texture(element_type: Type, dimension: TextureDimension) -> Self {
+        Type::Texture(Box::new(element_type), dimension)
+    }
+
+    pub fn depth_texture(dimension: TextureDimension) -> Self {
+        Type::DepthTexture(dimension)
+    }
+
+    pub fn sampler() -> Self {
+        Type::Primitive(Primitive::Sampler)
+    }
+
+    pub fn comparison_sampler() -> Self {
+        Type::Primitive(Primitive::ComparisonSampler)
+    }
+
+    pub fn buffer(element_type: Type, writable: bool) -> Self {
+        Type::Buffer(Box::new(element_type), writable)
+    }
+
+    pub fn storage_texture(texel_type: Type) -> Self {
+        Type::StorageTexture(Box::new(texel_type))
+    }
+
+    pub fn patch(control_point_type: Type, control_points: usize) -> Self {
+        Type::Patch(Box::new(control_point_type), control_points)
     }
 
     pub fn from_name(
         name: &str,
         output_tree: &AnnotatedSyntaxTree,
+    ) -> Result<Self, SemanticAnalysisError> {
+        let named_type = Type::resolve_name(name, output_tree)?;
+
+        if let Some(feature) = named_type.required_feature() {
+            output_tree.require_feature(feature);
+        }
+
+        Ok(named_type)
+    }
+
+    fn resolve_name(
+        name: &str,
+        output_tree: &AnnotatedSyntaxTree,
     ) -> Result<Self, SemanticAnalysisError> {
         match name {
             "float" => Ok(Type::float()),
@@ -167,10 +264,67 @@ impl Type {
             "float4x3" => Ok(Type::float4x3()),
             "float4x4" => Ok(Type::float4x4()),
             "uint" => Ok(Type::uint()),
+            "uint2" => Ok(Type::uintn(2)),
+            "uint3" => Ok(Type::uintn(3)),
+            "uint4" => Ok(Type::uintn(4)),
+            "int" => Ok(Type::int()),
+            "half" => Ok(Type::half()),
+            "ThreadId" => Ok(output_tree.thread_id_type()),
+            _ if name.ends_with('>') => Type::resolve_generic_name(name, output_tree),
             _ => output_tree.get_type(name),
         }
     }
 
+    // The types of the stage entry points, "patch<T, n>", "triangle<T>" or
+    // "triangle_stream<T>"
+    fn resolve_generic_name(
+        name: &str,
+        output_tree: &AnnotatedSyntaxTree,
+    ) -> Result<Self, SemanticAnalysisError> {
+        let unknown = || SemanticAnalysisError::UnknownType(name.to_owned());
+
+        let (generic, arguments) = name[..name.len() - 1].split_once('<').ok_or_else(unknown)?;
+        let (element_name, count) = match arguments.split_once(", ") {
+            Some((element_name, count)) => (element_name, Some(count)),
+            None => (arguments, None),
+        };
+        let element_type = Type::resolve_name(element_name, output_tree)?;
+
+        match (generic, count) {
+            ("patch", Some(count)) => match count.parse() {
+                Ok(control_points @ 1..=32) => Ok(Type::patch(element_type, control_points)),
+                Ok(control_points) => Err(SemanticAnalysisError::InvalidPatchSize(control_points)),
+                Err(_) => Err(unknown()),
+            },
+            (_, Some(_)) => Err(unknown()),
+            (generic, None) => {
+                for topology in [Topology::Point, Topology::Line, Topology::Triangle] {
+                    if generic == topology.keyword() {
+                        return Ok(Type::GeometryInput(Box::new(element_type), topology));
+                    } else if generic.strip_suffix("_stream") == Some(topology.keyword()) {
+                        return Ok(Type::Stream(Box::new(element_type), topology));
+                    }
+                }
+
+                Err(unknown())
+            }
+        }
+    }
+
+    // Primitive types which not every target can express
+    pub fn required_feature(&self) -> Option<Feature> {
+        match self {
+            Type::Primitive(Primitive::FloatVec(1)) => Some(Feature::SingleComponentVector),
+            Type::Primitive(Primitive::FloatMatrix(n, m)) if *n == 1 || *m == 1 => {
+                Some(Feature::SingleComponentMatrix)
+            }
+            Type::Primitive(Primitive::Half) => Some(Feature::SixteenBitTypes),
+            Type::Patch(_, _) => Some(Feature::TessellationShaders),
+            Type::GeometryInput(_, _) | Type::Stream(_, _) => Some(Feature::GeometryShaders),
+            _ => None,
+        }
+    }
+
     pub fn is_uint(&self) -> bool {
         match self {
             Type::Primitive(primitive) => primitive.is_uint(),
@@ -179,6 +333,23 @@ impl Type {
         }
     }
 
+    pub fn is_int(&self) -> bool {
+        match self {
+            Type::Primitive(Primitive::Int) => true,
+            Type::Alias(inner_type) => inner_type.is_int(),
+            _ => false,
+        }
+    }
+
+    // Integer scalars and vectors, which are never interpolated
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Type::Primitive(primitive) => primitive.is_integer(),
+            Type::Alias(inner_type) => inner_type.is_integer(),
+            _ => false,
+        }
+    }
+
     pub fn is_float(&self) -> bool {
         match self {
             Type::Primitive(primitive) => primitive.is_float(),
@@ -203,11 +374,96 @@ impl Type {
         }
     }
 
+    // The element type and whether it can be written, for buffers
+    pub fn buffer_element(&self) -> Option<(&Type, bool)> {
+        match self {
+            Type::Buffer(element_type, writable) => Some((element_type, *writable)),
+            Type::Alias(inner_type) => inner_type.buffer_element(),
+            _ => None,
+        }
+    }
+
+    // The element of a patch or geometry shader input, which are indexed like
+    // buffers but can't be written
+    pub fn stage_element(&self) -> Option<&Type> {
+        match self {
+            Type::Patch(element_type, _) | Type::GeometryInput(element_type, _) => {
+                Some(element_type)
+            }
+            Type::Alias(inner_type) => inner_type.stage_element(),
+            _ => None,
+        }
+    }
+
+    // Patches, primitives and streams only exist as parameters of the entry
+    // points of their stages
+    pub fn is_stage_type(&self) -> bool {
+        match self {
+            Type::Patch(_, _) | Type::GeometryInput(_, _) | Type::Stream(_, _) => true,
+            Type::Alias(inner_type) => inner_type.is_stage_type(),
+            _ => false,
+        }
+    }
+
+    pub fn texture_dimension(&self) -> Option<TextureDimension> {
+        match self {
+            Type::Texture(_, dimension) | Type::DepthTexture(dimension) => Some(*dimension),
+            Type::Alias(inner_type) => inner_type.texture_dimension(),
+            _ => None,
+        }
+    }
+
+    // The element type, for textures which can be sampled, depth textures
+    // hold a single "float"
+    pub fn texture_element(&self) -> Option<Type> {
+        match self {
+            Type::Texture(element_type, _) => Some(element_type.as_ref().clone()),
+            Type::DepthTexture(_) => Some(Type::float()),
+            Type::Alias(inner_type) => inner_type.texture_element(),
+            _ => None,
+        }
+    }
+
+    pub fn is_depth_texture(&self) -> bool {
+        match self {
+            Type::DepthTexture(_) => true,
+            Type::Alias(inner_type) => inner_type.is_depth_texture(),
+            _ => false,
+        }
+    }
+
+    // Comparison samplers are samplers too
+    pub fn is_sampler(&self) -> bool {
+        match self {
+            Type::Primitive(primitive) => {
+                matches!(primitive, Primitive::Sampler | Primitive::ComparisonSampler)
+            }
+            Type::Alias(inner_type) => inner_type.is_sampler(),
+            _ => false,
+        }
+    }
+
+    // The texel type, for storage textures
+    pub fn storage_texel(&self) -> Option<&Type> {
+        match self {
+            Type::StorageTexture(texel_type) => Some(texel_type),
+            Type::Alias(inner_type) => inner_type.storage_texel(),
+            _ => None,
+        }
+    }
+
     pub fn member_type(&self, member: &str) -> Result<Type, SemanticAnalysisError> {
         let (members, name) = match self {
             Type::Primitive(primitive) => (primitive.members(), primitive.to_string()),
             Type::Struct(structure) => (structure.members(), structure.name().to_owned()),
             Type::Alias(inner_type) => return inner_type.member_type(member),
+            Type::Buffer(_, _)
+            | Type::StorageTexture(_)
+            | Type::Texture(_, _)
+            | Type::DepthTexture(_)
+            | Type::Patch(_, _)
+            | Type::GeometryInput(_, _)
+            | Type::Stream(_, _) => (&[][..], self.to_string()),
         };
 
         for (name, member_type) in members {
@@ -227,13 +483,27 @@ impl Type {
             Type::Primitive(primitive) => primitive.members(),
             Type::Struct(structure) => structure.members(),
             Type::Alias(inner_type) => inner_type.members(),
+            Type::Buffer(_, _)
+            | Type::StorageTexture(_)
+            | Type::Texture(_, _)
+            | Type::DepthTexture(_)
+            | Type::Patch(_, _)
+            | Type::GeometryInput(_, _)
+            | Type::Stream(_, _) => &[],
         }
     }
 
     pub fn sum_type(&self, other: &Type) -> Result<Type, SemanticAnalysisError> {
         let left_primitive = match self {
             Type::Primitive(primitive) => primitive,
-            Type::Struct(_) => {
+            Type::Struct(_)
+            | Type::Buffer(_, _)
+            | Type::StorageTexture(_)
+            | Type::Texture(_, _)
+            | Type::DepthTexture(_)
+            | Type::Patch(_, _)
+            | Type::GeometryInput(_, _)
+            | Type::Stream(_, _) => {
                 return Err(SemanticAnalysisError::InvalidOperation(
                     self.to_string(),
                     "+",
@@ -245,7 +515,14 @@ impl Type {
 
         let right_primitive = match other {
             Type::Primitive(primitive) => primitive,
-            Type::Struct(_) => {
+            Type::Struct(_)
+            | Type::Buffer(_, _)
+            | Type::StorageTexture(_)
+            | Type::Texture(_, _)
+            | Type::DepthTexture(_)
+            | Type::Patch(_, _)
+            | Type::GeometryInput(_, _)
+            | Type::Stream(_, _) => {
                 return Err(SemanticAnalysisError::InvalidOperation(
                     self.to_string(),
                     "+",
@@ -261,7 +538,14 @@ impl Type {
     pub fn product_type(&self, other: &Type) -> Result<Type, SemanticAnalysisError> {
         let left_primitive = match self {
             Type::Primitive(primitive) => primitive,
-            Type::Struct(_) => {
+            Type::Struct(_)
+            | Type::Buffer(_, _)
+            | Type::StorageTexture(_)
+            | Type::Texture(_, _)
+            | Type::DepthTexture(_)
+            | Type::Patch(_, _)
+            | Type::GeometryInput(_, _)
+            | Type::Stream(_, _) => {
                 return Err(SemanticAnalysisError::InvalidOperation(
                     self.to_string(),
                     "*",
@@ -273,7 +557,14 @@ impl Type {
 
         let right_primitive = match other {
             Type::Primitive(primitive) => primitive,
-            Type::Struct(_) => {
+            Type::Struct(_)
+            | Type::Buffer(_, _)
+            | Type::StorageTexture(_)
+            | Type::Texture(_, _)
+            | Type::DepthTexture(_)
+            | Type::Patch(_, _)
+            | Type::GeometryInput(_, _)
+            | Type::Stream(_, _) => {
                 return Err(SemanticAnalysisError::InvalidOperation(
                     self.to_string(),
                     "*",
@@ -291,6 +582,26 @@ impl Type {
             Type::Primitive(primitive) => primitive.hlsl(),
             Type::Struct(structure) => structure.name().to_string(),
             Type::Alias(inner_type) => inner_type.hlsl(),
+            Type::Buffer(element_type, writable) => match writable {
+                true => format!("RWStructuredBuffer<{}>", element_type.hlsl()),
+                false => format!("StructuredBuffer<{}>", element_type.hlsl()),
+            },
+            Type::StorageTexture(texel_type) => format!("RWTexture2D<{}>", texel_type.hlsl()),
+            Type::Texture(element_type, dimension) => {
+                format!("{}<{}>", dimension.hlsl(), element_type.hlsl())
+            }
+            Type::DepthTexture(dimension) => format!("{}<float>", dimension.hlsl()),
+            // Patches passed to "domain_main" are "OutputPatch", primitives
+            // are declared as arrays
+            Type::Patch(element_type, control_points) => {
+                format!("InputPatch<{}, {}>", element_type.hlsl(), control_points)
+            }
+            Type::GeometryInput(element_type, topology) => {
+                format!("{} {}", topology.keyword(), element_type.hlsl())
+            }
+            Type::Stream(element_type, topology) => {
+                format!("{}<{}>", topology.hlsl_stream(), element_type.hlsl())
+            }
         }
     }
 
@@ -299,6 +610,166 @@ impl Type {
             Type::Primitive(primitive) => primitive.glsl(),
             Type::Struct(structure) => structure.name().to_string(),
             Type::Alias(inner_type) => inner_type.glsl(),
+            // Buffers are blocks holding a runtime sized array
+            Type::Buffer(element_type, _) => format!("{}[]", element_type.glsl()),
+            Type::StorageTexture(texel_type) => match texel_type.is_integer() {
+                true => "uimage2D".to_owned(),
+                false => "image2D".to_owned(),
+            },
+            Type::Texture(element_type, dimension) => match element_type.is_integer() {
+                true => format!("usampler{}", dimension.glsl()),
+                false => format!("sampler{}", dimension.glsl()),
+            },
+            Type::DepthTexture(dimension) => format!("sampler{}Shadow", dimension.glsl()),
+            Type::Patch(element_type, control_points) => {
+                format!("{}[{}]", element_type.glsl(), control_points)
+            }
+            Type::GeometryInput(element_type, topology) => {
+                format!("{}[{}]", element_type.glsl(), topology.vertices())
+            }
+            // Vertices are emitted through the output variables of the stage
+            Type::Stream(_, _) => panic!("GLSL streams aren't variables"),
+        }
+    }
+
+    pub fn wgsl(&self) -> String {
+        match self {
+            Type::Primitive(primitive) => primitive.wgsl(),
+            Type::Struct(structure) => structure.name().to_string(),
+            Type::Alias(inner_type) => inner_type.wgsl(),
+            Type::Buffer(element_type, _) => format!("array<{}>", element_type.wgsl()),
+            // The WGSL type depends on the format of the declaration
+            Type::StorageTexture(_) => panic!("WGSL output has no storage textures"),
+            Type::Texture(element_type, dimension) => match element_type.is_integer() {
+                true => format!("texture_{}<u32>", dimension.name()),
+                false => format!("texture_{}<f32>", dimension.name()),
+            },
+            Type::DepthTexture(dimension) => format!("texture_depth_{}", dimension.name()),
+            Type::Patch(_, _) | Type::GeometryInput(_, _) | Type::Stream(_, _) => {
+                panic!("WGSL output has no tessellation or geometry shaders")
+            }
+        }
+    }
+
+    pub fn msl(&self) -> String {
+        match self {
+            Type::Primitive(primitive) => primitive.msl(),
+            Type::Struct(structure) => structure.name().to_string(),
+            Type::Alias(inner_type) => inner_type.msl(),
+            Type::Buffer(element_type, writable) => match writable {
+                true => format!("device {}*", element_type.msl()),
+                false => format!("const device {}*", element_type.msl()),
+            },
+            Type::StorageTexture(texel_type) => match texel_type.is_integer() {
+                true => "texture2d<uint, access::read_write>".to_owned(),
+                false => "texture2d<float, access::read_write>".to_owned(),
+            },
+            Type::Texture(element_type, dimension) => match element_type.is_integer() {
+                true => format!("{}<uint>", dimension.msl()),
+                false => format!("{}<float>", dimension.msl()),
+            },
+            // As in "depth2d", following "texture2d"
+            Type::DepthTexture(dimension) => format!(
+                "depth{}<float>",
+                dimension.msl().trim_start_matches("texture")
+            ),
+            Type::Patch(_, _) | Type::GeometryInput(_, _) | Type::Stream(_, _) => {
+                panic!("MSL output has no tessellation or geometry shaders")
+            }
+        }
+    }
+
+    pub fn spirv(&self, module: &mut Module) -> Id {
+        match self {
+            Type::Primitive(primitive) => primitive.spirv(module),
+            Type::Struct(structure) => structure.spirv(module),
+            Type::Alias(inner_type) => inner_type.spirv(module),
+            Type::Buffer(_, _) => panic!("SPIR-V output has no storage buffers"),
+            Type::StorageTexture(_) => panic!("SPIR-V output has no storage textures"),
+            Type::Texture(element_type, dimension) => {
+                let sampled_type = match element_type.is_integer() {
+                    true => module.type_uint(),
+                    false => module.type_float(),
+                };
+                module.type_image(sampled_type, *dimension, false)
+            }
+            Type::DepthTexture(dimension) => {
+                let sampled_type = module.type_float();
+                module.type_image(sampled_type, *dimension, true)
+            }
+            Type::Patch(_, _) | Type::GeometryInput(_, _) | Type::Stream(_, _) => {
+                panic!("SPIR-V output has no tessellation or geometry shaders")
+            }
+        }
+    }
+
+    // Size and alignment in a uniform buffer
+    pub fn std140_layout(&self) -> (usize, usize) {
+        match self {
+            Type::Primitive(primitive) => primitive.std140_layout(),
+            Type::Struct(structure) => structure.std140_layout(),
+            Type::Alias(inner_type) => inner_type.std140_layout(),
+            Type::Buffer(_, _)
+            | Type::StorageTexture(_)
+            | Type::Texture(_, _)
+            | Type::DepthTexture(_)
+            | Type::Patch(_, _)
+            | Type::GeometryInput(_, _)
+            | Type::Stream(_, _) => (0, 1),
+        }
+    }
+
+    // Size and alignment in a shader storage buffer
+    pub fn std430_layout(&self) -> (usize, usize) {
+        match self {
+            Type::Primitive(primitive) => primitive.std430_layout(),
+            Type::Struct(structure) => structure.std430_layout(),
+            Type::Alias(inner_type) => inner_type.std430_layout(),
+            _ => self.std140_layout(),
+        }
+    }
+
+    // Size in an HLSL structured buffer, which packs its elements tightly
+    pub fn packed_size(&self) -> usize {
+        match self {
+            Type::Primitive(primitive) => primitive.packed_size(),
+            Type::Struct(structure) => structure.packed_size(),
+            Type::Alias(inner_type) => inner_type.packed_size(),
+            _ => 0,
+        }
+    }
+
+    // Whether std430 places each value of the type where HLSL packs it,
+    // "float3" rounds up to 16 bytes as the element of an array
+    pub fn is_packed_in_std430(&self) -> bool {
+        let (size, alignment) = self.std430_layout();
+        size.next_multiple_of(alignment) == self.packed_size()
+            && match self {
+                Type::Struct(structure) => structure.is_packed_in_std430(),
+                Type::Alias(inner_type) => inner_type.is_packed_in_std430(),
+                _ => true,
+            }
+    }
+
+    // Collects the names of the structs the type is built from
+    pub fn struct_names(&self, names: &mut Vec<String>) {
+        match self {
+            Type::Primitive(_) => {}
+            Type::Struct(structure) => {
+                if !names.iter().any(|name| name == structure.name()) {
+                    names.push(structure.name().to_owned());
+
+                    for (_, member_type) in structure.members() {
+                        member_type.struct_names(names);
+                    }
+                }
+            }
+            Type::Alias(inner_type) => inner_type.struct_names(names),
+            Type::Buffer(element_type, _)
+            | Type::Patch(element_type, _)
+            | Type::GeometryInput(element_type, _)
+            | Type::Stream(element_type, _) => element_type.struct_names(names),
+            Type::StorageTexture(_) | Type::Texture(_, _) | Type::DepthTexture(_) => {}
         }
     }
 }
@@ -317,6 +788,47 @@ impl PartialEq for Type {
                 _ => false,
             },
             Type::Alias(inner_type) => inner_type.as_ref().eq(other),
+            Type::Buffer(element1, writable1) => match other {
+                Type::Buffer(element2, writable2) => element1 == element2 && writable1 == writable2,
+                Type::Alias(inner_type) => self.eq(inner_type),
+                _ => false,
+            },
+            Type::StorageTexture(texel1) => match other {
+                Type::StorageTexture(texel2) => texel1 == texel2,
+                Type::Alias(inner_type) => self.eq(inner_type),
+                _ => false,
+            },
+            Type::Texture(element1, dimension1) => match other {
+                Type::Texture(element2, dimension2) => {
+                    element1 == element2 && dimension1 == dimension2
+                }
+                Type::Alias(inner_type) => self.eq(inner_type),
+                _ => false,
+            },
+            Type::DepthTexture(dimension1) => match other {
+                Type::DepthTexture(dimension2) => dimension1 == dimension2,
+                Type::Alias(inner_type) => self.eq(inner_type),
+                _ => false,
+            },
+            Type::Patch(element1, control_points1) => match other {
+                Type::Patch(element2, control_points2) => {
+                    element1 == element2 && control_points1 == control_points2
+                }
+                Type::Alias(inner_type) => self.eq(inner_type),
+                _ => false,
+            },
+            Type::GeometryInput(element1, topology1) => match other {
+                Type::GeometryInput(element2, topology2) => {
+                    element1 == element2 && topology1 == topology2
+                }
+                Type::Alias(inner_type) => self.eq(inner_type),
+                _ => false,
+            },
+            Type::Stream(element1, topology1) => match other {
+                Type::Stream(element2, topology2) => element1 == element2 && topology1 == topology2,
+                Type::Alias(inner_type) => self.eq(inner_type),
+                _ => false,
+            },
         }
     }
 }
@@ -327,6 +839,26 @@ impl std::fmt::Display for Type {
             Type::Primitive(primitive) => write!(f, "{}", primitive),
             Type::Struct(structure) => write!(f, "{}", structure.name()),
             Type::Alias(inner_type) => inner_type.fmt(f),
+            Type::Buffer(element_type, writable) => match writable {
+                true => write!(f, "rwbuffer<{}>", element_type),
+                false => write!(f, "buffer<{}>", element_type),
+            },
+            Type::StorageTexture(texel_type) => write!(f, "rwtexture<{}>", texel_type),
+            // Written like the declaration, which holds "float4" by default
+            Type::Texture(element_type, dimension) => match **element_type == Type::float4() {
+                true => write!(f, "{}", dimension.keyword()),
+                false => write!(f, "{}<{}>", dimension.keyword(), element_type),
+            },
+            Type::DepthTexture(dimension) => write!(f, "depth_{}", dimension.keyword()),
+            Type::Patch(element_type, control_points) => {
+                write!(f, "patch<{}, {}>", element_type, control_points)
+            }
+            Type::GeometryInput(element_type, topology) => {
+                write!(f, "{}<{}>", topology.keyword(), element_type)
+            }
+            Type::Stream(element_type, topology) => {
+                write!(f, "{}_stream<{}>", topology.keyword(), element_type)
+            }
         }
     }
 }
@@ -345,10 +877,19 @@ impl Primitive {
                     4 => FLOAT4_MEMBERS.as_ref().unwrap(),
                     _ => panic!("Invalid float vector dimension"),
                 },
+                Primitive::UintVec(dimension) => match dimension {
+                    2 => (*std::ptr::addr_of!(UINT2_MEMBERS)).as_ref().unwrap(),
+                    3 => (*std::ptr::addr_of!(UINT3_MEMBERS)).as_ref().unwrap(),
+                    4 => (*std::ptr::addr_of!(UINT4_MEMBERS)).as_ref().unwrap(),
+                    _ => panic!("Invalid uint vector dimension"),
+                },
                 Primitive::FloatMatrix(_, _)
                 | Primitive::Void
-                | Primitive::Texture
-                | Primitive::Uint => &[],
+                | Primitive::Sampler
+                | Primitive::ComparisonSampler
+                | Primitive::Uint
+                | Primitive::Int
+                | Primitive::Half => &[],
             }
         }
     }
@@ -360,6 +901,13 @@ impl Primitive {
         }
     }
 
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Primitive::Uint | Primitive::UintVec(_) | Primitive::Int
+        )
+    }
+
     pub fn is_float(&self) -> bool {
         match self {
             Primitive::Float => true,
@@ -383,14 +931,21 @@ impl Primitive {
 
     pub fn sum_type(&self, other: &Primitive) -> Result<Type, SemanticAnalysisError> {
         match self {
-            Primitive::FloatMatrix(_, _) | Primitive::Void | Primitive::Texture => Err(()),
+            Primitive::FloatMatrix(_, _)
+            | Primitive::Void
+            | Primitive::Sampler
+            | Primitive::ComparisonSampler => Err(()),
             Primitive::Float => match other {
                 Primitive::Float => Ok(Type::float()),
                 Primitive::FloatMatrix(_, _)
                 | Primitive::FloatVec(_)
                 | Primitive::Void
                 | Primitive::Uint
-                | Primitive::Texture => Err(()),
+                | Primitive::UintVec(_)
+                | Primitive::Int
+                | Primitive::Half
+                | Primitive::Sampler
+                | Primitive::ComparisonSampler => Err(()),
             },
             Primitive::FloatVec(left_dimension) => match other {
                 Primitive::FloatVec(right_dimension) => match left_dimension == right_dimension {
@@ -400,13 +955,31 @@ impl Primitive {
                 Primitive::FloatMatrix(_, _)
                 | Primitive::Float
                 | Primitive::Void
-                | Primitive::Texture
-                | Primitive::Uint => Err(()),
+                | Primitive::Sampler
+                | Primitive::ComparisonSampler
+                | Primitive::Uint
+                | Primitive::UintVec(_)
+                | Primitive::Int
+                | Primitive::Half => Err(()),
             },
             Primitive::Uint => match other {
                 Primitive::Uint => Ok(Type::uint()),
                 _ => Err(()),
             },
+            Primitive::UintVec(left_dimension) => match other {
+                Primitive::UintVec(right_dimension) if left_dimension == right_dimension => {
+                    Ok(Type::uintn(*left_dimension))
+                }
+                _ => Err(()),
+            },
+            Primitive::Int => match other {
+                Primitive::Int => Ok(Type::int()),
+                _ => Err(()),
+            },
+            Primitive::Half => match other {
+                Primitive::Half => Ok(Type::half()),
+                _ => Err(()),
+            },
         }
         .map_err(|_| {
             SemanticAnalysisError::InvalidOperation(self.to_string(), "+", other.to_string())
@@ -415,14 +988,20 @@ impl Primitive {
 
     pub fn product_type(&self, other: &Primitive) -> Result<Type, SemanticAnalysisError> {
         match self {
-            Primitive::Void | Primitive::Texture => Err(()),
+            Primitive::Void | Primitive::Sampler | Primitive::ComparisonSampler => Err(()),
             Primitive::Float => match other {
                 Primitive::Float => Ok(Type::float()),
                 Primitive::FloatVec(dimension) => {
                     Ok(Type::Primitive(Primitive::FloatVec(*dimension)))
                 }
                 Primitive::FloatMatrix(n, m) => Ok(Type::Primitive(Primitive::FloatMatrix(*n, *m))),
-                Primitive::Void | Primitive::Texture | Primitive::Uint => Err(()),
+                Primitive::Void
+                | Primitive::Sampler
+                | Primitive::ComparisonSampler
+                | Primitive::Uint
+                | Primitive::UintVec(_)
+                | Primitive::Int
+                | Primitive::Half => Err(()),
             },
             Primitive::FloatVec(left_dimension) => match other {
                 Primitive::Float => Ok(Type::Primitive(Primitive::FloatVec(*left_dimension))),
@@ -434,7 +1013,13 @@ impl Primitive {
                     true => Ok(Type::Primitive(Primitive::FloatVec(*m))),
                     false => Err(()),
                 },
-                Primitive::Void | Primitive::Texture | Primitive::Uint => Err(()),
+                Primitive::Void
+                | Primitive::Sampler
+                | Primitive::ComparisonSampler
+                | Primitive::Uint
+                | Primitive::UintVec(_)
+                | Primitive::Int
+                | Primitive::Half => Err(()),
             },
             Primitive::FloatMatrix(left_n, left_m) => match other {
                 Primitive::Float => Ok(Type::Primitive(Primitive::FloatMatrix(*left_n, *left_m))),
@@ -445,12 +1030,32 @@ impl Primitive {
                 Primitive::FloatMatrix(_, right_m) => {
                     Ok(Type::Primitive(Primitive::FloatMatrix(*left_n, *right_m)))
                 }
-                Primitive::Void | Primitive::Texture | Primitive::Uint => Err(()),
+                Primitive::Void
+                | Primitive::Sampler
+                | Primitive::ComparisonSampler
+                | Primitive::Uint
+                | Primitive::UintVec(_)
+                | Primitive::Int
+                | Primitive::Half => Err(()),
             },
             Primitive::Uint => match other {
                 Primitive::Uint => Ok(Type::uint()),
                 _ => Err(()),
             },
+            Primitive::UintVec(left_dimension) => match other {
+                Primitive::UintVec(right_dimension) if left_dimension == right_dimension => {
+                    Ok(Type::uintn(*left_dimension))
+                }
+                _ => Err(()),
+            },
+            Primitive::Int => match other {
+                Primitive::Int => Ok(Type::int()),
+                _ => Err(()),
+            },
+            Primitive::Half => match other {
+                Primitive::Half => Ok(Type::half()),
+                _ => Err(()),
+            },
         }
         .map_err(|_| {
             SemanticAnalysisError::InvalidOperation(self.to_string(), "*", other.to_string())
@@ -464,7 +1069,11 @@ impl Primitive {
             Primitive::FloatVec(dimension) => format!("float{}", dimension),
             Primitive::FloatMatrix(n, m) => format!("float{}x{}", n, m),
             Primitive::Uint => "uint".to_owned(),
-            Primitive::Texture => "Texture2D".to_owned(),
+            Primitive::UintVec(dimension) => format!("uint{}", dimension),
+            Primitive::Int => "int".to_owned(),
+            Primitive::Half => "float16_t".to_owned(),
+            Primitive::Sampler => "SamplerState".to_owned(),
+            Primitive::ComparisonSampler => "SamplerComparisonState".to_owned(),
         }
     }
 
@@ -475,7 +1084,98 @@ impl Primitive {
             Primitive::FloatVec(dimension) => format!("vec{}", dimension),
             Primitive::FloatMatrix(n, m) => format!("mat{}x{}", m, n),
             Primitive::Uint => "uint".to_owned(),
-            Primitive::Texture => "sampler2D".to_owned(),
+            Primitive::UintVec(dimension) => format!("uvec{}", dimension),
+            Primitive::Int => "int".to_owned(),
+            Primitive::Half => "float16_t".to_owned(),
+            Primitive::Sampler => "sampler".to_owned(),
+            Primitive::ComparisonSampler => "samplerShadow".to_owned(),
+        }
+    }
+
+    pub fn wgsl(&self) -> String {
+        match self {
+            Primitive::Void => "void".to_owned(),
+            Primitive::Float => "f32".to_owned(),
+            Primitive::FloatVec(dimension) => format!("vec{}<f32>", dimension),
+            Primitive::FloatMatrix(n, m) => format!("mat{}x{}<f32>", m, n),
+            Primitive::Uint => "u32".to_owned(),
+            Primitive::UintVec(dimension) => format!("vec{}<u32>", dimension),
+            Primitive::Int => "i32".to_owned(),
+            Primitive::Half => "f16".to_owned(),
+            Primitive::Sampler => "sampler".to_owned(),
+            Primitive::ComparisonSampler => "sampler_comparison".to_owned(),
+        }
+    }
+
+    pub fn msl(&self) -> String {
+        match self {
+            Primitive::Void => "void".to_owned(),
+            Primitive::Float => "float".to_owned(),
+            Primitive::FloatVec(dimension) => format!("float{}", dimension),
+            Primitive::FloatMatrix(n, m) => format!("float{}x{}", m, n),
+            Primitive::Uint => "uint".to_owned(),
+            Primitive::UintVec(dimension) => format!("uint{}", dimension),
+            Primitive::Int => "int".to_owned(),
+            Primitive::Half => "half".to_owned(),
+            Primitive::Sampler | Primitive::ComparisonSampler => "sampler".to_owned(),
+        }
+    }
+
+    pub fn spirv(&self, module: &mut Module) -> Id {
+        match self {
+            Primitive::Void => module.type_void(),
+            Primitive::Float => module.type_float(),
+            Primitive::FloatVec(dimension) => {
+                let float_type = module.type_float();
+                module.type_vector(float_type, *dimension as u32)
+            }
+            Primitive::FloatMatrix(n, m) => {
+                let float_type = module.type_float();
+                let column_type = module.type_vector(float_type, *n as u32);
+                module.type_matrix(column_type, *m as u32)
+            }
+            Primitive::Uint => module.type_uint(),
+            Primitive::UintVec(dimension) => {
+                let uint_type = module.type_uint();
+                module.type_vector(uint_type, *dimension as u32)
+            }
+            Primitive::Int => module.type_int(),
+            Primitive::Half => module.type_id(OP_TYPE_FLOAT, &[16]),
+            Primitive::Sampler | Primitive::ComparisonSampler => module.type_sampler(),
+        }
+    }
+
+    pub fn std140_layout(&self) -> (usize, usize) {
+        match self {
+            Primitive::Float | Primitive::Uint | Primitive::Int | Primitive::FloatVec(1) => (4, 4),
+            Primitive::FloatVec(2) | Primitive::UintVec(2) => (8, 8),
+            Primitive::FloatVec(3) | Primitive::UintVec(3) => (12, 16),
+            Primitive::FloatVec(_) | Primitive::UintVec(_) => (16, 16),
+            // Every column is aligned as a "float4"
+            Primitive::FloatMatrix(_, m) => (16 * m, 16),
+            Primitive::Half => (2, 2),
+            Primitive::Void | Primitive::Sampler | Primitive::ComparisonSampler => (0, 1),
+        }
+    }
+
+    // Vectors of three components are aligned as four, unlike in std140
+    // matrix columns are only aligned as their vector
+    pub fn std430_layout(&self) -> (usize, usize) {
+        match self {
+            Primitive::FloatMatrix(1, m) => (4 * m, 4),
+            Primitive::FloatMatrix(2, m) => (8 * m, 8),
+            Primitive::FloatMatrix(_, m) => (16 * m, 16),
+            primitive => primitive.std140_layout(),
+        }
+    }
+
+    pub fn packed_size(&self) -> usize {
+        match self {
+            Primitive::Float | Primitive::Uint | Primitive::Int => 4,
+            Primitive::FloatVec(n) | Primitive::UintVec(n) => 4 * n,
+            Primitive::FloatMatrix(n, m) => 4 * n * m,
+            Primitive::Half => 2,
+            Primitive::Void | Primitive::Sampler | Primitive::ComparisonSampler => 0,
         }
     }
 
@@ -505,6 +1205,24 @@ impl Primitive {
                 ("z".to_owned(), Type::Primitive(Primitive::Float)),
                 ("w".to_owned(), Type::Primitive(Primitive::Float)),
             ]));
+
+            UINT2_MEMBERS = Some(Rc::new(vec![
+                ("x".to_owned(), Type::uint()),
+                ("y".to_owned(), Type::uint()),
+            ]));
+
+            UINT3_MEMBERS = Some(Rc::new(vec![
+                ("x".to_owned(), Type::uint()),
+                ("y".to_owned(), Type::uint()),
+                ("z".to_owned(), Type::uint()),
+            ]));
+
+            UINT4_MEMBERS = Some(Rc::new(vec![
+                ("x".to_owned(), Type::uint()),
+                ("y".to_owned(), Type::uint()),
+                ("z".to_owned(), Type::uint()),
+                ("w".to_owned(), Type::uint()),
+            ]));
         });
     }
 }
@@ -517,7 +1235,188 @@ impl std::fmt::Display for Primitive {
             Primitive::FloatVec(dimension) => write!(f, "float{}", dimension),
             Primitive::FloatMatrix(n, m) => write!(f, "float{}x{}", n, m),
             Primitive::Uint => write!(f, "uint"),
-            Primitive::Texture => write!(f, "texture"),
+            Primitive::UintVec(dimension) => write!(f, "uint{}", dimension),
+            Primitive::Int => write!(f, "int"),
+            Primitive::Half => write!(f, "half"),
+            Primitive::Sampler => write!(f, "sampler"),
+            Primitive::ComparisonSampler => write!(f, "sampler_comparison"),
+        }
+    }
+}
+
+impl TextureDimension {
+    // The declaration keyword, also used to display the type
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            TextureDimension::D1 => "texture1d",
+            TextureDimension::D2 => "texture",
+            TextureDimension::D3 => "texture3d",
+            TextureDimension::Cube => "texture_cube",
+            TextureDimension::D2Array => "texture2d_array",
+            TextureDimension::CubeArray => "texture_cube_array",
+        }
+    }
+
+    // The name used by the reflection and WGSL
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextureDimension::D1 => "1d",
+            TextureDimension::D2 => "2d",
+            TextureDimension::D3 => "3d",
+            TextureDimension::Cube => "cube",
+            TextureDimension::D2Array => "2d_array",
+            TextureDimension::CubeArray => "cube_array",
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(
+            self,
+            TextureDimension::D2Array | TextureDimension::CubeArray
+        )
+    }
+
+    // The components of the "float" coordinates "sample_texture" takes, the
+    // array layer comes last
+    pub fn sample_coordinates(&self) -> usize {
+        match self {
+            TextureDimension::D1 => 1,
+            TextureDimension::D2 => 2,
+            TextureDimension::D3 | TextureDimension::Cube | TextureDimension::D2Array => 3,
+            TextureDimension::CubeArray => 4,
+        }
+    }
+
+    // The components of the texel coordinates "load" takes, including the
+    // array layer but not the mip level, cube textures can't be loaded from
+    pub fn load_coordinates(&self) -> Option<usize> {
+        match self {
+            TextureDimension::D1 => Some(1),
+            TextureDimension::D2 => Some(2),
+            TextureDimension::D3 | TextureDimension::D2Array => Some(3),
+            TextureDimension::Cube | TextureDimension::CubeArray => None,
+        }
+    }
+
+    // The components of the "sample_grad" gradients, which have no array layer
+    pub fn gradient_components(&self) -> usize {
+        match self {
+            TextureDimension::D1 => 1,
+            TextureDimension::D2 | TextureDimension::D2Array => 2,
+            TextureDimension::D3 | TextureDimension::Cube | TextureDimension::CubeArray => 3,
+        }
+    }
+
+    // The components of the "sample_offset" texel offset, neither 1D nor cube
+    // textures can be sampled with an offset by every target
+    pub fn offset_components(&self) -> Option<usize> {
+        match self {
+            TextureDimension::D2 | TextureDimension::D2Array => Some(2),
+            TextureDimension::D3 => Some(3),
+            TextureDimension::D1 | TextureDimension::Cube | TextureDimension::CubeArray => None,
+        }
+    }
+
+    // The components of the "texture_size" result, arrays end with their
+    // number of layers
+    pub fn size_components(&self) -> usize {
+        match self {
+            TextureDimension::D1 => 1,
+            TextureDimension::D2 | TextureDimension::Cube => 2,
+            TextureDimension::D3 | TextureDimension::D2Array | TextureDimension::CubeArray => 3,
+        }
+    }
+
+    // "gather" and "sample_compare" only take 2D and cube textures
+    pub fn is_2d_or_cube(&self) -> bool {
+        !matches!(self, TextureDimension::D1 | TextureDimension::D3)
+    }
+
+    pub fn required_feature(&self) -> Option<Feature> {
+        match self {
+            TextureDimension::D1 => Some(Feature::OneDimensionalTextures),
+            TextureDimension::CubeArray => Some(Feature::CubeArrayTextures),
+            _ => None,
+        }
+    }
+
+    pub fn hlsl(&self) -> &'static str {
+        match self {
+            TextureDimension::D1 => "Texture1D",
+            TextureDimension::D2 => "Texture2D",
+            TextureDimension::D3 => "Texture3D",
+            TextureDimension::Cube => "TextureCube",
+            TextureDimension::D2Array => "Texture2DArray",
+            TextureDimension::CubeArray => "TextureCubeArray",
+        }
+    }
+
+    // The suffix of the GLSL sampler and texture types, as in "sampler2D"
+    pub fn glsl(&self) -> &'static str {
+        match self {
+            TextureDimension::D1 => "1D",
+            TextureDimension::D2 => "2D",
+            TextureDimension::D3 => "3D",
+            TextureDimension::Cube => "Cube",
+            TextureDimension::D2Array => "2DArray",
+            TextureDimension::CubeArray => "CubeArray",
+        }
+    }
+
+    pub fn msl(&self) -> &'static str {
+        match self {
+            TextureDimension::D1 => "texture1d",
+            TextureDimension::D2 => "texture2d",
+            TextureDimension::D3 => "texture3d",
+            TextureDimension::Cube => "texturecube",
+            TextureDimension::D2Array => "texture2d_array",
+            TextureDimension::CubeArray => "texturecube_array",
+        }
+    }
+}
+
+impl Topology {
+    // The name of the geometry shader input, "triangle_stream" for the output
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Topology::Point => "point",
+            Topology::Line => "line",
+            Topology::Triangle => "triangle",
+        }
+    }
+
+    pub fn vertices(&self) -> usize {
+        match self {
+            Topology::Point => 1,
+            Topology::Line => 2,
+            Topology::Triangle => 3,
+        }
+    }
+
+    pub fn hlsl_stream(&self) -> &'static str {
+        match self {
+            Topology::Point => "PointStream",
+            Topology::Line => "LineStream",
+            Topology::Triangle => "TriangleStream",
+        }
+    }
+
+    // The layout of the geometry shader input
+    pub fn glsl_input(&self) -> &'static str {
+        match self {
+            Topology::Point => "points",
+            Topology::Line => "lines",
+            Topology::Triangle => "triangles",
+        }
+    }
+
+    // The layout of the geometry shader output, lines and triangles are
+    // written as strips
+    pub fn glsl_output(&self) -> &'static str {
+        match self {
+            Topology::Point => "points",
+            Topology::Line => "line_strip",
+            Topology::Triangle => "triangle_strip",
         }
     }
 }
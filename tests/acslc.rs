@@ -0,0 +1,218 @@
+use std::{
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+// A scratch directory for the outputs of one test
+fn output_directory(test_name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join("acslc_tests").join(test_name);
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn acslc(arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_acslc"))
+        .args(arguments)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn check_verifies_target() {
+    let directory = output_directory("check_verifies_target");
+    let output_path = directory.join("compute");
+    let output_path = output_path.to_str().unwrap();
+
+    // "--check" fails like a build for the same target
+    let output = acslc(&["tests/compute.acsl", "--target", "wgsl", "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with(
+        "error: The WGSL backend does not yet support compute shaders (\"#[compute]\")\n"
+    ));
+
+    let output = acslc(&["tests/register_space.acsl", "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = acslc(&[
+        "tests/register_space.acsl",
+        "--shader-model",
+        "5.1",
+        "--check",
+        "-o",
+        output_path,
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 0);
+}
+
+#[test]
+fn exit_codes() {
+    let directory = output_directory("exit_codes");
+    let output_path = directory.join("simple");
+
+    let output = acslc(&["tests/simple.acsl", "-o", output_path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty());
+    assert_eq!(
+        std::fs::read_to_string(&output_path).unwrap(),
+        std::fs::read_to_string("tests/simple.hlsl").unwrap()
+    );
+
+    let output = acslc(&["--help"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("Usage: acslc"));
+
+    let invalid = directory.join("invalid.acsl");
+    std::fs::write(&invalid, "fn vertex_main(").unwrap();
+    let output = acslc(&[invalid.to_str().unwrap(), "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("error: Unexpected end of file\n"));
+
+    let output = acslc(&["tests/simple.acsl", "--vertex-entry", "a b", "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with("error: Invalid entry point name \"a b\""));
+
+    let output = acslc(&["tests/simple.acsl", "--target", "dxil"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("error: Unknown target \"dxil\""));
+    assert!(stderr.contains("Usage: acslc"));
+
+    let output = acslc(&[]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let missing = directory.join("missing.acsl");
+    let output = acslc(&[missing.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with(&format!("error: Unable to read \"{}\"", missing.display())));
+
+    let unwritable = directory.join("missing").join("simple.hlsl");
+    let output = acslc(&["tests/simple.acsl", "-o", unwritable.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).starts_with(&format!(
+            "error: Unable to write \"{}\"",
+            unwritable.display()
+        ))
+    );
+}
+
+#[test]
+fn semantic_error_location() {
+    let directory = output_directory("semantic_error_location");
+    let input = directory.join("shader.acsl");
+    std::fs::write(
+        &input,
+        "struct Vertex {\n    position: float4 : SV_POSITION,\n}\n\nfn vertex_main(input: Vertex) -> Vertex {\n    return missing;\n}\n",
+    )
+    .unwrap();
+
+    // Errors inside a declaration point at its start
+    let output = acslc(&[input.to_str().unwrap(), "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        format!(
+            "error: Unknown variable name \"missing\"\n --> {}:5:1\n  |\n5 | fn vertex_main(input: Vertex) -> Vertex {{\n  | ^\n",
+            input.display()
+        )
+    );
+
+    // Errors about the whole file have no location
+    std::fs::write(
+        &input,
+        "struct Vertex {\n    position: float4 : SV_POSITION,\n}\n",
+    )
+    .unwrap();
+    let output = acslc(&[input.to_str().unwrap(), "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).ends_with(&format!("\n --> {}\n", input.display()))
+    );
+}
+
+// Kills the watching acslc when the test ends, even if an assertion failed
+struct Watcher(std::process::Child);
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+// Waits for the post-build command of a watching acslc to have run "builds" times
+fn wait_for_builds(log: &std::path::Path, builds: usize) {
+    let start = std::time::Instant::now();
+    loop {
+        let logged = std::fs::read_to_string(log).unwrap_or_default();
+        if logged.lines().count() >= builds {
+            assert_eq!(logged.lines().count(), builds);
+            return;
+        }
+
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Expected {} builds, found {}",
+            builds,
+            logged.lines().count()
+        );
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn watch_rebuilds() {
+    let directory = output_directory("watch_rebuilds");
+    let input = directory.join("shader.acsl");
+    let output_path = directory.join("shader.hlsl");
+    let log = directory.join("builds.log");
+    let simple = std::fs::read_to_string("tests/simple.acsl").unwrap();
+    std::fs::write(&input, &simple).unwrap();
+
+    let watcher = Watcher(
+        Command::new(env!("CARGO_BIN_EXE_acslc"))
+            .args([
+                input.to_str().unwrap(),
+                "--watch",
+                "--on-build",
+                &format!("echo built >> {}", log.display()),
+            ])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .unwrap(),
+    );
+
+    // The input is compiled once when watching starts
+    wait_for_builds(&log, 1);
+    assert_eq!(
+        std::fs::read_to_string(&output_path).unwrap(),
+        std::fs::read_to_string("tests/simple.hlsl").unwrap()
+    );
+
+    // A failed build keeps the previous output and skips the command
+    std::fs::write(&input, "fn vertex_main(").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    wait_for_builds(&log, 1);
+    assert_eq!(
+        std::fs::read_to_string(&output_path).unwrap(),
+        std::fs::read_to_string("tests/simple.hlsl").unwrap()
+    );
+
+    std::fs::write(&input, &simple).unwrap();
+    wait_for_builds(&log, 2);
+
+    // Only the input triggers a build
+    std::fs::write(directory.join("other.acsl"), &simple).unwrap();
+    std::fs::write(&input, &simple).unwrap();
+    wait_for_builds(&log, 3);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    drop(watcher);
+    wait_for_builds(&log, 3);
+}
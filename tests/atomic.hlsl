@@ -0,0 +1,56 @@
+// Generated from Alexandria Common Shader Language
+
+struct ThreadId {
+    uint3 dispatch_thread: SV_DispatchThreadID;
+    uint3 group: SV_GroupID;
+    uint3 group_thread: SV_GroupThreadID;
+    uint group_index: SV_GroupIndex;
+};
+ThreadId acsl_create_ThreadId(uint3 dispatch_thread, uint3 group, uint3 group_thread, uint group_index) {
+    ThreadId output;
+    output.dispatch_thread = dispatch_thread;
+    output.group = group;
+    output.group_thread = group_thread;
+    output.group_index = group_index;
+    return output;
+}
+
+RWStructuredBuffer<uint> histogram : register(u0);
+
+RWStructuredBuffer<uint> locks : register(u1);
+
+RWStructuredBuffer<int> balances : register(u2);
+
+groupshared uint group_maximum;
+
+groupshared int group_minimum;
+
+[numthreads(64, 1, 1)]
+void main(ThreadId id) {
+    uint one = uint(1.0);
+    uint bucket = id.dispatch_thread.x;
+    uint previous;
+    InterlockedAdd(histogram[bucket], one, previous);
+    InterlockedMax(group_maximum, previous);
+    InterlockedMin(histogram[bucket], id.group_index);
+    InterlockedAnd(histogram[bucket], previous);
+    InterlockedOr(group_maximum, one);
+    {
+        uint acsl_original;
+        InterlockedExchange(group_maximum, bucket, acsl_original);
+    }
+    uint locked;
+    InterlockedCompareExchange(locks[bucket], previous, one, locked);
+    InterlockedCompareStore(locks[bucket], locked, previous);
+    int change = int((0.0 - 1.0));
+    int balance;
+    InterlockedAdd(balances[bucket], change, balance);
+    InterlockedMin(group_minimum, balance);
+    {
+        int acsl_original;
+        InterlockedExchange(group_minimum, int(bucket), acsl_original);
+    }
+    InterlockedCompareStore(balances[bucket], balance, change);
+    GroupMemoryBarrierWithGroupSync();
+}
+
@@ -0,0 +1,104 @@
+mod common;
+
+use acsl::Compiler;
+use common::{error, fragment, kernel};
+
+const RESOURCES: &str = "buffer<uint> input : 0;\nrwbuffer<uint> counters : 1;\nrwbuffer<float> values : 2;\nrwbuffer<int> balances : 3;\n\ngroupshared count: uint;\ngroupshared total: float;";
+
+#[test]
+fn atomic_hlsl() {
+    let input_code = std::fs::read_to_string("tests/atomic.acsl").unwrap();
+    let target_code = std::fs::read_to_string("tests/atomic.hlsl").unwrap();
+
+    let hlsl = Compiler::default().compile_hlsl(&input_code).unwrap();
+
+    assert_eq!(hlsl.code(), target_code);
+}
+
+#[test]
+fn atomic_glsl() {
+    let input_code = std::fs::read_to_string("tests/atomic.acsl").unwrap();
+    let target_code_main = std::fs::read_to_string("tests/atomic_main_comp.glsl").unwrap();
+
+    let glsl = Compiler::default().compile_glsl(&input_code).unwrap();
+
+    assert_eq!(glsl.compute().len(), 1);
+    assert_eq!(glsl.compute()[0].1, target_code_main);
+}
+
+#[test]
+fn invalid_atomics() {
+    assert_eq!(
+        error(&kernel(RESOURCES, "    atomic_add(input[index], index);")),
+        "\"atomic_add\" can only operate on a \"uint\" or \"int\" element of a \"rwbuffer\" or group shared variable, found \"input[index]\""
+    );
+    assert_eq!(
+        error(&kernel(RESOURCES, "    atomic_max(index, index);")),
+        "\"atomic_max\" can only operate on a \"uint\" or \"int\" element of a \"rwbuffer\" or group shared variable, found \"index\""
+    );
+    assert_eq!(
+        error(&kernel(RESOURCES, "    atomic_or(total, index);")),
+        "\"atomic_or\" can only operate on a \"uint\" or \"int\" element of a \"rwbuffer\" or group shared variable, found \"total\""
+    );
+    assert_eq!(
+        error(&kernel(RESOURCES, "    atomic_exchange(values[index], index);")),
+        "\"atomic_exchange\" can only operate on a \"uint\" or \"int\" element of a \"rwbuffer\" or group shared variable, found \"values[index]\""
+    );
+    assert_eq!(
+        error(&kernel(RESOURCES, "    atomic_add(count, 1.0);")),
+        "Function \"atomic_add\" expects parameter 2 to be of type \"uint\", instead found type \"float\""
+    );
+    assert_eq!(
+        error(&kernel(RESOURCES, "    atomic_add(balances[index], index);")),
+        "Function \"atomic_add\" expects parameter 2 to be of type \"int\", instead found type \"uint\""
+    );
+    assert_eq!(
+        error(&kernel(
+            RESOURCES,
+            "    atomic_compare_exchange(count, index);"
+        )),
+        "Function \"atomic_compare_exchange\" expects 3 parameters, only 2 provided"
+    );
+    assert_eq!(
+        error(&kernel(
+            RESOURCES,
+            "    counters[index] = atomic_add(count, index) + index;"
+        )),
+        "\"atomic_add\" can only be called on its own or to define a variable"
+    );
+}
+
+#[test]
+fn signed_integers() {
+    let code = fragment(
+        "",
+        "    let offset = float_to_int(uv.x * 8.0) + uint_to_int(2);\n    let steps = int_to_float(offset) + uint_to_float(int_to_uint(offset));\n    return position * steps;",
+    );
+
+    let hlsl = acsl::compile_hlsl(&code).unwrap();
+    assert!(hlsl
+        .contains("    int offset = (int((uv.x * 8.0)) + int(2u));\n    float steps = (float(offset) + float(uint(offset)));\n"));
+
+    let glsl = Compiler::default().compile_glsl(&code).unwrap();
+    assert!(glsl
+        .fragment()
+        .contains("    int offset = (int((uv.x * 8.0)) + int(2u));\n"));
+
+    let wgsl = acsl::compile_wgsl(&code).unwrap();
+    assert!(wgsl
+        .contains("    var offset: i32 = (i32((uv.x * 8.0)) + i32(2u));\n    var steps: f32 = (f32(offset) + f32(u32(offset)));\n"));
+
+    let msl = acsl::compile_msl(&code).unwrap();
+    assert!(msl.contains("    int offset = (int((uv.x * 8.0)) + int(2u));\n"));
+
+    let disassembly = acsl::disassemble_spirv(&acsl::compile_spirv(&code).unwrap());
+    for instruction in [
+        "OpTypeInt 32 1",
+        "OpConvertFToS",
+        "OpBitcast",
+        "OpConvertSToF",
+        "OpIAdd",
+    ] {
+        assert!(disassembly.contains(instruction), "{}", instruction);
+    }
+}
@@ -0,0 +1,51 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+struct ThreadId {
+    uvec3 dispatch_thread;
+    uvec3 group;
+    uvec3 group_thread;
+    uint group_index;
+};
+
+layout(std430, binding = 0) buffer acsl_buffer_0 {
+    uint histogram[];
+};
+
+layout(std430, binding = 1) buffer acsl_buffer_1 {
+    uint locks[];
+};
+
+layout(std430, binding = 2) buffer acsl_buffer_2 {
+    int balances[];
+};
+
+shared uint group_maximum;
+
+shared int group_minimum;
+
+void main() {
+    ThreadId id = ThreadId(gl_GlobalInvocationID, gl_WorkGroupID, gl_LocalInvocationID, gl_LocalInvocationIndex);
+
+    uint one = uint(1.0);
+    uint bucket = id.dispatch_thread.x;
+    uint previous = atomicAdd(histogram[bucket], one);
+    atomicMax(group_maximum, previous);
+    atomicMin(histogram[bucket], id.group_index);
+    atomicAnd(histogram[bucket], previous);
+    atomicOr(group_maximum, one);
+    atomicExchange(group_maximum, bucket);
+    uint locked = atomicCompSwap(locks[bucket], previous, one);
+    atomicCompSwap(locks[bucket], locked, previous);
+    int change = int((0.0 - 1.0));
+    int balance = atomicAdd(balances[bucket], change);
+    atomicMin(group_minimum, balance);
+    atomicExchange(group_minimum, int(bucket));
+    atomicCompSwap(balances[bucket], balance, change);
+    memoryBarrierShared();
+    barrier();
+}
+
@@ -0,0 +1,81 @@
+// Generated from Alexandria Common Shader Language
+
+struct ThreadId {
+    uint3 dispatch_thread: SV_DispatchThreadID;
+    uint3 group: SV_GroupID;
+    uint3 group_thread: SV_GroupThreadID;
+    uint group_index: SV_GroupIndex;
+};
+ThreadId acsl_create_ThreadId(uint3 dispatch_thread, uint3 group, uint3 group_thread, uint group_index) {
+    ThreadId output;
+    output.dispatch_thread = dispatch_thread;
+    output.group = group;
+    output.group_thread = group_thread;
+    output.group_index = group_index;
+    return output;
+}
+
+struct VertexInput {
+    float4 position: POSITION;
+    uint bone: BLENDINDICES;
+};
+VertexInput acsl_create_VertexInput(float4 position, uint bone) {
+    VertexInput output;
+    output.position = position;
+    output.bone = bone;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+};
+PixelInput acsl_create_PixelInput(float4 position) {
+    PixelInput output;
+    output.position = position;
+    return output;
+}
+
+struct Particle {
+    float4 position;
+    float4 velocity;
+};
+Particle acsl_create_Particle(float4 position, float4 velocity) {
+    Particle output;
+    output.position = position;
+    output.velocity = velocity;
+    return output;
+}
+
+struct Params {
+    float delta_time;
+};
+Params acsl_create_Params(float delta_time) {
+    Params output;
+    output.delta_time = delta_time;
+    return output;
+}
+
+cbuffer acsl_constant_buffer_0 : register(b0) {
+    Params params;
+}
+
+StructuredBuffer<float4x4> bones : register(t8);
+
+StructuredBuffer<Particle> particles_in : register(t9);
+
+RWStructuredBuffer<Particle> particles_out : register(u2, space1);
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(mul(vertex_input.position, bones[vertex_input.bone]));
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    return pixel_input.position;
+}
+
+[numthreads(64, 1, 1)]
+void simulate(ThreadId id) {
+    Particle particle = particles_in[id.dispatch_thread.x];
+    particles_out[id.dispatch_thread.x] = acsl_create_Particle((particle.position + (particle.velocity * params.delta_time)), particle.velocity);
+}
+
@@ -0,0 +1,146 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion, ShaderModel};
+use common::{error, kernel};
+
+const RESOURCES: &str = "buffer<float> input : 0;\nrwbuffer<float> output : 1;";
+
+#[test]
+fn buffer_hlsl() {
+    let input_code = std::fs::read_to_string("tests/buffer.acsl").unwrap();
+    let target_code = std::fs::read_to_string("tests/buffer.hlsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().shader_model(ShaderModel::Sm51));
+    let hlsl = compiler.compile_hlsl(&input_code).unwrap();
+
+    assert_eq!(hlsl.code(), target_code);
+}
+
+#[test]
+fn buffer_glsl() {
+    let input_code = std::fs::read_to_string("tests/buffer.acsl").unwrap();
+    let target_code_vertex = std::fs::read_to_string("tests/buffer_vertex.glsl").unwrap();
+    let target_code_simulate = std::fs::read_to_string("tests/buffer_simulate_comp.glsl").unwrap();
+
+    let glsl = Compiler::default().compile_glsl(&input_code).unwrap();
+
+    assert_eq!(glsl.vertex(), target_code_vertex);
+    assert_eq!(glsl.compute().len(), 1);
+    assert_eq!(glsl.compute()[0].1, target_code_simulate);
+}
+
+#[test]
+fn buffer_vulkan() {
+    let input_code = std::fs::read_to_string("tests/buffer.acsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+
+    assert!(glsl.compute()[0]
+        .1
+        .contains("layout(std430, set = 3, binding = 2) buffer acsl_buffer_2 {\n"));
+}
+
+#[test]
+fn max_buffers() {
+    let compiler = Compiler::new(CompileOptions::new().max_buffers(2));
+
+    let error = compiler
+        .compile_hlsl(kernel(RESOURCES, "    return;").replace(": 1;", ": 2;"))
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(error, "Slot 2 is beyond the max slot of 2 for buffers");
+
+    // Read-only buffers start after the textures
+    let compiler = Compiler::new(CompileOptions::new().max_textures(4));
+    let hlsl = compiler
+        .compile_hlsl(kernel(RESOURCES, "    return;"))
+        .unwrap();
+    assert!(hlsl
+        .code()
+        .contains("StructuredBuffer<float> input : register(t4);\n"));
+    assert!(hlsl
+        .code()
+        .contains("RWStructuredBuffer<float> output : register(u1);\n"));
+}
+
+#[test]
+fn unsupported_targets() {
+    let input_code = std::fs::read_to_string("tests/buffer.acsl").unwrap();
+    let input_code = input_code.replace(", space 1", "");
+
+    let error = acsl::compile_msl(&input_code).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "The MSL backend does not yet support storage buffers (\"buffer\", \"rwbuffer\")"
+    );
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Essl300));
+    let error = compiler
+        .compile_glsl(&input_code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL ES 3.00 does not support storage buffers (\"buffer\", \"rwbuffer\")"
+    );
+}
+
+#[test]
+fn invalid_buffers() {
+    assert_eq!(
+        error(&kernel(RESOURCES, "    input[id.dispatch_thread.x] = 1.0;")),
+        "Attempting to write to read-only buffer \"input\", only \"rwbuffer\" can be written"
+    );
+    assert_eq!(
+        error(&kernel(RESOURCES, "    output[id.dispatch_thread] = 1.0;")),
+        "Indices must be of type \"uint\", instead found \"uint3\""
+    );
+    assert_eq!(
+        error(&kernel(RESOURCES, "    output[id.dispatch_thread.x] = id.dispatch_thread.x;")),
+        "Attempting to set \"output[id.dispatch_thread.x]\" to type \"uint\" when it has a type of \"float\""
+    );
+    assert_eq!(
+        error(&kernel(
+            RESOURCES,
+            "    let value = id.dispatch_thread[id.group_index];"
+        )),
+        "Only buffers, patches and primitives can be indexed, found type \"uint3\""
+    );
+    assert_eq!(
+        error(&kernel(RESOURCES, "    output = input;")),
+        "Attempting to modify immutable variable \"output\""
+    );
+    assert_eq!(
+        error("buffer<float> first : 0;\nrwbuffer<float> second : 0;"),
+        "Reusing buffer slot 0"
+    );
+}
+
+#[test]
+fn element_layout() {
+    // HLSL packs the elements tightly, std430 must place them the same way
+    let layout = "struct Light {\n    direction: float3,\n    intensity: float,\n}\n\nstruct Pair {\n    a: float3,\n    b: float3,\n}\n\nstruct Nested {\n    light: Light,\n    color: float4,\n}\n\nstruct Tail {\n    light: Light,\n    uv: float2,\n}\n";
+    for (element, stride) in [
+        ("Light", 16),
+        ("Nested", 32),
+        ("float2", 8),
+        ("float4x4", 64),
+    ] {
+        let code = kernel(
+            &format!("{}\nbuffer<{}> elements : 0;", layout, element),
+            "    return;",
+        );
+        let reflection = Compiler::default().reflect(&code).unwrap();
+        assert_eq!(reflection.buffers()[0].stride(), stride);
+    }
+
+    for element in ["float3", "Pair", "Tail", "float3x3"] {
+        assert_eq!(
+            error(&format!("{}\nrwbuffer<{}> elements : 0;", layout, element)),
+            format!("Buffer \"elements\" has elements of type \"{}\", which std430 pads unlike HLSL, use four component vectors and matrix columns", element)
+        );
+    }
+}
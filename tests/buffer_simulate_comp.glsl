@@ -0,0 +1,39 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+struct ThreadId {
+    uvec3 dispatch_thread;
+    uvec3 group;
+    uvec3 group_thread;
+    uint group_index;
+};
+
+struct Particle {
+    vec4 position;
+    vec4 velocity;
+};
+
+struct Params {
+    float delta_time;
+};
+
+layout(location = 0) uniform Params params;
+
+layout(std430, binding = 1) readonly buffer acsl_buffer_1 {
+    Particle particles_in[];
+};
+
+layout(std430, binding = 2) buffer acsl_buffer_2 {
+    Particle particles_out[];
+};
+
+void main() {
+    ThreadId id = ThreadId(gl_GlobalInvocationID, gl_WorkGroupID, gl_LocalInvocationID, gl_LocalInvocationIndex);
+
+    Particle particle = particles_in[id.dispatch_thread.x];
+    particles_out[id.dispatch_thread.x] = Particle((particle.position + (particle.velocity * params.delta_time)), particle.velocity);
+}
+
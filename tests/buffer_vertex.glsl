@@ -0,0 +1,49 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in uint acsl_vertex_input_bone;
+
+
+struct VertexInput {
+    vec4 position;
+    uint bone;
+};
+
+struct PixelInput {
+    vec4 position;
+};
+
+struct Particle {
+    vec4 position;
+    vec4 velocity;
+};
+
+struct Params {
+    float delta_time;
+};
+
+layout(location = 0) uniform Params params;
+
+layout(std430, binding = 0) readonly buffer acsl_buffer_0 {
+    mat4x4 bones[];
+};
+
+layout(std430, binding = 1) readonly buffer acsl_buffer_1 {
+    Particle particles_in[];
+};
+
+layout(std430, binding = 2) buffer acsl_buffer_2 {
+    Particle particles_out[];
+};
+
+void main() {
+    VertexInput vertex_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_bone);
+
+    PixelInput acsl_vertex_output = PixelInput((vertex_input.position * bones[vertex_input.bone]));
+    gl_Position = acsl_vertex_output.position;
+    return;
+}
+
+
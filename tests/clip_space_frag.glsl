@@ -0,0 +1,41 @@
+#version 450
+
+// Generated from Alexandria Common Shader Language
+
+layout(location = 0) out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec4 acsl_pixel_input_color;
+layout(location = 2) in vec2 acsl_pixel_input_uv;
+
+struct MatrixBuffer {
+    mat4x4 object;
+    mat4x4 view;
+};
+
+struct VertexInput {
+    vec4 position;
+    vec4 color;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 color;
+    vec2 uv;
+};
+
+layout(std140, set = 0, binding = 0) uniform acsl_constant_buffer_0 {
+    MatrixBuffer matrix_buffer;
+};
+
+layout(set = 1, binding = 0) uniform texture2D tex;
+layout(set = 2, binding = 0) uniform sampler acsl_tex_sampler_state;
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_color, acsl_pixel_input_uv);
+
+    acsl_fragment_color = (pixel_input.color * texture(sampler2D(tex, acsl_tex_sampler_state), pixel_input.uv));
+    return;
+}
+
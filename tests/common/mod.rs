@@ -0,0 +1,41 @@
+// Each test crate only uses some of the fixtures
+#![allow(dead_code)]
+
+/// Compiles `code` to HLSL, expecting it to be rejected, and returns the error message
+pub fn error(code: &str) -> String {
+    match acsl::compile_hlsl(code) {
+        Ok(_) => panic!("Expected the code to be rejected"),
+        Err(error) => error.to_string(),
+    }
+}
+
+/// A kernel named `main` running `body`, after `declarations`
+pub fn kernel(declarations: &str, body: &str) -> String {
+    format!(
+        "{}\n\n#[compute(64)]\nfn main(id: ThreadId) {{\n    let index = id.dispatch_thread.x;\n{}\n}}\n",
+        declarations, body
+    )
+}
+
+/// A pipeline passing `PixelInput` straight through `vertex_main`. Its `SV_POSITION` member is
+/// followed by `pixel_input`, and `fragment_main` runs `body` returning `output`
+pub fn shader(declarations: &str, pixel_input: &str, output: &str, body: &str) -> String {
+    format!(
+        "struct PixelInput {{\n    position: float4 : SV_POSITION,\n{}}}\n\n{}\n\nfn vertex_main(pixel_input: PixelInput) -> PixelInput {{\n    return pixel_input;\n}}\n\nfn fragment_main(pixel_input: PixelInput) -> {} {{\n{}\n}}\n",
+        pixel_input, declarations, output, body
+    )
+}
+
+/// A pipeline whose `fragment_main` returns a `float4` from `body`, with the interpolated
+/// `uv` and `position` in local variables
+pub fn fragment(declarations: &str, body: &str) -> String {
+    shader(
+        declarations,
+        "    uv: float2 : TEXCOORD,\n",
+        "float4",
+        &format!(
+            "    let uv = pixel_input.uv;\n    let position = pixel_input.position;\n{}",
+            body
+        ),
+    )
+}
@@ -0,0 +1,48 @@
+// Generated from Alexandria Common Shader Language
+
+struct ThreadId {
+    uint3 dispatch_thread: SV_DispatchThreadID;
+    uint3 group: SV_GroupID;
+    uint3 group_thread: SV_GroupThreadID;
+    uint group_index: SV_GroupIndex;
+};
+ThreadId acsl_create_ThreadId(uint3 dispatch_thread, uint3 group, uint3 group_thread, uint group_index) {
+    ThreadId output;
+    output.dispatch_thread = dispatch_thread;
+    output.group = group;
+    output.group_thread = group_thread;
+    output.group_index = group_index;
+    return output;
+}
+
+struct Params {
+    float scale;
+};
+Params acsl_create_Params(float scale) {
+    Params output;
+    output.scale = scale;
+    return output;
+}
+
+cbuffer acsl_constant_buffer_0 : register(b0) {
+    Params params;
+}
+
+groupshared float total;
+
+float brightness(float value) {
+    return (value * params.scale);
+}
+
+[numthreads(8, 8, 1)]
+void main(ThreadId id) {
+    float index = float((id.dispatch_thread.x + id.group_thread.y));
+    total = brightness(index);
+    GroupMemoryBarrierWithGroupSync();
+}
+
+[numthreads(64, 1, 1)]
+void clear() {
+    total = 0.0;
+}
+
@@ -0,0 +1,95 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::error;
+
+#[test]
+fn compute_hlsl() {
+    let input_code = std::fs::read_to_string("tests/compute.acsl").unwrap();
+    let target_code = std::fs::read_to_string("tests/compute.hlsl").unwrap();
+
+    assert_eq!(acsl::compile_hlsl(&input_code).unwrap(), target_code);
+}
+
+#[test]
+fn compute_glsl() {
+    let input_code = std::fs::read_to_string("tests/compute.acsl").unwrap();
+    let target_code_main = std::fs::read_to_string("tests/compute_main_comp.glsl").unwrap();
+    let target_code_clear = std::fs::read_to_string("tests/compute_clear_comp.glsl").unwrap();
+
+    let glsl = Compiler::default().compile_glsl(&input_code).unwrap();
+
+    // There is no graphics pipeline
+    assert_eq!(glsl.vertex(), "");
+    assert_eq!(glsl.fragment(), "");
+
+    let compute = glsl.compute();
+    assert_eq!(compute.len(), 2);
+    assert_eq!(compute[0].0, "main");
+    assert_eq!(compute[0].1, target_code_main);
+    assert_eq!(compute[1].0, "clear");
+    assert_eq!(compute[1].1, target_code_clear);
+}
+
+#[test]
+fn compute_with_graphics() {
+    let mut input_code = std::fs::read_to_string("tests/texture.acsl").unwrap();
+    input_code.push_str(
+        "\ngroupshared counter: uint;\n\n#[compute(32)]\nfn count() {\n    counter = counter;\n}\n",
+    );
+
+    let glsl = Compiler::default().compile_glsl(&input_code).unwrap();
+
+    // Kernels and shared memory stay out of the graphics stages
+    let target_code_vertex = std::fs::read_to_string("tests/texture_vertex.glsl").unwrap();
+    let target_code_fragment = std::fs::read_to_string("tests/texture_frag.glsl").unwrap();
+    assert_eq!(glsl.vertex(), target_code_vertex);
+    assert_eq!(glsl.fragment(), target_code_fragment);
+
+    assert_eq!(glsl.compute().len(), 1);
+    assert!(glsl.compute()[0].1.contains("shared uint counter;\n"));
+    assert!(!glsl.compute()[0].1.contains("sampler2D"));
+}
+
+#[test]
+fn unsupported_targets() {
+    let input_code = std::fs::read_to_string("tests/compute.acsl").unwrap();
+
+    let error = acsl::compile_wgsl(&input_code).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "The WGSL backend does not yet support compute shaders (\"#[compute]\")"
+    );
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Glsl330));
+    let error = compiler
+        .compile_glsl(&input_code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL 3.30 does not support compute shaders (\"#[compute]\")"
+    );
+}
+
+#[test]
+fn invalid_kernels() {
+    assert_eq!(
+        error("#[compute(8)] fn main() -> float { return 1.0; }"),
+        "Compute kernel \"main\" must not return a value, instead it returns \"float\""
+    );
+    assert_eq!(
+        error("#[compute(8)] fn main(value: float) { return; }"),
+        "Compute kernel \"main\" must take no parameters or a single \"ThreadId\""
+    );
+    assert_eq!(
+        error("#[compute(8, 0)] fn main() { return; }"),
+        "Invalid thread group size [8, 0], \"compute\" takes one to three non-zero sizes"
+    );
+    assert_eq!(
+        error("#[kernel(8)] fn main() { return; }"),
+        "Unknown attribute \"kernel\""
+    );
+    assert_eq!(error(""), "No \"vertex_main()\" function defined");
+}
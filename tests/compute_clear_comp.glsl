@@ -0,0 +1,12 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+shared float total;
+
+void main() {
+    total = 0.0;
+}
+
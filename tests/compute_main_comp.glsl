@@ -0,0 +1,34 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(local_size_x = 8, local_size_y = 8, local_size_z = 1) in;
+
+struct ThreadId {
+    uvec3 dispatch_thread;
+    uvec3 group;
+    uvec3 group_thread;
+    uint group_index;
+};
+
+struct Params {
+    float scale;
+};
+
+layout(location = 0) uniform Params params;
+
+shared float total;
+
+float brightness(float value) {
+    return (value * params.scale);
+}
+
+void main() {
+    ThreadId id = ThreadId(gl_GlobalInvocationID, gl_WorkGroupID, gl_LocalInvocationID, gl_LocalInvocationIndex);
+
+    float index = float((id.dispatch_thread.x + id.group_thread.y));
+    total = brightness(index);
+    memoryBarrierShared();
+    barrier();
+}
+
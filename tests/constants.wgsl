@@ -0,0 +1,22 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+}
+
+const PIXEL_COLOR: vec4<f32> = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput(vertex_input.position);
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    return PIXEL_COLOR;
+}
+
@@ -0,0 +1,17 @@
+// Generated from Alexandria Common Shader Language
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+};
+PixelInput acsl_create_PixelInput(float4 position) {
+    PixelInput output;
+    output.position = position;
+    return output;
+}
+
+static const float4 PIXEL_COLOR = float4(1.0, 1.0, 1.0, 1.0);
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    return PIXEL_COLOR;
+}
+
@@ -4,7 +4,6 @@
 
 layout (location = 0) in vec4 acsl_vertex_input_position;
 
-out vec4 acsl_pixel_input_position;
 
 struct VertexInput {
     vec4 position;
@@ -20,8 +19,7 @@ void main() {
     VertexInput vertex_input = VertexInput(acsl_vertex_input_position);
 
     PixelInput acsl_vertex_output = PixelInput(vertex_input.position);
-    acsl_pixel_input_position = acsl_vertex_output.position;
-    gl_Position = acsl_pixel_input_position;
+    gl_Position = acsl_vertex_output.position;
     return;
 }
 
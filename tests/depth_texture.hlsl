@@ -0,0 +1,61 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float4 shadow_position: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float4 shadow_position) {
+    VertexInput output;
+    output.position = position;
+    output.shadow_position = shadow_position;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float4 shadow_position: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float4 shadow_position) {
+    PixelInput output;
+    output.position = position;
+    output.shadow_position = shadow_position;
+    return output;
+}
+
+Texture2D<float> shadow_map : register(t0);
+SamplerComparisonState acsl_shadow_map_sampler_state : register(s0);
+uint2 acsl_shadow_map_size() {
+    uint width, height;
+    shadow_map.GetDimensions(width, height);
+    return uint2(width, height);
+}
+
+TextureCube<float> point_shadows : register(t1);
+SamplerComparisonState acsl_point_shadows_sampler_state : register(s1);
+
+Texture2DArray<float> cascades : register(t2);
+SamplerComparisonState acsl_cascades_sampler_state : register(s2);
+
+Texture2D<float> soft_shadow_map : register(t3);
+SamplerComparisonState acsl_soft_shadow_map_sampler_state : register(s3);
+
+SamplerComparisonState shadow : register(s8);
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(vertex_input.position, vertex_input.shadow_position);
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    float2 uv = float2(pixel_input.shadow_position.x, pixel_input.shadow_position.y);
+    float depth = pixel_input.shadow_position.z;
+    float3 direction = float3(uv.x, uv.y, 1.0);
+    float3 cascade = float3(uv.x, uv.y, pixel_input.shadow_position.w);
+    float lit = shadow_map.SampleCmp(acsl_shadow_map_sampler_state, uv, depth);
+    float filtered = soft_shadow_map.SampleCmp(shadow, uv, depth);
+    float point_lit = point_shadows.SampleCmpLevelZero(acsl_point_shadows_sampler_state, direction, depth);
+    float cascade_lit = cascades.SampleCmpLevelZero(shadow, cascade, depth);
+    uint2 size = acsl_shadow_map_size();
+    float visibility = ((((lit * filtered) * point_lit) * cascade_lit) * float(size.x));
+    return float4(visibility, visibility, visibility, 1.0);
+}
+
@@ -0,0 +1,36 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float4 shadow_position [[attribute(1)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float4 shadow_position;
+};
+
+constexpr sampler shadow(filter::linear, mip_filter::linear, address::clamp_to_edge, compare_func::less_equal);
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    return PixelInput{vertex_input.position, vertex_input.shadow_position};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]], depth2d<float> shadow_map [[texture(0)]], sampler acsl_shadow_map_sampler_state [[sampler(0)]], depthcube<float> point_shadows [[texture(1)]], sampler acsl_point_shadows_sampler_state [[sampler(1)]], depth2d_array<float> cascades [[texture(2)]], sampler acsl_cascades_sampler_state [[sampler(2)]], depth2d<float> soft_shadow_map [[texture(3)]], sampler acsl_soft_shadow_map_sampler_state [[sampler(3)]]) {
+    float2 uv = float2(pixel_input.shadow_position.x, pixel_input.shadow_position.y);
+    float depth = pixel_input.shadow_position.z;
+    float3 direction = float3(uv.x, uv.y, 1.0);
+    float3 cascade = float3(uv.x, uv.y, pixel_input.shadow_position.w);
+    float lit = shadow_map.sample_compare(acsl_shadow_map_sampler_state, uv, depth);
+    float filtered = soft_shadow_map.sample_compare(shadow, uv, depth);
+    float point_lit = point_shadows.sample_compare(acsl_point_shadows_sampler_state, direction, depth, level(0));
+    float cascade_lit = cascades.sample_compare(shadow, cascade.xy, uint(round(cascade.z)), depth, level(0));
+    uint2 size = uint2(shadow_map.get_width(), shadow_map.get_height());
+    float visibility = ((((lit * filtered) * point_lit) * cascade_lit) * float(size.x));
+    return float4(visibility, visibility, visibility, 1.0);
+}
+
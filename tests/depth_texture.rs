@@ -0,0 +1,101 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::{error, fragment};
+
+#[test]
+fn depth_texture_vulkan() {
+    let input_code = std::fs::read_to_string("tests/depth_texture.acsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+
+    // The texture is combined with its own comparison sampler
+    assert!(glsl.fragment().contains(
+        "layout(set = 1, binding = 0) uniform texture2D shadow_map;\nlayout(set = 2, binding = 0) uniform samplerShadow acsl_shadow_map_sampler_state;\n"
+    ));
+    assert!(glsl.fragment().contains(
+        "float lit = texture(sampler2DShadow(shadow_map, acsl_shadow_map_sampler_state), vec3(uv, depth));\n"
+    ));
+    assert!(glsl.fragment().contains(
+        "uvec2(textureSize(sampler2DShadow(shadow_map, acsl_shadow_map_sampler_state), 0));\n"
+    ));
+}
+
+#[test]
+fn depth_texture_essl() {
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Essl300));
+    let glsl = compiler
+        .compile_glsl(fragment(
+            "depth_texture shadow_map : 0;",
+            "    let lit = sample_compare_level_zero(shadow_map, uv, 0.5);\n    return float4(lit, lit, lit, 1.0);",
+        ))
+        .unwrap();
+
+    assert!(glsl
+        .fragment()
+        .contains("precision highp sampler2DShadow;\n"));
+    assert!(glsl
+        .fragment()
+        .contains("float lit = textureLod(shadow_map, vec3(uv, 0.5), 0.0);\n"));
+}
+
+#[test]
+fn depth_texture_with_several_samplers() {
+    let explicit = fragment(
+        "depth_texture shadow_map : 0;\nsampler shadow : 0 { compare: less };\nsampler soft : 1 { filter: linear, compare: less };",
+        "    let lit = sample_compare(shadow_map, shadow, uv, 0.5) * sample_compare(shadow_map, soft, uv, 0.5);\n    return float4(lit, lit, lit, 1.0);",
+    );
+    let implicit = fragment(
+        "depth_texture shadow_map : 0;\nsampler shadow : 0 { compare: less };",
+        "    let lit = sample_compare(shadow_map, uv, 0.5) * sample_compare(shadow_map, shadow, uv, 0.5);\n    return float4(lit, lit, lit, 1.0);",
+    );
+
+    // OpenGL compares a depth texture with the one state of its texture unit
+    let vulkan = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    for code in [explicit, implicit] {
+        let error = Compiler::default()
+            .compile_glsl(&code)
+            .err()
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            error,
+            "GLSL 4.30 does not support sampling a texture with more than one sampler (\"sample\")"
+        );
+
+        assert!(vulkan.compile_glsl(&code).is_ok());
+        assert!(acsl::compile_hlsl(&code).is_ok());
+    }
+}
+
+#[test]
+fn invalid_depth_textures() {
+    assert_eq!(
+        error(&fragment(
+            "depth_texture shadow_map : 0;",
+            "    return sample_texture(shadow_map, uv);"
+        )),
+        "\"sample_texture\" can't be used on textures of type \"depth_texture\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "depth_texture shadow_map : 0;\nsampler smooth : 0;",
+            "    let lit = sample_compare(shadow_map, smooth, uv, 0.5);\n    return float4(lit, lit, lit, 1.0);"
+        )),
+        "Function \"sample_compare\" expects parameter 2 to be of type \"sampler_comparison\", instead found type \"sampler\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "depth_texture shadow_map : 0;",
+            "    let lit = sample_compare(shadow_map, uv);\n    return float4(lit, lit, lit, 1.0);"
+        )),
+        "Function \"sample_compare\" expects 3 parameters, only 2 provided"
+    );
+
+    // Depth textures always hold a single "float"
+    assert_eq!(
+        error("depth_texture<float> shadow_map : 0;"),
+        "Unexpected token < at 1:14"
+    );
+}
@@ -0,0 +1,46 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) shadow_position: vec4<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) shadow_position: vec4<f32>,
+}
+
+@group(0) @binding(32) var shadow_map: texture_depth_2d;
+@group(0) @binding(40) var acsl_shadow_map_sampler_state: sampler_comparison;
+
+@group(0) @binding(33) var point_shadows: texture_depth_cube;
+@group(0) @binding(41) var acsl_point_shadows_sampler_state: sampler_comparison;
+
+@group(0) @binding(34) var cascades: texture_depth_2d_array;
+@group(0) @binding(42) var acsl_cascades_sampler_state: sampler_comparison;
+
+@group(0) @binding(35) var soft_shadow_map: texture_depth_2d;
+@group(0) @binding(43) var acsl_soft_shadow_map_sampler_state: sampler_comparison;
+
+@group(0) @binding(48) var shadow: sampler_comparison;
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput(vertex_input.position, vertex_input.shadow_position);
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    var uv: vec2<f32> = vec2<f32>(pixel_input.shadow_position.x, pixel_input.shadow_position.y);
+    var depth: f32 = pixel_input.shadow_position.z;
+    var direction: vec3<f32> = vec3<f32>(uv.x, uv.y, 1.0);
+    var cascade: vec3<f32> = vec3<f32>(uv.x, uv.y, pixel_input.shadow_position.w);
+    var lit: f32 = textureSampleCompare(shadow_map, acsl_shadow_map_sampler_state, uv, depth);
+    var filtered: f32 = textureSampleCompare(soft_shadow_map, shadow, uv, depth);
+    var point_lit: f32 = textureSampleCompareLevel(point_shadows, acsl_point_shadows_sampler_state, direction, depth);
+    var cascade_lit: f32 = textureSampleCompareLevel(cascades, shadow, cascade.xy, i32(round(cascade.z)), depth);
+    var size: vec2<u32> = textureDimensions(shadow_map);
+    var visibility: f32 = ((((lit * filtered) * point_lit) * cascade_lit) * f32(size.x));
+    return vec4<f32>(visibility, visibility, visibility, 1.0);
+}
+
@@ -0,0 +1,44 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec4 acsl_pixel_input_shadow_position;
+
+struct VertexInput {
+    vec4 position;
+    vec4 shadow_position;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 shadow_position;
+};
+
+layout(location = 32) uniform sampler2DShadow shadow_map;
+
+layout(location = 33) uniform samplerCubeShadow point_shadows;
+
+layout(location = 34) uniform sampler2DArrayShadow cascades;
+
+layout(location = 35) uniform sampler2DShadow soft_shadow_map;
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_shadow_position);
+
+    vec2 uv = vec2(pixel_input.shadow_position.x, pixel_input.shadow_position.y);
+    float depth = pixel_input.shadow_position.z;
+    vec3 direction = vec3(uv.x, uv.y, 1.0);
+    vec3 cascade = vec3(uv.x, uv.y, pixel_input.shadow_position.w);
+    float lit = texture(shadow_map, vec3(uv, depth));
+    float filtered = texture(soft_shadow_map, vec3(uv, depth));
+    float point_lit = textureGrad(point_shadows, vec4(direction, depth), vec3(0.0), vec3(0.0));
+    float cascade_lit = textureGrad(cascades, vec4(cascade, depth), vec2(0.0), vec2(0.0));
+    uvec2 size = uvec2(textureSize(shadow_map, 0));
+    float visibility = ((((lit * filtered) * point_lit) * cascade_lit) * float(size.x));
+    acsl_fragment_color = vec4(visibility, visibility, visibility, 1.0);
+    return;
+}
+
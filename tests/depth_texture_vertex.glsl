@@ -0,0 +1,37 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in vec4 acsl_vertex_input_shadow_position;
+
+layout(location = 1) out vec4 acsl_pixel_input_shadow_position;
+
+struct VertexInput {
+    vec4 position;
+    vec4 shadow_position;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 shadow_position;
+};
+
+layout(location = 32) uniform sampler2DShadow shadow_map;
+
+layout(location = 33) uniform samplerCubeShadow point_shadows;
+
+layout(location = 34) uniform sampler2DArrayShadow cascades;
+
+layout(location = 35) uniform sampler2DShadow soft_shadow_map;
+
+void main() {
+    VertexInput vertex_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_shadow_position);
+
+    PixelInput acsl_vertex_output = PixelInput(vertex_input.position, vertex_input.shadow_position);
+    acsl_pixel_input_shadow_position = acsl_vertex_output.shadow_position;
+    gl_Position = acsl_vertex_output.position;
+    return;
+}
+
+
@@ -0,0 +1,48 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float4 color: COLOR;
+};
+VertexInput acsl_create_VertexInput(float4 position, float4 color) {
+    VertexInput output;
+    output.position = position;
+    output.color = color;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float4 color: COLOR;
+    uint front_face: ACSL_IS_FRONT_FACE;
+};
+PixelInput acsl_create_PixelInput(float4 position, float4 color, uint front_face) {
+    PixelInput output;
+    output.position = position;
+    output.color = color;
+    output.front_face = front_face;
+    return output;
+}
+
+struct FragmentOutput {
+    float4 color: SV_TARGET0;
+    float depth: SV_DEPTH;
+};
+FragmentOutput acsl_create_FragmentOutput(float4 color, float depth) {
+    FragmentOutput output;
+    output.color = color;
+    output.depth = depth;
+    return output;
+}
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(vertex_input.position, vertex_input.color, uint(1.0));
+}
+
+FragmentOutput fragment_main(PixelInput acsl_pixel_input, bool acsl_front_facing: SV_IsFrontFace) {
+    PixelInput pixel_input = acsl_pixel_input;
+    pixel_input.front_face = uint(acsl_front_facing);
+    float facing = float(pixel_input.front_face);
+    return acsl_create_FragmentOutput((pixel_input.color * facing), (pixel_input.position.z * 0.5));
+}
+
@@ -0,0 +1,33 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float4 color [[attribute(1)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float4 color;
+    uint front_face [[flat]];
+};
+
+struct FragmentOutput {
+    float4 color [[color(0)]];
+    float depth [[depth(any)]];
+};
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    return PixelInput{vertex_input.position, vertex_input.color, uint(1.0)};
+}
+
+fragment FragmentOutput fragment_main(PixelInput acsl_pixel_input [[stage_in]], bool acsl_front_facing [[front_facing]]) {
+    PixelInput pixel_input = acsl_pixel_input;
+    pixel_input.front_face = uint(acsl_front_facing);
+    float facing = float(pixel_input.front_face);
+    return FragmentOutput{(pixel_input.color * facing), (pixel_input.position.z * 0.5)};
+}
+
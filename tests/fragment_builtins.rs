@@ -0,0 +1,125 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::{error, kernel, shader};
+
+#[test]
+fn discard() {
+    let code = shader(
+        "",
+        "",
+        "float4",
+        "    discard;\n    return pixel_input.position;",
+    );
+
+    assert!(acsl::compile_hlsl(&code)
+        .unwrap()
+        .contains("    discard;\n    return pixel_input.position;\n"));
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Essl300));
+    let glsl = compiler.compile_glsl(&code).unwrap();
+    assert!(glsl.fragment().contains("    discard;\n"));
+
+    assert!(acsl::compile_wgsl(&code)
+        .unwrap()
+        .contains("    discard;\n"));
+    assert!(acsl::compile_msl(&code)
+        .unwrap()
+        .contains("    discard_fragment();\n"));
+
+    // Nothing follows the kill in its block
+    let disassembly = acsl::disassemble_spirv(&acsl::compile_spirv(&code).unwrap());
+    assert!(disassembly.contains("OpKill\n               OpFunctionEnd"));
+}
+
+#[test]
+fn discard_in_helper() {
+    let code = shader(
+        "fn clip() -> float {\n    discard;\n    return 1.0;\n}",
+        "",
+        "float4",
+        "    let alpha = clip();\n    return pixel_input.position * alpha;",
+    );
+
+    // Functions only the fragment stage calls can discard, whatever its name
+    let compiler = Compiler::new(CompileOptions::new().fragment_entry_point("PSMain"));
+    let hlsl = compiler.compile_hlsl(&code).unwrap();
+    assert!(hlsl
+        .code()
+        .contains("float clip() {\n    discard;\n    return 1.0;\n}\n"));
+    assert!(hlsl.code().contains(" PSMain("));
+
+    let glsl = Compiler::default().compile_glsl(&code).unwrap();
+    assert!(glsl.fragment().contains("    discard;\n"));
+    assert!(!glsl.vertex().contains("discard"));
+    assert!(acsl::compile_wgsl(&code).is_ok());
+    assert!(acsl::compile_msl(&code).is_ok());
+    assert!(acsl::compile_spirv(&code).is_ok());
+}
+
+#[test]
+fn fragment_position() {
+    let code = shader("", "", "float4", "    return pixel_input.position;");
+
+    // The position only reaches the fragment shader through "gl_FragCoord"
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    let glsl = compiler.compile_glsl(&code).unwrap();
+    assert!(!glsl.vertex().contains("acsl_pixel_input_position"));
+    assert!(glsl
+        .vertex()
+        .contains("gl_Position = acsl_vertex_output.position;\n"));
+    assert!(!glsl.fragment().contains("acsl_pixel_input_position"));
+    assert!(glsl
+        .fragment()
+        .contains("PixelInput pixel_input = PixelInput(gl_FragCoord);\n"));
+}
+
+#[test]
+fn front_face() {
+    let code = std::fs::read_to_string("tests/fragment_builtins.acsl").unwrap();
+
+    // Only the fragment shader reads the facing, from a parameter
+    let hlsl = Compiler::default().compile_hlsl_stages(&code).unwrap();
+    assert!(!hlsl.vertex().contains("SV_IsFrontFace"));
+    assert!(hlsl
+        .vertex()
+        .contains("    uint front_face: ACSL_IS_FRONT_FACE;\n"));
+    assert!(hlsl.pixel().contains(
+        "FragmentOutput fragment_main(PixelInput acsl_pixel_input, bool acsl_front_facing: SV_IsFrontFace) {\n    PixelInput pixel_input = acsl_pixel_input;\n    pixel_input.front_face = uint(acsl_front_facing);\n"
+    ));
+}
+
+#[test]
+fn invalid_fragment_builtins() {
+    assert_eq!(
+        error(&shader(
+            "",
+            "    front_face: float : SV_IS_FRONT_FACE,\n",
+            "float4",
+            "    return pixel_input.position;"
+        )),
+        "Semantic \"SV_IS_FRONT_FACE\" must be of type \"uint\", instead it is \"float\""
+    );
+    assert_eq!(
+        error("struct Output {\n    depth: float4 : SV_DEPTH,\n}\n"),
+        "Semantic \"SV_DEPTH\" must be of type \"float\", instead it is \"float4\""
+    );
+    assert_eq!(
+        error(&format!(
+            "{}\nfn clip() -> float {{\n    discard;\n    return 1.0;\n}}\n",
+            shader("", "", "float4", "    return pixel_input.position;")
+        )),
+        "\"discard\" can only be used in \"fragment_main()\" and the functions only it calls, not in \"clip()\""
+    );
+    assert_eq!(
+        error(&format!(
+            "{}\n{}",
+            kernel(
+                "fn clip() -> float {\n    discard;\n    return 1.0;\n}",
+                "    let alpha = clip();"
+            ),
+            shader("", "", "float4", "    return pixel_input.position * clip();")
+        )),
+        "\"discard\" can only be used in \"fragment_main()\" and the functions only it calls, not in \"clip()\""
+    );
+}
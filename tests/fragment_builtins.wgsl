@@ -0,0 +1,31 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) @interpolate(flat) front_face: u32,
+}
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+    @builtin(frag_depth) depth: f32,
+}
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput(vertex_input.position, vertex_input.color, u32(1.0));
+}
+
+@fragment
+fn fragment_main(acsl_pixel_input: PixelInput, @builtin(front_facing) acsl_front_facing: bool) -> FragmentOutput {
+    var pixel_input: PixelInput = acsl_pixel_input;
+    pixel_input.front_face = select(0u, 1u, acsl_front_facing);
+    var facing: f32 = f32(pixel_input.front_face);
+    return FragmentOutput((pixel_input.color * facing), (pixel_input.position.z * 0.5));
+}
+
@@ -0,0 +1,35 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(location = 0) out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec4 acsl_pixel_input_color;
+
+struct VertexInput {
+    vec4 position;
+    vec4 color;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 color;
+    uint front_face;
+};
+
+struct FragmentOutput {
+    vec4 color;
+    float depth;
+};
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_color, uint(gl_FrontFacing));
+
+    float facing = float(pixel_input.front_face);
+    FragmentOutput acsl_fragment_output = FragmentOutput((pixel_input.color * facing), (pixel_input.position.z * 0.5));
+    acsl_fragment_color = acsl_fragment_output.color;
+    gl_FragDepth = acsl_fragment_output.depth;
+    return;
+}
+
@@ -0,0 +1,35 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in vec4 acsl_vertex_input_color;
+
+layout(location = 1) out vec4 acsl_pixel_input_color;
+
+struct VertexInput {
+    vec4 position;
+    vec4 color;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 color;
+    uint front_face;
+};
+
+struct FragmentOutput {
+    vec4 color;
+    float depth;
+};
+
+void main() {
+    VertexInput vertex_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_color);
+
+    PixelInput acsl_vertex_output = PixelInput(vertex_input.position, vertex_input.color, uint(1.0));
+    acsl_pixel_input_color = acsl_vertex_output.color;
+    gl_Position = acsl_vertex_output.position;
+    return;
+}
+
+
@@ -0,0 +1,32 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float2 uv [[attribute(1)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float2 uv;
+};
+
+struct Tint {
+    float4 color;
+};
+
+float4 tinted_sample(float2 uv, constant Tint& tint, texture2d<float> tex, sampler acsl_tex_sampler_state) {
+    return (tex.sample(acsl_tex_sampler_state, uv) * tint.color);
+}
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    return PixelInput{vertex_input.position, vertex_input.uv};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]], constant Tint& tint [[buffer(1)]], texture2d<float> tex [[texture(2)]], sampler acsl_tex_sampler_state [[sampler(2)]]) {
+    return tinted_sample(pixel_input.uv, tint, tex, acsl_tex_sampler_state);
+}
+
@@ -0,0 +1,37 @@
+// Generated from Alexandria Common Shader Language
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct Tint {
+    float4 color;
+};
+Tint acsl_create_Tint(float4 color) {
+    Tint output;
+    output.color = color;
+    return output;
+}
+
+cbuffer acsl_constant_buffer_1 : register(b1) {
+    Tint tint;
+}
+
+Texture2D<float4> tex : register(t2);
+SamplerState acsl_tex_sampler_state : register(s2);
+
+float4 tinted_sample(float2 uv) {
+    return (tex.Sample(acsl_tex_sampler_state, uv) * tint.color);
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    return tinted_sample(pixel_input.uv);
+}
+
@@ -0,0 +1,28 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(vertex_input.position, vertex_input.uv);
+}
+
@@ -0,0 +1,67 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float3 normal: NORMAL;
+};
+VertexInput acsl_create_VertexInput(float4 position, float3 normal) {
+    VertexInput output;
+    output.position = position;
+    output.normal = normal;
+    return output;
+}
+
+struct GeometryInput {
+    float4 position: SV_POSITION;
+    float3 normal: NORMAL;
+};
+GeometryInput acsl_create_GeometryInput(float4 position, float3 normal) {
+    GeometryInput output;
+    output.position = position;
+    output.normal = normal;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float4 color: COLOR;
+};
+PixelInput acsl_create_PixelInput(float4 position, float4 color) {
+    PixelInput output;
+    output.position = position;
+    output.color = color;
+    return output;
+}
+
+static const float LENGTH = 0.1;
+
+GeometryInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_GeometryInput(vertex_input.position, vertex_input.normal);
+}
+
+PixelInput normal_start(GeometryInput vertex) {
+    return acsl_create_PixelInput(vertex.position, float4(1.0, 1.0, 0.0, 1.0));
+}
+
+PixelInput normal_end(GeometryInput vertex) {
+    float3 normal = (vertex.normal * LENGTH);
+    return acsl_create_PixelInput((vertex.position + float4(normal.x, normal.y, normal.z, 0.0)), float4(1.0, 0.0, 0.0, 1.0));
+}
+
+[maxvertexcount(6)]
+void geometry_main(triangle GeometryInput vertices[3], inout LineStream<PixelInput> stream) {
+    stream.Append(normal_start(vertices[0u]));
+    stream.Append(normal_end(vertices[0u]));
+    stream.RestartStrip();
+    stream.Append(normal_start(vertices[1u]));
+    stream.Append(normal_end(vertices[1u]));
+    stream.RestartStrip();
+    stream.Append(normal_start(vertices[2u]));
+    stream.Append(normal_end(vertices[2u]));
+    stream.RestartStrip();
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    return pixel_input.color;
+}
+
@@ -0,0 +1,126 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::error;
+
+fn normals() -> String {
+    std::fs::read_to_string("tests/geometry.acsl").unwrap()
+}
+
+#[test]
+fn geometry_hlsl() {
+    let target_code = std::fs::read_to_string("tests/geometry.hlsl").unwrap();
+
+    assert_eq!(acsl::compile_hlsl(normals()).unwrap(), target_code);
+}
+
+#[test]
+fn geometry_glsl() {
+    let glsl = Compiler::default().compile_glsl(normals()).unwrap();
+
+    for (code, file) in [
+        (glsl.vertex(), "tests/geometry_vertex.glsl"),
+        (glsl.geometry(), "tests/geometry_geom.glsl"),
+        (glsl.fragment(), "tests/geometry_frag.glsl"),
+    ] {
+        assert_eq!(code, std::fs::read_to_string(file).unwrap());
+    }
+    assert_eq!(glsl.tess_control(), "");
+    assert_eq!(glsl.tess_evaluation(), "");
+}
+
+#[test]
+fn geometry_hlsl_stages() {
+    let hlsl = Compiler::default().compile_hlsl_stages(normals()).unwrap();
+
+    assert!(hlsl
+        .geometry()
+        .contains("[maxvertexcount(6)]\nvoid geometry_main(triangle GeometryInput vertices[3], inout LineStream<PixelInput> stream)"));
+    assert!(hlsl.geometry().contains("PixelInput normal_start("));
+    assert!(!hlsl.vertex().contains("normal_start"));
+    assert_eq!(hlsl.hull(), "");
+    assert_eq!(hlsl.domain(), "");
+}
+
+#[test]
+fn geometry_targets() {
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Glsl330));
+    assert!(compiler.compile_glsl(normals()).is_ok());
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Essl300));
+    let error = compiler.compile_glsl(normals()).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "GLSL ES 3.00 does not support geometry shaders (\"#[geometry]\")"
+    );
+
+    let error = acsl::compile_msl(normals()).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "MSL does not support geometry shaders (\"#[geometry]\")"
+    );
+
+    let error = acsl::compile_spirv(normals()).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "The SPIR-V backend does not yet support geometry shaders (\"#[geometry]\")"
+    );
+}
+
+#[test]
+fn invalid_attributes() {
+    let code = normals().replace("#[geometry(6)]\n", "");
+    assert_eq!(
+        error(&code),
+        "\"geometry_main()\" must be declared as \"#[geometry(n)] fn geometry_main(vertices: triangle<T>, stream: triangle_stream<U>)\""
+    );
+
+    let code = normals().replace("#[geometry(6)]", "#[geometry(0)]");
+    assert_eq!(
+        error(&code),
+        "Invalid vertex count [0], \"geometry\" takes one non-zero maximum number of vertices"
+    );
+
+    let code = normals().replace("#[geometry(6)]", "#[geometry(3, 4)]");
+    assert_eq!(
+        error(&code),
+        "Invalid vertex count [3, 4], \"geometry\" takes one non-zero maximum number of vertices"
+    );
+
+    let code = normals().replace("fn normal_start", "#[geometry(6)]\nfn normal_start");
+    assert_eq!(
+        error(&code),
+        "\"#[geometry]\" can't be used on \"normal_start()\""
+    );
+}
+
+#[test]
+fn invalid_emit() {
+    let code = normals().replacen(
+        "emit(stream, normal_start(vertices[0]));",
+        "emit(stream, vertices[0]);",
+        1,
+    );
+    assert_eq!(
+        error(&code),
+        "Function \"emit\" expects parameter 2 to be of type \"PixelInput\", instead found type \"GeometryInput\""
+    );
+
+    let code = normals().replacen("end_primitive(stream);", "end_primitive();", 1);
+    assert_eq!(
+        error(&code),
+        "Function \"end_primitive\" expects 1 parameters, only 0 provided"
+    );
+}
+
+#[test]
+fn mismatched_stages() {
+    let code = normals().replace(
+        "fn fragment_main(pixel_input: PixelInput) -> float4 {\n    return pixel_input.color;",
+        "fn fragment_main(pixel_input: GeometryInput) -> float4 {\n    return pixel_input.position;",
+    );
+    assert_eq!(
+        error(&code),
+        "The input of \"fragment_main()\" must be the output of \"geometry_main()\" (\"PixelInput\"), instead it is \"GeometryInput\""
+    );
+}
@@ -0,0 +1,41 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec4 acsl_pixel_input_color;
+
+struct VertexInput {
+    vec4 position;
+    vec3 normal;
+};
+
+struct GeometryInput {
+    vec4 position;
+    vec3 normal;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 color;
+};
+
+const float LENGTH = 0.1;
+
+PixelInput normal_start(GeometryInput vertex) {
+    return PixelInput(vertex.position, vec4(1.0, 1.0, 0.0, 1.0));
+}
+
+PixelInput normal_end(GeometryInput vertex) {
+    vec3 normal = (vertex.normal * LENGTH);
+    return PixelInput((vertex.position + vec4(normal.x, normal.y, normal.z, 0.0)), vec4(1.0, 0.0, 0.0, 1.0));
+}
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_color);
+
+    acsl_fragment_color = pixel_input.color;
+    return;
+}
+
@@ -0,0 +1,60 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(triangles) in;
+layout(line_strip, max_vertices = 6) out;
+
+layout(location = 1) in vec3 acsl_geometry_input_normal[];
+
+layout(location = 1) out vec4 acsl_pixel_input_color;
+
+struct VertexInput {
+    vec4 position;
+    vec3 normal;
+};
+
+struct GeometryInput {
+    vec4 position;
+    vec3 normal;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 color;
+};
+
+const float LENGTH = 0.1;
+
+PixelInput normal_start(GeometryInput vertex) {
+    return PixelInput(vertex.position, vec4(1.0, 1.0, 0.0, 1.0));
+}
+
+PixelInput normal_end(GeometryInput vertex) {
+    vec3 normal = (vertex.normal * LENGTH);
+    return PixelInput((vertex.position + vec4(normal.x, normal.y, normal.z, 0.0)), vec4(1.0, 0.0, 0.0, 1.0));
+}
+
+void acsl_emit(PixelInput acsl_vertex) {
+    gl_Position = acsl_vertex.position;
+    acsl_pixel_input_color = acsl_vertex.color;
+    EmitVertex();
+}
+
+void geometry_main(GeometryInput[3] vertices) {
+    acsl_emit(normal_start(vertices[0u]));
+    acsl_emit(normal_end(vertices[0u]));
+    EndPrimitive();
+    acsl_emit(normal_start(vertices[1u]));
+    acsl_emit(normal_end(vertices[1u]));
+    EndPrimitive();
+    acsl_emit(normal_start(vertices[2u]));
+    acsl_emit(normal_end(vertices[2u]));
+    EndPrimitive();
+}
+
+void main() {
+    GeometryInput acsl_vertices[3] = GeometryInput[3](GeometryInput(gl_in[0].gl_Position, acsl_geometry_input_normal[0]), GeometryInput(gl_in[1].gl_Position, acsl_geometry_input_normal[1]), GeometryInput(gl_in[2].gl_Position, acsl_geometry_input_normal[2]));
+    geometry_main(acsl_vertices);
+}
+
@@ -0,0 +1,47 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in vec3 acsl_vertex_input_normal;
+
+layout(location = 1) out vec3 acsl_geometry_input_normal;
+
+struct VertexInput {
+    vec4 position;
+    vec3 normal;
+};
+
+struct GeometryInput {
+    vec4 position;
+    vec3 normal;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 color;
+};
+
+const float LENGTH = 0.1;
+
+GeometryInput vertex_main(VertexInput vertex_input) {
+    return GeometryInput(vertex_input.position, vertex_input.normal);
+}
+
+PixelInput normal_start(GeometryInput vertex) {
+    return PixelInput(vertex.position, vec4(1.0, 1.0, 0.0, 1.0));
+}
+
+PixelInput normal_end(GeometryInput vertex) {
+    vec3 normal = (vertex.normal * LENGTH);
+    return PixelInput((vertex.position + vec4(normal.x, normal.y, normal.z, 0.0)), vec4(1.0, 0.0, 0.0, 1.0));
+}
+
+
+void main() {
+    VertexInput acsl_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_normal);
+    GeometryInput acsl_output = vertex_main(acsl_input);
+    gl_Position = acsl_output.position;
+    acsl_geometry_input_normal = acsl_output.normal;
+}
+
@@ -0,0 +1,44 @@
+#version 330 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in vec4 acsl_vertex_input_color;
+layout (location = 2) in vec2 acsl_vertex_input_uv;
+
+out vec4 acsl_pixel_input_color;
+out vec2 acsl_pixel_input_uv;
+
+struct MatrixBuffer {
+    mat4x4 object;
+    mat4x4 view;
+};
+
+struct VertexInput {
+    vec4 position;
+    vec4 color;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec4 color;
+    vec2 uv;
+};
+
+uniform MatrixBuffer matrix_buffer;
+
+uniform sampler2D tex;
+
+void main() {
+    VertexInput vertex_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_color, acsl_vertex_input_uv);
+
+    vec4 output_position = (vertex_input.position * (matrix_buffer.object * matrix_buffer.view));
+    PixelInput acsl_vertex_output = PixelInput(output_position, vertex_input.color, vertex_input.uv);
+    acsl_pixel_input_color = acsl_vertex_output.color;
+    acsl_pixel_input_uv = acsl_vertex_output.uv;
+    gl_Position = acsl_vertex_output.position;
+    return;
+}
+
+
@@ -0,0 +1,83 @@
+macro_rules! run_test {
+    ($test_name:literal, $options:expr) => {
+        let input_code = std::fs::read_to_string("tests/texture.acsl").unwrap();
+        let target_code_glsl_vertex =
+            std::fs::read_to_string(format!("{}_vertex.glsl", $test_name)).unwrap();
+        let target_code_glsl_fragment =
+            std::fs::read_to_string(format!("{}_frag.glsl", $test_name)).unwrap();
+
+        let compiler = acsl::Compiler::new($options);
+        let glsl = compiler.compile_glsl(&input_code).unwrap();
+
+        assert_eq!(glsl.vertex(), target_code_glsl_vertex);
+        assert_eq!(glsl.fragment(), target_code_glsl_fragment);
+    };
+}
+
+#[test]
+fn glsl_330() {
+    run_test!(
+        "tests/glsl_330",
+        acsl::CompileOptions::new().glsl_version(acsl::GlslVersion::Glsl330)
+    );
+}
+
+#[test]
+fn essl_300() {
+    run_test!(
+        "tests/essl_300",
+        acsl::CompileOptions::new().glsl_version(acsl::GlslVersion::Essl300)
+    );
+}
+
+#[test]
+fn vulkan() {
+    run_test!(
+        "tests/vulkan",
+        acsl::CompileOptions::new().glsl_version(acsl::GlslVersion::Vulkan)
+    );
+}
+
+#[test]
+fn clip_space_fixups() {
+    run_test!(
+        "tests/clip_space",
+        acsl::CompileOptions::new()
+            .glsl_version(acsl::GlslVersion::Vulkan)
+            .flip_y(true)
+            .fix_depth_range(true)
+    );
+}
+
+#[test]
+fn unsupported_feature() {
+    let input_code = "struct Settings {
+    scale: float1,
+}
+
+struct VertexInput {
+    position: float4 : POSITION,
+}
+
+struct PixelInput {
+    position: float4 : SV_POSITION,
+}
+
+cbuffer settings: Settings : 0;
+
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput {
+        position: vertex_input.position,
+    };
+}
+
+fn fragment_main(pixel_input: PixelInput) -> float4 {
+    return pixel_input.position;
+}
+";
+
+    acsl::compile_hlsl(input_code).unwrap();
+
+    let error = acsl::compile_glsl(input_code).err().unwrap();
+    assert!(error.to_string().contains("does not support"));
+}
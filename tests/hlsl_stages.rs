@@ -0,0 +1,48 @@
+macro_rules! run_test {
+    ($test_name:literal) => {
+        let input_code = std::fs::read_to_string(format!("{}.acsl", $test_name)).unwrap();
+        let target_code_vertex =
+            std::fs::read_to_string(format!("{}_vertex.hlsl", $test_name)).unwrap();
+        let target_code_pixel =
+            std::fs::read_to_string(format!("{}_pixel.hlsl", $test_name)).unwrap();
+
+        let hlsl = acsl::Compiler::default()
+            .compile_hlsl_stages(&input_code)
+            .unwrap();
+
+        assert_eq!(hlsl.vertex(), target_code_vertex);
+        assert_eq!(hlsl.pixel(), target_code_pixel);
+    };
+}
+
+#[test]
+fn texture() {
+    run_test!("tests/texture");
+}
+
+#[test]
+fn constants() {
+    run_test!("tests/constants");
+}
+
+#[test]
+fn functions() {
+    run_test!("tests/functions");
+}
+
+#[test]
+fn entry_point_names() {
+    let input_code = std::fs::read_to_string("tests/simple.acsl").unwrap();
+    let compiler = acsl::Compiler::new(
+        acsl::CompileOptions::new()
+            .vertex_entry_point("VSMain")
+            .fragment_entry_point("PSMain"),
+    );
+
+    let hlsl = compiler.compile_hlsl_stages(&input_code).unwrap();
+
+    assert!(hlsl.vertex().contains(" VSMain("));
+    assert!(!hlsl.vertex().contains("PSMain"));
+    assert!(hlsl.pixel().contains(" PSMain("));
+    assert!(!hlsl.pixel().contains("VSMain"));
+}
@@ -0,0 +1,44 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float4 color: COLOR;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float4 color, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.color = color;
+    output.uv = uv;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    nointerpolation float material: TEXCOORD0;
+    noperspective float2 screen_uv: TEXCOORD1;
+    centroid float2 uv: TEXCOORD2;
+    sample float4 color: COLOR;
+    uint object_id: TEXCOORD3;
+};
+PixelInput acsl_create_PixelInput(float4 position, float material, float2 screen_uv, float2 uv, float4 color, uint object_id) {
+    PixelInput output;
+    output.position = position;
+    output.material = material;
+    output.screen_uv = screen_uv;
+    output.uv = uv;
+    output.color = color;
+    output.object_id = object_id;
+    return output;
+}
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    float4 position = vertex_input.position;
+    return acsl_create_PixelInput(position, vertex_input.uv.x, float2(position.x, position.y), vertex_input.uv, vertex_input.color, uint(vertex_input.uv.y));
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    float id = float(pixel_input.object_id);
+    return ((pixel_input.color * pixel_input.material) * id);
+}
+
@@ -0,0 +1,31 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float4 color [[attribute(1)]];
+    float2 uv [[attribute(2)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float material [[flat]];
+    float2 screen_uv [[center_no_perspective]];
+    float2 uv [[centroid_perspective]];
+    float4 color [[sample_perspective]];
+    uint object_id [[flat]];
+};
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    float4 position = vertex_input.position;
+    return PixelInput{position, vertex_input.uv.x, float2(position.x, position.y), vertex_input.uv, vertex_input.color, uint(vertex_input.uv.y)};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]]) {
+    float id = float(pixel_input.object_id);
+    return ((pixel_input.color * pixel_input.material) * id);
+}
+
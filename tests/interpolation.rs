@@ -0,0 +1,102 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::{error, shader};
+
+#[test]
+fn interpolation_vulkan() {
+    let input_code = std::fs::read_to_string("tests/interpolation.acsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+    assert!(glsl
+        .vertex()
+        .contains("layout(location = 1) flat out float acsl_pixel_input_material;\n"));
+    assert!(glsl
+        .fragment()
+        .contains("layout(location = 5) flat in uint acsl_pixel_input_object_id;\n"));
+}
+
+#[test]
+fn interpolation_glsl_versions() {
+    let input_code = std::fs::read_to_string("tests/interpolation.acsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Essl300));
+    let error = compiler
+        .compile_glsl(&input_code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL ES 3.00 does not support linear interpolation (\"#[noperspective]\")"
+    );
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Glsl330));
+    let error = compiler
+        .compile_glsl(&input_code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL 3.30 does not support per sample interpolation (\"#[sample]\")"
+    );
+
+    // Integers are flat even without the attribute
+    let (_, glsl) = acsl::compile_glsl(shader(
+        "",
+        "    layer: uint : TEXCOORD,\n",
+        "float4",
+        "    return pixel_input.position;",
+    ))
+    .unwrap();
+    assert!(glsl.contains("flat in uint acsl_pixel_input_layer;\n"));
+}
+
+#[test]
+fn invalid_interpolation() {
+    assert_eq!(
+        error(&shader(
+            "",
+            "    #[smooth] uv: float2 : TEXCOORD,\n",
+            "float4",
+            "    return pixel_input.position;"
+        )),
+        "Unknown attribute \"smooth\""
+    );
+    assert_eq!(
+        error(&shader(
+            "",
+            "    #[noperspective] layer: uint : TEXCOORD,\n",
+            "float4",
+            "    return pixel_input.position;"
+        )),
+        "Member \"layer\" of \"PixelInput\" can't be \"noperspective\", integers are always \"flat\""
+    );
+    assert_eq!(
+        error("struct Light {\n    #[flat] color: float4,\n}\n"),
+        "Member \"color\" of \"Light\" can't be \"flat\", only members with a user semantic passed from \"vertex_main()\" to \"fragment_main()\" are interpolated"
+    );
+    assert_eq!(
+        error(
+            "struct PixelInput {\n    #[noperspective] position: float4 : SV_POSITION,\n}\n\nfn vertex_main(pixel_input: PixelInput) -> PixelInput {\n    return pixel_input;\n}\n"
+        ),
+        "Member \"position\" of \"PixelInput\" can't be \"noperspective\", only members with a user semantic passed from \"vertex_main()\" to \"fragment_main()\" are interpolated"
+    );
+    assert_eq!(
+        error(
+            "struct VertexInput {\n    #[flat] position: float4 : POSITION,\n}\n\nstruct PixelInput {\n    position: float4 : SV_POSITION,\n}\n\nfn vertex_main(vertex_input: VertexInput) -> PixelInput {\n    return PixelInput { position: vertex_input.position };\n}\n"
+        ),
+        "Member \"position\" of \"VertexInput\" can't be \"flat\", only members with a user semantic passed from \"vertex_main()\" to \"fragment_main()\" are interpolated"
+    );
+    assert_eq!(
+        error(&shader(
+            "struct Output {\n    #[centroid] color: float4 : SV_TARGET0,\n}",
+            "",
+            "Output",
+            "    return Output { color: pixel_input.position };"
+        )),
+        "Member \"color\" of \"Output\" can't be \"centroid\", only members with a user semantic passed from \"vertex_main()\" to \"fragment_main()\" are interpolated"
+    );
+}
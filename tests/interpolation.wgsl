@@ -0,0 +1,29 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) @interpolate(flat) material: f32,
+    @location(2) @interpolate(linear) screen_uv: vec2<f32>,
+    @location(3) @interpolate(perspective, centroid) uv: vec2<f32>,
+    @location(4) @interpolate(perspective, sample) color: vec4<f32>,
+    @location(5) @interpolate(flat) object_id: u32,
+}
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    var position: vec4<f32> = vertex_input.position;
+    return PixelInput(position, vertex_input.uv.x, vec2<f32>(position.x, position.y), vertex_input.uv, vertex_input.color, u32(vertex_input.uv.y));
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    var id: f32 = f32(pixel_input.object_id);
+    return ((pixel_input.color * pixel_input.material) * id);
+}
+
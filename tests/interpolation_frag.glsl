@@ -0,0 +1,36 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) flat in float acsl_pixel_input_material;
+layout(location = 2) noperspective in vec2 acsl_pixel_input_screen_uv;
+layout(location = 3) centroid in vec2 acsl_pixel_input_uv;
+layout(location = 4) sample in vec4 acsl_pixel_input_color;
+layout(location = 5) flat in uint acsl_pixel_input_object_id;
+
+struct VertexInput {
+    vec4 position;
+    vec4 color;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    float material;
+    vec2 screen_uv;
+    vec2 uv;
+    vec4 color;
+    uint object_id;
+};
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_material, acsl_pixel_input_screen_uv, acsl_pixel_input_uv, acsl_pixel_input_color, acsl_pixel_input_object_id);
+
+    float id = float(pixel_input.object_id);
+    acsl_fragment_color = ((pixel_input.color * pixel_input.material) * id);
+    return;
+}
+
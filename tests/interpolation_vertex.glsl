@@ -0,0 +1,44 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in vec4 acsl_vertex_input_color;
+layout (location = 2) in vec2 acsl_vertex_input_uv;
+
+layout(location = 1) flat out float acsl_pixel_input_material;
+layout(location = 2) noperspective out vec2 acsl_pixel_input_screen_uv;
+layout(location = 3) centroid out vec2 acsl_pixel_input_uv;
+layout(location = 4) sample out vec4 acsl_pixel_input_color;
+layout(location = 5) flat out uint acsl_pixel_input_object_id;
+
+struct VertexInput {
+    vec4 position;
+    vec4 color;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    float material;
+    vec2 screen_uv;
+    vec2 uv;
+    vec4 color;
+    uint object_id;
+};
+
+void main() {
+    VertexInput vertex_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_color, acsl_vertex_input_uv);
+
+    vec4 position = vertex_input.position;
+    PixelInput acsl_vertex_output = PixelInput(position, vertex_input.uv.x, vec2(position.x, position.y), vertex_input.uv, vertex_input.color, uint(vertex_input.uv.y));
+    acsl_pixel_input_material = acsl_vertex_output.material;
+    acsl_pixel_input_screen_uv = acsl_vertex_output.screen_uv;
+    acsl_pixel_input_uv = acsl_vertex_output.uv;
+    acsl_pixel_input_color = acsl_vertex_output.color;
+    acsl_pixel_input_object_id = acsl_vertex_output.object_id;
+    gl_Position = acsl_vertex_output.position;
+    return;
+}
+
+
@@ -0,0 +1,68 @@
+use acsl::{CompileOptions, Compiler, GlslVersion};
+
+fn options() -> CompileOptions {
+    CompileOptions::new()
+        .semantic_location("COLOR", 4)
+        .semantic_location("texcoord0", 3)
+}
+
+#[test]
+fn semantic_locations() {
+    let input_code = std::fs::read_to_string("tests/texture.acsl").unwrap();
+    let compiler = Compiler::new(options());
+
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+    assert!(glsl.vertex().contains(
+        "layout (location = 0) in vec4 acsl_vertex_input_position;\nlayout (location = 4) in vec4 acsl_vertex_input_color;\nlayout (location = 3) in vec2 acsl_vertex_input_uv;\n"
+    ));
+    assert!(glsl
+        .vertex()
+        .contains("layout(location = 4) out vec4 acsl_pixel_input_color;\n"));
+    assert!(glsl
+        .fragment()
+        .contains("layout(location = 3) in vec2 acsl_pixel_input_uv;\n"));
+
+    let wgsl = compiler.compile_wgsl(&input_code).unwrap();
+    assert!(wgsl.code().contains("@location(4) color: vec4<f32>,\n"));
+
+    let spirv = compiler.compile_spirv(&input_code).unwrap();
+    assert!(spirv.disassemble().contains("Location 4\n"));
+
+    let reflection = compiler.reflect(&input_code).unwrap();
+    let interstage_variables = reflection.interstage_variables();
+    assert_eq!(interstage_variables[0].name(), "color");
+    assert_eq!(interstage_variables[0].location(), 4);
+    assert_eq!(interstage_variables[1].name(), "uv");
+    assert_eq!(interstage_variables[1].location(), 3);
+}
+
+#[test]
+fn interstage_locations_glsl_330() {
+    let input_code = std::fs::read_to_string("tests/texture.acsl").unwrap();
+
+    // Interstage variables are matched by name before GLSL 4.10
+    let compiler = Compiler::new(options().glsl_version(GlslVersion::Glsl330));
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+    assert!(glsl
+        .vertex()
+        .contains("\nout vec4 acsl_pixel_input_color;\n"));
+    assert!(glsl
+        .vertex()
+        .contains("layout (location = 4) in vec4 acsl_vertex_input_color;\n"));
+}
+
+#[test]
+fn reusing_locations() {
+    let input_code = std::fs::read_to_string("tests/texture.acsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().semantic_location("TEXCOORD", 1));
+    let error = compiler
+        .compile_hlsl(&input_code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "Location 1 is used by more than one member of \"VertexInput\""
+    );
+}
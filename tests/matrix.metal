@@ -0,0 +1,27 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct MatrixBuffer {
+    float4x4 object;
+    float4x4 view;
+};
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+};
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]], constant MatrixBuffer& matrix_buffer [[buffer(0)]]) {
+    return PixelInput{(vertex_input.position * (matrix_buffer.object * matrix_buffer.view))};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]]) {
+    return float4(1.0, 1.0, 1.0, 1.0);
+}
+
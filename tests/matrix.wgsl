@@ -0,0 +1,27 @@
+// Generated from Alexandria Common Shader Language
+
+struct MatrixBuffer {
+    object: mat4x4<f32>,
+    view: mat4x4<f32>,
+}
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> matrix_buffer: MatrixBuffer;
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput((vertex_input.position * (matrix_buffer.object * matrix_buffer.view)));
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0, 1.0, 1.0, 1.0);
+}
+
@@ -4,7 +4,6 @@
 
 out vec4 acsl_fragment_color;
 
-in vec4 acsl_pixel_input_position;
 
 struct MatrixBuffer {
     mat4x4 object;
@@ -23,7 +22,7 @@ layout(location = 0) uniform MatrixBuffer matrix_buffer;
 
 
 void main() {
-    PixelInput pixel_input = PixelInput(acsl_pixel_input_position);
+    PixelInput pixel_input = PixelInput(gl_FragCoord);
 
     acsl_fragment_color = vec4(1.0, 1.0, 1.0, 1.0);
     return;
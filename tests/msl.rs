@@ -0,0 +1,100 @@
+macro_rules! run_test {
+    ($test_name:literal) => {
+        let input_code = std::fs::read_to_string(format!("{}.acsl", $test_name)).unwrap();
+        let target_code = std::fs::read_to_string(format!("{}.metal", $test_name)).unwrap();
+
+        let compiled_code = acsl::compile_msl(&input_code).unwrap();
+
+        assert_eq!(compiled_code, target_code);
+    };
+}
+
+#[test]
+fn simple_1() {
+    run_test!("tests/simple");
+}
+
+#[test]
+fn matrix() {
+    run_test!("tests/matrix");
+}
+
+#[test]
+fn variables() {
+    run_test!("tests/variables");
+}
+
+#[test]
+fn constants() {
+    run_test!("tests/constants");
+}
+
+#[test]
+fn texture() {
+    run_test!("tests/texture");
+}
+
+#[test]
+fn texture_dimensions() {
+    run_test!("tests/texture_dimensions");
+}
+
+#[test]
+fn type_alias() {
+    run_test!("tests/type_alias");
+}
+
+#[test]
+fn functions() {
+    run_test!("tests/functions");
+}
+
+#[test]
+fn sampler() {
+    run_test!("tests/sampler");
+}
+
+#[test]
+fn texture_functions() {
+    run_test!("tests/texture_functions");
+}
+
+#[test]
+fn depth_texture() {
+    run_test!("tests/depth_texture");
+}
+
+#[test]
+fn render_targets() {
+    run_test!("tests/render_targets");
+}
+
+#[test]
+fn fragment_builtins() {
+    run_test!("tests/fragment_builtins");
+}
+
+#[test]
+fn vertex_builtins() {
+    run_test!("tests/vertex_builtins");
+}
+
+#[test]
+fn interpolation() {
+    run_test!("tests/interpolation");
+}
+
+#[test]
+fn sampling_option_names() {
+    let input_code = "struct PixelInput {\n    position: float4 : SV_POSITION,\n    uv: float2 : TEXCOORD,\n}\n\ntexture bias : 0;\n\nfn darken(level: float4) -> float4 {\n    return level * 0.5;\n}\n\nfn vertex_main(pixel_input: PixelInput) -> PixelInput {\n    return pixel_input;\n}\n\nfn fragment_main(pixel_input: PixelInput) -> float4 {\n    let level = sample_level(bias, pixel_input.uv, 2.0);\n    return darken(level);\n}\n";
+
+    // Identifiers hiding the Metal sampling options are renamed
+    let msl = acsl::compile_msl(input_code).unwrap();
+    assert!(msl.contains("float4 darken(float4 acsl_level) {\n    return (acsl_level * 0.5);\n"));
+    assert!(msl.contains(
+        "float4 acsl_level = acsl_bias.sample(acsl_acsl_bias_sampler_state, pixel_input.uv, level(2.0));\n"
+    ));
+    assert!(msl.contains(
+        "texture2d<float> acsl_bias [[texture(0)]], sampler acsl_acsl_bias_sampler_state [[sampler(0)]]"
+    ));
+}
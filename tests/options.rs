@@ -0,0 +1,82 @@
+fn options() -> acsl::CompileOptions {
+    acsl::CompileOptions::new()
+        .glsl_version(acsl::GlslVersion::Glsl450)
+        .header(None::<String>)
+        .vertex_entry_point("VSMain")
+        .fragment_entry_point("PSMain")
+        .indentation("\t")
+}
+
+#[test]
+fn custom_options() {
+    let input_code = std::fs::read_to_string("tests/texture.acsl").unwrap();
+    let target_code_hlsl = std::fs::read_to_string("tests/options.hlsl").unwrap();
+    let target_code_glsl_vertex = std::fs::read_to_string("tests/options_vertex.glsl").unwrap();
+    let target_code_glsl_fragment = std::fs::read_to_string("tests/options_frag.glsl").unwrap();
+
+    let compiler = acsl::Compiler::new(options());
+
+    let hlsl = compiler.compile_hlsl(&input_code).unwrap();
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+
+    assert_eq!(hlsl.code(), target_code_hlsl);
+    assert_eq!(glsl.vertex(), target_code_glsl_vertex);
+    assert_eq!(glsl.fragment(), target_code_glsl_fragment);
+}
+
+#[test]
+fn slot_limits() {
+    let input_code = std::fs::read_to_string("tests/texture.acsl").unwrap();
+
+    let compiler = acsl::Compiler::new(acsl::CompileOptions::new().max_textures(0));
+
+    assert!(compiler.compile_hlsl(&input_code).is_err());
+}
+
+#[test]
+fn invalid_entry_point_names() {
+    let input_code = format!(
+        "{}\nfn outer(value: float4) -> float4 {{\n    return value;\n}}\n",
+        std::fs::read_to_string("tests/simple.acsl").unwrap()
+    );
+    let error = |options: acsl::CompileOptions| {
+        acsl::Compiler::new(options)
+            .compile_hlsl(&input_code)
+            .err()
+            .unwrap()
+            .to_string()
+    };
+
+    for name in ["a b", "", "2d", "acsl_main", "void", "main", "level"] {
+        assert_eq!(
+            error(acsl::CompileOptions::new().vertex_entry_point(name)),
+            format!("Invalid entry point name \"{}\", expected an identifier which is not a reserved word and doesn't begin with \"gl_\" or \"acsl_\"", name)
+        );
+    }
+
+    // Names of declarations and builtin functions are taken
+    for name in ["outer", "fragment_main", "VertexInput", "float4", "floor"] {
+        assert_eq!(
+            error(acsl::CompileOptions::new().vertex_entry_point(name)),
+            format!(
+                "Entry point name \"{}\" is already used by another declaration",
+                name
+            )
+        );
+    }
+    assert_eq!(
+        error(
+            acsl::CompileOptions::new()
+                .vertex_entry_point("main_stage")
+                .fragment_entry_point("main_stage")
+        ),
+        "Entry point name \"main_stage\" is already used by another declaration"
+    );
+
+    let compiler = acsl::Compiler::new(
+        acsl::CompileOptions::new()
+            .vertex_entry_point("vertex_main")
+            .fragment_entry_point("_PSMain2"),
+    );
+    assert!(compiler.compile_hlsl(&input_code).is_ok());
+}
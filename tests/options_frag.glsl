@@ -0,0 +1,36 @@
+#version 450 core
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec4 acsl_pixel_input_color;
+layout(location = 2) in vec2 acsl_pixel_input_uv;
+
+struct MatrixBuffer {
+	mat4x4 object;
+	mat4x4 view;
+};
+
+struct VertexInput {
+	vec4 position;
+	vec4 color;
+	vec2 uv;
+};
+
+struct PixelInput {
+	vec4 position;
+	vec4 color;
+	vec2 uv;
+};
+
+layout(location = 0) uniform MatrixBuffer matrix_buffer;
+
+layout(location = 32) uniform sampler2D tex;
+
+
+void main() {
+	PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_color, acsl_pixel_input_uv);
+
+	acsl_fragment_color = (pixel_input.color * texture(tex, pixel_input.uv));
+	return;
+}
+
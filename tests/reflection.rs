@@ -0,0 +1,58 @@
+macro_rules! run_test {
+    ($test_name:literal) => {
+        let input_filepath = std::path::PathBuf::from(format!("{}.acsl", $test_name));
+        let target_filepath = std::path::PathBuf::from(format!("{}.json", $test_name));
+
+        let input_code = std::fs::read_to_string(input_filepath).unwrap();
+        let target_json = std::fs::read_to_string(target_filepath).unwrap();
+
+        let reflection = acsl::reflect(&input_code).unwrap();
+
+        assert_eq!(reflection.to_json(), target_json);
+    };
+}
+
+#[test]
+fn texture() {
+    run_test!("tests/texture");
+}
+
+#[test]
+fn texture_dimensions() {
+    run_test!("tests/texture_dimensions");
+}
+
+#[test]
+fn buffer() {
+    run_test!("tests/buffer");
+}
+
+#[test]
+fn storage_texture() {
+    run_test!("tests/storage_texture");
+}
+
+#[test]
+fn sampler() {
+    run_test!("tests/sampler");
+}
+
+#[test]
+fn texture_functions() {
+    run_test!("tests/texture_functions");
+}
+
+#[test]
+fn depth_texture() {
+    run_test!("tests/depth_texture");
+}
+
+#[test]
+fn vertex_builtins() {
+    run_test!("tests/vertex_builtins");
+}
+
+#[test]
+fn interpolation() {
+    run_test!("tests/interpolation");
+}
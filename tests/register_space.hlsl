@@ -0,0 +1,48 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct Tint {
+    float4 color;
+};
+Tint acsl_create_Tint(float4 color) {
+    Tint output;
+    output.color = color;
+    return output;
+}
+
+cbuffer acsl_constant_buffer_0_space1 : register(b0, space1) {
+    Tint tint;
+}
+
+Texture2D<float4> tex : register(t0, space2);
+SamplerState acsl_tex_sampler_state : register(s0, space2);
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(vertex_input.position, vertex_input.uv);
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    return (tex.Sample(acsl_tex_sampler_state, pixel_input.uv) * tint.color);
+}
+
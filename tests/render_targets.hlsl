@@ -0,0 +1,55 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float3 normal: NORMAL;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float3 normal, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.normal = normal;
+    output.uv = uv;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float3 normal: NORMAL;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float3 normal, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.normal = normal;
+    output.uv = uv;
+    return output;
+}
+
+struct GBuffer {
+    float4 albedo: SV_TARGET0;
+    float4 normal: SV_TARGET1;
+    float2 material: SV_TARGET2;
+    uint object_id: SV_TARGET3;
+};
+GBuffer acsl_create_GBuffer(float4 albedo, float4 normal, float2 material, uint object_id) {
+    GBuffer output;
+    output.albedo = albedo;
+    output.normal = normal;
+    output.material = material;
+    output.object_id = object_id;
+    return output;
+}
+
+Texture2D<float4> albedo : register(t0);
+SamplerState acsl_albedo_sampler_state : register(s0);
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(vertex_input.position, vertex_input.normal, vertex_input.uv);
+}
+
+GBuffer fragment_main(PixelInput pixel_input) {
+    float3 normal = pixel_input.normal;
+    return acsl_create_GBuffer(albedo.Sample(acsl_albedo_sampler_state, pixel_input.uv), float4(normal.x, normal.y, normal.z, 0.0), float2(0.5, 1.0), uint(pixel_input.position.z));
+}
+
@@ -0,0 +1,34 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float3 normal [[attribute(1)]];
+    float2 uv [[attribute(2)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float3 normal;
+    float2 uv;
+};
+
+struct GBuffer {
+    float4 albedo [[color(0)]];
+    float4 normal [[color(1)]];
+    float2 material [[color(2)]];
+    uint object_id [[color(3)]];
+};
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    return PixelInput{vertex_input.position, vertex_input.normal, vertex_input.uv};
+}
+
+fragment GBuffer fragment_main(PixelInput pixel_input [[stage_in]], texture2d<float> albedo [[texture(0)]], sampler acsl_albedo_sampler_state [[sampler(0)]]) {
+    float3 normal = pixel_input.normal;
+    return GBuffer{albedo.sample(acsl_albedo_sampler_state, pixel_input.uv), float4(normal.x, normal.y, normal.z, 0.0), float2(0.5, 1.0), uint(pixel_input.position.z)};
+}
+
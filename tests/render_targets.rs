@@ -0,0 +1,91 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::{error, shader};
+
+const UV: &str = "    uv: float2 : TEXCOORD,\n";
+
+#[test]
+fn render_targets_glsl_330() {
+    let input_code = std::fs::read_to_string("tests/render_targets.acsl").unwrap();
+
+    // Outputs are located even without explicit interstage locations
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Glsl330));
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+    assert!(glsl
+        .fragment()
+        .contains("layout(location = 3) out uint acsl_fragment_object_id;\n"));
+    assert!(!glsl.fragment().contains("acsl_fragment_color"));
+}
+
+#[test]
+fn sparse_render_targets() {
+    let hlsl = acsl::compile_hlsl(shader(
+        "struct Output {\n    emissive: float4 : SV_TARGET5,\n    color: float4 : SV_TARGET0,\n}",
+        UV,
+        "Output",
+        "    return Output {\n        emissive: pixel_input.position,\n        color: pixel_input.position,\n    };",
+    ))
+    .unwrap();
+    assert!(hlsl.contains("Output fragment_main(PixelInput pixel_input) {\n"));
+
+    let (_, glsl) = acsl::compile_glsl(shader(
+        "struct Output {\n    emissive: float4 : SV_TARGET5,\n    color: float4 : SV_TARGET0,\n}",
+        UV,
+        "Output",
+        "    return Output {\n        emissive: pixel_input.position,\n        color: pixel_input.position,\n    };",
+    ))
+    .unwrap();
+    assert!(glsl.contains(
+        "layout(location = 5) out vec4 acsl_fragment_emissive;\nlayout(location = 0) out vec4 acsl_fragment_color;\n"
+    ));
+}
+
+#[test]
+fn invalid_render_targets() {
+    assert_eq!(
+        error(&shader(
+            "struct Output {\n    color: float4 : COLOR,\n}",
+            UV,
+            "Output",
+            "    return Output { color: pixel_input.position };"
+        )),
+        "Member \"color\" of \"Output\" must have a render target semantic from \"SV_TARGET0\" to \"SV_TARGET7\" or \"SV_DEPTH\""
+    );
+    assert_eq!(
+        error(&shader(
+            "struct Output {\n    color: float4 : SV_TARGET8,\n}",
+            UV,
+            "Output",
+            "    return Output { color: pixel_input.position };"
+        )),
+        "Member \"color\" of \"Output\" must have a render target semantic from \"SV_TARGET0\" to \"SV_TARGET7\" or \"SV_DEPTH\""
+    );
+    assert_eq!(
+        error(&shader(
+            "struct Output {\n    color: float4 : SV_TARGET1,\n    bloom: float4 : SV_TARGET1,\n}",
+            UV,
+            "Output",
+            "    return Output { color: pixel_input.position, bloom: pixel_input.position };"
+        )),
+        "Reusing render target 1"
+    );
+    assert_eq!(
+        error(&shader(
+            "struct Output {\n    input: PixelInput : SV_TARGET0,\n}",
+            UV,
+            "Output",
+            "    return Output { input: pixel_input };"
+        )),
+        "Render target \"input\" can't be of type \"PixelInput\", expected a float or uint scalar or vector"
+    );
+    assert_eq!(
+        error(&shader(
+            "type Output = float2;",
+            UV,
+            "Output",
+            "    return pixel_input.uv;"
+        )),
+        "\"fragment_main()\" must return a float4 or a structure of render targets, instead it is \"float2\""
+    );
+}
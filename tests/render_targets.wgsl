@@ -0,0 +1,35 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+struct GBuffer {
+    @location(0) albedo: vec4<f32>,
+    @location(1) normal: vec4<f32>,
+    @location(2) material: vec2<f32>,
+    @location(3) object_id: u32,
+}
+
+@group(0) @binding(32) var albedo: texture_2d<f32>;
+@group(0) @binding(40) var acsl_albedo_sampler_state: sampler;
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput(vertex_input.position, vertex_input.normal, vertex_input.uv);
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> GBuffer {
+    var normal: vec3<f32> = pixel_input.normal;
+    return GBuffer(textureSample(albedo, acsl_albedo_sampler_state, pixel_input.uv), vec4<f32>(normal.x, normal.y, normal.z, 0.0), vec2<f32>(0.5, 1.0), u32(pixel_input.position.z));
+}
+
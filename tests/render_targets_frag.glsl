@@ -0,0 +1,46 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(location = 0) out vec4 acsl_fragment_albedo;
+layout(location = 1) out vec4 acsl_fragment_normal;
+layout(location = 2) out vec2 acsl_fragment_material;
+layout(location = 3) out uint acsl_fragment_object_id;
+
+layout(location = 1) in vec3 acsl_pixel_input_normal;
+layout(location = 2) in vec2 acsl_pixel_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec3 normal;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec3 normal;
+    vec2 uv;
+};
+
+struct GBuffer {
+    vec4 albedo;
+    vec4 normal;
+    vec2 material;
+    uint object_id;
+};
+
+layout(location = 32) uniform sampler2D albedo;
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_normal, acsl_pixel_input_uv);
+
+    vec3 normal = pixel_input.normal;
+    GBuffer acsl_fragment_output = GBuffer(texture(albedo, pixel_input.uv), vec4(normal.x, normal.y, normal.z, 0.0), vec2(0.5, 1.0), uint(pixel_input.position.z));
+    acsl_fragment_albedo = acsl_fragment_output.albedo;
+    acsl_fragment_normal = acsl_fragment_output.normal;
+    acsl_fragment_material = acsl_fragment_output.material;
+    acsl_fragment_object_id = acsl_fragment_output.object_id;
+    return;
+}
+
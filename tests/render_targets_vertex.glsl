@@ -0,0 +1,43 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in vec3 acsl_vertex_input_normal;
+layout (location = 2) in vec2 acsl_vertex_input_uv;
+
+layout(location = 1) out vec3 acsl_pixel_input_normal;
+layout(location = 2) out vec2 acsl_pixel_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec3 normal;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec3 normal;
+    vec2 uv;
+};
+
+struct GBuffer {
+    vec4 albedo;
+    vec4 normal;
+    vec2 material;
+    uint object_id;
+};
+
+layout(location = 32) uniform sampler2D albedo;
+
+void main() {
+    VertexInput vertex_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_normal, acsl_vertex_input_uv);
+
+    PixelInput acsl_vertex_output = PixelInput(vertex_input.position, vertex_input.normal, vertex_input.uv);
+    acsl_pixel_input_normal = acsl_vertex_output.normal;
+    acsl_pixel_input_uv = acsl_vertex_output.uv;
+    gl_Position = acsl_vertex_output.position;
+    return;
+}
+
+
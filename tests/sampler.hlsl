@@ -0,0 +1,52 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+Texture2D<float4> albedo : register(t0);
+SamplerState acsl_albedo_sampler_state : register(s0);
+
+Texture2D<float4> normals : register(t1);
+SamplerState acsl_normals_sampler_state : register(s1);
+
+TextureCube<float4> environment : register(t2);
+SamplerState acsl_environment_sampler_state : register(s2);
+
+Texture2D<float4> detail : register(t3);
+SamplerState acsl_detail_sampler_state : register(s3);
+
+SamplerState linear_clamp : register(s8);
+
+SamplerState pixelated : register(s9);
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(vertex_input.position, vertex_input.uv);
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    float3 direction = float3(pixel_input.uv.x, pixel_input.uv.y, 1.0);
+    float4 color = albedo.Sample(linear_clamp, pixel_input.uv);
+    float4 normal = normals.Sample(linear_clamp, pixel_input.uv);
+    float4 reflection = environment.Sample(pixelated, direction);
+    return (((color * normal) + reflection) + detail.Sample(acsl_detail_sampler_state, pixel_input.uv));
+}
+
@@ -0,0 +1,32 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float2 uv [[attribute(1)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float2 uv;
+};
+
+constexpr sampler linear_clamp(filter::linear, mip_filter::linear, address::clamp_to_edge);
+
+constexpr sampler pixelated(filter::nearest, mip_filter::nearest, address::mirrored_repeat);
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    return PixelInput{vertex_input.position, vertex_input.uv};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]], texture2d<float> albedo [[texture(0)]], sampler acsl_albedo_sampler_state [[sampler(0)]], texture2d<float> normals [[texture(1)]], sampler acsl_normals_sampler_state [[sampler(1)]], texturecube<float> environment [[texture(2)]], sampler acsl_environment_sampler_state [[sampler(2)]], texture2d<float> detail [[texture(3)]], sampler acsl_detail_sampler_state [[sampler(3)]]) {
+    float3 direction = float3(pixel_input.uv.x, pixel_input.uv.y, 1.0);
+    float4 color = albedo.sample(linear_clamp, pixel_input.uv);
+    float4 normal = normals.sample(linear_clamp, pixel_input.uv);
+    float4 reflection = environment.sample(pixelated, direction);
+    return (((color * normal) + reflection) + detail.sample(acsl_detail_sampler_state, pixel_input.uv));
+}
+
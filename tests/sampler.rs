@@ -0,0 +1,149 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::{error, fragment};
+
+#[test]
+fn sampler_vulkan() {
+    let input_code = std::fs::read_to_string("tests/sampler.acsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+
+    // Sampler declarations follow the samplers of the textures
+    assert!(glsl
+        .fragment()
+        .contains("layout(set = 2, binding = 8) uniform sampler linear_clamp;\n"));
+    assert!(glsl
+        .fragment()
+        .contains("vec4 color = texture(sampler2D(albedo, linear_clamp), pixel_input.uv);\n"));
+    assert!(glsl
+        .fragment()
+        .contains("texture(samplerCube(environment, pixelated), direction);\n"));
+}
+
+#[test]
+fn max_samplers() {
+    let compiler = Compiler::new(CompileOptions::new().max_samplers(1));
+
+    let error = compiler
+        .compile_hlsl("sampler clamped : 1 { address: clamp };")
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(error, "Slot 1 is beyond the max slot of 1 for samplers");
+
+    let compiler = Compiler::new(CompileOptions::new().max_textures(2));
+    let hlsl = compiler
+        .compile_hlsl(fragment(
+            "sampler clamped : 1 { address: clamp };",
+            "    return pixel_input.position;",
+        ))
+        .unwrap();
+    assert!(hlsl
+        .code()
+        .contains("SamplerState clamped : register(s3);\n"));
+}
+
+#[test]
+fn default_sampler_state() {
+    let reflection = acsl::reflect(fragment(
+        "sampler smooth : 0;",
+        "    return pixel_input.position;",
+    ))
+    .unwrap();
+
+    assert_eq!(reflection.samplers()[0].filter(), "linear");
+    assert_eq!(reflection.samplers()[0].address(), "wrap");
+    assert!(reflection.samplers()[0].textures().is_empty());
+}
+
+#[test]
+fn texture_with_several_samplers() {
+    let code = fragment(
+        "texture albedo : 0;\nsampler smooth : 0;\nsampler pixelated : 1 { filter: point };",
+        "    return sample(albedo, smooth, uv) + sample(albedo, pixelated, uv);",
+    );
+
+    // OpenGL samples a texture with the one state of its texture unit
+    let error = Compiler::default()
+        .compile_glsl(&code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL 4.30 does not support sampling a texture with more than one sampler (\"sample\")"
+    );
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    assert!(compiler.compile_glsl(&code).is_ok());
+    assert!(acsl::compile_hlsl(&code).is_ok());
+    assert!(acsl::compile_wgsl(&code).is_ok());
+    assert!(acsl::compile_msl(&code).is_ok());
+    assert!(acsl::compile_spirv(&code).is_ok());
+
+    let reflection = acsl::reflect(&code).unwrap();
+    assert_eq!(reflection.samplers()[0].textures(), ["albedo"]);
+    assert_eq!(reflection.samplers()[1].textures(), ["albedo"]);
+
+    // The texture's own sampler counts as another sampler
+    let code = fragment(
+        "texture albedo : 0;\nsampler smooth : 0;",
+        "    return sample(albedo, smooth, uv) + sample_level(albedo, uv, 1.0);",
+    );
+    let error = Compiler::default()
+        .compile_glsl(&code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL 4.30 does not support sampling a texture with more than one sampler (\"sample\")"
+    );
+    assert!(compiler.compile_glsl(&code).is_ok());
+
+    let reflection = acsl::reflect(&code).unwrap();
+    assert_eq!(reflection.samplers()[0].textures(), ["albedo"]);
+}
+
+#[test]
+fn invalid_samplers() {
+    assert_eq!(
+        error("sampler smooth : 0 { filter: cubic };"),
+        "Sampler state \"filter\" can't be \"cubic\", expected \"point\" or \"linear\""
+    );
+    assert_eq!(
+        error("sampler smooth : 0 { address: border };"),
+        "Sampler state \"address\" can't be \"border\", expected \"wrap\", \"clamp\" or \"mirror\""
+    );
+    assert_eq!(
+        error("sampler smooth : 0 { anisotropy: linear };"),
+        "Unknown sampler state \"anisotropy\", expected \"filter\", \"address\" or \"compare\""
+    );
+    assert_eq!(
+        error("sampler first : 0;\nsampler second : 0;"),
+        "Reusing sampler slot 0"
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture albedo : 0;\ntexture normals : 1;",
+            "    return sample(albedo, normals, pixel_input.uv);"
+        )),
+        "Function \"sample\" expects parameter 2 to be of type \"sampler\", instead found type \"texture\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture albedo : 0;\nsampler smooth : 0;",
+            "    return sample(albedo, smooth, pixel_input.position);"
+        )),
+        "Function \"sample\" expects parameter 3 to be of type \"float2\", instead found type \"float4\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture albedo : 0;\nsampler smooth : 0;",
+            "    return sample(albedo, pixel_input.uv);"
+        )),
+        "Function \"sample\" expects 3 parameters, only 2 provided"
+    );
+}
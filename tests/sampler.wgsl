@@ -0,0 +1,42 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+@group(0) @binding(32) var albedo: texture_2d<f32>;
+@group(0) @binding(40) var acsl_albedo_sampler_state: sampler;
+
+@group(0) @binding(33) var normals: texture_2d<f32>;
+@group(0) @binding(41) var acsl_normals_sampler_state: sampler;
+
+@group(0) @binding(34) var environment: texture_cube<f32>;
+@group(0) @binding(42) var acsl_environment_sampler_state: sampler;
+
+@group(0) @binding(35) var detail: texture_2d<f32>;
+@group(0) @binding(43) var acsl_detail_sampler_state: sampler;
+
+@group(0) @binding(48) var linear_clamp: sampler;
+
+@group(0) @binding(49) var pixelated: sampler;
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput(vertex_input.position, vertex_input.uv);
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    var direction: vec3<f32> = vec3<f32>(pixel_input.uv.x, pixel_input.uv.y, 1.0);
+    var color: vec4<f32> = textureSample(albedo, linear_clamp, pixel_input.uv);
+    var normal: vec4<f32> = textureSample(normals, linear_clamp, pixel_input.uv);
+    var reflection: vec4<f32> = textureSample(environment, pixelated, direction);
+    return (((color * normal) + reflection) + textureSample(detail, acsl_detail_sampler_state, pixel_input.uv));
+}
+
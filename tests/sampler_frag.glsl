@@ -0,0 +1,38 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec2 acsl_pixel_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+};
+
+layout(location = 32) uniform sampler2D albedo;
+
+layout(location = 33) uniform sampler2D normals;
+
+layout(location = 34) uniform samplerCube environment;
+
+layout(location = 35) uniform sampler2D detail;
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_uv);
+
+    vec3 direction = vec3(pixel_input.uv.x, pixel_input.uv.y, 1.0);
+    vec4 color = texture(albedo, pixel_input.uv);
+    vec4 normal = texture(normals, pixel_input.uv);
+    vec4 reflection = texture(environment, direction);
+    acsl_fragment_color = (((color * normal) + reflection) + texture(detail, pixel_input.uv));
+    return;
+}
+
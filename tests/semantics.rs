@@ -0,0 +1,94 @@
+mod common;
+
+use common::error;
+
+fn shader(vertex_input: &str, pixel_input: &str, body: &str) -> String {
+    format!(
+        "struct VertexInput {{\n{}}}\n\nstruct PixelInput {{\n{}}}\n\nfn vertex_main(vertex_input: VertexInput) -> PixelInput {{\n{}\n}}\n\nfn fragment_main(pixel_input: PixelInput) -> float4 {{\n    return float4(1.0, 1.0, 1.0, 1.0);\n}}\n",
+        vertex_input, pixel_input, body
+    )
+}
+
+#[test]
+fn user_semantics() {
+    let hlsl = acsl::compile_hlsl(shader(
+        "    position: float4 : POSITION,\n    uv: float2 : texcoord,\n",
+        "    position: float4 : SV_POSITION,\n    uv: float2 : TEXCOORD0,\n    color: float4 : TEXCOORD1,\n",
+        "    return PixelInput {\n        position: vertex_input.position,\n        uv: vertex_input.uv,\n        color: vertex_input.position,\n    };",
+    ))
+    .unwrap();
+    assert!(hlsl.contains("float2 uv: texcoord;\n"));
+    assert!(hlsl.contains("float4 color: TEXCOORD1;\n"));
+}
+
+#[test]
+fn missing_semantics() {
+    assert_eq!(
+        error(&shader(
+            "    position: float4,\n",
+            "    position: float4 : SV_POSITION,\n",
+            "    return PixelInput { position: vertex_input.position };"
+        )),
+        "The parameter for \"vertex_main()\" must be a structure with semantics, instead it is \"VertexInput\""
+    );
+    assert_eq!(
+        error(&shader(
+            "    position: float4 : POSITION,\n",
+            "    uv: float4 : TEXCOORD,\n",
+            "    return PixelInput { uv: vertex_input.position };"
+        )),
+        "\"PixelInput\" must have exactly one member with the \"SV_POSITION\" semantic, found 0"
+    );
+}
+
+#[test]
+fn invalid_semantics() {
+    assert_eq!(
+        error(&shader(
+            "    position: float4 : POSITION,\n",
+            "    position: float4 : sv_position,\n",
+            "    return PixelInput { position: vertex_input.position };"
+        )),
+        "Member \"position\" of \"PixelInput\" has the semantic \"sv_position\", which can't be used in the structure passed from \"vertex_main()\" to \"fragment_main()\""
+    );
+    assert_eq!(
+        error(&shader(
+            "    position: float4 : POSITION,\n    vertex_id: uint : SV_VERTEX_ID,\n",
+            "    position: float4 : SV_POSITION,\n    vertex_id: uint : SV_VERTEX_ID,\n",
+            "    return PixelInput { position: vertex_input.position, vertex_id: vertex_input.vertex_id };"
+        )),
+        "Member \"vertex_id\" of \"PixelInput\" has the semantic \"SV_VERTEX_ID\", which can't be used in the structure passed from \"vertex_main()\" to \"fragment_main()\""
+    );
+    assert_eq!(
+        error(&shader(
+            "    position: float4 : POSITION,\n    color: float4 : SV_TARGET,\n",
+            "    position: float4 : SV_POSITION,\n",
+            "    return PixelInput { position: vertex_input.color };"
+        )),
+        "Member \"color\" of \"VertexInput\" has the semantic \"SV_TARGET\", which can't be used in the input of \"vertex_main()\""
+    );
+}
+
+#[test]
+fn duplicate_semantics() {
+    assert_eq!(
+        error(&shader(
+            "    position: float4 : POSITION,\n    uv: float2 : TEXCOORD,\n    uv2: float2 : TEXCOORD0,\n",
+            "    position: float4 : SV_POSITION,\n",
+            "    return PixelInput { position: vertex_input.position };"
+        )),
+        "Semantic \"TEXCOORD0\" is used more than once in \"VertexInput\""
+    );
+    assert_eq!(
+        error(&shader(
+            "    position: float4 : POSITION,\n",
+            "    position: float4 : SV_POSITION,\n    clip_position: float4 : SV_POSITION,\n",
+            "    return PixelInput { position: vertex_input.position, clip_position: vertex_input.position };"
+        )),
+        "Semantic \"SV_POSITION\" is used more than once in \"PixelInput\""
+    );
+    assert_eq!(
+        error("struct PixelInput {\n    position: float4 : SV_POSITION,\n}\n\nstruct Output {\n    depth: float : SV_DEPTH,\n    far_depth: float : SV_DEPTH,\n}\n\nfn vertex_main(pixel_input: PixelInput) -> PixelInput {\n    return pixel_input;\n}\n\nfn fragment_main(pixel_input: PixelInput) -> Output {\n    return Output { depth: pixel_input.position.z, far_depth: pixel_input.position.w };\n}\n"),
+        "Semantic \"SV_DEPTH\" is used more than once in \"Output\""
+    );
+}
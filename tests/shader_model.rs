@@ -0,0 +1,137 @@
+use acsl::{CompileOptions, Compiler, ShaderModel};
+
+macro_rules! run_test {
+    ($test_name:literal, $shader_model:expr) => {
+        let input_code = std::fs::read_to_string(format!("{}.acsl", $test_name)).unwrap();
+        let target_code = std::fs::read_to_string(format!("{}.hlsl", $test_name)).unwrap();
+
+        let compiler = Compiler::new(CompileOptions::new().shader_model($shader_model));
+        let hlsl = compiler.compile_hlsl(&input_code).unwrap();
+
+        assert_eq!(hlsl.code(), target_code);
+    };
+}
+
+fn error(test_name: &str, shader_model: ShaderModel) -> String {
+    let input_code = std::fs::read_to_string(format!("{}.acsl", test_name)).unwrap();
+    let compiler = Compiler::new(CompileOptions::new().shader_model(shader_model));
+
+    match compiler.compile_hlsl(&input_code) {
+        Ok(_) => panic!("Expected {} to be rejected", test_name),
+        Err(error) => error.to_string(),
+    }
+}
+
+#[test]
+fn register_space() {
+    run_test!("tests/register_space", ShaderModel::Sm51);
+}
+
+#[test]
+fn register_space_unsupported() {
+    assert!(error("tests/register_space", ShaderModel::Sm50)
+        .contains("HLSL Shader Model 5.0 does not support register spaces"));
+}
+
+#[test]
+fn register_space_reflection() {
+    let input_code = std::fs::read_to_string("tests/register_space.acsl").unwrap();
+    let reflection = acsl::reflect(&input_code).unwrap();
+
+    assert_eq!(reflection.constant_buffers()[0].space(), 1);
+    assert_eq!(reflection.textures()[0].space(), 2);
+}
+
+#[test]
+fn register_space_slots() {
+    let input_code = std::fs::read_to_string("tests/register_space.acsl").unwrap();
+    let compiler = Compiler::new(CompileOptions::new().shader_model(ShaderModel::Sm51));
+
+    // A slot can be used once in every space
+    let shared = input_code
+        .replace(
+            "cbuffer tint: Tint : 0, space 1;",
+            "cbuffer tint: Tint : 0, space 1;\ncbuffer fog: Tint : 0;",
+        )
+        .replace(
+            "texture tex : 0, space 2;",
+            "texture tex : 0, space 2;\ntexture mask : 0;",
+        );
+    let hlsl = compiler.compile_hlsl(&shared).unwrap();
+    assert!(hlsl
+        .code()
+        .contains("cbuffer acsl_constant_buffer_0_space1 : register(b0, space1) {\n"));
+    assert!(hlsl
+        .code()
+        .contains("cbuffer acsl_constant_buffer_0 : register(b0) {\n"));
+    assert!(hlsl
+        .code()
+        .contains("Texture2D<float4> mask : register(t0);\n"));
+
+    // The other targets have no spaces to tell them apart
+    let error = acsl::compile_glsl(&shared).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "GLSL 4.30 does not support slots shared by resources in different spaces (\"space\")"
+    );
+
+    let reused = input_code.replace(
+        "cbuffer tint: Tint : 0, space 1;",
+        "cbuffer tint: Tint : 0, space 1;\ncbuffer fog: Tint : 0, space 1;",
+    );
+    let error = compiler.compile_hlsl(&reused).err().unwrap().to_string();
+    assert_eq!(error, "Reusing constant buffer slot 0 in space 1");
+
+    let reused = input_code.replace(
+        "texture tex : 0, space 2;",
+        "texture tex : 0, space 2;\ntexture mask : 0, space 2;",
+    );
+    let error = compiler.compile_hlsl(&reused).err().unwrap().to_string();
+    assert_eq!(error, "Reusing texture slot 0 in space 2");
+
+    let reused = input_code.replace(
+        "texture tex : 0, space 2;",
+        "texture tex : 0, space 2;\ntexture mask : 1;\ntexture detail : 1;",
+    );
+    let error = compiler.compile_glsl(&reused).err().unwrap().to_string();
+    assert_eq!(error, "Reusing texture slot 1");
+}
+
+#[test]
+fn wave() {
+    run_test!("tests/wave", ShaderModel::Sm62);
+}
+
+#[test]
+fn sixteen_bit_types_unsupported() {
+    assert!(error("tests/wave", ShaderModel::Sm60)
+        .contains("HLSL Shader Model 6.0 does not support 16-bit types"));
+}
+
+#[test]
+fn wave_intrinsics_unsupported() {
+    let input_code = std::fs::read_to_string("tests/wave.acsl").unwrap();
+
+    // Only the wave intrinsics, without the 16-bit types
+    let input_code = input_code
+        .replace("half_to_float(scale(half(", "(((")
+        .replace(
+            "fn scale(value: half) -> half {\n    return value * half(2.0);\n}\n",
+            "",
+        );
+
+    let compiler = Compiler::new(CompileOptions::new().shader_model(ShaderModel::Sm51));
+    let error = compiler
+        .compile_hlsl(&input_code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.contains("HLSL Shader Model 5.1 does not support wave intrinsics"));
+
+    let error = Compiler::default()
+        .compile_glsl(&input_code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.contains("GLSL 4.30 does not support wave intrinsics"));
+}
@@ -46,6 +46,11 @@ fn texture() {
     run_test!("tests/texture");
 }
 
+#[test]
+fn texture_dimensions() {
+    run_test!("tests/texture_dimensions");
+}
+
 #[test]
 fn type_alias() {
     run_test!("tests/type_alias");
@@ -55,3 +60,38 @@ fn type_alias() {
 fn constant() {
     run_test!("tests/constants");
 }
+
+#[test]
+fn sampler() {
+    run_test!("tests/sampler");
+}
+
+#[test]
+fn texture_functions() {
+    run_test!("tests/texture_functions");
+}
+
+#[test]
+fn depth_texture() {
+    run_test!("tests/depth_texture");
+}
+
+#[test]
+fn render_targets() {
+    run_test!("tests/render_targets");
+}
+
+#[test]
+fn fragment_builtins() {
+    run_test!("tests/fragment_builtins");
+}
+
+#[test]
+fn vertex_builtins() {
+    run_test!("tests/vertex_builtins");
+}
+
+#[test]
+fn interpolation() {
+    run_test!("tests/interpolation");
+}
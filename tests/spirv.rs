@@ -0,0 +1,119 @@
+use acsl::Compiler;
+
+macro_rules! run_test {
+    ($test_name:literal) => {
+        let input_code = std::fs::read_to_string(format!("{}.acsl", $test_name)).unwrap();
+        let target_code = std::fs::read_to_string(format!("{}.spvasm", $test_name)).unwrap();
+
+        let compiled_code = Compiler::default()
+            .compile_spirv(&input_code)
+            .unwrap()
+            .disassemble();
+
+        assert_eq!(compiled_code, target_code);
+    };
+}
+
+#[test]
+fn simple_1() {
+    run_test!("tests/simple");
+}
+
+#[test]
+fn matrix() {
+    run_test!("tests/matrix");
+}
+
+#[test]
+fn variables() {
+    run_test!("tests/variables");
+}
+
+#[test]
+fn constants() {
+    run_test!("tests/constants");
+}
+
+#[test]
+fn texture() {
+    run_test!("tests/texture");
+}
+
+#[test]
+fn texture_dimensions() {
+    run_test!("tests/texture_dimensions");
+}
+
+#[test]
+fn type_alias() {
+    run_test!("tests/type_alias");
+}
+
+#[test]
+fn functions() {
+    run_test!("tests/functions");
+}
+
+#[test]
+fn header() {
+    let input_code = std::fs::read_to_string("tests/simple.acsl").unwrap();
+    let words = acsl::compile_spirv(&input_code).unwrap();
+
+    assert_eq!(words[0], 0x07230203);
+    assert_eq!(words[1], 0x00010000);
+
+    // Every id is below the bound
+    let bound = words[3];
+    assert!(bound > 1);
+    assert!(!acsl::disassemble_spirv(&words).contains(&format!("%{} ", bound)));
+}
+
+#[test]
+fn clip_space_fixups() {
+    let input_code = std::fs::read_to_string("tests/simple.acsl").unwrap();
+    let compiler = Compiler::new(
+        acsl::CompileOptions::new()
+            .flip_y(true)
+            .fix_depth_range(true),
+    );
+
+    let disassembly = compiler.compile_spirv(&input_code).unwrap().disassemble();
+
+    assert!(disassembly.contains("OpFNegate"));
+    assert!(disassembly.contains(" 0.5\n"));
+}
+
+#[test]
+fn sampler() {
+    run_test!("tests/sampler");
+}
+
+#[test]
+fn texture_functions() {
+    run_test!("tests/texture_functions");
+}
+
+#[test]
+fn depth_texture() {
+    run_test!("tests/depth_texture");
+}
+
+#[test]
+fn render_targets() {
+    run_test!("tests/render_targets");
+}
+
+#[test]
+fn fragment_builtins() {
+    run_test!("tests/fragment_builtins");
+}
+
+#[test]
+fn vertex_builtins() {
+    run_test!("tests/vertex_builtins");
+}
+
+#[test]
+fn interpolation() {
+    run_test!("tests/interpolation");
+}
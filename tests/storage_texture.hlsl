@@ -0,0 +1,31 @@
+// Generated from Alexandria Common Shader Language
+
+struct ThreadId {
+    uint3 dispatch_thread: SV_DispatchThreadID;
+    uint3 group: SV_GroupID;
+    uint3 group_thread: SV_GroupThreadID;
+    uint group_index: SV_GroupIndex;
+};
+ThreadId acsl_create_ThreadId(uint3 dispatch_thread, uint3 group, uint3 group_thread, uint group_index) {
+    ThreadId output;
+    output.dispatch_thread = dispatch_thread;
+    output.group = group;
+    output.group_thread = group_thread;
+    output.group_index = group_index;
+    return output;
+}
+
+RWTexture2D<float4> image : register(u8);
+
+RWTexture2D<float> heights : register(u9);
+
+RWTexture2D<uint> counts : register(u10);
+
+[numthreads(8, 8, 1)]
+void main(ThreadId id) {
+    uint2 coordinates = uint2(id.dispatch_thread.x, id.dispatch_thread.y);
+    float height = heights[coordinates];
+    image[coordinates] = (image[coordinates] * height);
+    counts[coordinates] = (counts[coordinates] + id.group_index);
+}
+
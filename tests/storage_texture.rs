@@ -0,0 +1,128 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::{error, kernel};
+
+const IMAGE: &str = "rwtexture<float4> image : 0;";
+
+#[test]
+fn storage_texture_hlsl() {
+    let input_code = std::fs::read_to_string("tests/storage_texture.acsl").unwrap();
+    let target_code = std::fs::read_to_string("tests/storage_texture.hlsl").unwrap();
+
+    let hlsl = Compiler::default().compile_hlsl(&input_code).unwrap();
+
+    assert_eq!(hlsl.code(), target_code);
+}
+
+#[test]
+fn storage_texture_glsl() {
+    let input_code = std::fs::read_to_string("tests/storage_texture.acsl").unwrap();
+    let target_code_main = std::fs::read_to_string("tests/storage_texture_main_comp.glsl").unwrap();
+
+    let glsl = Compiler::default().compile_glsl(&input_code).unwrap();
+
+    assert_eq!(glsl.compute().len(), 1);
+    assert_eq!(glsl.compute()[0].1, target_code_main);
+}
+
+#[test]
+fn storage_texture_vulkan() {
+    let input_code = std::fs::read_to_string("tests/storage_texture.acsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+
+    assert!(glsl.compute()[0]
+        .1
+        .contains("layout(r32ui, set = 4, binding = 2) uniform uimage2D counts;\n"));
+}
+
+#[test]
+fn max_storage_textures() {
+    let compiler = Compiler::new(CompileOptions::new().max_storage_textures(1));
+
+    let error = compiler
+        .compile_hlsl("rwtexture<float> image : 1;")
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "Slot 1 is beyond the max slot of 1 for storage textures"
+    );
+
+    // Storage textures start after the writable buffers
+    let compiler = Compiler::new(CompileOptions::new().max_buffers(2));
+    let hlsl = compiler.compile_hlsl(kernel(IMAGE, "    return;")).unwrap();
+    assert!(hlsl
+        .code()
+        .contains("RWTexture2D<float4> image : register(u2);\n"));
+}
+
+#[test]
+fn unsupported_targets() {
+    let input_code = std::fs::read_to_string("tests/storage_texture.acsl").unwrap();
+
+    let error = acsl::compile_msl(&input_code).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "The MSL backend does not yet support storage textures (\"rwtexture\")"
+    );
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Glsl330));
+    let error = compiler
+        .compile_glsl(&input_code)
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL 3.30 does not support storage textures (\"rwtexture\")"
+    );
+}
+
+#[test]
+fn invalid_storage_textures() {
+    assert_eq!(
+        error("rwtexture<float3> image : 0;"),
+        "Storage textures are of type \"float3\" but can only be of type \"float\", \"float2\", \"float4\" or \"uint\""
+    );
+    assert_eq!(
+        error("rwtexture<uint, rgba8> image : 0;"),
+        "Format \"rgba8\" can't hold texels of type \"uint\""
+    );
+    assert_eq!(
+        error("rwtexture<float, rgba> image : 0;"),
+        "Format \"rgba\" can't hold texels of type \"float\""
+    );
+    assert_eq!(
+        error(&kernel(
+            IMAGE,
+            "    let coordinates = uint2(index, index);\n    store(image, coordinates, 1.0);"
+        )),
+        "Function \"store\" expects parameter 3 to be of type \"float4\", instead found type \"float\""
+    );
+    assert_eq!(
+        error(&kernel(IMAGE, "    store(image, index, 1.0);")),
+        "Function \"store\" expects parameter 2 to be of type \"uint2\", instead found type \"uint\""
+    );
+    assert_eq!(
+        error(&kernel(
+            IMAGE,
+            "    let coordinates = uint2(index, index);\n    store(coordinates, coordinates, coordinates);"
+        )),
+        "Function \"store\" expects parameter 1 to be of type \"rwtexture\", instead found type \"uint2\""
+    );
+    assert_eq!(
+        error(&kernel(
+            IMAGE,
+            "    let coordinates = uint2(index, index);\n    let texel = load(image);"
+        )),
+        "Function \"load\" expects 2 parameters, only 1 provided"
+    );
+    assert_eq!(
+        error("rwtexture<float> first : 0;\nrwtexture<float> second : 0;"),
+        "Reusing storage texture slot 0"
+    );
+}
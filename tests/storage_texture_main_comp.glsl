@@ -0,0 +1,28 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(local_size_x = 8, local_size_y = 8, local_size_z = 1) in;
+
+struct ThreadId {
+    uvec3 dispatch_thread;
+    uvec3 group;
+    uvec3 group_thread;
+    uint group_index;
+};
+
+layout(rgba8, binding = 0) uniform image2D image;
+
+layout(r32f, binding = 1) uniform image2D heights;
+
+layout(r32ui, binding = 2) uniform uimage2D counts;
+
+void main() {
+    ThreadId id = ThreadId(gl_GlobalInvocationID, gl_WorkGroupID, gl_LocalInvocationID, gl_LocalInvocationIndex);
+
+    uvec2 coordinates = uvec2(id.dispatch_thread.x, id.dispatch_thread.y);
+    float height = imageLoad(heights, ivec2(coordinates)).x;
+    imageStore(image, ivec2(coordinates), (imageLoad(image, ivec2(coordinates)) * height));
+    imageStore(counts, ivec2(coordinates), uvec4((imageLoad(counts, ivec2(coordinates)).x + id.group_index)));
+}
+
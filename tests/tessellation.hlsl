@@ -0,0 +1,96 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct ControlPoint {
+    float4 position: POSITION;
+    float2 uv: TEXCOORD;
+};
+ControlPoint acsl_create_ControlPoint(float4 position, float2 uv) {
+    ControlPoint output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct PatchConstants {
+    float edges[3]: SV_TessFactor;
+    float inside: SV_InsideTessFactor;
+};
+PatchConstants acsl_create_PatchConstants(float3 edges, float inside) {
+    PatchConstants output;
+    output.edges[0] = edges.x;
+    output.edges[1] = edges.y;
+    output.edges[2] = edges.z;
+    output.inside = inside;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct Terrain {
+    float detail;
+    float height;
+};
+Terrain acsl_create_Terrain(float detail, float height) {
+    Terrain output;
+    output.detail = detail;
+    output.height = height;
+    return output;
+}
+
+cbuffer acsl_constant_buffer_0 : register(b0) {
+    Terrain terrain;
+}
+
+Texture2D<float4> heightmap : register(t0);
+SamplerState acsl_heightmap_sampler_state : register(s0);
+
+ControlPoint vertex_main(VertexInput vertex_input) {
+    return acsl_create_ControlPoint(vertex_input.position, vertex_input.uv);
+}
+
+[domain("tri")]
+[partitioning("integer")]
+[outputtopology("triangle_cw")]
+[outputcontrolpoints(3)]
+[patchconstantfunc("hull_constants")]
+ControlPoint hull_main(InputPatch<ControlPoint, 3> control_points, uint id : SV_OutputControlPointID) {
+    return control_points[id];
+}
+
+PatchConstants hull_constants(InputPatch<ControlPoint, 3> control_points) {
+    float detail = terrain.detail;
+    return acsl_create_PatchConstants(float3(detail, detail, detail), detail);
+}
+
+[domain("tri")]
+PixelInput domain_main(PatchConstants constants, float3 coordinates : SV_DomainLocation, const OutputPatch<ControlPoint, 3> control_points) {
+    float4 position = (((control_points[0u].position * coordinates.x) + (control_points[1u].position * coordinates.y)) + (control_points[2u].position * coordinates.z));
+    float2 uv = (((control_points[0u].uv * coordinates.x) + (control_points[1u].uv * coordinates.y)) + (control_points[2u].uv * coordinates.z));
+    float height = (heightmap.SampleLevel(acsl_heightmap_sampler_state, uv, 0.0).x * terrain.height);
+    return acsl_create_PixelInput((position + float4(0.0, height, 0.0, 0.0)), uv);
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    return heightmap.SampleLevel(acsl_heightmap_sampler_state, pixel_input.uv, 0.0);
+}
+
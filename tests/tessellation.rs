@@ -0,0 +1,239 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::error;
+
+fn terrain() -> String {
+    std::fs::read_to_string("tests/tessellation.acsl").unwrap()
+}
+
+// A pass-through pipeline subdividing quads
+const QUADS: &str = "
+struct Vertex {
+    position: float4 : SV_POSITION,
+}
+
+struct Constants {
+    edges: float4 : SV_TESS_FACTOR,
+    inside: float2 : SV_INSIDE_TESS_FACTOR,
+}
+
+fn vertex_main(vertex: Vertex) -> Vertex {
+    return vertex;
+}
+
+fn hull_main(control_points: patch<Vertex, 4>, id: uint) -> Vertex {
+    return control_points[id];
+}
+
+fn hull_constants(control_points: patch<Vertex, 4>) -> Constants {
+    return Constants {
+        edges: float4(4.0, 4.0, 4.0, 4.0),
+        inside: float2(4.0, 4.0),
+    };
+}
+
+fn domain_main(constants: Constants, coordinates: float2, control_points: patch<Vertex, 4>) -> Vertex {
+    let top = control_points[0].position * (1.0 - coordinates.x) + control_points[1].position * coordinates.x;
+    let bottom = control_points[3].position * (1.0 - coordinates.x) + control_points[2].position * coordinates.x;
+    return Vertex {
+        position: top * (1.0 - coordinates.y) + bottom * coordinates.y,
+    };
+}
+
+fn fragment_main(vertex: Vertex) -> float4 {
+    return float4(1.0, 1.0, 1.0, 1.0);
+}
+";
+
+#[test]
+fn tessellation_hlsl() {
+    let target_code = std::fs::read_to_string("tests/tessellation.hlsl").unwrap();
+
+    assert_eq!(acsl::compile_hlsl(terrain()).unwrap(), target_code);
+}
+
+#[test]
+fn tessellation_glsl() {
+    let glsl = Compiler::default().compile_glsl(terrain()).unwrap();
+
+    for (code, file) in [
+        (glsl.vertex(), "tests/tessellation_vertex.glsl"),
+        (glsl.tess_control(), "tests/tessellation_tesc.glsl"),
+        (glsl.tess_evaluation(), "tests/tessellation_tese.glsl"),
+        (glsl.fragment(), "tests/tessellation_frag.glsl"),
+    ] {
+        assert_eq!(code, std::fs::read_to_string(file).unwrap());
+    }
+    assert_eq!(glsl.geometry(), "");
+}
+
+#[test]
+fn tessellation_hlsl_stages() {
+    let hlsl = Compiler::default().compile_hlsl_stages(terrain()).unwrap();
+
+    // Both hull functions make up the hull shader
+    assert!(hlsl.vertex().contains("ControlPoint vertex_main("));
+    assert!(!hlsl.vertex().contains("hull_main"));
+    assert!(hlsl.hull().contains("[outputcontrolpoints(3)]\n"));
+    assert!(hlsl.hull().contains("PatchConstants hull_constants("));
+    assert!(!hlsl.hull().contains("Texture2D"));
+    assert!(hlsl
+        .domain()
+        .contains("[domain(\"tri\")]\nPixelInput domain_main("));
+    assert!(hlsl.domain().contains("Texture2D<float4> heightmap"));
+    assert!(hlsl.pixel().contains("float4 fragment_main("));
+    assert_eq!(hlsl.geometry(), "");
+}
+
+#[test]
+fn quad_domain() {
+    let hlsl = acsl::compile_hlsl(QUADS).unwrap();
+    assert!(hlsl.contains("[domain(\"quad\")]\n[partitioning(\"integer\")]"));
+    assert!(
+        hlsl.contains("float edges[4]: SV_TessFactor;\n    float inside[2]: SV_InsideTessFactor;")
+    );
+    assert!(hlsl.contains("float2 coordinates : SV_DomainLocation"));
+
+    let glsl = Compiler::default().compile_glsl(QUADS).unwrap();
+    assert!(glsl.tess_control().contains("layout(vertices = 4) out;\n"));
+    assert!(glsl
+        .tess_control()
+        .contains("gl_TessLevelInner[1] = acsl_constants.inside.y;\n"));
+    assert!(glsl
+        .tess_evaluation()
+        .contains("layout(quads, equal_spacing, cw) in;\n"));
+    assert!(glsl.tess_evaluation().contains(
+        "Constants acsl_constants = Constants(vec4(gl_TessLevelOuter[0], gl_TessLevelOuter[1], gl_TessLevelOuter[2], gl_TessLevelOuter[3]), vec2(gl_TessLevelInner[0], gl_TessLevelInner[1]));\n"
+    ));
+    assert!(glsl
+        .tess_evaluation()
+        .contains("domain_main(acsl_constants, gl_TessCoord.xy, acsl_control_points);\n"));
+}
+
+#[test]
+fn clip_space_after_tessellation() {
+    let compiler = Compiler::new(
+        CompileOptions::new()
+            .glsl_version(GlslVersion::Vulkan)
+            .flip_y(true),
+    );
+    let glsl = compiler.compile_glsl(QUADS).unwrap();
+
+    // Only the last stage before the rasterizer writes the clip space position
+    assert!(!glsl.vertex().contains("gl_Position.y = -gl_Position.y;"));
+    assert!(glsl
+        .tess_control()
+        .contains("gl_out[gl_InvocationID].gl_Position = acsl_output.position;\n"));
+    assert!(glsl
+        .tess_evaluation()
+        .contains("gl_Position = acsl_output.position;\n    gl_Position.y = -gl_Position.y;\n"));
+}
+
+#[test]
+fn unsupported_targets() {
+    let error = acsl::compile_wgsl(terrain()).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "WGSL does not support tessellation shaders (\"hull_main\", \"domain_main\")"
+    );
+
+    let error = acsl::compile_spirv(terrain()).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "The SPIR-V backend does not yet support tessellation shaders (\"hull_main\", \"domain_main\")"
+    );
+
+    let error = acsl::compile_msl(terrain()).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "The MSL backend does not yet support tessellation shaders (\"hull_main\", \"domain_main\")"
+    );
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Glsl330));
+    let error = compiler.compile_glsl(terrain()).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "GLSL 3.30 does not support tessellation shaders (\"hull_main\", \"domain_main\")"
+    );
+}
+
+#[test]
+fn missing_stages() {
+    let terrain = terrain();
+    let start = terrain.find("fn domain_main").unwrap();
+    let end = terrain.find("fn fragment_main").unwrap();
+    let code = format!("{}{}", &terrain[..start], &terrain[end..]);
+    assert_eq!(
+        error(&code),
+        "No \"domain_main()\" function defined, it is required by \"hull_main()\""
+    );
+}
+
+#[test]
+fn mismatched_stages() {
+    let code = terrain().replace(
+        "coordinates: float3, control_points: patch<ControlPoint, 3>",
+        "coordinates: float3, control_points: patch<VertexInput, 3>",
+    );
+    assert_eq!(
+        error(&code),
+        "The input of \"domain_main()\" must be the output of \"hull_main()\" (\"ControlPoint\"), instead it is \"VertexInput\""
+    );
+
+    let code = QUADS.replace("coordinates: float2,", "coordinates: float3,");
+    assert_eq!(
+        error(&code),
+        "The input of \"domain_main()\" must be the output of \"hull_constants()\" (\"float2\"), instead it is \"float3\""
+    );
+}
+
+#[test]
+fn invalid_entry_points() {
+    let code = terrain().replace(
+        "fn hull_main(control_points: patch<ControlPoint, 3>, id: uint) -> ControlPoint {\n    return control_points[id];",
+        "fn hull_main(control_points: patch<ControlPoint, 3>) -> ControlPoint {\n    return control_points[0];",
+    );
+    assert_eq!(
+        error(&code),
+        "\"hull_main()\" must be declared as \"fn hull_main(control_points: patch<T, n>, id: uint) -> U\""
+    );
+
+    let code = QUADS
+        .replace("edges: float4 :", "edges: float3 :")
+        .replace("float4(4.0, 4.0, 4.0, 4.0)", "float3(4.0, 4.0, 4.0)");
+    assert_eq!(
+        error(&code),
+        "\"Constants\" must have a \"float3\" \"SV_TESS_FACTOR\" and a \"float\" \"SV_INSIDE_TESS_FACTOR\" for triangles, or a \"float4\" and a \"float2\" for quads"
+    );
+
+    let code = terrain().replace(
+        "patch<ControlPoint, 3>) -> PatchConstants",
+        "patch<ControlPoint, 33>) -> PatchConstants",
+    );
+    assert_eq!(
+        error(&code),
+        "Patches hold 1 to 32 control points, found 33"
+    );
+}
+
+#[test]
+fn patches_outside_entry_points() {
+    let code = terrain().replace(
+        "fn fragment_main",
+        "fn first(control_points: patch<ControlPoint, 3>) -> ControlPoint {\n    return control_points[0];\n}\n\nfn fragment_main",
+    );
+    assert_eq!(
+        error(&code),
+        "Only the parameters of the stage entry points can be a \"patch<ControlPoint, 3>\", not \"first\""
+    );
+
+    let code = terrain().replace(
+        "    return control_points[id];",
+        "    let copy = control_points;\n    return copy[id];",
+    );
+    assert_eq!(
+        error(&code),
+        "Only the parameters of the stage entry points can be a \"patch<ControlPoint, 3>\", not \"copy\""
+    );
+}
@@ -0,0 +1,44 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec2 acsl_pixel_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct ControlPoint {
+    vec4 position;
+    vec2 uv;
+};
+
+struct PatchConstants {
+    vec3 edges;
+    float inside;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct Terrain {
+    float detail;
+    float height;
+};
+
+layout(location = 0) uniform Terrain terrain;
+
+layout(location = 32) uniform sampler2D heightmap;
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_uv);
+
+    acsl_fragment_color = textureLod(heightmap, pixel_input.uv, 0.0);
+    return;
+}
+
@@ -0,0 +1,65 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(vertices = 3) out;
+
+layout(location = 0) in vec4 acsl_hull_input_position[];
+layout(location = 1) in vec2 acsl_hull_input_uv[];
+
+layout(location = 0) out vec4 acsl_domain_input_position[];
+layout(location = 1) out vec2 acsl_domain_input_uv[];
+
+struct VertexInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct ControlPoint {
+    vec4 position;
+    vec2 uv;
+};
+
+struct PatchConstants {
+    vec3 edges;
+    float inside;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct Terrain {
+    float detail;
+    float height;
+};
+
+layout(location = 0) uniform Terrain terrain;
+
+layout(location = 32) uniform sampler2D heightmap;
+
+ControlPoint hull_main(ControlPoint[3] control_points, uint id) {
+    return control_points[id];
+}
+
+PatchConstants hull_constants(ControlPoint[3] control_points) {
+    float detail = terrain.detail;
+    return PatchConstants(vec3(detail, detail, detail), detail);
+}
+
+void main() {
+    ControlPoint acsl_control_points[3] = ControlPoint[3](ControlPoint(acsl_hull_input_position[0], acsl_hull_input_uv[0]), ControlPoint(acsl_hull_input_position[1], acsl_hull_input_uv[1]), ControlPoint(acsl_hull_input_position[2], acsl_hull_input_uv[2]));
+    ControlPoint acsl_output = hull_main(acsl_control_points, uint(gl_InvocationID));
+    acsl_domain_input_position[gl_InvocationID] = acsl_output.position;
+    acsl_domain_input_uv[gl_InvocationID] = acsl_output.uv;
+
+    if (gl_InvocationID == 0) {
+        PatchConstants acsl_constants = hull_constants(acsl_control_points);
+        gl_TessLevelOuter[0] = acsl_constants.edges.x;
+        gl_TessLevelOuter[1] = acsl_constants.edges.y;
+        gl_TessLevelOuter[2] = acsl_constants.edges.z;
+        gl_TessLevelInner[0] = acsl_constants.inside;
+    }
+}
+
@@ -0,0 +1,55 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout(triangles, equal_spacing, cw) in;
+
+layout(location = 0) in vec4 acsl_domain_input_position[];
+layout(location = 1) in vec2 acsl_domain_input_uv[];
+
+layout(location = 1) out vec2 acsl_pixel_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct ControlPoint {
+    vec4 position;
+    vec2 uv;
+};
+
+struct PatchConstants {
+    vec3 edges;
+    float inside;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct Terrain {
+    float detail;
+    float height;
+};
+
+layout(location = 0) uniform Terrain terrain;
+
+layout(location = 32) uniform sampler2D heightmap;
+
+PixelInput domain_main(PatchConstants constants, vec3 coordinates, ControlPoint[3] control_points) {
+    vec4 position = (((control_points[0u].position * coordinates.x) + (control_points[1u].position * coordinates.y)) + (control_points[2u].position * coordinates.z));
+    vec2 uv = (((control_points[0u].uv * coordinates.x) + (control_points[1u].uv * coordinates.y)) + (control_points[2u].uv * coordinates.z));
+    float height = (textureLod(heightmap, uv, 0.0).x * terrain.height);
+    return PixelInput((position + vec4(0.0, height, 0.0, 0.0)), uv);
+}
+
+void main() {
+    PatchConstants acsl_constants = PatchConstants(vec3(gl_TessLevelOuter[0], gl_TessLevelOuter[1], gl_TessLevelOuter[2]), gl_TessLevelInner[0]);
+    ControlPoint acsl_control_points[3] = ControlPoint[3](ControlPoint(acsl_domain_input_position[0], acsl_domain_input_uv[0]), ControlPoint(acsl_domain_input_position[1], acsl_domain_input_uv[1]), ControlPoint(acsl_domain_input_position[2], acsl_domain_input_uv[2]));
+    PixelInput acsl_output = domain_main(acsl_constants, gl_TessCoord, acsl_control_points);
+    gl_Position = acsl_output.position;
+    acsl_pixel_input_uv = acsl_output.uv;
+}
+
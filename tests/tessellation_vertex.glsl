@@ -0,0 +1,51 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in vec2 acsl_vertex_input_uv;
+
+layout(location = 0) out vec4 acsl_hull_input_position;
+layout(location = 1) out vec2 acsl_hull_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct ControlPoint {
+    vec4 position;
+    vec2 uv;
+};
+
+struct PatchConstants {
+    vec3 edges;
+    float inside;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct Terrain {
+    float detail;
+    float height;
+};
+
+layout(location = 0) uniform Terrain terrain;
+
+layout(location = 32) uniform sampler2D heightmap;
+
+ControlPoint vertex_main(VertexInput vertex_input) {
+    return ControlPoint(vertex_input.position, vertex_input.uv);
+}
+
+
+void main() {
+    VertexInput acsl_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_uv);
+    ControlPoint acsl_output = vertex_main(acsl_input);
+    acsl_hull_input_position = acsl_output.position;
+    acsl_hull_input_uv = acsl_output.uv;
+}
+
@@ -41,7 +41,7 @@ cbuffer acsl_constant_buffer_0 : register(b0) {
     MatrixBuffer matrix_buffer;
 }
 
-Texture2D tex : register(t0);
+Texture2D<float4> tex : register(t0);
 SamplerState acsl_tex_sampler_state : register(s0);
 
 PixelInput vertex_main(VertexInput vertex_input) {
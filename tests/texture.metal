@@ -0,0 +1,32 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct MatrixBuffer {
+    float4x4 object;
+    float4x4 view;
+};
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float4 color [[attribute(1)]];
+    float2 uv [[attribute(2)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float4 color;
+    float2 uv;
+};
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]], constant MatrixBuffer& matrix_buffer [[buffer(0)]]) {
+    float4 output_position = (vertex_input.position * (matrix_buffer.object * matrix_buffer.view));
+    return PixelInput{output_position, vertex_input.color, vertex_input.uv};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]], texture2d<float> tex [[texture(0)]], sampler acsl_tex_sampler_state [[sampler(0)]]) {
+    return (pixel_input.color * tex.sample(acsl_tex_sampler_state, pixel_input.uv));
+}
+
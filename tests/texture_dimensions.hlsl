@@ -0,0 +1,59 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+Texture1D<float4> gradient : register(t0);
+SamplerState acsl_gradient_sampler_state : register(s0);
+
+Texture2D<float4> albedo : register(t1);
+SamplerState acsl_albedo_sampler_state : register(s1);
+
+Texture3D<float4> volume : register(t2);
+SamplerState acsl_volume_sampler_state : register(s2);
+
+TextureCube<float4> environment : register(t3);
+SamplerState acsl_environment_sampler_state : register(s3);
+
+Texture2DArray<float4> layers : register(t4);
+SamplerState acsl_layers_sampler_state : register(s4);
+
+TextureCubeArray<float4> probes : register(t5);
+SamplerState acsl_probes_sampler_state : register(s5);
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(vertex_input.position, vertex_input.uv);
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    float u = pixel_input.uv.x;
+    float3 direction = float3(pixel_input.uv.x, pixel_input.uv.y, 1.0);
+    float4 color = (gradient.Sample(acsl_gradient_sampler_state, u) * albedo.Sample(acsl_albedo_sampler_state, pixel_input.uv));
+    float4 fog = volume.Sample(acsl_volume_sampler_state, direction);
+    float4 reflection = environment.Sample(acsl_environment_sampler_state, direction);
+    float3 layer = float3(pixel_input.uv.x, pixel_input.uv.y, 2.0);
+    float4 detail = layers.Sample(acsl_layers_sampler_state, layer);
+    float4 probe_direction = float4(direction.x, direction.y, direction.z, 1.0);
+    float4 probe = probes.Sample(acsl_probes_sampler_state, probe_direction);
+    return ((((color + fog) + reflection) + detail) + probe);
+}
+
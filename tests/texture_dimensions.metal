@@ -0,0 +1,33 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float2 uv [[attribute(1)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float2 uv;
+};
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    return PixelInput{vertex_input.position, vertex_input.uv};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]], texture1d<float> gradient [[texture(0)]], sampler acsl_gradient_sampler_state [[sampler(0)]], texture2d<float> albedo [[texture(1)]], sampler acsl_albedo_sampler_state [[sampler(1)]], texture3d<float> volume [[texture(2)]], sampler acsl_volume_sampler_state [[sampler(2)]], texturecube<float> environment [[texture(3)]], sampler acsl_environment_sampler_state [[sampler(3)]], texture2d_array<float> layers [[texture(4)]], sampler acsl_layers_sampler_state [[sampler(4)]], texturecube_array<float> probes [[texture(5)]], sampler acsl_probes_sampler_state [[sampler(5)]]) {
+    float u = pixel_input.uv.x;
+    float3 direction = float3(pixel_input.uv.x, pixel_input.uv.y, 1.0);
+    float4 color = (gradient.sample(acsl_gradient_sampler_state, u) * albedo.sample(acsl_albedo_sampler_state, pixel_input.uv));
+    float4 fog = volume.sample(acsl_volume_sampler_state, direction);
+    float4 reflection = environment.sample(acsl_environment_sampler_state, direction);
+    float3 layer = float3(pixel_input.uv.x, pixel_input.uv.y, 2.0);
+    float4 detail = layers.sample(acsl_layers_sampler_state, layer.xy, uint(round(layer.z)));
+    float4 probe_direction = float4(direction.x, direction.y, direction.z, 1.0);
+    float4 probe = probes.sample(acsl_probes_sampler_state, probe_direction.xyz, uint(round(probe_direction.w)));
+    return ((((color + fog) + reflection) + detail) + probe);
+}
+
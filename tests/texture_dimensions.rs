@@ -0,0 +1,88 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::{error, fragment};
+
+#[test]
+fn texture_dimensions_load() {
+    let code = fragment(
+        "texture1d gradient : 0;\ntexture3d volume : 1;\ntexture2d_array layers : 2;",
+        "    let x = float_to_uint(position.x);\n    let y = float_to_uint(position.y);\n    let a = load(gradient, x);\n    let b = load(volume, uint3(x, y, y), x);\n    let c = load(layers, uint3(x, y, x));\n    return a + b + c;",
+    );
+
+    // Loads return the element type, at mip level 0 unless given
+    let hlsl = acsl::compile_hlsl(&code).unwrap();
+    assert!(hlsl.contains("float4 a = gradient.Load(uint2(x, 0));\n"));
+    assert!(hlsl.contains("float4 b = volume.Load(uint4(uint3(x, y, y), x));\n"));
+
+    let (_, fragment_shader) = acsl::compile_glsl(&code).unwrap();
+    assert!(fragment_shader.contains("vec4 a = texelFetch(gradient, int(x), 0);\n"));
+    assert!(fragment_shader.contains("texelFetch(layers, ivec3(uvec3(x, y, x)), 0);\n"));
+
+    // WGSL and MSL take the mip level and array layer separately
+    let wgsl = acsl::compile_wgsl(&code).unwrap();
+    assert!(wgsl.contains("textureLoad(gradient, i32(x), 0);\n"));
+    assert!(wgsl.contains(
+        "textureLoad(layers, vec2<i32>(vec3<u32>(x, y, x).xy), i32(vec3<u32>(x, y, x).z), 0);\n"
+    ));
+
+    let msl = acsl::compile_msl(&code).unwrap();
+    assert!(msl.contains("float4 a = gradient.read(x);\n"));
+    assert!(msl.contains("float4 b = volume.read(uint3(x, y, y), x);\n"));
+}
+
+#[test]
+fn invalid_texture_coordinates() {
+    assert_eq!(
+        error(&fragment(
+            "texture3d volume : 0;",
+            "    return sample_texture(volume, uv);"
+        )),
+        "Function \"sample_texture\" expects parameter 2 to be of type \"float3\", instead found type \"float2\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture1d gradient : 0;",
+            "    let texel = load(gradient, uv.x);\n    return position;"
+        )),
+        "Function \"load\" expects parameter 2 to be of type \"uint\", instead found type \"float\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture_cube environment : 0;",
+            "    let texel = load(environment, position);\n    return position;"
+        )),
+        "\"load\" can't be used on textures of type \"texture_cube\""
+    );
+}
+
+#[test]
+fn unsupported_texture_dimensions() {
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Glsl330));
+    let error = compiler
+        .compile_glsl(fragment(
+            "texture_cube_array probes : 0;",
+            "    return sample_texture(probes, position);",
+        ))
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL 3.30 does not support cube map arrays (\"texture_cube_array\")"
+    );
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Essl300));
+    let error = compiler
+        .compile_glsl(fragment(
+            "texture1d gradient : 0;",
+            "    return sample_texture(gradient, uv.x);",
+        ))
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL ES 3.00 does not support 1D textures (\"texture1d\")"
+    );
+}
@@ -0,0 +1,49 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+@group(0) @binding(32) var gradient: texture_1d<f32>;
+@group(0) @binding(40) var acsl_gradient_sampler_state: sampler;
+
+@group(0) @binding(33) var albedo: texture_2d<f32>;
+@group(0) @binding(41) var acsl_albedo_sampler_state: sampler;
+
+@group(0) @binding(34) var volume: texture_3d<f32>;
+@group(0) @binding(42) var acsl_volume_sampler_state: sampler;
+
+@group(0) @binding(35) var environment: texture_cube<f32>;
+@group(0) @binding(43) var acsl_environment_sampler_state: sampler;
+
+@group(0) @binding(36) var layers: texture_2d_array<f32>;
+@group(0) @binding(44) var acsl_layers_sampler_state: sampler;
+
+@group(0) @binding(37) var probes: texture_cube_array<f32>;
+@group(0) @binding(45) var acsl_probes_sampler_state: sampler;
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput(vertex_input.position, vertex_input.uv);
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    var u: f32 = pixel_input.uv.x;
+    var direction: vec3<f32> = vec3<f32>(pixel_input.uv.x, pixel_input.uv.y, 1.0);
+    var color: vec4<f32> = (textureSample(gradient, acsl_gradient_sampler_state, u) * textureSample(albedo, acsl_albedo_sampler_state, pixel_input.uv));
+    var fog: vec4<f32> = textureSample(volume, acsl_volume_sampler_state, direction);
+    var reflection: vec4<f32> = textureSample(environment, acsl_environment_sampler_state, direction);
+    var layer: vec3<f32> = vec3<f32>(pixel_input.uv.x, pixel_input.uv.y, 2.0);
+    var detail: vec4<f32> = textureSample(layers, acsl_layers_sampler_state, layer.xy, i32(round(layer.z)));
+    var probe_direction: vec4<f32> = vec4<f32>(direction.x, direction.y, direction.z, 1.0);
+    var probe: vec4<f32> = textureSample(probes, acsl_probes_sampler_state, probe_direction.xyz, i32(round(probe_direction.w)));
+    return ((((color + fog) + reflection) + detail) + probe);
+}
+
@@ -0,0 +1,47 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec2 acsl_pixel_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+};
+
+layout(location = 32) uniform sampler1D gradient;
+
+layout(location = 33) uniform sampler2D albedo;
+
+layout(location = 34) uniform sampler3D volume;
+
+layout(location = 35) uniform samplerCube environment;
+
+layout(location = 36) uniform sampler2DArray layers;
+
+layout(location = 37) uniform samplerCubeArray probes;
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_uv);
+
+    float u = pixel_input.uv.x;
+    vec3 direction = vec3(pixel_input.uv.x, pixel_input.uv.y, 1.0);
+    vec4 color = (texture(gradient, u) * texture(albedo, pixel_input.uv));
+    vec4 fog = texture(volume, direction);
+    vec4 reflection = texture(environment, direction);
+    vec3 layer = vec3(pixel_input.uv.x, pixel_input.uv.y, 2.0);
+    vec4 detail = texture(layers, layer);
+    vec4 probe_direction = vec4(direction.x, direction.y, direction.z, 1.0);
+    vec4 probe = texture(probes, probe_direction);
+    acsl_fragment_color = ((((color + fog) + reflection) + detail) + probe);
+    return;
+}
+
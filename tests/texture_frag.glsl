@@ -4,9 +4,8 @@
 
 out vec4 acsl_fragment_color;
 
-in vec4 acsl_pixel_input_position;
-in vec4 acsl_pixel_input_color;
-in vec2 acsl_pixel_input_uv;
+layout(location = 1) in vec4 acsl_pixel_input_color;
+layout(location = 2) in vec2 acsl_pixel_input_uv;
 
 struct MatrixBuffer {
     mat4x4 object;
@@ -31,7 +30,7 @@ layout(location = 32) uniform sampler2D tex;
 
 
 void main() {
-    PixelInput pixel_input = PixelInput(acsl_pixel_input_position, acsl_pixel_input_color, acsl_pixel_input_uv);
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_color, acsl_pixel_input_uv);
 
     acsl_fragment_color = (pixel_input.color * texture(tex, pixel_input.uv));
     return;
@@ -0,0 +1,69 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.uv = uv;
+    return output;
+}
+
+Texture2D<float4> albedo : register(t0);
+SamplerState acsl_albedo_sampler_state : register(s0);
+uint2 acsl_albedo_size() {
+    uint width, height;
+    albedo.GetDimensions(width, height);
+    return uint2(width, height);
+}
+
+Texture3D<float4> volume : register(t1);
+SamplerState acsl_volume_sampler_state : register(s1);
+
+Texture2D<uint> ids : register(t2);
+SamplerState acsl_ids_sampler_state : register(s2);
+
+Texture2D<float4> detail : register(t3);
+SamplerState acsl_detail_sampler_state : register(s3);
+
+Texture3D<float> density : register(t4);
+SamplerState acsl_density_sampler_state : register(s4);
+
+Texture2D<float2> flow : register(t5);
+SamplerState acsl_flow_sampler_state : register(s5);
+
+SamplerState clamped : register(s8);
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    return acsl_create_PixelInput(vertex_input.position, vertex_input.uv);
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    float2 uv = pixel_input.uv;
+    float3 direction = float3(uv.x, uv.y, 0.5);
+    float4 level = albedo.SampleLevel(acsl_albedo_sampler_state, uv, 2.0);
+    float4 biased = detail.SampleBias(clamped, uv, (0.0 - 1.0));
+    float4 graded = volume.SampleGrad(acsl_volume_sampler_state, direction, float3(0.5, 0.0, 0.0), float3(0.0, 0.5, 0.0));
+    float4 shifted = detail.Sample(clamped, uv, int2(1, -2));
+    float4 reds = albedo.Gather(acsl_albedo_sampler_state, uv);
+    float thickness = density.SampleLevel(acsl_density_sampler_state, direction, 0.0);
+    float2 motion = flow.Sample(clamped, uv);
+    uint2 size = acsl_albedo_size();
+    uint2 texel = uint2(size.x, uint(uv.y));
+    uint id = ids.Load(uint3(texel, uint(1.0)));
+    return (((((level + biased) + graded) + shifted) + ((reds * float(id)) * thickness)) + float4(motion.x, motion.y, 0.0, 0.0));
+}
+
@@ -0,0 +1,38 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float2 uv [[attribute(1)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float2 uv;
+};
+
+constexpr sampler clamped(filter::linear, mip_filter::linear, address::clamp_to_edge);
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    return PixelInput{vertex_input.position, vertex_input.uv};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]], texture2d<float> albedo [[texture(0)]], sampler acsl_albedo_sampler_state [[sampler(0)]], texture3d<float> volume [[texture(1)]], sampler acsl_volume_sampler_state [[sampler(1)]], texture2d<uint> ids [[texture(2)]], sampler acsl_ids_sampler_state [[sampler(2)]], texture2d<float> detail [[texture(3)]], sampler acsl_detail_sampler_state [[sampler(3)]], texture3d<float> density [[texture(4)]], sampler acsl_density_sampler_state [[sampler(4)]], texture2d<float> flow [[texture(5)]], sampler acsl_flow_sampler_state [[sampler(5)]]) {
+    float2 uv = pixel_input.uv;
+    float3 direction = float3(uv.x, uv.y, 0.5);
+    float4 acsl_level = albedo.sample(acsl_albedo_sampler_state, uv, level(2.0));
+    float4 biased = detail.sample(clamped, uv, bias((0.0 - 1.0)));
+    float4 graded = volume.sample(acsl_volume_sampler_state, direction, gradient3d(float3(0.5, 0.0, 0.0), float3(0.0, 0.5, 0.0)));
+    float4 shifted = detail.sample(clamped, uv, int2(1, -2));
+    float4 reds = albedo.gather(acsl_albedo_sampler_state, uv);
+    float thickness = density.sample(acsl_density_sampler_state, direction, level(0.0)).x;
+    float2 motion = flow.sample(clamped, uv).xy;
+    uint2 size = uint2(albedo.get_width(), albedo.get_height());
+    uint2 texel = uint2(size.x, uint(uv.y));
+    uint id = ids.read(texel, uint(1.0)).x;
+    return (((((acsl_level + biased) + graded) + shifted) + ((reds * float(id)) * thickness)) + float4(motion.x, motion.y, 0.0, 0.0));
+}
+
@@ -0,0 +1,107 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::{error, fragment};
+
+#[test]
+fn sample_compare() {
+    let code = fragment(
+        "texture shadow_map : 0;\nsampler shadow : 0 { compare: less_equal };",
+        "    let lit = sample_compare(shadow_map, shadow, uv, 0.5);\n    return float4(lit, lit, lit, 1.0);",
+    );
+
+    let hlsl = acsl::compile_hlsl(&code).unwrap();
+    assert!(hlsl.contains("SamplerComparisonState shadow : register(s8);\n"));
+    assert!(hlsl.contains("float lit = shadow_map.SampleCmp(shadow, uv, 0.5);\n"));
+
+    // The reference is folded into the coordinates in GLSL
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    let glsl = compiler.compile_glsl(&code).unwrap();
+    assert!(glsl
+        .fragment()
+        .contains("layout(set = 2, binding = 8) uniform samplerShadow shadow;\n"));
+    assert!(glsl
+        .fragment()
+        .contains("float lit = texture(sampler2DShadow(shadow_map, shadow), vec3(uv, 0.5));\n"));
+
+    let disassembly = acsl::disassemble_spirv(&acsl::compile_spirv(&code).unwrap());
+    assert!(disassembly.contains("OpImageSampleDrefImplicitLod"));
+
+    let reflection = acsl::reflect(&code).unwrap();
+    assert_eq!(reflection.samplers()[0].compare(), Some("less_equal"));
+}
+
+#[test]
+fn unsupported_texture_functions() {
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Glsl330));
+    let error = compiler
+        .compile_glsl(fragment(
+            "texture albedo : 0;",
+            "    return gather(albedo, uv);",
+        ))
+        .err()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        error,
+        "GLSL 3.30 does not support texture gathers (\"gather\")"
+    );
+
+    let error = acsl::compile_wgsl(fragment(
+        "texture shadow_map : 0;\nsampler shadow : 0 { compare: less };",
+        "    let lit = sample_compare(shadow_map, shadow, uv, 0.5);\n    return float4(lit, lit, lit, 1.0);",
+    ))
+    .err()
+    .unwrap()
+    .to_string();
+    assert_eq!(
+        error,
+        "WGSL does not support comparison sampling of color textures (\"sample_compare\")"
+    );
+}
+
+#[test]
+fn invalid_texture_functions() {
+    assert_eq!(
+        error(&fragment(
+            "texture<uint> ids : 0;",
+            "    return sample_level(ids, uv, 0.0);"
+        )),
+        "\"sample_level\" can't be used on textures of type \"texture<uint>\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture3d volume : 0;",
+            "    return gather(volume, float3(uv.x, uv.y, 0.0));"
+        )),
+        "\"gather\" can't be used on textures of type \"texture3d\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture albedo : 0;",
+            "    return sample_offset(albedo, uv, float2(8.0, 0.5));"
+        )),
+        "Texture offsets must be built from literals with whole values between -8 and 7, found \"float2(8, 0.5)\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture albedo : 0;\nsampler smooth : 0;",
+            "    let lit = sample_compare(albedo, smooth, uv, 0.5);\n    return float4(lit, lit, lit, 1.0);"
+        )),
+        "Function \"sample_compare\" expects parameter 2 to be of type \"sampler_comparison\", instead found type \"sampler\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture<uint> ids : 0;",
+            "    let texel = load(ids, uint2(float_to_uint(uv.x), float_to_uint(uv.y)));\n    return float4(texel, texel, texel, 1.0);"
+        )),
+        "Function \"float4\" expects parameter 1 to be of type \"float\", instead found type \"uint\""
+    );
+    assert_eq!(
+        error(&fragment(
+            "texture albedo : 0;",
+            "    return sample_level(uv, uv, 0.0);"
+        )),
+        "Function \"sample_level\" expects parameter 1 to be of type \"texture\", instead found type \"float2\""
+    );
+}
@@ -0,0 +1,54 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+@group(0) @binding(32) var albedo: texture_2d<f32>;
+@group(0) @binding(40) var acsl_albedo_sampler_state: sampler;
+
+@group(0) @binding(33) var volume: texture_3d<f32>;
+@group(0) @binding(41) var acsl_volume_sampler_state: sampler;
+
+@group(0) @binding(34) var ids: texture_2d<u32>;
+@group(0) @binding(42) var acsl_ids_sampler_state: sampler;
+
+@group(0) @binding(35) var detail: texture_2d<f32>;
+@group(0) @binding(43) var acsl_detail_sampler_state: sampler;
+
+@group(0) @binding(36) var density: texture_3d<f32>;
+@group(0) @binding(44) var acsl_density_sampler_state: sampler;
+
+@group(0) @binding(37) var flow: texture_2d<f32>;
+@group(0) @binding(45) var acsl_flow_sampler_state: sampler;
+
+@group(0) @binding(48) var clamped: sampler;
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput(vertex_input.position, vertex_input.uv);
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    var uv: vec2<f32> = pixel_input.uv;
+    var direction: vec3<f32> = vec3<f32>(uv.x, uv.y, 0.5);
+    var level: vec4<f32> = textureSampleLevel(albedo, acsl_albedo_sampler_state, uv, 2.0);
+    var biased: vec4<f32> = textureSampleBias(detail, clamped, uv, (0.0 - 1.0));
+    var graded: vec4<f32> = textureSampleGrad(volume, acsl_volume_sampler_state, direction, vec3<f32>(0.5, 0.0, 0.0), vec3<f32>(0.0, 0.5, 0.0));
+    var shifted: vec4<f32> = textureSample(detail, clamped, uv, vec2<i32>(1, -2));
+    var reds: vec4<f32> = textureGather(0, albedo, acsl_albedo_sampler_state, uv);
+    var thickness: f32 = textureSampleLevel(density, acsl_density_sampler_state, direction, 0.0).x;
+    var motion: vec2<f32> = textureSample(flow, clamped, uv).xy;
+    var size: vec2<u32> = textureDimensions(albedo);
+    var texel: vec2<u32> = vec2<u32>(size.x, u32(uv.y));
+    var id: u32 = textureLoad(ids, vec2<i32>(texel), i32(u32(1.0))).x;
+    return (((((level + biased) + graded) + shifted) + ((reds * f32(id)) * thickness)) + vec4<f32>(motion.x, motion.y, 0.0, 0.0));
+}
+
@@ -0,0 +1,50 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec2 acsl_pixel_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+};
+
+layout(location = 32) uniform sampler2D albedo;
+
+layout(location = 33) uniform sampler3D volume;
+
+layout(location = 34) uniform usampler2D ids;
+
+layout(location = 35) uniform sampler2D detail;
+
+layout(location = 36) uniform sampler3D density;
+
+layout(location = 37) uniform sampler2D flow;
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_uv);
+
+    vec2 uv = pixel_input.uv;
+    vec3 direction = vec3(uv.x, uv.y, 0.5);
+    vec4 level = textureLod(albedo, uv, 2.0);
+    vec4 biased = texture(detail, uv, (0.0 - 1.0));
+    vec4 graded = textureGrad(volume, direction, vec3(0.5, 0.0, 0.0), vec3(0.0, 0.5, 0.0));
+    vec4 shifted = textureOffset(detail, uv, ivec2(1, -2));
+    vec4 reds = textureGather(albedo, uv);
+    float thickness = textureLod(density, direction, 0.0).x;
+    vec2 motion = texture(flow, uv).xy;
+    uvec2 size = uvec2(textureSize(albedo, 0));
+    uvec2 texel = uvec2(size.x, uint(uv.y));
+    uint id = texelFetch(ids, ivec2(texel), int(uint(1.0))).x;
+    acsl_fragment_color = (((((level + biased) + graded) + shifted) + ((reds * float(id)) * thickness)) + vec4(motion.x, motion.y, 0.0, 0.0));
+    return;
+}
+
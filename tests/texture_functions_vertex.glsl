@@ -0,0 +1,41 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 1) in vec2 acsl_vertex_input_uv;
+
+layout(location = 1) out vec2 acsl_pixel_input_uv;
+
+struct VertexInput {
+    vec4 position;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+};
+
+layout(location = 32) uniform sampler2D albedo;
+
+layout(location = 33) uniform sampler3D volume;
+
+layout(location = 34) uniform usampler2D ids;
+
+layout(location = 35) uniform sampler2D detail;
+
+layout(location = 36) uniform sampler3D density;
+
+layout(location = 37) uniform sampler2D flow;
+
+void main() {
+    VertexInput vertex_input = VertexInput(acsl_vertex_input_position, acsl_vertex_input_uv);
+
+    PixelInput acsl_vertex_output = PixelInput(vertex_input.position, vertex_input.uv);
+    acsl_pixel_input_uv = acsl_vertex_output.uv;
+    gl_Position = acsl_vertex_output.position;
+    return;
+}
+
+
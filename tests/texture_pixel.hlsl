@@ -0,0 +1,22 @@
+// Generated from Alexandria Common Shader Language
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float4 color: COLOR;
+    float2 uv: TEXCOORD;
+};
+PixelInput acsl_create_PixelInput(float4 position, float4 color, float2 uv) {
+    PixelInput output;
+    output.position = position;
+    output.color = color;
+    output.uv = uv;
+    return output;
+}
+
+Texture2D<float4> tex : register(t0);
+SamplerState acsl_tex_sampler_state : register(s0);
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    return (pixel_input.color * tex.Sample(acsl_tex_sampler_state, pixel_input.uv));
+}
+
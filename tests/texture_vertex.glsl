@@ -6,9 +6,8 @@ layout (location = 0) in vec4 acsl_vertex_input_position;
 layout (location = 1) in vec4 acsl_vertex_input_color;
 layout (location = 2) in vec2 acsl_vertex_input_uv;
 
-out vec4 acsl_pixel_input_position;
-out vec4 acsl_pixel_input_color;
-out vec2 acsl_pixel_input_uv;
+layout(location = 1) out vec4 acsl_pixel_input_color;
+layout(location = 2) out vec2 acsl_pixel_input_uv;
 
 struct MatrixBuffer {
     mat4x4 object;
@@ -36,10 +35,9 @@ void main() {
 
     vec4 output_position = (vertex_input.position * (matrix_buffer.object * matrix_buffer.view));
     PixelInput acsl_vertex_output = PixelInput(output_position, vertex_input.color, vertex_input.uv);
-    acsl_pixel_input_position = acsl_vertex_output.position;
     acsl_pixel_input_color = acsl_vertex_output.color;
     acsl_pixel_input_uv = acsl_vertex_output.uv;
-    gl_Position = acsl_pixel_input_position;
+    gl_Position = acsl_vertex_output.position;
     return;
 }
 
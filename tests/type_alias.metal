@@ -0,0 +1,22 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+};
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]]) {
+    return PixelInput{vertex_input.position};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]]) {
+    return float4(1.0, 1.0, 1.0, 1.0);
+}
+
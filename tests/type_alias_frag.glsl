@@ -4,7 +4,6 @@
 
 out vec4 acsl_fragment_color;
 
-in vec4 acsl_pixel_input_position;
 
 struct VertexInput {
     vec4 position;
@@ -16,7 +15,7 @@ struct PixelInput {
 
 
 void main() {
-    PixelInput pixel_input = PixelInput(acsl_pixel_input_position);
+    PixelInput pixel_input = PixelInput(gl_FragCoord);
 
     acsl_fragment_color = vec4(1.0, 1.0, 1.0, 1.0);
     return;
@@ -0,0 +1,30 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct MatrixBuffer {
+    float4x4 object;
+    float4x4 view;
+};
+
+struct VertexInput {
+    float4 position [[attribute(0)]];
+    float4 color [[attribute(1)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float4 color;
+};
+
+vertex PixelInput vertex_main(VertexInput vertex_input [[stage_in]], constant MatrixBuffer& matrix_buffer [[buffer(0)]]) {
+    float4 output_position = (vertex_input.position * (matrix_buffer.object * matrix_buffer.view));
+    return PixelInput{output_position, vertex_input.color};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]]) {
+    return pixel_input.color;
+}
+
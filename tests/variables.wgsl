@@ -0,0 +1,30 @@
+// Generated from Alexandria Common Shader Language
+
+struct MatrixBuffer {
+    object: mat4x4<f32>,
+    view: mat4x4<f32>,
+}
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> matrix_buffer: MatrixBuffer;
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    var output_position: vec4<f32> = (vertex_input.position * (matrix_buffer.object * matrix_buffer.view));
+    return PixelInput(output_position, vertex_input.color);
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    return pixel_input.color;
+}
+
@@ -4,8 +4,7 @@
 
 out vec4 acsl_fragment_color;
 
-in vec4 acsl_pixel_input_position;
-in vec4 acsl_pixel_input_color;
+layout(location = 1) in vec4 acsl_pixel_input_color;
 
 struct MatrixBuffer {
     mat4x4 object;
@@ -26,7 +25,7 @@ layout(location = 0) uniform MatrixBuffer matrix_buffer;
 
 
 void main() {
-    PixelInput pixel_input = PixelInput(acsl_pixel_input_position, acsl_pixel_input_color);
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_color);
 
     acsl_fragment_color = pixel_input.color;
     return;
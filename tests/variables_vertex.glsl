@@ -5,8 +5,7 @@
 layout (location = 0) in vec4 acsl_vertex_input_position;
 layout (location = 1) in vec4 acsl_vertex_input_color;
 
-out vec4 acsl_pixel_input_position;
-out vec4 acsl_pixel_input_color;
+layout(location = 1) out vec4 acsl_pixel_input_color;
 
 struct MatrixBuffer {
     mat4x4 object;
@@ -30,9 +29,8 @@ void main() {
 
     vec4 output_position = (vertex_input.position * (matrix_buffer.object * matrix_buffer.view));
     PixelInput acsl_vertex_output = PixelInput(output_position, vertex_input.color);
-    acsl_pixel_input_position = acsl_vertex_output.position;
     acsl_pixel_input_color = acsl_vertex_output.color;
-    gl_Position = acsl_pixel_input_position;
+    gl_Position = acsl_vertex_output.position;
     return;
 }
 
@@ -0,0 +1,41 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    float4 position: POSITION;
+    uint vertex_id: SV_VertexID;
+    uint instance_id: SV_InstanceID;
+    float2 uv: TEXCOORD;
+};
+VertexInput acsl_create_VertexInput(float4 position, uint vertex_id, uint instance_id, float2 uv) {
+    VertexInput output;
+    output.position = position;
+    output.vertex_id = vertex_id;
+    output.instance_id = instance_id;
+    output.uv = uv;
+    return output;
+}
+
+struct PixelInput {
+    float4 position: SV_POSITION;
+    float2 uv: TEXCOORD;
+    float shade: SHADE;
+};
+PixelInput acsl_create_PixelInput(float4 position, float2 uv, float shade) {
+    PixelInput output;
+    output.position = position;
+    output.uv = uv;
+    output.shade = shade;
+    return output;
+}
+
+PixelInput vertex_main(VertexInput vertex_input) {
+    float4 position = vertex_input.position;
+    float offset = (float(vertex_input.instance_id) * 0.5);
+    return acsl_create_PixelInput(float4((position.x + offset), position.y, position.z, position.w), vertex_input.uv, float(vertex_input.vertex_id));
+}
+
+float4 fragment_main(PixelInput pixel_input) : SV_TARGET {
+    float shade = pixel_input.shade;
+    return float4(shade, shade, shade, 1.0);
+}
+
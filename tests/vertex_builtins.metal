@@ -0,0 +1,36 @@
+// Generated from Alexandria Common Shader Language
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct VertexInput {
+    float4 position;
+    uint vertex_id;
+    uint instance_id;
+    float2 uv;
+};
+
+struct acsl_VertexInput_attributes {
+    float4 position [[attribute(0)]];
+    float2 uv [[attribute(3)]];
+};
+
+struct PixelInput {
+    float4 position [[position]];
+    float2 uv;
+    float shade;
+};
+
+vertex PixelInput vertex_main(acsl_VertexInput_attributes acsl_vertex_input [[stage_in]], uint acsl_vertex_id [[vertex_id]], uint acsl_instance_id [[instance_id]]) {
+    VertexInput vertex_input = VertexInput{acsl_vertex_input.position, acsl_vertex_id, acsl_instance_id, acsl_vertex_input.uv};
+    float4 position = vertex_input.position;
+    float offset = (float(vertex_input.instance_id) * 0.5);
+    return PixelInput{float4((position.x + offset), position.y, position.z, position.w), vertex_input.uv, float(vertex_input.vertex_id)};
+}
+
+fragment float4 fragment_main(PixelInput pixel_input [[stage_in]]) {
+    float shade = pixel_input.shade;
+    return float4(shade, shade, shade, 1.0);
+}
+
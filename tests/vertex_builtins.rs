@@ -0,0 +1,61 @@
+mod common;
+
+use acsl::{CompileOptions, Compiler, GlslVersion};
+use common::error;
+
+#[test]
+fn vertex_builtins_vulkan() {
+    let input_code = std::fs::read_to_string("tests/vertex_builtins.acsl").unwrap();
+
+    let compiler = Compiler::new(CompileOptions::new().glsl_version(GlslVersion::Vulkan));
+    let glsl = compiler.compile_glsl(&input_code).unwrap();
+    assert!(glsl.vertex().contains(
+        "VertexInput vertex_input = VertexInput(acsl_vertex_input_position, uint(gl_VertexIndex), uint(gl_InstanceIndex), acsl_vertex_input_uv);\n"
+    ));
+    assert!(!glsl.vertex().contains("acsl_vertex_input_vertex_id"));
+}
+
+#[test]
+fn fullscreen_triangle() {
+    let input_code = "struct VertexInput {
+    vertex_id: uint : SV_VERTEX_ID,
+}
+
+struct PixelInput {
+    position: float4 : SV_POSITION,
+}
+
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    let x = uint_to_float(vertex_input.vertex_id);
+
+    return PixelInput {
+        position: float4(x, x, 0.0, 1.0),
+    };
+}
+
+fn fragment_main(pixel_input: PixelInput) -> float4 {
+    return pixel_input.position;
+}
+";
+
+    // Only system values leave nothing to fetch from vertex buffers
+    let (vertex, _) = acsl::compile_glsl(input_code).unwrap();
+    assert!(!vertex.contains(" in "));
+    assert!(acsl::reflect(input_code)
+        .unwrap()
+        .vertex_attributes()
+        .is_empty());
+
+    let msl = acsl::compile_msl(input_code).unwrap();
+    assert!(!msl.contains("acsl_VertexInput_attributes"));
+    assert!(msl.contains("vertex PixelInput vertex_main(uint acsl_vertex_id [[vertex_id]]) {\n"));
+    assert!(msl.contains("VertexInput vertex_input = VertexInput{acsl_vertex_id};\n"));
+}
+
+#[test]
+fn invalid_vertex_builtins() {
+    assert_eq!(
+        error("struct VertexInput {\n    instance: float : SV_INSTANCE_ID,\n}\n"),
+        "Semantic \"SV_INSTANCE_ID\" must be of type \"uint\", instead it is \"float\""
+    );
+}
@@ -0,0 +1,28 @@
+// Generated from Alexandria Common Shader Language
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @builtin(vertex_index) vertex_id: u32,
+    @builtin(instance_index) instance_id: u32,
+    @location(3) uv: vec2<f32>,
+}
+
+struct PixelInput {
+    @builtin(position) position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) shade: f32,
+}
+
+@vertex
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    var position: vec4<f32> = vertex_input.position;
+    var offset: f32 = (f32(vertex_input.instance_id) * 0.5);
+    return PixelInput(vec4<f32>((position.x + offset), position.y, position.z, position.w), vertex_input.uv, f32(vertex_input.vertex_id));
+}
+
+@fragment
+fn fragment_main(pixel_input: PixelInput) -> @location(0) vec4<f32> {
+    var shade: f32 = pixel_input.shade;
+    return vec4<f32>(shade, shade, shade, 1.0);
+}
+
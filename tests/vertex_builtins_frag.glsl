@@ -0,0 +1,31 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+out vec4 acsl_fragment_color;
+
+layout(location = 1) in vec2 acsl_pixel_input_uv;
+layout(location = 2) in float acsl_pixel_input_shade;
+
+struct VertexInput {
+    vec4 position;
+    uint vertex_id;
+    uint instance_id;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+    float shade;
+};
+
+
+void main() {
+    PixelInput pixel_input = PixelInput(gl_FragCoord, acsl_pixel_input_uv, acsl_pixel_input_shade);
+
+    float shade = pixel_input.shade;
+    acsl_fragment_color = vec4(shade, shade, shade, 1.0);
+    return;
+}
+
@@ -0,0 +1,36 @@
+#version 430 core
+
+// Generated from Alexandria Common Shader Language
+
+layout (location = 0) in vec4 acsl_vertex_input_position;
+layout (location = 3) in vec2 acsl_vertex_input_uv;
+
+layout(location = 1) out vec2 acsl_pixel_input_uv;
+layout(location = 2) out float acsl_pixel_input_shade;
+
+struct VertexInput {
+    vec4 position;
+    uint vertex_id;
+    uint instance_id;
+    vec2 uv;
+};
+
+struct PixelInput {
+    vec4 position;
+    vec2 uv;
+    float shade;
+};
+
+void main() {
+    VertexInput vertex_input = VertexInput(acsl_vertex_input_position, uint(gl_VertexID), uint(gl_InstanceID), acsl_vertex_input_uv);
+
+    vec4 position = vertex_input.position;
+    float offset = (float(vertex_input.instance_id) * 0.5);
+    PixelInput acsl_vertex_output = PixelInput(vec4((position.x + offset), position.y, position.z, position.w), vertex_input.uv, float(vertex_input.vertex_id));
+    acsl_pixel_input_uv = acsl_vertex_output.uv;
+    acsl_pixel_input_shade = acsl_vertex_output.shade;
+    gl_Position = acsl_vertex_output.position;
+    return;
+}
+
+
@@ -0,0 +1,114 @@
+macro_rules! run_test {
+    ($test_name:literal) => {
+        let input_code = std::fs::read_to_string(format!("{}.acsl", $test_name)).unwrap();
+        let target_code = std::fs::read_to_string(format!("{}.wgsl", $test_name)).unwrap();
+
+        let compiled_code = acsl::compile_wgsl(&input_code).unwrap();
+
+        assert_eq!(compiled_code, target_code);
+    };
+}
+
+#[test]
+fn simple_1() {
+    run_test!("tests/simple");
+}
+
+#[test]
+fn matrix() {
+    run_test!("tests/matrix");
+}
+
+#[test]
+fn variables() {
+    run_test!("tests/variables");
+}
+
+#[test]
+fn constants() {
+    run_test!("tests/constants");
+}
+
+#[test]
+fn texture() {
+    run_test!("tests/texture");
+}
+
+#[test]
+fn texture_dimensions() {
+    run_test!("tests/texture_dimensions");
+}
+
+#[test]
+fn type_alias() {
+    run_test!("tests/type_alias");
+}
+
+#[test]
+fn unsupported_feature() {
+    let input_code = "struct Settings {
+    scale: float1,
+}
+
+struct VertexInput {
+    position: float4 : POSITION,
+}
+
+struct PixelInput {
+    position: float4 : SV_POSITION,
+}
+
+cbuffer settings: Settings : 0;
+
+fn vertex_main(vertex_input: VertexInput) -> PixelInput {
+    return PixelInput {
+        position: vertex_input.position,
+    };
+}
+
+fn fragment_main(pixel_input: PixelInput) -> float4 {
+    return pixel_input.position;
+}
+";
+
+    let error = acsl::compile_wgsl(input_code).err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "WGSL does not support single component vectors (\"float1\")"
+    );
+}
+
+#[test]
+fn sampler() {
+    run_test!("tests/sampler");
+}
+
+#[test]
+fn texture_functions() {
+    run_test!("tests/texture_functions");
+}
+
+#[test]
+fn depth_texture() {
+    run_test!("tests/depth_texture");
+}
+
+#[test]
+fn render_targets() {
+    run_test!("tests/render_targets");
+}
+
+#[test]
+fn fragment_builtins() {
+    run_test!("tests/fragment_builtins");
+}
+
+#[test]
+fn vertex_builtins() {
+    run_test!("tests/vertex_builtins");
+}
+
+#[test]
+fn interpolation() {
+    run_test!("tests/interpolation");
+}